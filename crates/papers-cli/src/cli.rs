@@ -18,6 +18,67 @@ pub enum AdvancedMode {
 pub struct Cli {
     #[command(subcommand)]
     pub entity: EntityCommand,
+    /// Truncate abstracts and notes to N characters. 0 hides abstracts in list views and shows
+    /// them in full in get views.
+    #[arg(long, global = true, value_name = "N", default_value_t = 0)]
+    pub abstract_chars: usize,
+    /// Directory for cached OpenAlex responses. Overrides $PAPERS_CACHE_DIR; defaults to the
+    /// platform cache directory (e.g. ~/.cache/papers/requests).
+    #[arg(long, global = true, value_name = "DIR")]
+    pub cache_dir: Option<std::path::PathBuf>,
+    /// Bypass the response cache: every request goes to the network and nothing is stored.
+    #[arg(long, global = true, conflicts_with = "cache_dir")]
+    pub no_cache: bool,
+    /// Sort object keys in --json output so the same data always prints identically.
+    #[arg(long, global = true)]
+    pub sort_keys: bool,
+    /// Log each API request to stderr: URL (keys masked), HTTP status, body size, and elapsed time.
+    #[arg(long, short = 'v', global = true)]
+    pub verbose: bool,
+    /// Always use Zotero's local API, failing if it isn't reachable. Overrides $ZOTERO_TRANSPORT.
+    #[arg(long, global = true, conflicts_with = "zotero_remote")]
+    pub zotero_local: bool,
+    /// Always use the Zotero web API, even when the desktop app is running. Overrides
+    /// $ZOTERO_TRANSPORT.
+    #[arg(long, global = true)]
+    pub zotero_remote: bool,
+}
+
+impl Cli {
+    /// The Zotero transport forced by `--zotero-local`/`--zotero-remote`, if any.
+    pub fn zotero_transport(&self) -> Option<papers_zotero::Transport> {
+        if self.zotero_local {
+            Some(papers_zotero::Transport::Local)
+        } else if self.zotero_remote {
+            Some(papers_zotero::Transport::Remote)
+        } else {
+            None
+        }
+    }
+
+    /// The OpenAlex response cache to use, or `None` under `--no-cache`.
+    ///
+    /// `env_cache_dir` is the value of `$PAPERS_CACHE_DIR`. A directory given
+    /// by `--cache-dir` or the environment must be usable; the platform
+    /// default is best-effort and silently skipped if unavailable.
+    pub fn response_cache(
+        &self,
+        env_cache_dir: Option<std::ffi::OsString>,
+    ) -> Result<Option<papers_core::DiskCache>, String> {
+        if self.no_cache {
+            return Ok(None);
+        }
+        let dir = self
+            .cache_dir
+            .clone()
+            .or_else(|| env_cache_dir.filter(|v| !v.is_empty()).map(std::path::PathBuf::from));
+        let explicit = dir.is_some();
+        match papers_core::DiskCache::locate(dir, std::time::Duration::from_secs(600)) {
+            Ok(cache) => Ok(Some(cache)),
+            Err(e) if explicit => Err(e.to_string()),
+            Err(_) => Ok(None),
+        }
+    }
 }
 
 #[derive(Subcommand)]
@@ -25,7 +86,7 @@ pub enum EntityCommand {
     /// Scholarly works: articles, preprints, datasets, and more
     Work {
         #[command(subcommand)]
-        cmd: WorkCommand,
+        cmd: Box<WorkCommand>,
     },
     /// Disambiguated researcher profiles
     Author {
@@ -74,9 +135,31 @@ pub enum EntityCommand {
     },
     /// Your personal Zotero reference library
     Zotero {
+        /// Print the response's Last-Modified-Version (library version) to stderr
+        #[arg(long, global = true)]
+        show_version: bool,
         #[command(subcommand)]
         cmd: ZoteroCommand,
     },
+    /// DataLab document conversion service
+    Datalab {
+        #[command(subcommand)]
+        cmd: DatalabCommand,
+    },
+    /// Search every cached extraction for a regex
+    Grep {
+        /// Regular expression to search for
+        pattern: String,
+        /// Case-insensitive matching
+        #[arg(long, short = 'i')]
+        ignore_case: bool,
+        /// Lines of context around each match
+        #[arg(long, short = 'C', default_value_t = 0)]
+        context: usize,
+        /// Output raw JSON
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 /// Shared args for all list commands
@@ -117,6 +200,10 @@ pub struct ListArgs {
     /// Output raw JSON instead of formatted text
     #[arg(long)]
     pub json: bool,
+
+    /// Print the OpenAlex response body unmodified (every field, nothing slimmed)
+    #[arg(long, conflicts_with = "json")]
+    pub raw: bool,
 }
 
 /// Shorthand filter flags for `work list`.
@@ -168,6 +255,14 @@ pub struct WorkFilterArgs {
     #[arg(long)]
     pub year: Option<String>,
 
+    /// Only works published on or after this date (YYYY-MM-DD). Conflicts with --year
+    #[arg(long = "from-date", value_name = "DATE", conflicts_with = "year")]
+    pub from_publication_date: Option<String>,
+
+    /// Only works published on or before this date (YYYY-MM-DD). Conflicts with --year
+    #[arg(long = "to-date", value_name = "DATE", conflicts_with = "year")]
+    pub to_publication_date: Option<String>,
+
     /// Filter by citation count (e.g. ">100", "10-50")
     #[arg(long)]
     pub citations: Option<String>,
@@ -187,6 +282,10 @@ pub struct WorkFilterArgs {
     /// Filter for open access works only
     #[arg(long)]
     pub open: bool,
+
+    /// Leave out retracted works
+    #[arg(long)]
+    pub exclude_retracted: bool,
 }
 
 /// Shorthand filter flags for `author list`.
@@ -249,6 +348,14 @@ pub struct SourceFilterArgs {
     /// Filter by works count (e.g. ">100000")
     #[arg(long)]
     pub works: Option<String>,
+
+    /// Filter by DOAJ membership (true or false)
+    #[arg(long, value_name = "BOOL")]
+    pub doaj: Option<bool>,
+
+    /// Filter by whether the source charges an APC (true or false)
+    #[arg(long, value_name = "BOOL")]
+    pub apc: Option<bool>,
 }
 
 /// Shorthand filter flags for `institution list`.
@@ -384,6 +491,20 @@ pub enum WorkCommand {
         args: ListArgs,
         #[command(flatten)]
         work_filters: WorkFilterArgs,
+        /// Fields per result: minimal (no authors/venue/abstract), standard,
+        /// or detailed (adds all topics and the top keywords)
+        #[arg(long, value_enum, default_value = "standard")]
+        detail: Detail,
+        /// Walk successive pages (of -n results each) and stop after N results in total
+        #[arg(long, value_name = "N", conflicts_with = "raw")]
+        limit_total: Option<usize>,
+        /// Also write the results to a Parquet file (needs the `arrow` build feature)
+        #[arg(long, value_name = "PATH", conflicts_with = "raw")]
+        parquet: Option<std::path::PathBuf>,
+        /// Also upsert the results into a SQLite database's `works` table, keyed
+        /// by OpenAlex ID (needs the `sqlite` build feature)
+        #[arg(long, value_name = "PATH", conflicts_with = "raw")]
+        to_sqlite: Option<std::path::PathBuf>,
     },
     /// Get a single work by ID (OpenAlex ID, DOI, PMID, or PMCID)
     Get {
@@ -392,6 +513,17 @@ pub enum WorkCommand {
         /// Output raw JSON
         #[arg(long)]
         json: bool,
+        /// Print the OpenAlex response body unmodified (every field, nothing slimmed)
+        #[arg(long, conflicts_with = "json")]
+        raw: bool,
+    },
+    /// Show citations per year as a bar chart
+    Citations {
+        /// Work ID (OpenAlex ID, DOI, PMID, or PMCID)
+        id: String,
+        /// Output raw JSON (the counts_by_year array)
+        #[arg(long)]
+        json: bool,
     },
     /// Type-ahead search for works by title
     Autocomplete {
@@ -430,6 +562,19 @@ pub enum WorkCommand {
         #[arg(long, value_name = "QUALITY")]
         advanced: Option<AdvancedMode>,
     },
+    /// Open a work in the browser: its open-access PDF, else its DOI, else its OpenAlex page
+    Open {
+        /// Work ID (OpenAlex ID, DOI, PMID, or PMCID)
+        id: String,
+    },
+    /// Add a work to the Zotero library as an item of the matching type (skipped if its DOI is already present)
+    Import {
+        /// Work ID (OpenAlex ID, DOI, PMID, or PMCID)
+        id: String,
+        /// Output raw JSON
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -447,6 +592,9 @@ pub enum AuthorCommand {
         id: String,
         #[arg(long)]
         json: bool,
+        /// Print the OpenAlex response body unmodified (every field, nothing slimmed)
+        #[arg(long, conflicts_with = "json")]
+        raw: bool,
     },
     /// Type-ahead search for authors
     Autocomplete {
@@ -454,6 +602,15 @@ pub enum AuthorCommand {
         #[arg(long)]
         json: bool,
     },
+    /// Compare two authors side by side (works, citations, h-index, i10-index)
+    Compare {
+        /// First author (OpenAlex ID, ORCID, or name)
+        first: String,
+        /// Second author (OpenAlex ID, ORCID, or name)
+        second: String,
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -471,6 +628,9 @@ pub enum SourceCommand {
         id: String,
         #[arg(long)]
         json: bool,
+        /// Print the OpenAlex response body unmodified (every field, nothing slimmed)
+        #[arg(long, conflicts_with = "json")]
+        raw: bool,
     },
     /// Type-ahead search for sources
     Autocomplete {
@@ -478,6 +638,13 @@ pub enum SourceCommand {
         #[arg(long)]
         json: bool,
     },
+    /// Impact card for a source: h-index, 2-year mean citedness, works, OA share
+    Impact {
+        /// Source ID, ISSN, or name (names resolve to the most-cited match)
+        source: String,
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -495,10 +662,16 @@ pub enum InstitutionCommand {
         id: String,
         #[arg(long)]
         json: bool,
+        /// Print the OpenAlex response body unmodified (every field, nothing slimmed)
+        #[arg(long, conflicts_with = "json")]
+        raw: bool,
     },
     /// Type-ahead search for institutions
     Autocomplete {
         query: String,
+        /// Only suggest institutions in this country (e.g. "US", "GB")
+        #[arg(long)]
+        country: Option<String>,
         #[arg(long)]
         json: bool,
     },
@@ -519,6 +692,9 @@ pub enum TopicCommand {
         id: String,
         #[arg(long)]
         json: bool,
+        /// Print the OpenAlex response body unmodified (every field, nothing slimmed)
+        #[arg(long, conflicts_with = "json")]
+        raw: bool,
     },
 }
 
@@ -537,6 +713,9 @@ pub enum PublisherCommand {
         id: String,
         #[arg(long)]
         json: bool,
+        /// Print the OpenAlex response body unmodified (every field, nothing slimmed)
+        #[arg(long, conflicts_with = "json")]
+        raw: bool,
     },
     /// Type-ahead search for publishers
     Autocomplete {
@@ -561,6 +740,9 @@ pub enum FunderCommand {
         id: String,
         #[arg(long)]
         json: bool,
+        /// Print the OpenAlex response body unmodified (every field, nothing slimmed)
+        #[arg(long, conflicts_with = "json")]
+        raw: bool,
     },
     /// Type-ahead search for funders
     Autocomplete {
@@ -568,6 +750,13 @@ pub enum FunderCommand {
         #[arg(long)]
         json: bool,
     },
+    /// List works funded by a funder (via grants.funder)
+    Works {
+        /// Funder name or OpenAlex ID (e.g. "nih" or "F4320332161")
+        funder: String,
+        #[command(flatten)]
+        args: ListArgs,
+    },
 }
 
 #[derive(Subcommand)]
@@ -585,6 +774,9 @@ pub enum DomainCommand {
         id: String,
         #[arg(long)]
         json: bool,
+        /// Print the OpenAlex response body unmodified (every field, nothing slimmed)
+        #[arg(long, conflicts_with = "json")]
+        raw: bool,
     },
 }
 
@@ -603,6 +795,9 @@ pub enum FieldCommand {
         id: String,
         #[arg(long)]
         json: bool,
+        /// Print the OpenAlex response body unmodified (every field, nothing slimmed)
+        #[arg(long, conflicts_with = "json")]
+        raw: bool,
     },
 }
 
@@ -621,6 +816,9 @@ pub enum SubfieldCommand {
         id: String,
         #[arg(long)]
         json: bool,
+        /// Print the OpenAlex response body unmodified (every field, nothing slimmed)
+        #[arg(long, conflicts_with = "json")]
+        raw: bool,
     },
     /// Type-ahead search for subfields
     Autocomplete {
@@ -642,6 +840,10 @@ pub enum ZoteroExtractCommand {
         /// Results per page (1-100, default 25)
         #[arg(long, short = 'n', default_value = "25")]
         limit: u32,
+        /// Sort order: key, date (most recently extracted first), or title.
+        /// Entries without a meta.json are listed last
+        #[arg(long, value_enum, default_value = "key")]
+        sort: ExtractSort,
         /// Output raw JSON
         #[arg(long)]
         json: bool,
@@ -650,6 +852,9 @@ pub enum ZoteroExtractCommand {
     Text {
         /// Item key (e.g. LF4MJWZK), DOI, or title search
         query: String,
+        /// Strip running headers, page numbers, and other extraction artifacts
+        #[arg(long)]
+        clean: bool,
     },
     /// Print cached structured JSON for a paper (must already be extracted)
     Json {
@@ -660,6 +865,45 @@ pub enum ZoteroExtractCommand {
     Get {
         /// Item key (e.g. LF4MJWZK), DOI, or title search
         query: String,
+        /// Output raw JSON (key, local path/existence, backup status, meta)
+        #[arg(long)]
+        json: bool,
+    },
+    /// Compare the local extraction with the Zotero-backed ZIP (mode, length, content hash)
+    Diff {
+        /// Item key (e.g. LF4MJWZK), DOI, or title search
+        query: String,
+        /// Output raw JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Extract the PDF of every top-level item in a collection, skipping cached items
+    Collection {
+        /// Collection key (e.g. AB12CDEF) or name search
+        key: String,
+        /// Quality level: fast, balanced (default), or accurate
+        #[arg(long, short = 'm', default_value = "balanced")]
+        mode: AdvancedMode,
+        /// Maximum extractions running at once
+        #[arg(long, default_value_t = 4)]
+        concurrency: usize,
+        /// Print the estimated DataLab credit cost and exit without extracting
+        #[arg(long)]
+        dry_run: bool,
+        /// Output raw JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Write every cached extraction to a directory as `<key>-<title>.md`
+    Export {
+        /// Destination directory (created if missing)
+        dir: std::path::PathBuf,
+        /// Prepend YAML frontmatter (title, authors, DOI, extracted date, key)
+        #[arg(long)]
+        frontmatter: bool,
+        /// Output raw JSON
+        #[arg(long)]
+        json: bool,
     },
     /// Upload all locally-cached extractions that are missing from Zotero
     Upload {
@@ -737,6 +981,24 @@ pub enum ZoteroCommand {
         #[command(subcommand)]
         cmd: ZoteroPermissionCommand,
     },
+    /// Full-text indexing status of attachments
+    Fulltext {
+        #[command(subcommand)]
+        cmd: ZoteroFulltextCommand,
+    },
+    /// Library totals: items by type, items with PDFs, collections, tags
+    Stats {
+        /// Output raw JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Check the Zotero connection: credentials, local API, install path, and which transport
+    /// would be used
+    Doctor {
+        /// Output raw JSON
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -822,10 +1084,42 @@ pub enum ZoteroWorkCommand {
     Annotations {
         /// Item key (e.g. LF4MJWZK) or a title/creator search string
         key: String,
+        /// Only annotations of this color: a name (yellow, red, green, blue,
+        /// purple, magenta, orange, gray) or a hex like #ffd400
+        #[arg(long, value_parser = parse_annotation_color)]
+        color: Option<String>,
+        /// Only annotations of this type
+        #[arg(long = "type", value_enum)]
+        annotation_type: Option<AnnotationType>,
+        /// Output format; markdown groups quotes and comments by page for literature notes
+        #[arg(long, value_enum, default_value = "text", conflicts_with = "json")]
+        format: AnnotationFormat,
         /// Output raw JSON
         #[arg(long)]
         json: bool,
     },
+    /// Notes and page-ordered annotations of a work as one markdown document
+    Review {
+        /// Item key (e.g. LF4MJWZK) or a title/creator search string
+        key: String,
+        /// Output the gathered item, notes, and annotations as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Add or remove tags on a work
+    Tag {
+        /// Item key (e.g. LF4MJWZK) or a title/creator search string
+        key: String,
+        /// Tag to add (repeatable); tags the work already has are left alone
+        #[arg(long, required_unless_present = "remove")]
+        add: Vec<String>,
+        /// Tag to remove (repeatable)
+        #[arg(long)]
+        remove: Vec<String>,
+        /// Output the work's resulting tags as JSON
+        #[arg(long)]
+        json: bool,
+    },
     /// List tags attached to a work
     Tags {
         /// Item key (e.g. LF4MJWZK) or a title/creator search string
@@ -859,6 +1153,54 @@ pub enum ZoteroWorkCommand {
         /// Quality level: fast, balanced (default), or accurate
         #[arg(long, short = 'm', default_value = "balanced")]
         mode: AdvancedMode,
+        /// Output format; html re-extracts items cached as markdown only
+        #[arg(long, value_enum, default_value = "markdown")]
+        format: ExtractFormat,
+        /// Print the estimated DataLab credit cost and exit without extracting
+        #[arg(long)]
+        dry_run: bool,
+        /// Strip running headers, page numbers, and other extraction artifacts (markdown only)
+        #[arg(long)]
+        clean: bool,
+    },
+    /// Print one section of a work's cached extraction (e.g. abstract, conclusion)
+    Section {
+        /// Item key (e.g. LF4MJWZK) or a title/creator search string
+        key: String,
+        /// Section heading to look for (case-insensitive, fuzzy)
+        name: String,
+    },
+    /// Search a work's cached extraction for a regex, printing matching lines
+    Grep {
+        /// Item key (e.g. LF4MJWZK) or a title/creator search string
+        key: String,
+        /// Regular expression to search for
+        pattern: String,
+        /// Case-insensitive matching
+        #[arg(long, short = 'i')]
+        ignore_case: bool,
+        /// Lines of context around each match
+        #[arg(long, short = 'C', default_value_t = 2)]
+        context: usize,
+        /// Output raw JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Word, character, and heading counts plus reading time (from its cached extraction)
+    Stats {
+        /// Item key (e.g. LF4MJWZK) or a title/creator search string
+        key: String,
+        /// Output raw JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print an outline of a work's headings (from its cached extraction)
+    Toc {
+        /// Item key (e.g. LF4MJWZK) or a title/creator search string
+        key: String,
+        /// Output raw JSON
+        #[arg(long)]
+        json: bool,
     },
     /// Get the CDN view URL for a work's primary PDF attachment
     ViewUrl {
@@ -1126,6 +1468,79 @@ pub enum ZoteroCollectionCommand {
         #[arg(long)]
         json: bool,
     },
+    /// Item counts for a collection: by type, PDFs, annotated items, sub-collections
+    Stats {
+        /// Collection key (e.g. AB12CDEF) or a name search string
+        key: String,
+        /// Output raw JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Export every item in a collection (attachments and notes are skipped)
+    Export {
+        /// Collection key (e.g. AB12CDEF) or a name search string
+        key: String,
+        /// Output format
+        #[arg(long, value_enum, default_value = "bibtex")]
+        format: ExportFormat,
+    },
+}
+
+/// Summary detail level for `work list`.
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum Detail {
+    Minimal,
+    Standard,
+    Detailed,
+}
+
+/// Sort order for `zotero extract list`.
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum ExtractSort {
+    Key,
+    Date,
+    Title,
+}
+
+/// Output format for `zotero work extract`.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExtractFormat {
+    Markdown,
+    Html,
+}
+
+/// Accept a Zotero annotation color name (any case) or a `#rrggbb` hex.
+fn parse_annotation_color(s: &str) -> Result<String, String> {
+    let is_hex = s.strip_prefix('#').is_some_and(|h| h.len() == 6 && h.chars().all(|c| c.is_ascii_hexdigit()));
+    if is_hex || papers_core::zotero::ANNOTATION_COLORS.iter().any(|(_, name)| name.eq_ignore_ascii_case(s)) {
+        return Ok(s.to_string());
+    }
+    let names: Vec<&str> = papers_core::zotero::ANNOTATION_COLORS.iter().map(|&(_, name)| name).collect();
+    Err(format!("expected #rrggbb or one of: {}", names.join(", ")))
+}
+
+/// Annotation type filter for `zotero work annotations`.
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum AnnotationType {
+    Highlight,
+    Underline,
+    Note,
+    Text,
+    Image,
+    Ink,
+}
+
+/// Output format for `zotero work annotations`.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnnotationFormat {
+    Text,
+    Markdown,
+}
+
+/// Output format for `zotero collection export`.
+#[derive(ValueEnum, Clone, Debug)]
+pub enum ExportFormat {
+    Bibtex,
 }
 
 #[derive(Subcommand)]
@@ -1165,6 +1580,24 @@ pub enum ZoteroTagCommand {
         #[arg(long)]
         json: bool,
     },
+    /// Add or remove tags on every top-level item matching a search
+    Bulk {
+        /// Quick search (title, creator, year) selecting the items
+        #[arg(long, short = 's')]
+        search: String,
+        /// Tag to add (repeatable)
+        #[arg(long, required_unless_present = "remove")]
+        add: Vec<String>,
+        /// Tag to remove (repeatable)
+        #[arg(long)]
+        remove: Vec<String>,
+        /// List the items that would change without writing anything
+        #[arg(long)]
+        dry_run: bool,
+        /// Output the matched count and changed keys as JSON
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -1211,6 +1644,12 @@ pub enum ZoteroSettingCommand {
         #[arg(long)]
         json: bool,
     },
+    /// List colored tags (the tagColors setting) with their colors
+    TagColors {
+        /// Output raw JSON
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -1235,3 +1674,130 @@ pub enum ZoteroPermissionCommand {
         json: bool,
     },
 }
+
+#[derive(Subcommand)]
+pub enum ZoteroFulltextCommand {
+    /// List file attachments with and without indexed full text
+    Status {
+        /// Only list attachments whose full text is not indexed
+        #[arg(long)]
+        missing: bool,
+        /// Output raw JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum DatalabCommand {
+    /// List the workflow step types DataLab supports
+    StepTypes {
+        /// Output raw JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Submit a document for conversion and print its request ID without waiting
+    Submit {
+        /// PDF (or other supported document) to convert
+        file: std::path::PathBuf,
+        /// Processing mode
+        #[arg(long, value_enum, default_value = "balanced")]
+        mode: AdvancedMode,
+        /// Output raw JSON (request_id and request_check_url)
+        #[arg(long)]
+        json: bool,
+    },
+    /// Fetch the result of a job started with `datalab submit`
+    Result {
+        /// Request ID printed by `datalab submit`
+        id: String,
+        /// Output the full DataLab poll response as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const WORK_GET_BODY: &str = r#"{"id": "https://openalex.org/W1", "display_name": "Bitonic Sort"}"#;
+
+    /// Parse `args`, build the client the way `papers` does with `$PAPERS_CACHE_DIR`
+    /// pointing at a fresh temp dir, and count how many of two `work get` calls
+    /// reach the server.
+    async fn work_get_twice_hits(args: &[&str]) -> usize {
+        let mock = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/works/W1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(WORK_GET_BODY))
+            .mount(&mock)
+            .await;
+        let cache_dir = std::env::temp_dir().join(format!(
+            "papers-no-cache-test-{}-{}",
+            std::process::id(),
+            args.len()
+        ));
+        let _ = std::fs::remove_dir_all(&cache_dir);
+
+        let cli = Cli::try_parse_from(args).unwrap();
+        let mut client = papers_core::OpenAlexClient::new().with_base_url(mock.uri());
+        if let Some(cache) = cli.response_cache(Some(cache_dir.clone().into())).unwrap() {
+            client = client.with_cache(cache);
+        }
+        for _ in 0..2 {
+            let work = papers_core::api::work_get(&client, "W1", &papers_core::GetParams::default()).await.unwrap();
+            assert_eq!(work.display_name.as_deref(), Some("Bitonic Sort"));
+        }
+        let _ = std::fs::remove_dir_all(&cache_dir);
+        mock.received_requests().await.unwrap().len()
+    }
+
+    #[tokio::test]
+    async fn test_work_get_no_cache_hits_server_every_call() {
+        assert_eq!(work_get_twice_hits(&["papers", "--no-cache", "work", "get", "W1"]).await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_work_get_cached_without_no_cache_flag() {
+        assert_eq!(work_get_twice_hits(&["papers", "work", "get", "W1"]).await, 1);
+    }
+
+    #[test]
+    fn test_no_cache_conflicts_with_cache_dir() {
+        assert!(Cli::try_parse_from(["papers", "--no-cache", "--cache-dir", "x", "work", "get", "W1"]).is_err());
+    }
+
+    #[test]
+    fn test_raw_conflicts_with_work_list_exports() {
+        for flag in [&["--limit-total", "50"][..], &["--parquet", "w.parquet"], &["--to-sqlite", "w.db"]] {
+            let args = ["papers", "work", "list", "--raw"].into_iter().chain(flag.iter().copied());
+            assert!(Cli::try_parse_from(args).is_err(), "--raw accepted with {flag:?}");
+        }
+        assert!(Cli::try_parse_from(["papers", "work", "list", "--raw"]).is_ok());
+    }
+
+    #[test]
+    fn test_annotation_color_must_be_known_or_hex() {
+        let parse = |color: &str| Cli::try_parse_from(["papers", "zotero", "work", "annotations", "ABCD1234", "--color", color]);
+        for ok in ["yellow", "Yellow", "#ffd400", "#ABCDEF"] {
+            assert!(parse(ok).is_ok(), "rejected {ok}");
+        }
+        let Err(err) = parse("yelow") else { panic!("accepted an unknown color") };
+        let err = err.to_string();
+        assert!(err.contains("yellow, red, green, blue, purple, magenta, orange, gray"), "{err}");
+        assert!(parse("#ffd40").is_err());
+    }
+
+    #[test]
+    fn test_grep_is_top_level() {
+        let cli = Cli::try_parse_from(["papers", "grep", "bitonic", "-i", "-C", "1"]).unwrap();
+        assert!(matches!(
+            cli.entity,
+            EntityCommand::Grep { ref pattern, ignore_case: true, context: 1, json: false } if pattern == "bitonic"
+        ));
+        assert!(Cli::try_parse_from(["papers", "zotero", "extract", "grep", "bitonic"]).is_err());
+    }
+}
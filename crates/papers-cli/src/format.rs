@@ -3,16 +3,51 @@ use papers_core::summary::{
     AuthorSummary, DomainSummary, FieldSummary, FunderSummary, InstitutionSummary,
     PublisherSummary, SlimListResponse, SourceSummary, SubfieldSummary, TopicSummary, WorkSummary,
 };
-use papers_core::text::WorkTextResult;
+use papers_core::text::{BatchItemResult, BatchOutcome, CostEstimate, DocumentMatches, DocumentStats, ExportSummary, Match, ExtractionDiff, ExtractionDivergence, ExtractionSnapshot, WorkTextResult};
+use papers_core::zotero::{CollectionStats, FulltextEntry, FulltextStatus, LibraryStats, WorkReview};
 use papers_core::{
     Author, AutocompleteResponse, Domain, Field, FindWorksResponse, Funder, Institution, ListMeta,
     Publisher, Source, Subfield, Topic, Work,
 };
+use std::borrow::Cow;
 use std::collections::HashMap;
-use papers_zotero::{Collection, Creator, DeletedObjects, Group, Item, ItemFulltext, PagedResponse, SavedSearch, SettingEntry, Tag, VersionedResponse};
+use papers_datalab::{DatalabClient, MarkerPollResponse, MarkerRequest, MarkerSubmitResponse, ProcessingMode, StepTypesResponse};
+use papers_zotero::{Collection, Creator, DeletedObjects, Diagnosis, Group, Item, ItemFulltext, PagedResponse, Resolution, SavedSearch, SettingEntry, Tag, TagColor, Transport, VersionedResponse};
+
+// ── Truncation ────────────────────────────────────────────────────────────
+
+/// Truncate `s` to at most `max` characters, appending `…` when anything was
+/// cut. Slices on a char boundary so multibyte text never panics.
+pub fn truncate_chars(s: &str, max: usize) -> Cow<'_, str> {
+    match s.char_indices().nth(max) {
+        Some((byte_idx, _)) => Cow::Owned(format!("{}…", &s[..byte_idx])),
+        None => Cow::Borrowed(s),
+    }
+}
+
+/// Text for a get view: full when `max` is 0, otherwise truncated.
+fn truncate_full_by_default(s: &str, max: usize) -> Cow<'_, str> {
+    if max == 0 { Cow::Borrowed(s) } else { truncate_chars(s, max) }
+}
 
 // ── Meta line ─────────────────────────────────────────────────────────────
 
+/// Pretty-print `val` as JSON with the keys of every object sorted, so the
+/// same data always serializes to the same bytes.
+pub fn to_json_sorted<T: serde::Serialize>(val: &T) -> serde_json::Result<String> {
+    fn sort(value: serde_json::Value) -> serde_json::Value {
+        match value {
+            serde_json::Value::Object(map) => {
+                let sorted: std::collections::BTreeMap<_, _> = map.into_iter().map(|(k, v)| (k, sort(v))).collect();
+                serde_json::Value::Object(sorted.into_iter().collect())
+            }
+            serde_json::Value::Array(items) => serde_json::Value::Array(items.into_iter().map(sort).collect()),
+            other => other,
+        }
+    }
+    serde_json::to_string_pretty(&sort(serde_json::to_value(val)?))
+}
+
 fn meta_line(meta: &ListMeta) -> String {
     let page = meta.page.unwrap_or(1);
     let per_page = meta.per_page.unwrap_or(10);
@@ -24,7 +59,10 @@ fn meta_line(meta: &ListMeta) -> String {
 
 // ── Work ──────────────────────────────────────────────────────────────────
 
-pub fn format_work_list(resp: &SlimListResponse<WorkSummary>) -> String {
+const RETRACTED_MARKER: &str = "⚠ RETRACTED";
+
+/// Abstracts are hidden unless `abstract_chars` is non-zero.
+pub fn format_work_list(resp: &SlimListResponse<WorkSummary>, abstract_chars: usize) -> String {
     let mut out = format!("{}\n", meta_line(&resp.meta));
     for (i, w) in resp.results.iter().enumerate() {
         let title = w.title.as_deref().unwrap_or("(untitled)");
@@ -32,6 +70,9 @@ pub fn format_work_list(resp: &SlimListResponse<WorkSummary>) -> String {
             .publication_year
             .map_or(String::new(), |y| format!(" ({y})"));
         out.push_str(&format!("\n {:>2}  {}{}\n", i + 1, title, year));
+        if w.is_retracted {
+            out.push_str(&format!("     {RETRACTED_MARKER}\n"));
+        }
 
         if !w.authors.is_empty() {
             out.push_str(&format!("     {}\n", w.authors.join(" · ")));
@@ -62,25 +103,67 @@ pub fn format_work_list(resp: &SlimListResponse<WorkSummary>) -> String {
         if let Some(topic) = &w.primary_topic {
             out.push_str(&format!("     Topic: {topic}\n"));
         }
+        if !w.topics.is_empty() {
+            out.push_str(&format!("     Topics: {}\n", w.topics.join(" · ")));
+        }
+        if !w.keywords.is_empty() {
+            out.push_str(&format!("     Keywords: {}\n", w.keywords.join(", ")));
+        }
         if let Some(doi) = &w.doi {
             out.push_str(&format!("     DOI: {doi}\n"));
         }
-        if let Some(abs) = &w.abstract_text {
-            let snippet = if abs.len() > 200 {
-                format!("{}…", abs.chars().take(200).collect::<String>())
-            } else {
-                abs.clone()
-            };
-            out.push_str(&format!("\n     {snippet}\n"));
+        if let Some(abs) = &w.abstract_text
+            && abstract_chars > 0
+        {
+            out.push_str(&format!("\n     {}\n", truncate_chars(abs, abstract_chars)));
         }
     }
     out
 }
 
-pub fn format_work_get(w: &Work) -> String {
+/// Write works to a Parquet file at `path`, one row per work with columns
+/// `id`, `title`, `year`, `citations`, `doi`, and `venue`.
+#[cfg(feature = "arrow")]
+pub fn format_work_list_parquet(
+    results: &[WorkSummary],
+    path: &std::path::Path,
+) -> Result<(), parquet::errors::ParquetError> {
+    use arrow::array::{ArrayRef, Int32Array, Int64Array, StringArray};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::record_batch::RecordBatch;
+    use std::sync::Arc;
+
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Utf8, false),
+        Field::new("title", DataType::Utf8, true),
+        Field::new("year", DataType::Int32, true),
+        Field::new("citations", DataType::Int64, true),
+        Field::new("doi", DataType::Utf8, true),
+        Field::new("venue", DataType::Utf8, true),
+    ]));
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(StringArray::from_iter_values(results.iter().map(|w| w.id.as_str()))),
+        Arc::new(results.iter().map(|w| w.title.as_deref()).collect::<StringArray>()),
+        Arc::new(results.iter().map(|w| w.publication_year).collect::<Int32Array>()),
+        Arc::new(results.iter().map(|w| w.cited_by_count).collect::<Int64Array>()),
+        Arc::new(results.iter().map(|w| w.doi.as_deref()).collect::<StringArray>()),
+        Arc::new(results.iter().map(|w| w.journal.as_deref()).collect::<StringArray>()),
+    ];
+    let batch = RecordBatch::try_new(schema.clone(), columns)?;
+    let file = std::fs::File::create(path)?;
+    let mut writer = parquet::arrow::ArrowWriter::try_new(file, schema, None)?;
+    writer.write(&batch)?;
+    writer.close()?;
+    Ok(())
+}
+
+pub fn format_work_get(w: &Work, abstract_chars: usize) -> String {
     let mut out = String::new();
     let title = w.display_name.as_deref().unwrap_or("(untitled)");
     out.push_str(&format!("Work: {title}\n"));
+    if w.is_retracted == Some(true) {
+        out.push_str(&format!("{RETRACTED_MARKER}\n"));
+    }
     out.push_str(&format!("ID:   {}\n", w.id));
     if let Some(doi) = &w.doi {
         out.push_str(&format!("DOI:  {doi}\n"));
@@ -153,14 +236,18 @@ pub fn format_work_get(w: &Work) -> String {
     }
 
     if let Some(abs) = &w.abstract_text {
-        out.push_str(&format!("\nAbstract:\n  {abs}\n"));
+        out.push_str(&format!("\nAbstract:\n  {}\n", truncate_full_by_default(abs, abstract_chars)));
     }
 
     out
 }
 
-pub fn format_work_get_response(response: &WorkGetResponse, zotero_configured: bool) -> String {
-    let mut out = format_work_get(&response.work);
+pub fn format_work_get_response(
+    response: &WorkGetResponse,
+    zotero_configured: bool,
+    abstract_chars: usize,
+) -> String {
+    let mut out = format_work_get(&response.work, abstract_chars);
     if zotero_configured {
         out.push('\n');
         if let Some(z) = &response.zotero {
@@ -182,6 +269,51 @@ pub fn format_work_get_response(response: &WorkGetResponse, zotero_configured: b
     out
 }
 
+/// Width in characters of the longest bar in the citations timeline.
+const TIMELINE_BAR_WIDTH: u64 = 40;
+
+/// Render `counts_by_year` as one horizontal bar per year, oldest first, scaled
+/// so the busiest year spans `TIMELINE_BAR_WIDTH` characters.
+pub fn format_work_citations_timeline(w: &Work) -> String {
+    let title = w.display_name.as_deref().unwrap_or("(untitled)");
+    let mut out = format!("Citations by year: {title}\n");
+    let mut years: Vec<(i32, u64)> = w
+        .counts_by_year
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .map(|c| (c.year, c.cited_by_count.unwrap_or(0).max(0) as u64))
+        .collect();
+    if years.is_empty() {
+        out.push_str("\nNo yearly citation data.\n");
+        return out;
+    }
+    years.sort_by_key(|&(year, _)| year);
+
+    let max = years.iter().map(|&(_, c)| c).max().unwrap_or(0);
+    let count_width = max.to_string().len();
+    out.push('\n');
+    for (year, count) in &years {
+        // Round up so any non-zero year gets at least one block
+        let len = if max == 0 { 0 } else { (count * TIMELINE_BAR_WIDTH).div_ceil(max) };
+        let bar = "█".repeat(len as usize);
+        out.push_str(&format!("  {year}  {count:>count_width$}  {bar}\n"));
+    }
+    let total: u64 = years.iter().map(|&(_, c)| c).sum();
+    out.push_str(&format!("\n{total} citations across {} years\n", years.len()));
+    out
+}
+
+/// Render the candidates from `FilterError::Suggestions` when a work lookup
+/// has no exact match.
+pub fn format_work_suggestions(query: &str, suggestions: &[(String, u64)]) -> String {
+    let mut out = format!("No exact match for \"{query}\". Did you mean:\n\n");
+    for (i, (name, citations)) in suggestions.iter().enumerate() {
+        out.push_str(&format!(" {:>2}  {name}\n     {citations} citations\n", i + 1));
+    }
+    out
+}
+
 // ── Author ────────────────────────────────────────────────────────────────
 
 pub fn format_author_list(resp: &SlimListResponse<AuthorSummary>) -> String {
@@ -258,6 +390,33 @@ pub fn format_author_get(a: &Author) -> String {
     out
 }
 
+/// Side-by-side impact metrics for two authors, one column each.
+pub fn format_author_comparison(a: &Author, b: &Author) -> String {
+    let column = |author: &Author| -> Vec<String> {
+        let stats = author.summary_stats.as_ref();
+        let opt = |v: Option<i64>| v.map_or_else(|| "-".to_string(), |v| v.to_string());
+        vec![
+            author.display_name.clone().unwrap_or_else(|| "?".to_string()),
+            author.id.strip_prefix("https://openalex.org/").unwrap_or(&author.id).to_string(),
+            opt(author.works_count),
+            opt(author.cited_by_count),
+            opt(stats.and_then(|s| s.h_index)),
+            opt(stats.and_then(|s| s.i10_index)),
+        ]
+    };
+    let labels = ["", "ID", "Works", "Citations", "h-index", "i10-index"];
+    let (left, right) = (column(a), column(b));
+    let width = left.iter().map(|s| s.chars().count()).max().unwrap_or(0);
+
+    let mut out = String::new();
+    for ((label, l), r) in labels.iter().zip(&left).zip(&right) {
+        let line = format!("{label:<10}  {l:<width$}  {r}");
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
 // ── Source ────────────────────────────────────────────────────────────────
 
 pub fn format_source_list(resp: &SlimListResponse<SourceSummary>) -> String {
@@ -321,6 +480,31 @@ pub fn format_source_get(s: &Source) -> String {
     out
 }
 
+/// Compact impact card for a source. The OA share is taken over the years in
+/// `counts_by_year`, not the source's whole history.
+pub fn format_source_impact(s: &Source) -> String {
+    let name = s.display_name.as_deref().unwrap_or("?");
+    let mut out = match &s.issn_l {
+        Some(issn) => format!("{name} (ISSN {issn})\n"),
+        None => format!("{name}\n"),
+    };
+    let stats = s.summary_stats.as_ref();
+    let opt = |v: Option<String>| v.unwrap_or_else(|| "-".to_string());
+    let (works, oa_works) = s.counts_by_year.iter().flatten().fold((0, 0), |(w, oa), y| {
+        (w + y.works_count.unwrap_or(0), oa + y.oa_works_count.unwrap_or(0))
+    });
+    let rows = [
+        ("h-index", opt(stats.and_then(|s| s.h_index).map(|h| h.to_string()))),
+        ("2yr mean citedness", opt(stats.and_then(|s| s.two_yr_mean_citedness).map(|c| format!("{c:.2}")))),
+        ("Works", opt(s.works_count.map(|w| w.to_string()))),
+        ("OA works", opt((works > 0).then(|| format!("{:.1}%", 100.0 * oa_works as f64 / works as f64)))),
+    ];
+    for (label, value) in rows {
+        out.push_str(&format!("  {label:<19} {value}\n"));
+    }
+    out
+}
+
 // ── Institution ───────────────────────────────────────────────────────────
 
 pub fn format_institution_list(resp: &SlimListResponse<InstitutionSummary>) -> String {
@@ -766,16 +950,7 @@ fn strip_html(html: &str) -> String {
 }
 
 fn creator_display(c: &Creator) -> String {
-    if let Some(name) = &c.name {
-        name.clone()
-    } else {
-        match (&c.first_name, &c.last_name) {
-            (Some(f), Some(l)) => format!("{l}, {f}"),
-            (None, Some(l)) => l.clone(),
-            (Some(f), None) => f.clone(),
-            _ => "?".to_string(),
-        }
-    }
+    c.sort_name().unwrap_or_else(|| "?".to_string())
 }
 
 pub fn format_zotero_work_list(resp: &PagedResponse<Item>) -> String {
@@ -790,14 +965,8 @@ pub fn format_zotero_work_list(resp: &PagedResponse<Item>) -> String {
             .data
             .date
             .as_deref()
-            .and_then(|d| {
-                let y = d.split(['-', '/']).next()?;
-                if y.len() == 4 && y.chars().all(|c| c.is_ascii_digit()) {
-                    Some(format!(" ({y})"))
-                } else {
-                    None
-                }
-            })
+            .and_then(papers_core::zotero::parse_date)
+            .map(|(y, _, _)| format!(" ({y})"))
             .unwrap_or_default();
         out.push_str(&format!("\n {:>2}  [{}] {}{}\n", i + 1, item.key, title, year));
         if !item.data.creators.is_empty() {
@@ -828,7 +997,7 @@ pub fn format_zotero_work_list(resp: &PagedResponse<Item>) -> String {
     out
 }
 
-pub fn format_zotero_item_get(item: &Item) -> String {
+pub fn format_zotero_item_get(item: &Item, abstract_chars: usize) -> String {
     let mut out = String::new();
     let title = item.data.title.as_deref().unwrap_or("(untitled)");
     out.push_str(&format!("{}: {}\n", item.data.item_type, title));
@@ -857,7 +1026,9 @@ pub fn format_zotero_item_get(item: &Item) -> String {
         }
     }
     if let Some(abs) = &item.data.abstract_note {
-        if !abs.is_empty() { out.push_str(&format!("\nAbstract:\n  {abs}\n")); }
+        if !abs.is_empty() {
+            out.push_str(&format!("\nAbstract:\n  {}\n", truncate_full_by_default(abs, abstract_chars)));
+        }
     }
     if !item.data.tags.is_empty() {
         let tag_names: Vec<&str> = item.data.tags.iter().map(|t| t.tag.as_str()).collect();
@@ -881,15 +1052,15 @@ pub fn format_zotero_item_get(item: &Item) -> String {
         let stripped = strip_html(note);
         let trimmed = stripped.trim();
         if !trimmed.is_empty() {
-            out.push_str(&format!("\nNote:\n  {trimmed}\n"));
+            out.push_str(&format!("\nNote:\n  {}\n", truncate_full_by_default(trimmed, abstract_chars)));
         }
     }
     // Annotation fields
-    if let Some(ann_type) = item.data.extra_fields.get("annotationType").and_then(|v| v.as_str()) {
-        out.push_str(&format!("Annotation type: {ann_type}\n"));
-    }
-    if let Some(text) = item.data.extra_fields.get("annotationText").and_then(|v| v.as_str()) {
-        if !text.is_empty() {
+    if let Some(ann) = item.as_annotation() {
+        if let Some(ann_type) = ann.annotation_type {
+            out.push_str(&format!("Annotation type: {ann_type}\n"));
+        }
+        if let Some(text) = ann.text {
             let snippet = if text.chars().count() > 500 {
                 format!("{}…", text.chars().take(500).collect::<String>())
             } else {
@@ -897,17 +1068,16 @@ pub fn format_zotero_item_get(item: &Item) -> String {
             };
             out.push_str(&format!("Text: {snippet}\n"));
         }
-    }
-    if let Some(comment) = item.data.extra_fields.get("annotationComment").and_then(|v| v.as_str()) {
-        if !comment.is_empty() {
+        if let Some(comment) = ann.comment {
             out.push_str(&format!("Comment: {comment}\n"));
         }
-    }
-    if let Some(page) = item.data.extra_fields.get("annotationPageLabel").and_then(|v| v.as_str()) {
-        out.push_str(&format!("Page: {page}\n"));
-    }
-    if let Some(color) = item.data.extra_fields.get("annotationColor").and_then(|v| v.as_str()) {
-        out.push_str(&format!("Color: {color}\n"));
+        if let Some(page) = ann.page_label {
+            out.push_str(&format!("Page: {page}\n"));
+        }
+        if let Some(color) = ann.color {
+            let name = papers_core::zotero::annotation_color_name(color).unwrap_or(color);
+            out.push_str(&format!("Color: {name}\n"));
+        }
     }
     out
 }
@@ -966,54 +1136,131 @@ pub fn format_zotero_annotation_list_vec(items: &[Item]) -> String {
 }
 
 fn push_annotation_entry(out: &mut String, i: usize, item: &Item) {
-    let ann_type = item
-        .data
-        .extra_fields
-        .get("annotationType")
-        .and_then(|v| v.as_str())
-        .unwrap_or("?");
-    let page = item
-        .data
-        .extra_fields
-        .get("annotationPageLabel")
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    let color = item
-        .data
-        .extra_fields
-        .get("annotationColor")
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
+    let ann = item.as_annotation().unwrap_or_default();
     let parent = item.data.parent_item.as_deref().unwrap_or("");
-    out.push_str(&format!("\n {:>2}  [{}] {}", i + 1, item.key, ann_type));
-    if !page.is_empty() {
+    let color_name = ann.color.and_then(papers_core::zotero::annotation_color_name);
+    out.push_str(&format!("\n {:>2}  [{}] ", i + 1, item.key));
+    if let Some(name) = color_name {
+        out.push_str(&format!("{name} "));
+    }
+    out.push_str(ann.annotation_type.unwrap_or("?"));
+    if let Some(page) = ann.page_label {
         out.push_str(&format!(" (p. {page})"));
     }
-    if !color.is_empty() {
+    if let Some(color) = ann.color.filter(|_| color_name.is_none()) {
         out.push_str(&format!(" {color}"));
     }
     out.push('\n');
     if !parent.is_empty() {
         out.push_str(&format!("     Parent: {parent}\n"));
     }
-    if let Some(text) = item.data.extra_fields.get("annotationText").and_then(|v| v.as_str()) {
-        if !text.is_empty() {
-            let snippet = if text.chars().count() > 120 {
-                format!("{}…", text.chars().take(120).collect::<String>())
-            } else {
-                text.to_string()
-            };
-            out.push_str(&format!("     \"{snippet}\"\n"));
-        }
+    if let Some(text) = ann.text {
+        let snippet = if text.chars().count() > 120 {
+            format!("{}…", text.chars().take(120).collect::<String>())
+        } else {
+            text.to_string()
+        };
+        out.push_str(&format!("     \"{snippet}\"\n"));
     }
-    if let Some(comment) = item.data.extra_fields.get("annotationComment").and_then(|v| v.as_str()) {
-        if !comment.is_empty() {
-            out.push_str(&format!("     Note: {comment}\n"));
+    if let Some(comment) = ann.comment {
+        out.push_str(&format!("     Note: {comment}\n"));
+    }
+}
+
+/// Annotations as a markdown list for literature notes, grouped under a
+/// `## Page` heading per page label in reading order (see
+/// [`papers_core::zotero::compare_annotations`]). Highlighted text is quoted, with the comment as a nested
+/// item; image and ink annotations, which have no text, get a placeholder.
+pub fn format_annotations_markdown(items: &[Item]) -> String {
+    if items.is_empty() {
+        return "No annotations.\n".to_string();
+    }
+    let mut out = String::new();
+    push_annotations_markdown(&mut out, items, "##");
+    out
+}
+
+/// The body of [`format_annotations_markdown`], with page headings at level
+/// `heading` (`##`, `###`, ...).
+fn push_annotations_markdown(out: &mut String, items: &[Item], heading: &str) {
+    let mut sorted: Vec<&Item> = items.iter().collect();
+    sorted.sort_by(|a, b| papers_core::zotero::compare_annotations(a, b));
+    let annotations = sorted.iter().map(|item| item.as_annotation().unwrap_or_default());
+
+    let mut page = None;
+    for (i, ann) in annotations.enumerate() {
+        if i == 0 || ann.page_label != page {
+            page = ann.page_label;
+            if i > 0 {
+                out.push('\n');
+            }
+            match page {
+                Some(label) => out.push_str(&format!("{heading} Page {label}\n\n")),
+                None => out.push_str(&format!("{heading} No page\n\n")),
+            }
+        }
+        let kind = ann.annotation_type.unwrap_or("annotation");
+        let comment = ann.comment.map(str::trim);
+        match (ann.text, comment) {
+            // Sticky notes and text boxes keep their content in the comment
+            (None, Some(comment)) if matches!(kind, "note" | "text") => {
+                out.push_str(&format!("- {comment}\n"));
+                continue;
+            }
+            (Some(text), _) => {
+                let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+                out.push_str(&format!("- \"{text}\"\n"));
+            }
+            (None, _) => out.push_str(&format!("- *[{kind}]*\n")),
+        }
+        if let Some(comment) = comment {
+            out.push_str(&format!("  - {comment}\n"));
         }
     }
 }
 
-pub fn format_zotero_note_list(resp: &PagedResponse<Item>) -> String {
+/// A work's notes and annotations as one markdown document for `zotero work
+/// review`: the title as `#`, then `## Notes` (each note's text, paragraphs
+/// kept) and `## Annotations` (as in [`format_annotations_markdown`], one
+/// heading level down).
+pub fn format_work_review(review: &WorkReview) -> String {
+    let title = review.item.data.title.as_deref().unwrap_or("(untitled)");
+    let mut out = format!("# {title}\n");
+    let authors: Vec<String> = review.item.data.authors().into_iter().map(creator_display).collect();
+    if !authors.is_empty() {
+        out.push_str(&format!("\n{}\n", authors.join("; ")));
+    }
+
+    out.push_str("\n## Notes\n\n");
+    let notes: Vec<String> = review
+        .notes
+        .iter()
+        .filter_map(|n| n.data.note.as_deref())
+        .map(|html| {
+            let html = html.replace("</p>", "</p>\n\n").replace("<br>", "\n").replace("<br/>", "\n");
+            strip_html(&html).lines().map(str::trim).collect::<Vec<_>>().join("\n")
+        })
+        .map(|text| text.trim().replace("\n\n\n", "\n\n"))
+        .filter(|text| !text.is_empty())
+        .collect();
+    if notes.is_empty() {
+        out.push_str("*No notes.*\n");
+    } else {
+        out.push_str(&notes.join("\n\n---\n\n"));
+        out.push('\n');
+    }
+
+    out.push_str("\n## Annotations\n\n");
+    if review.annotations.is_empty() {
+        out.push_str("*No annotations.*\n");
+    } else {
+        push_annotations_markdown(&mut out, &review.annotations, "###");
+    }
+    out
+}
+
+/// Note previews default to 80 characters; a non-zero `note_chars` overrides that.
+pub fn format_zotero_note_list(resp: &PagedResponse<Item>, note_chars: usize) -> String {
     let header = match resp.total_results {
         Some(n) if n > 0 => format!("Found {} results · showing {}\n", n, resp.items.len()),
         _ => format!("{} note(s)\n", resp.items.len()),
@@ -1024,14 +1271,10 @@ pub fn format_zotero_note_list(resp: &PagedResponse<Item>) -> String {
         out.push_str(&format!("\n {:>2}  [{}] parent: {parent}\n", i + 1, item.key));
         if let Some(note) = &item.data.note {
             let stripped = strip_html(note);
-            let trimmed = stripped.trim().to_string();
+            let trimmed = stripped.trim();
             if !trimmed.is_empty() {
-                let preview = if trimmed.chars().count() > 80 {
-                    format!("{}…", trimmed.chars().take(80).collect::<String>())
-                } else {
-                    trimmed
-                };
-                out.push_str(&format!("     {preview}\n"));
+                let max = if note_chars == 0 { 80 } else { note_chars };
+                out.push_str(&format!("     {}\n", truncate_chars(trimmed, max)));
             }
         }
     }
@@ -1079,6 +1322,55 @@ pub fn format_zotero_collection_get(coll: &Collection) -> String {
     out
 }
 
+pub fn format_collection_stats(stats: &CollectionStats) -> String {
+    let mut out = format!("Collection: {} ({})\n", stats.name, stats.key);
+    out.push_str(&format!("Items:            {}\n", stats.items));
+    let width = stats.by_type.keys().map(|t| t.len()).max().unwrap_or(0);
+    for (item_type, n) in &stats.by_type {
+        out.push_str(&format!("  {item_type:<width$}  {n}\n"));
+    }
+    out.push_str(&format!("PDFs:             {}\n", stats.pdfs));
+    out.push_str(&format!("With annotations: {}\n", stats.with_annotations));
+    out.push_str(&format!("Sub-collections:  {}\n", stats.subcollections));
+    out
+}
+
+pub fn format_library_stats(stats: &LibraryStats) -> String {
+    let mut out = format!("Items:            {}\n", stats.items);
+    let width = stats.by_type.keys().map(|t| t.len()).max().unwrap_or(0);
+    for (item_type, n) in &stats.by_type {
+        out.push_str(&format!("  {item_type:<width$}  {n}\n"));
+    }
+    out.push_str(&format!("With PDFs:        {}\n", stats.items_with_pdfs));
+    out.push_str(&format!("Collections:      {}\n", stats.collections));
+    out.push_str(&format!("Tags:             {}\n", stats.tags));
+    out
+}
+
+/// Indexed and unindexed attachment lists; `missing_only` prints just the latter.
+pub fn format_fulltext_status(status: &FulltextStatus, missing_only: bool) -> String {
+    fn entry(e: &FulltextEntry) -> String {
+        let title = e.title.as_deref().unwrap_or("(untitled)");
+        let parent = e.parent_item.as_deref().map(|p| format!("  (in {p})")).unwrap_or_default();
+        let version = e.version.map(|v| format!("  v{v}")).unwrap_or_default();
+        format!("  {}  {title}{parent}{version}\n", e.key)
+    }
+    let total = status.indexed.len() + status.missing.len();
+    let mut out = String::new();
+    if !missing_only {
+        out.push_str(&format!("Indexed: {} of {total} attachment(s)\n", status.indexed.len()));
+        for e in &status.indexed {
+            out.push_str(&entry(e));
+        }
+        out.push('\n');
+    }
+    out.push_str(&format!("Not indexed: {}\n", status.missing.len()));
+    for e in &status.missing {
+        out.push_str(&entry(e));
+    }
+    out
+}
+
 pub fn format_zotero_tag_list(resp: &PagedResponse<Tag>) -> String {
     let header = match resp.total_results {
         Some(n) if n > 0 => format!("Found {} tags · showing {}\n", n, resp.items.len()),
@@ -1175,6 +1467,203 @@ pub fn format_work_text(result: &WorkTextResult) -> String {
     out
 }
 
+// ── Extraction diff ───────────────────────────────────────────────────────
+
+fn extraction_side(snap: Option<&ExtractionSnapshot>, missing: &str) -> String {
+    let Some(snap) = snap else { return format!("✗  {missing}") };
+    let mut parts = vec![
+        snap.processing_mode.clone().unwrap_or_else(|| "unknown mode".into()),
+        format!("{} bytes", snap.markdown_len),
+        format!("md5 {}", &snap.markdown_md5[..12.min(snap.markdown_md5.len())]),
+    ];
+    if let Some(at) = &snap.extracted_at {
+        parts.push(format!("extracted {at}"));
+    }
+    format!("✓  {}", parts.join(" · "))
+}
+
+pub fn format_extraction_diff(diff: &ExtractionDiff) -> String {
+    let opt = |v: &Option<String>| v.clone().unwrap_or_else(|| "-".into());
+    let mut out = format!("{}\n", diff.item_key);
+    out.push_str(&format!("  local:  {}\n", extraction_side(diff.local.as_ref(), "not cached")));
+    out.push_str(&format!("  remote: {}\n", extraction_side(diff.remote.as_ref(), "no papers_extract zip")));
+    if diff.local.is_none() || diff.remote.is_none() {
+        return out;
+    }
+    if diff.is_match() {
+        out.push_str("  identical\n");
+        return out;
+    }
+    out.push_str("  differs:\n");
+    for d in &diff.divergences {
+        let line = match d {
+            ExtractionDivergence::ProcessingMode { local, remote } => {
+                format!("mode: {} (local) vs {} (remote)", opt(local), opt(remote))
+            }
+            ExtractionDivergence::MarkdownLength { local, remote } => {
+                format!("markdown length: {local} (local) vs {remote} (remote) bytes")
+            }
+            ExtractionDivergence::MarkdownContent => "markdown content (same length)".to_string(),
+            ExtractionDivergence::ExtractedAt { local, remote } => {
+                format!("extracted: {} (local) vs {} (remote)", opt(local), opt(remote))
+            }
+        };
+        out.push_str(&format!("    - {line}\n"));
+    }
+    out
+}
+
+// ── Batch extraction ──────────────────────────────────────────────────────
+
+pub fn format_batch_extraction(results: &[BatchItemResult]) -> String {
+    let mut out = String::new();
+    let (mut extracted, mut cached, mut skipped, mut failed) = (0, 0, 0, 0);
+    for r in results {
+        let title = r.title.as_deref().unwrap_or("(untitled)");
+        let mark = match &r.outcome {
+            BatchOutcome::Extracted => { extracted += 1; "✓" }
+            BatchOutcome::Cached => { cached += 1; "·" }
+            BatchOutcome::Skipped => { skipped += 1; "-" }
+            BatchOutcome::Failed { .. } => { failed += 1; "✗" }
+        };
+        out.push_str(&format!("{mark} {}  {title}\n", r.key));
+        if let BatchOutcome::Failed { error } = &r.outcome {
+            out.push_str(&format!("    {error}\n"));
+        }
+    }
+    out.push_str(&format!(
+        "\n{extracted} extracted, {cached} already cached, {skipped} done in an earlier run, {failed} failed\n"
+    ));
+    out
+}
+
+// ── DataLab cost estimates ────────────────────────────────────────────────
+
+fn mode_name(mode: &ProcessingMode) -> String {
+    serde_json::to_value(mode).ok().and_then(|v| v.as_str().map(String::from)).unwrap_or_default()
+}
+
+pub fn format_cost_estimate(key: &str, pages: Option<u32>, mode: &ProcessingMode) -> String {
+    let rate = papers_datalab::credits_per_page(mode);
+    let name = mode_name(mode);
+    match pages {
+        Some(pages) => {
+            let req = MarkerRequest { mode: mode.clone(), ..Default::default() };
+            let credits = DatalabClient::estimate_cost(&req, pages);
+            format!("{key}: {pages} pages × {rate} credits/page ({name}) ≈ {credits} credits\n")
+        }
+        None => format!("{key}: page count unknown (not a PDF); {rate} credits/page ({name})\n"),
+    }
+}
+
+pub fn format_collection_estimate(estimates: &[CostEstimate], credits_per_page: u64) -> String {
+    let mut out = String::new();
+    let (mut total, mut pages, mut cached, mut unknown) = (0, 0, 0, 0);
+    for e in estimates {
+        let title = e.title.as_deref().unwrap_or("(untitled)");
+        let cost = if e.cached {
+            cached += 1;
+            "cached".to_string()
+        } else if let (Some(p), Some(c)) = (e.pages, e.credits) {
+            pages += p;
+            total += c;
+            format!("{p} pages, {c} credits")
+        } else {
+            unknown += 1;
+            e.error.clone().unwrap_or_else(|| "page count unknown".to_string())
+        };
+        out.push_str(&format!("{}  {title}  [{cost}]\n", e.key));
+    }
+    out.push_str(&format!(
+        "\n≈ {total} credits for {pages} pages at {credits_per_page} credits/page ({cached} cached"
+    ));
+    if unknown > 0 {
+        out.push_str(&format!(", {unknown} not estimated"));
+    }
+    out.push_str(")\n");
+    out
+}
+
+// ── Extracted document structure ──────────────────────────────────────────
+
+/// grep-style output: `N:line` for matches, `N-line` for context, and `--`
+/// between non-adjacent groups. Overlapping context is printed once.
+pub fn format_grep_matches(matches: &[Match]) -> String {
+    if matches.is_empty() {
+        return "No matches.\n".to_string();
+    }
+    let mut lines: std::collections::BTreeMap<usize, (&str, bool)> = std::collections::BTreeMap::new();
+    for m in matches {
+        let first = m.line_number - m.before.len();
+        for (i, l) in m.before.iter().enumerate() {
+            lines.entry(first + i).or_insert((l, false));
+        }
+        lines.insert(m.line_number, (&m.line, true));
+        for (i, l) in m.after.iter().enumerate() {
+            lines.entry(m.line_number + 1 + i).or_insert((l, false));
+        }
+    }
+    let mut out = String::new();
+    let mut prev: Option<usize> = None;
+    for (n, (text, is_match)) in lines {
+        if prev.is_some_and(|p| n > p + 1) {
+            out.push_str("--\n");
+        }
+        let sep = if is_match { ':' } else { '-' };
+        out.push_str(&format!("{n}{sep}{text}\n"));
+        prev = Some(n);
+    }
+    out
+}
+
+pub fn format_library_grep(docs: &[DocumentMatches]) -> String {
+    if docs.is_empty() {
+        return "No matches.\n".to_string();
+    }
+    let mut out = String::new();
+    for doc in docs {
+        out.push_str(&format!("{}  {}\n", doc.key, doc.title.as_deref().unwrap_or("(untitled)")));
+        for line in format_grep_matches(&doc.matches).lines() {
+            out.push_str(&format!("  {line}\n"));
+        }
+        out.push('\n');
+    }
+    let total: usize = docs.iter().map(|d| d.matches.len()).sum();
+    out.push_str(&format!("{total} matching lines in {} documents\n", docs.len()));
+    out
+}
+
+pub fn format_extraction_export(summary: &ExportSummary, dir: &std::path::Path) -> String {
+    let mut out = String::new();
+    for file in &summary.exported {
+        let name = file.path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+        out.push_str(&format!("  {name}\n"));
+    }
+    for key in &summary.skipped {
+        out.push_str(&format!("  {key}  (skipped: markdown unreadable)\n"));
+    }
+    out.push_str(&format!("Exported {} extractions to {}\n", summary.exported.len(), dir.display()));
+    out
+}
+
+pub fn format_document_stats(stats: &DocumentStats) -> String {
+    format!(
+        "Words:     {}\nChars:     {}\nHeadings:  {}\nReading:   ~{} min\n",
+        stats.words, stats.chars, stats.headings, stats.est_reading_minutes
+    )
+}
+
+/// Indented outline; the shallowest heading level present is unindented.
+pub fn format_toc(toc: &[(usize, String)]) -> String {
+    if toc.is_empty() {
+        return "No headings found.\n".to_string();
+    }
+    let base = toc.iter().map(|(level, _)| *level).min().unwrap_or(1);
+    toc.iter()
+        .map(|(level, title)| format!("{}{title}\n", "  ".repeat(level - base)))
+        .collect()
+}
+
 // ── Zotero fulltext ───────────────────────────────────────────────────────
 
 pub fn format_zotero_work_fulltext(resp: &VersionedResponse<ItemFulltext>) -> String {
@@ -1206,6 +1695,13 @@ pub fn format_zotero_work_fulltext(resp: &VersionedResponse<ItemFulltext>) -> St
 
 // ── Zotero settings ───────────────────────────────────────────────────────
 
+pub fn format_last_modified_version(version: Option<u64>) -> String {
+    match version {
+        Some(v) => format!("Last-Modified-Version: {v}\n"),
+        None => "Last-Modified-Version: (not reported)\n".to_string(),
+    }
+}
+
 pub fn format_zotero_setting_list(resp: &VersionedResponse<HashMap<String, SettingEntry>>) -> String {
     let mut out = String::new();
     if let Some(v) = resp.last_modified_version {
@@ -1232,6 +1728,19 @@ pub fn format_zotero_setting_get(key: &str, resp: &VersionedResponse<SettingEntr
     out
 }
 
+/// Numbered like Zotero's `1`–`9` shortcuts for assigning colored tags.
+pub fn format_zotero_tag_colors(colors: &[TagColor]) -> String {
+    if colors.is_empty() {
+        return "No colored tags\n".to_string();
+    }
+    let width = colors.iter().map(|c| c.name.chars().count()).max().unwrap_or(0);
+    colors
+        .iter()
+        .enumerate()
+        .map(|(i, c)| format!(" {:>2}  {:<width$}  {}\n", i + 1, c.name, c.color))
+        .collect()
+}
+
 // ── Zotero deleted ────────────────────────────────────────────────────────
 
 pub fn format_zotero_deleted_list(resp: &VersionedResponse<DeletedObjects>) -> String {
@@ -1261,6 +1770,55 @@ pub fn format_zotero_deleted_list(resp: &VersionedResponse<DeletedObjects>) -> S
     out
 }
 
+// ── DataLab ───────────────────────────────────────────────────────────────
+
+pub fn format_datalab_step_types(resp: &StepTypesResponse) -> String {
+    if resp.step_types.is_empty() {
+        return "No step types available.\n".to_string();
+    }
+    let width = resp.step_types.iter().map(|s| s.step_type.len()).max().unwrap_or(0);
+    let mut out = String::new();
+    for s in &resp.step_types {
+        out.push_str(&format!("{:<width$}  {} (v{})\n", s.step_type, s.name, s.version));
+        if !s.description.is_empty() {
+            out.push_str(&format!("{:<width$}  {}\n", "", s.description));
+        }
+    }
+    out
+}
+
+pub fn format_datalab_submit(resp: &MarkerSubmitResponse) -> String {
+    format!(
+        "Submitted: {id}\nCheck URL: {url}\n\nFetch the result with: papers datalab result {id}\n",
+        id = resp.request_id,
+        url = resp.request_check_url,
+    )
+}
+
+/// Markdown of a completed job, or the first other output DataLab returned.
+pub fn format_datalab_result(resp: &MarkerPollResponse) -> String {
+    let body = resp
+        .markdown
+        .clone()
+        .or_else(|| resp.html.clone())
+        .or_else(|| resp.json.as_ref().map(|j| serde_json::to_string_pretty(j).unwrap_or_default()))
+        .unwrap_or_default();
+    if body.ends_with('\n') { body } else { format!("{body}\n") }
+}
+
+/// A job still processing, with its progress and page count when reported.
+pub fn format_datalab_pending(id: &str, resp: &MarkerPollResponse) -> String {
+    let mut details = Vec::new();
+    if let Some(progress) = resp.progress {
+        details.push(format!("{:.0}%", progress.clamp(0.0, 1.0) * 100.0));
+    }
+    if let Some(pages) = resp.page_count {
+        details.push(format!("{pages} pages"));
+    }
+    let details = if details.is_empty() { String::new() } else { format!(" ({})", details.join(", ")) };
+    format!("Job {id} is still processing{details}; run `papers datalab result {id}` again later.\n")
+}
+
 // ── Zotero permission ─────────────────────────────────────────────────────
 
 pub fn format_zotero_permission_list(info: &serde_json::Value) -> String {
@@ -1284,3 +1842,473 @@ pub fn format_zotero_permission_list(info: &serde_json::Value) -> String {
     out
 }
 
+// ── Zotero doctor ─────────────────────────────────────────────────────────
+
+/// Connection report for `zotero doctor`, ending with how to fix whatever
+/// stops a connection.
+pub fn format_zotero_doctor(d: &Diagnosis) -> String {
+    let set = |ok: bool| if ok { "set" } else { "missing" };
+    let mut out = format!(
+        "Credentials:  ZOTERO_USER_ID {}, ZOTERO_API_KEY {}\n",
+        set(d.user_id_set),
+        set(d.api_key_set)
+    );
+    let reachable = if d.local_reachable { "reachable" } else { "not reachable" };
+    out.push_str(&format!("Local API:    {reachable} at {}\n", d.local_url));
+    out.push_str(&format!("Installed:    {}\n", d.installed_path.as_deref().unwrap_or("not found")));
+    let transport = match d.transport {
+        Transport::Auto => "auto (local API if reachable, else web API)",
+        Transport::Local => "local (forced)",
+        Transport::Remote => "remote (forced)",
+    };
+    out.push_str(&format!("Transport:    {transport}\n"));
+    if !d.check_launched {
+        out.push_str("              ZOTERO_CHECK_LAUNCHED=0: not-running check disabled\n");
+    }
+    let resolution = match d.resolution {
+        Resolution::Local => "local API",
+        Resolution::Remote => "web API (api.zotero.org)",
+        Resolution::NotRunning => "error: Zotero is installed but the local API is not reachable",
+        Resolution::LocalUnavailable => "error: the local API is required but not reachable",
+    };
+    out.push_str(&format!("Would use:    {resolution}\n"));
+
+    let mut fixes = Vec::new();
+    if !d.user_id_set || !d.api_key_set {
+        fixes.push("Set ZOTERO_USER_ID and ZOTERO_API_KEY (create a key at https://www.zotero.org/settings/keys).");
+    }
+    match d.resolution {
+        Resolution::NotRunning => {
+            fixes.push("Start Zotero and check Zotero → Settings → Advanced → \"Enable Local API\".");
+            fixes.push("Or set ZOTERO_CHECK_LAUNCHED=0 to skip this check and use the remote web API.");
+        }
+        Resolution::LocalUnavailable => {
+            fixes.push("Start Zotero and check Zotero → Settings → Advanced → \"Enable Local API\".");
+            fixes.push("Or drop --zotero-local / ZOTERO_TRANSPORT=local to fall back to the web API.");
+        }
+        Resolution::Local | Resolution::Remote => {}
+    }
+    if !fixes.is_empty() {
+        out.push('\n');
+        for fix in fixes {
+            out.push_str(&format!("Fix: {fix}\n"));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_last_modified_version() {
+        assert_eq!(format_last_modified_version(Some(4384)), "Last-Modified-Version: 4384\n");
+        assert_eq!(format_last_modified_version(None), "Last-Modified-Version: (not reported)\n");
+    }
+
+    fn annotation(color: &str) -> Item {
+        serde_json::from_value(serde_json::json!({
+            "key": "ANN00001", "version": 1,
+            "library": {"type": "user", "id": 1, "name": "test", "links": {}},
+            "data": {
+                "key": "ANN00001", "version": 1, "itemType": "annotation", "parentItem": "ATT00001",
+                "annotationType": "highlight", "annotationText": "Quoted", "annotationPageLabel": "2",
+                "annotationColor": color
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn batch_extraction_counts_each_outcome() {
+        let result = |key: &str, outcome| BatchItemResult { key: key.into(), title: None, outcome };
+        let out = format_batch_extraction(&[
+            result("AAAA0001", BatchOutcome::Extracted),
+            result("AAAA0002", BatchOutcome::Cached),
+            result("AAAA0003", BatchOutcome::Skipped),
+            result("AAAA0004", BatchOutcome::Failed { error: "boom".into() }),
+        ]);
+        assert!(out.contains("- AAAA0003  (untitled)\n"));
+        assert!(out.ends_with("1 extracted, 1 already cached, 1 done in an earlier run, 1 failed\n"));
+    }
+
+    #[test]
+    fn annotations_markdown_grouped_by_page() {
+        let rich: Item =
+            serde_json::from_str(include_str!("../../papers-zotero/tests/fixtures/annotation.json")).unwrap();
+        let mut image = annotation("#ffd400");
+        image.data.extra_fields.insert("annotationType".into(), "image".into());
+        image.data.extra_fields.remove("annotationText");
+        image.data.extra_fields.insert("annotationPageLabel".into(), "7".into());
+        image.data.extra_fields.insert("annotationSortIndex".into(), "00006|000000|00100".into());
+        let mut note = annotation("#ffd400");
+        note.data.extra_fields.insert("annotationType".into(), "note".into());
+        note.data.extra_fields.remove("annotationText");
+        note.data.extra_fields.remove("annotationPageLabel");
+        note.data.extra_fields.insert("annotationComment".into(), "Revisit the proof".into());
+
+        let md = format_annotations_markdown(&[note, image, rich]);
+        assert_eq!(
+            md,
+            "## Page 4\n\n\
+             - \"Our solver converges in a handful of iterations even for stiff constraints.\"\n  \
+             - Compare against XPBD\n\
+             \n## Page 7\n\n\
+             - *[image]*\n\
+             \n## No page\n\n\
+             - Revisit the proof\n"
+        );
+    }
+
+    #[test]
+    fn work_review_has_notes_and_annotations() {
+        let item: Item = serde_json::from_value(serde_json::json!({
+            "key": "WORK0001", "version": 1,
+            "library": {"type": "user", "id": 1, "name": "test", "links": {}},
+            "data": {
+                "key": "WORK0001", "version": 1, "itemType": "journalArticle", "title": "Vertex Block Descent",
+                "creators": [{"creatorType": "author", "firstName": "Anka", "lastName": "Chen"}]
+            }
+        }))
+        .unwrap();
+        let mut note = item.clone();
+        note.data.item_type = "note".into();
+        note.data.note = Some("<p>Fast and <b>stable</b>.</p><p>Try on cloth.</p>".into());
+        let review = WorkReview { item, notes: vec![note], annotations: vec![annotation("#ffd400")] };
+
+        assert_eq!(
+            format_work_review(&review),
+            "# Vertex Block Descent\n\nChen, Anka\n\n\
+             ## Notes\n\nFast and stable.\n\nTry on cloth.\n\n\
+             ## Annotations\n\n### Page 2\n\n- \"Quoted\"\n"
+        );
+    }
+
+    #[test]
+    fn collection_stats_lists_types_aligned() {
+        let stats = CollectionStats {
+            key: "COLL0001".into(),
+            name: "Simulation".into(),
+            items: 3,
+            by_type: [("book".to_string(), 1), ("journalArticle".to_string(), 2)].into(),
+            pdfs: 2,
+            with_annotations: 1,
+            subcollections: 0,
+        };
+        assert_eq!(
+            format_collection_stats(&stats),
+            "Collection: Simulation (COLL0001)\n\
+             Items:            3\n\
+             \x20 book            1\n\
+             \x20 journalArticle  2\n\
+             PDFs:             2\n\
+             With annotations: 1\n\
+             Sub-collections:  0\n"
+        );
+    }
+
+    #[test]
+    fn annotation_colors_named_when_standard() {
+        let text = format_zotero_annotation_list_vec(&[annotation("#5FB236"), annotation("#123456")]);
+        assert!(text.contains("[ANN00001] green highlight (p. 2)\n"), "{text}");
+        assert!(text.contains("[ANN00001] highlight (p. 2) #123456\n"), "{text}");
+        assert!(format_zotero_item_get(&annotation("#ffd400"), 0).contains("Color: yellow\n"));
+    }
+
+    #[test]
+    fn datalab_submit_and_result_text() {
+        let submitted: MarkerSubmitResponse = serde_json::from_str(
+            r#"{"request_id": "job-42", "request_check_url": "https://www.datalab.to/api/v1/marker/job-42"}"#,
+        )
+        .unwrap();
+        let text = format_datalab_submit(&submitted);
+        assert!(text.starts_with("Submitted: job-42\nCheck URL: https://www.datalab.to/api/v1/marker/job-42\n"));
+        assert!(text.contains("papers datalab result job-42"));
+
+        let done: MarkerPollResponse =
+            serde_json::from_str(r##"{"status": "complete", "markdown": "# Converted"}"##).unwrap();
+        assert_eq!(format_datalab_result(&done), "# Converted\n");
+
+        let pending: MarkerPollResponse =
+            serde_json::from_str(r#"{"status": "processing", "progress": 0.4, "page_count": 12}"#).unwrap();
+        assert_eq!(
+            format_datalab_pending("job-42", &pending),
+            "Job job-42 is still processing (40%, 12 pages); run `papers datalab result job-42` again later.\n"
+        );
+    }
+
+    #[test]
+    fn to_json_sorted_is_byte_identical() {
+        let keys = ["zeta", "alpha", "mu", "beta", "omega", "delta", "kappa", "gamma"];
+        let forward: HashMap<&str, serde_json::Value> =
+            keys.iter().map(|&k| (k, serde_json::json!({"y": k, "x": [{"b": 1, "a": 2}]}))).collect();
+        let reverse: HashMap<&str, serde_json::Value> =
+            keys.iter().rev().map(|&k| (k, serde_json::json!({"x": [{"a": 2, "b": 1}], "y": k}))).collect();
+
+        let a = to_json_sorted(&forward).unwrap();
+        assert_eq!(a, to_json_sorted(&reverse).unwrap());
+        assert!(a.starts_with("{\n  \"alpha\": {\n    \"x\": [\n      {\n        \"a\": 2,"));
+        assert!(a.find("\"beta\"").unwrap() < a.find("\"zeta\"").unwrap());
+    }
+
+    #[test]
+    fn test_format_grep_matches_merges_overlapping_context() {
+        let m = |n: usize, line: &str, before: &[&str], after: &[&str]| Match {
+            line_number: n,
+            line: line.to_string(),
+            before: before.iter().map(|s| s.to_string()).collect(),
+            after: after.iter().map(|s| s.to_string()).collect(),
+        };
+        let out = format_grep_matches(&[
+            m(2, "gpu a", &["one"], &["three"]),
+            m(4, "gpu b", &["three"], &["five"]),
+            m(9, "gpu c", &["eight"], &[]),
+        ]);
+        assert_eq!(out, "1-one\n2:gpu a\n3-three\n4:gpu b\n5-five\n--\n8-eight\n9:gpu c\n");
+    }
+
+    #[test]
+    fn test_format_toc_indents_relative_to_shallowest() {
+        let toc = vec![(2, "Intro".to_string()), (3, "Background".to_string()), (2, "Methods".to_string())];
+        assert_eq!(format_toc(&toc), "Intro\n  Background\nMethods\n");
+        assert_eq!(format_toc(&[]), "No headings found.\n");
+    }
+
+    #[test]
+    fn test_format_collection_estimate() {
+        let est = |key: &str, cached, pages: Option<u32>, credits: Option<u64>| CostEstimate {
+            key: key.to_string(),
+            title: Some(format!("Paper {key}")),
+            cached,
+            pages,
+            credits,
+            error: None,
+        };
+        let out = format_collection_estimate(
+            &[est("A", true, None, Some(0)), est("B", false, Some(12), Some(48)), est("C", false, None, None)],
+            4,
+        );
+        assert!(out.contains("A  Paper A  [cached]\n"));
+        assert!(out.contains("B  Paper B  [12 pages, 48 credits]\n"));
+        assert!(out.contains("C  Paper C  [page count unknown]\n"));
+        assert!(out.ends_with("≈ 48 credits for 12 pages at 4 credits/page (1 cached, 1 not estimated)\n"));
+    }
+
+    #[test]
+    fn test_format_datalab_step_types() {
+        let resp: StepTypesResponse = serde_json::from_value(serde_json::json!({
+            "step_types": [
+                {"id": 1, "type": "step", "step_type": "marker_parse", "name": "Marker Parse",
+                 "description": "Convert a document to markdown", "settings_schema": {},
+                 "version": "1.0", "is_public": true},
+                {"id": 2, "type": "step", "step_type": "ocr", "name": "OCR",
+                 "description": "", "settings_schema": {}, "version": "2.1", "is_public": true}
+            ]
+        }))
+        .unwrap();
+        assert_eq!(
+            format_datalab_step_types(&resp),
+            "marker_parse  Marker Parse (v1.0)\n              Convert a document to markdown\nocr           OCR (v2.1)\n"
+        );
+    }
+
+    #[test]
+    fn retracted_works_are_marked() {
+        let work = |id: &str, retracted: Option<bool>| -> Work {
+            serde_json::from_value(serde_json::json!({
+                "id": format!("https://openalex.org/{id}"),
+                "display_name": format!("Paper {id}"),
+                "publication_year": 2020,
+                "is_retracted": retracted,
+            }))
+            .unwrap()
+        };
+        let get = format_work_get(&work("W1", Some(true)), 0);
+        assert!(get.starts_with("Work: Paper W1\n⚠ RETRACTED\n"));
+        assert!(!format_work_get(&work("W2", Some(false)), 0).contains("RETRACTED"));
+
+        let resp = SlimListResponse {
+            meta: ListMeta {
+                count: 3,
+                db_response_time_ms: 0,
+                page: Some(1),
+                per_page: Some(10),
+                next_cursor: None,
+                groups_count: None,
+            },
+            results: vec![
+                WorkSummary::from(work("W1", Some(true))),
+                WorkSummary::from(work("W2", Some(false))),
+                WorkSummary::from(work("W3", None)),
+            ],
+        };
+        let list = format_work_list(&resp, 0);
+        assert!(list.contains(" 1  Paper W1 (2020)\n     ⚠ RETRACTED\n"));
+        assert_eq!(list.matches("RETRACTED").count(), 1);
+        assert!(!resp.results[2].is_retracted);
+    }
+
+    #[cfg(feature = "arrow")]
+    #[test]
+    fn work_list_parquet_round_trip() {
+        use arrow::array::{Array, Int32Array, Int64Array, StringArray};
+        use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+        let work: Work =
+            serde_json::from_str(include_str!("../../papers-openalex/tests/fixtures/work.json")).unwrap();
+        let bare: Work =
+            serde_json::from_value(serde_json::json!({"id": "https://openalex.org/W2"})).unwrap();
+        let results = vec![WorkSummary::from(work), WorkSummary::from(bare)];
+
+        let path = std::env::temp_dir().join(format!("papers-parquet-{}.parquet", std::process::id()));
+        format_work_list_parquet(&results, &path).unwrap();
+        let file = std::fs::File::open(&path).unwrap();
+        let batches: Vec<_> = ParquetRecordBatchReaderBuilder::try_new(file)
+            .unwrap()
+            .build()
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(batches.len(), 1);
+        let batch = &batches[0];
+        assert_eq!(batch.num_rows(), 2);
+        let column = |name: &str| batch.column_by_name(name).unwrap().clone();
+        let ids = column("id");
+        let ids = ids.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(ids.value(0), "https://openalex.org/W2741809807");
+        assert_eq!(ids.value(1), "https://openalex.org/W2");
+        let years = column("year");
+        let years = years.as_any().downcast_ref::<Int32Array>().unwrap();
+        assert_eq!(years.value(0), results[0].publication_year.unwrap());
+        assert!(years.is_null(1));
+        let citations = column("citations");
+        let citations = citations.as_any().downcast_ref::<Int64Array>().unwrap();
+        assert_eq!(citations.value(0), results[0].cited_by_count.unwrap());
+        let venue = column("venue");
+        let venue = venue.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(venue.value(0), "PeerJ");
+        assert!(venue.is_null(1));
+    }
+
+    fn timeline_work(counts_by_year: serde_json::Value) -> Work {
+        serde_json::from_value(serde_json::json!({
+            "id": "https://openalex.org/W1",
+            "display_name": "A Great Paper",
+            "counts_by_year": counts_by_year,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn citations_timeline_sorts_years_and_scales_bars() {
+        // OpenAlex returns newest first
+        let w = timeline_work(serde_json::json!([
+            {"year": 2022, "cited_by_count": 40},
+            {"year": 2021, "cited_by_count": 0},
+            {"year": 2020, "cited_by_count": 10},
+        ]));
+        let out = format_work_citations_timeline(&w);
+        let rows: Vec<&str> = out.lines().filter(|l| l.starts_with("  20")).collect();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0], format!("  2020  10  {}", "█".repeat(10)));
+        assert_eq!(rows[1], "  2021   0  ");
+        assert_eq!(rows[2], format!("  2022  40  {}", "█".repeat(40)));
+        assert!(out.contains("50 citations across 3 years"));
+    }
+
+    #[test]
+    fn author_comparison_lines_up_columns() {
+        let author = |id: &str, name: &str, stats: serde_json::Value| -> Author {
+            serde_json::from_value(serde_json::json!({
+                "id": format!("https://openalex.org/{id}"),
+                "display_name": name,
+                "works_count": 120,
+                "cited_by_count": 5400,
+                "summary_stats": stats,
+            }))
+            .unwrap()
+        };
+        let a = author("A1", "Alice Smith", serde_json::json!({"h_index": 31, "i10_index": 70}));
+        let b = author("A2", "Bo", serde_json::json!({"h_index": 9}));
+        let out = format_author_comparison(&a, &b);
+        let lines: Vec<_> = out.lines().collect();
+        assert_eq!(lines[0], "            Alice Smith  Bo");
+        assert_eq!(lines[1], "ID          A1           A2");
+        assert_eq!(lines[2], "Works       120          120");
+        assert_eq!(lines[4], "h-index     31           9");
+        assert_eq!(lines[5], "i10-index   70           -");
+    }
+
+    #[test]
+    fn source_impact_card_renders_stats() {
+        let source: Source =
+            serde_json::from_str(include_str!("../../papers-openalex/tests/fixtures/source.json")).unwrap();
+        let out = format_source_impact(&source);
+        let lines: Vec<_> = out.lines().collect();
+        assert_eq!(lines[0], "Nature (ISSN 0028-0836)");
+        assert_eq!(lines[1], "  h-index             1822");
+        assert_eq!(lines[2], "  2yr mean citedness  14.71");
+        assert_eq!(lines[3], "  Works               447325");
+        assert_eq!(lines[4], "  OA works            56.9%");
+
+        let bare: Source = serde_json::from_value(serde_json::json!({"id": "https://openalex.org/S1"})).unwrap();
+        assert!(format_source_impact(&bare).contains("  OA works            -\n"));
+    }
+
+    #[test]
+    fn citations_timeline_empty_counts() {
+        let out = format_work_citations_timeline(&timeline_work(serde_json::json!([])));
+        assert!(out.contains("No yearly citation data."));
+        let out = format_work_citations_timeline(&timeline_work(serde_json::Value::Null));
+        assert!(out.contains("No yearly citation data."));
+    }
+
+    #[test]
+    fn work_suggestions_render_in_text_mode() {
+        let suggestions = vec![
+            ("Attention Is All You Need".to_string(), 90000),
+            ("Attention Is Not All You Need".to_string(), 120),
+        ];
+        let out = format_work_suggestions("attention all you need", &suggestions);
+        assert!(out.starts_with("No exact match for \"attention all you need\". Did you mean:"));
+        assert!(out.contains("  1  Attention Is All You Need\n     90000 citations\n"));
+        assert!(out.contains("  2  Attention Is Not All You Need\n     120 citations\n"));
+    }
+
+    #[test]
+    fn truncate_chars_short_input_unchanged() {
+        assert_eq!(truncate_chars("abc", 3), "abc");
+        assert!(matches!(truncate_chars("abc", 10), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn truncate_chars_ascii() {
+        assert_eq!(truncate_chars("abcdef", 3), "abc…");
+    }
+
+    #[test]
+    fn truncate_chars_multibyte_at_boundary() {
+        // β is 2 bytes, → is 3 bytes, ℝ is 3 bytes: byte slicing at 3 would split →.
+        let s = "β→ℝx";
+        assert_eq!(truncate_chars(s, 1), "β…");
+        assert_eq!(truncate_chars(s, 2), "β→…");
+        assert_eq!(truncate_chars(s, 3), "β→ℝ…");
+        assert_eq!(truncate_chars(s, 4), "β→ℝx");
+    }
+
+    #[test]
+    fn truncate_chars_emoji_and_cjk() {
+        assert_eq!(truncate_chars("🦀🦀🦀", 2), "🦀🦀…");
+        assert_eq!(truncate_chars("深層学習の研究", 4), "深層学習…");
+    }
+
+    #[test]
+    fn truncate_full_by_default_zero_is_full() {
+        let s = "Ünïcödé abstract";
+        assert_eq!(truncate_full_by_default(s, 0), s);
+        assert_eq!(truncate_full_by_default(s, 3), "Ünï…");
+    }
+}
@@ -1,38 +1,55 @@
 mod cli;
 mod format;
+#[cfg(feature = "sqlite")]
+mod sqlite;
 
 use clap::Parser;
 use cli::{
-    AdvancedMode, AuthorCommand, AuthorFilterArgs, Cli, DomainCommand, DomainFilterArgs,
-    EntityCommand, FieldCommand, FieldFilterArgs, FunderCommand, FunderFilterArgs,
+    AdvancedMode, AnnotationFormat, AnnotationType, AuthorCommand, AuthorFilterArgs, Cli, DatalabCommand, DomainCommand, DomainFilterArgs,
+    Detail, EntityCommand, ExportFormat, ExtractFormat, ExtractSort, FieldCommand, FieldFilterArgs, FunderCommand, FunderFilterArgs,
     InstitutionCommand, InstitutionFilterArgs, PublisherCommand, PublisherFilterArgs,
     SourceCommand, SourceFilterArgs, SubfieldCommand, SubfieldFilterArgs, TopicCommand,
     TopicFilterArgs, WorkCommand, WorkFilterArgs, ZoteroAnnotationCommand, ZoteroAttachmentCommand,
-    ZoteroCollectionCommand, ZoteroCommand, ZoteroDeletedCommand, ZoteroExtractCommand,
+    ZoteroCollectionCommand, ZoteroCommand, ZoteroDeletedCommand, ZoteroExtractCommand, ZoteroFulltextCommand,
     ZoteroGroupCommand, ZoteroNoteCommand, ZoteroPermissionCommand, ZoteroSearchCommand,
     ZoteroSettingCommand, ZoteroTagCommand, ZoteroWorkCommand,
 };
 use papers_core::{
     filter::FilterError,
-    AuthorListParams, DiskCache, DomainListParams, FieldListParams, FindWorksParams,
+    AuthorListParams, DomainListParams, CACHE_DIR_ENV, FieldListParams, FindWorksParams,
     FunderListParams, GetParams, InstitutionListParams, OpenAlexClient, PublisherListParams,
     SourceListParams, SubfieldListParams, TopicListParams, WorkListParams,
 };
-use papers_core::zotero::{resolve_collection_key, resolve_item_key, resolve_search_key};
-use papers_zotero::{CollectionListParams, DeletedParams, Item, ItemListParams, TagListParams, ZoteroClient};
-use std::time::Duration;
+use papers_core::zotero::{
+    ImportOutcome, resolve_collection_key, resolve_item_key, resolve_search_key,
+};
+use papers_zotero::{CollectionListParams, DeletedParams, Item, ItemListParams, TagListParams, Transport, ZoteroClient};
+use std::sync::OnceLock;
+
+/// Transport forced by `--zotero-local`/`--zotero-remote`; `None` defers to
+/// `$ZOTERO_TRANSPORT`.
+static ZOTERO_TRANSPORT: OnceLock<Option<Transport>> = OnceLock::new();
 
 async fn zotero_client() -> Result<ZoteroClient, papers_zotero::ZoteroError> {
-    ZoteroClient::from_env_prefer_local().await
+    let transport = match ZOTERO_TRANSPORT.get().copied().flatten() {
+        Some(t) => t,
+        None => Transport::from_env()?,
+    };
+    ZoteroClient::from_env_with_transport(transport).await
 }
 
 /// Returns the Zotero client when available, `Ok(None)` when Zotero is simply
 /// not configured (env vars absent), or `Err` when Zotero is installed but not
-/// running (so the caller can surface the error).
+/// running, a forced local API is unreachable, or a Zotero env var is invalid
+/// (so the caller can surface the error).
 async fn optional_zotero() -> Result<Option<ZoteroClient>, papers_zotero::ZoteroError> {
-    match ZoteroClient::from_env_prefer_local().await {
+    match zotero_client().await {
         Ok(z) => Ok(Some(z)),
-        Err(e @ papers_zotero::ZoteroError::NotRunning { .. }) => Err(e),
+        Err(
+            e @ (papers_zotero::ZoteroError::NotRunning { .. }
+            | papers_zotero::ZoteroError::LocalUnavailable { .. }
+            | papers_zotero::ZoteroError::InvalidEnv { .. }),
+        ) => Err(e),
         Err(_) => Ok(None),
     }
 }
@@ -58,11 +75,14 @@ fn work_list_params(args: &cli::ListArgs, wf: &WorkFilterArgs) -> WorkListParams
         source: wf.source.clone(),
         institution: wf.institution.clone(),
         year: wf.year.clone(),
+        from_publication_date: wf.from_publication_date.clone(),
+        to_publication_date: wf.to_publication_date.clone(),
         citations: wf.citations.clone(),
         country: wf.country.clone(),
         continent: wf.continent.clone(),
         r#type: wf.entity_type.clone(),
         open: if wf.open { Some(true) } else { None },
+        exclude_retracted: if wf.exclude_retracted { Some(true) } else { None },
     }
 }
 
@@ -106,6 +126,8 @@ fn source_list_params(args: &cli::ListArgs, sf: &SourceFilterArgs) -> SourceList
         open: if sf.open { Some(true) } else { None },
         citations: sf.citations.clone(),
         works: sf.works.clone(),
+        is_in_doaj: sf.doaj,
+        has_apc: sf.apc,
     }
 }
 
@@ -238,16 +260,33 @@ fn subfield_list_params(args: &cli::ListArgs, sf: &SubfieldFilterArgs) -> Subfie
     }
 }
 
+/// Set from `--sort-keys` before any command runs.
+static SORT_KEYS: OnceLock<bool> = OnceLock::new();
+
+/// With `--show-version`, report a response's `Last-Modified-Version` on
+/// stderr, leaving stdout (and `--json` output) untouched.
+fn print_version(show: bool, version: Option<u64>) {
+    if show {
+        eprint!("{}", format::format_last_modified_version(version));
+    }
+}
+
 fn print_json<T: serde::Serialize>(val: &T) {
-    println!("{}", serde_json::to_string_pretty(val).expect("JSON serialization failed"));
+    let json = if SORT_KEYS.get().copied().unwrap_or(false) {
+        format::to_json_sorted(val)
+    } else {
+        serde_json::to_string_pretty(val)
+    };
+    println!("{}", json.expect("JSON serialization failed"));
 }
 
-/// Returns true if this attachment can have annotation children (PDF, EPUB, or HTML snapshot).
-fn is_annotatable_attachment(att: &Item) -> bool {
-    matches!(
-        att.data.content_type.as_deref(),
-        Some("application/pdf") | Some("application/epub+zip") | Some("text/html")
-    )
+/// Print an unmodified OpenAlex response body (`--raw`).
+fn print_raw(body: Result<String, FilterError>) {
+    match body {
+        Ok(body) if body.ends_with('\n') => print!("{body}"),
+        Ok(body) => println!("{body}"),
+        Err(e) => exit_err(&e.to_string()),
+    }
 }
 
 fn exit_err(msg: &str) -> ! {
@@ -255,6 +294,13 @@ fn exit_err(msg: &str) -> ! {
     std::process::exit(1);
 }
 
+/// Cached DataLab markdown for `key`, or exit pointing at `work extract`.
+fn cached_markdown_or_exit(key: &str) -> String {
+    papers_core::text::datalab_cached_markdown(key).unwrap_or_else(|| {
+        exit_err(&format!("No cached extraction for {key}. Run: papers zotero work extract {key}"))
+    })
+}
+
 /// Find the first PDF attachment key for a given item key.
 async fn find_pdf_attachment_key(zotero: &ZoteroClient, item_key: &str) -> Result<String, String> {
     Ok(find_pdf_attachment(zotero, item_key).await?.key)
@@ -325,29 +371,93 @@ fn main() {
         .expect("main thread panicked");
 }
 
+/// Send the API clients' request diagnostics (`openalex_request` and
+/// `zotero_request` spans) to stderr.
+fn init_diagnostics() {
+    use tracing::Level;
+    use tracing_subscriber::filter::Targets;
+    use tracing_subscriber::layer::SubscriberExt as _;
+
+    // Only our clients: h2 and hyper-util log their own debug events
+    let targets = Targets::new()
+        .with_target("papers_openalex", Level::DEBUG)
+        .with_target("papers_zotero", Level::DEBUG);
+    let subscriber = tracing_subscriber::fmt()
+        .with_max_level(Level::DEBUG)
+        .with_writer(std::io::stderr)
+        .finish()
+        .with(targets);
+    tracing::subscriber::set_global_default(subscriber).expect("tracing subscriber already set");
+}
+
 async fn papers_main() {
     let cli = Cli::parse();
+    if cli.verbose {
+        init_diagnostics();
+    }
+    let abstract_chars = cli.abstract_chars;
+    SORT_KEYS.set(cli.sort_keys).expect("--sort-keys is only set once");
+    ZOTERO_TRANSPORT.set(cli.zotero_transport()).expect("the Zotero transport is only set once");
     let mut client = OpenAlexClient::new();
-    if let Ok(cache) = DiskCache::default_location(Duration::from_secs(600)) {
-        client = client.with_cache(cache);
+    match cli.response_cache(std::env::var_os(CACHE_DIR_ENV)) {
+        Ok(Some(cache)) => client = client.with_cache(cache),
+        Ok(None) => {}
+        Err(e) => exit_err(&e),
     }
 
     match cli.entity {
-        EntityCommand::Work { cmd } => match cmd {
-            WorkCommand::List { args, work_filters } => {
+        EntityCommand::Work { cmd } => match *cmd {
+            WorkCommand::List { args, work_filters, .. } if args.raw => {
+                print_raw(papers_core::api::work_list_raw(&client, &work_list_params(&args, &work_filters)).await);
+            }
+            WorkCommand::List { args, work_filters, detail, limit_total, parquet, to_sqlite } => {
                 let params = work_list_params(&args, &work_filters);
-                match papers_core::api::work_list(&client, &params).await {
+                let profile = match detail {
+                    Detail::Minimal => papers_core::summary::SlimProfile::Minimal,
+                    Detail::Standard => papers_core::summary::SlimProfile::Standard,
+                    Detail::Detailed => papers_core::summary::SlimProfile::Detailed,
+                };
+                let result = match limit_total {
+                    Some(n) => papers_core::api::work_list_limited(&client, &params, profile, n).await,
+                    None => papers_core::api::work_list_with_profile(&client, &params, profile).await,
+                };
+                match result {
                     Ok(resp) => {
+                        if let Some(path) = &parquet {
+                            #[cfg(feature = "arrow")]
+                            if let Err(e) = format::format_work_list_parquet(&resp.results, path) {
+                                exit_err(&format!("Failed to write {}: {e}", path.display()));
+                            }
+                            #[cfg(not(feature = "arrow"))]
+                            exit_err(&format!(
+                                "cannot write {}: papers was built without the `arrow` feature",
+                                path.display()
+                            ));
+                        }
+                        if let Some(path) = &to_sqlite {
+                            #[cfg(feature = "sqlite")]
+                            if let Err(e) = sqlite::open(path).and_then(|mut conn| sqlite::upsert_works(&mut conn, &resp.results)) {
+                                exit_err(&format!("Failed to write {}: {e}", path.display()));
+                            }
+                            #[cfg(not(feature = "sqlite"))]
+                            exit_err(&format!(
+                                "cannot write {}: papers was built without the `sqlite` feature",
+                                path.display()
+                            ));
+                        }
                         if args.json {
                             print_json(&resp);
                         } else {
-                            print!("{}", format::format_work_list(&resp));
+                            print!("{}", format::format_work_list(&resp, abstract_chars));
                         }
                     }
                     Err(e) => exit_err(&e.to_string()),
                 }
             }
-            WorkCommand::Get { id, json } => {
+            WorkCommand::Get { id, raw: true, .. } => {
+                print_raw(papers_core::api::work_get_raw(&client, &id, &GetParams::default()).await);
+            }
+            WorkCommand::Get { id, json, .. } => {
                 let zotero = optional_zotero().await.unwrap_or_else(|e| exit_err(&e.to_string()));
                 let zotero_configured = zotero.is_some();
                 match papers_core::api::work_get_response(&client, zotero.as_ref(), &id, &GetParams::default()).await {
@@ -355,7 +465,7 @@ async fn papers_main() {
                         if json {
                             print_json(&response);
                         } else {
-                            print!("{}", format::format_work_get_response(&response, zotero_configured));
+                            print!("{}", format::format_work_get_response(&response, zotero_configured, abstract_chars));
                         }
                     }
                     Err(FilterError::Suggestions { query, suggestions }) if json => {
@@ -370,6 +480,25 @@ async fn papers_main() {
                         }));
                         std::process::exit(1);
                     }
+                    Err(FilterError::Suggestions { query, suggestions }) => {
+                        eprint!("{}", format::format_work_suggestions(&query, &suggestions));
+                        std::process::exit(1);
+                    }
+                    Err(e) => exit_err(&e.to_string()),
+                }
+            }
+            WorkCommand::Citations { id, json } => {
+                let params = GetParams::builder()
+                    .select("id,display_name,counts_by_year")
+                    .build();
+                match papers_core::api::work_get(&client, &id, &params).await {
+                    Ok(work) => {
+                        if json {
+                            print_json(&work.counts_by_year.unwrap_or_default());
+                        } else {
+                            print!("{}", format::format_work_citations_timeline(&work));
+                        }
+                    }
                     Err(e) => exit_err(&e.to_string()),
                 }
             }
@@ -454,13 +583,7 @@ async fn papers_main() {
                         }
 
                         // Open DOI URL in browser
-                        let url = format!("https://doi.org/{bare}");
-                        #[cfg(target_os = "windows")]
-                        let _ = std::process::Command::new("cmd").args(["/c", "start", &url]).spawn();
-                        #[cfg(target_os = "macos")]
-                        let _ = std::process::Command::new("open").arg(&url).spawn();
-                        #[cfg(target_os = "linux")]
-                        let _ = std::process::Command::new("xdg-open").arg(&url).spawn();
+                        let _ = papers_core::browser::open_url(&format!("https://doi.org/{bare}"));
 
                         eprintln!("Waiting for paper to appear in Zotero...");
                         match papers_core::text::poll_zotero_for_work(
@@ -482,9 +605,40 @@ async fn papers_main() {
                     Err(e) => exit_err(&e.to_string()),
                 }
             }
+            WorkCommand::Open { id } => {
+                let work = papers_core::api::work_get(&client, &id, &GetParams::default())
+                    .await
+                    .unwrap_or_else(|e| exit_err(&e.to_string()));
+                let (link, url) = papers_core::browser::work_open_url(&work);
+                println!("Opening {link}: {url}");
+                if let Err(e) = papers_core::browser::open_url(&url) {
+                    exit_err(&format!("Failed to open browser: {e}"));
+                }
+            }
+            WorkCommand::Import { id, json } => {
+                let zotero = zotero_client().await.unwrap_or_else(|e| exit_err(&e.to_string()));
+                let work = papers_core::api::work_get(&client, &id, &GetParams::default())
+                    .await
+                    .unwrap_or_else(|e| exit_err(&e.to_string()));
+                let title = work.display_name.as_deref().unwrap_or(&work.id);
+                match papers_core::zotero::import_work(&zotero, &work).await {
+                    Ok(outcome) if json => print_json(&outcome),
+                    Ok(ImportOutcome::Created(key)) => {
+                        println!("Created Zotero item {key}: {title}");
+                    }
+                    Ok(ImportOutcome::AlreadyExists(key)) => {
+                        println!("Skipped: \"{title}\" is already in Zotero as {key}");
+                    }
+                    Err(e) => exit_err(&e.to_string()),
+                }
+            }
         },
 
         EntityCommand::Author { cmd } => match cmd {
+            AuthorCommand::List { args, filters } if args.raw => {
+                let params = author_list_params(&args, &filters);
+                print_raw(papers_core::api::author_list_raw(&client, &params).await);
+            }
             AuthorCommand::List { args, filters } => {
                 let params = author_list_params(&args, &filters);
                 match papers_core::api::author_list(&client, &params).await {
@@ -498,7 +652,10 @@ async fn papers_main() {
                     Err(e) => exit_err(&e.to_string()),
                 }
             }
-            AuthorCommand::Get { id, json } => {
+            AuthorCommand::Get { id, raw: true, .. } => {
+                print_raw(papers_core::api::author_get_raw(&client, &id, &GetParams::default()).await);
+            }
+            AuthorCommand::Get { id, json, .. } => {
                 match papers_core::api::author_get(&client, &id, &GetParams::default()).await {
                     Ok(author) => {
                         if json {
@@ -522,9 +679,30 @@ async fn papers_main() {
                     Err(e) => exit_err(&e.to_string()),
                 }
             }
+            AuthorCommand::Compare { first, second, json } => {
+                let params = GetParams::builder()
+                    .select("id,display_name,works_count,cited_by_count,summary_stats")
+                    .build();
+                let mut authors = Vec::with_capacity(2);
+                for id in [&first, &second] {
+                    match papers_core::api::author_get(&client, id, &params).await {
+                        Ok(author) => authors.push(author),
+                        Err(e) => exit_err(&e.to_string()),
+                    }
+                }
+                if json {
+                    print_json(&authors);
+                } else {
+                    print!("{}", format::format_author_comparison(&authors[0], &authors[1]));
+                }
+            }
         },
 
         EntityCommand::Source { cmd } => match cmd {
+            SourceCommand::List { args, filters } if args.raw => {
+                let params = source_list_params(&args, &filters);
+                print_raw(papers_core::api::source_list_raw(&client, &params).await);
+            }
             SourceCommand::List { args, filters } => {
                 let params = source_list_params(&args, &filters);
                 match papers_core::api::source_list(&client, &params).await {
@@ -538,7 +716,10 @@ async fn papers_main() {
                     Err(e) => exit_err(&e.to_string()),
                 }
             }
-            SourceCommand::Get { id, json } => {
+            SourceCommand::Get { id, raw: true, .. } => {
+                print_raw(papers_core::api::source_get_raw(&client, &id, &GetParams::default()).await);
+            }
+            SourceCommand::Get { id, json, .. } => {
                 match papers_core::api::source_get(&client, &id, &GetParams::default()).await {
                     Ok(source) => {
                         if json {
@@ -562,9 +743,28 @@ async fn papers_main() {
                     Err(e) => exit_err(&e.to_string()),
                 }
             }
+            SourceCommand::Impact { source, json } => {
+                let params = GetParams::builder()
+                    .select("id,display_name,issn_l,works_count,summary_stats,counts_by_year")
+                    .build();
+                match papers_core::api::source_get(&client, &source, &params).await {
+                    Ok(source) => {
+                        if json {
+                            print_json(&source);
+                        } else {
+                            print!("{}", format::format_source_impact(&source));
+                        }
+                    }
+                    Err(e) => exit_err(&e.to_string()),
+                }
+            }
         },
 
         EntityCommand::Institution { cmd } => match cmd {
+            InstitutionCommand::List { args, filters } if args.raw => {
+                let params = institution_list_params(&args, &filters);
+                print_raw(papers_core::api::institution_list_raw(&client, &params).await);
+            }
             InstitutionCommand::List { args, filters } => {
                 let params = institution_list_params(&args, &filters);
                 match papers_core::api::institution_list(&client, &params).await {
@@ -578,7 +778,10 @@ async fn papers_main() {
                     Err(e) => exit_err(&e.to_string()),
                 }
             }
-            InstitutionCommand::Get { id, json } => {
+            InstitutionCommand::Get { id, raw: true, .. } => {
+                print_raw(papers_core::api::institution_get_raw(&client, &id, &GetParams::default()).await);
+            }
+            InstitutionCommand::Get { id, json, .. } => {
                 match papers_core::api::institution_get(&client, &id, &GetParams::default()).await {
                     Ok(inst) => {
                         if json {
@@ -590,8 +793,8 @@ async fn papers_main() {
                     Err(e) => exit_err(&e.to_string()),
                 }
             }
-            InstitutionCommand::Autocomplete { query, json } => {
-                match papers_core::api::institution_autocomplete(&client, &query).await {
+            InstitutionCommand::Autocomplete { query, country, json } => {
+                match papers_core::api::institution_autocomplete(&client, &query, country.as_deref()).await {
                     Ok(resp) => {
                         if json {
                             print_json(&resp);
@@ -605,6 +808,10 @@ async fn papers_main() {
         },
 
         EntityCommand::Topic { cmd } => match cmd {
+            TopicCommand::List { args, filters } if args.raw => {
+                let params = topic_list_params(&args, &filters);
+                print_raw(papers_core::api::topic_list_raw(&client, &params).await);
+            }
             TopicCommand::List { args, filters } => {
                 let params = topic_list_params(&args, &filters);
                 match papers_core::api::topic_list(&client, &params).await {
@@ -618,7 +825,10 @@ async fn papers_main() {
                     Err(e) => exit_err(&e.to_string()),
                 }
             }
-            TopicCommand::Get { id, json } => {
+            TopicCommand::Get { id, raw: true, .. } => {
+                print_raw(papers_core::api::topic_get_raw(&client, &id, &GetParams::default()).await);
+            }
+            TopicCommand::Get { id, json, .. } => {
                 match papers_core::api::topic_get(&client, &id, &GetParams::default()).await {
                     Ok(topic) => {
                         if json {
@@ -633,6 +843,10 @@ async fn papers_main() {
         },
 
         EntityCommand::Publisher { cmd } => match cmd {
+            PublisherCommand::List { args, filters } if args.raw => {
+                let params = publisher_list_params(&args, &filters);
+                print_raw(papers_core::api::publisher_list_raw(&client, &params).await);
+            }
             PublisherCommand::List { args, filters } => {
                 let params = publisher_list_params(&args, &filters);
                 match papers_core::api::publisher_list(&client, &params).await {
@@ -646,7 +860,10 @@ async fn papers_main() {
                     Err(e) => exit_err(&e.to_string()),
                 }
             }
-            PublisherCommand::Get { id, json } => {
+            PublisherCommand::Get { id, raw: true, .. } => {
+                print_raw(papers_core::api::publisher_get_raw(&client, &id, &GetParams::default()).await);
+            }
+            PublisherCommand::Get { id, json, .. } => {
                 match papers_core::api::publisher_get(&client, &id, &GetParams::default()).await {
                     Ok(pub_) => {
                         if json {
@@ -673,6 +890,10 @@ async fn papers_main() {
         },
 
         EntityCommand::Funder { cmd } => match cmd {
+            FunderCommand::List { args, filters } if args.raw => {
+                let params = funder_list_params(&args, &filters);
+                print_raw(papers_core::api::funder_list_raw(&client, &params).await);
+            }
             FunderCommand::List { args, filters } => {
                 let params = funder_list_params(&args, &filters);
                 match papers_core::api::funder_list(&client, &params).await {
@@ -686,7 +907,10 @@ async fn papers_main() {
                     Err(e) => exit_err(&e.to_string()),
                 }
             }
-            FunderCommand::Get { id, json } => {
+            FunderCommand::Get { id, raw: true, .. } => {
+                print_raw(papers_core::api::funder_get_raw(&client, &id, &GetParams::default()).await);
+            }
+            FunderCommand::Get { id, json, .. } => {
                 match papers_core::api::funder_get(&client, &id, &GetParams::default()).await {
                     Ok(funder) => {
                         if json {
@@ -710,9 +934,30 @@ async fn papers_main() {
                     Err(e) => exit_err(&e.to_string()),
                 }
             }
+            FunderCommand::Works { funder, args } if args.raw => {
+                let params = work_list_params(&args, &WorkFilterArgs::default());
+                print_raw(papers_core::api::funder_works_raw(&client, &funder, &params).await);
+            }
+            FunderCommand::Works { funder, args } => {
+                let params = work_list_params(&args, &WorkFilterArgs::default());
+                match papers_core::api::funder_works(&client, &funder, &params).await {
+                    Ok(resp) => {
+                        if args.json {
+                            print_json(&resp);
+                        } else {
+                            print!("{}", format::format_work_list(&resp, abstract_chars));
+                        }
+                    }
+                    Err(e) => exit_err(&e.to_string()),
+                }
+            }
         },
 
         EntityCommand::Domain { cmd } => match cmd {
+            DomainCommand::List { args, filters } if args.raw => {
+                let params = domain_list_params(&args, &filters);
+                print_raw(papers_core::api::domain_list_raw(&client, &params).await);
+            }
             DomainCommand::List { args, filters } => {
                 let params = domain_list_params(&args, &filters);
                 match papers_core::api::domain_list(&client, &params).await {
@@ -726,7 +971,10 @@ async fn papers_main() {
                     Err(e) => exit_err(&e.to_string()),
                 }
             }
-            DomainCommand::Get { id, json } => {
+            DomainCommand::Get { id, raw: true, .. } => {
+                print_raw(papers_core::api::domain_get_raw(&client, &id, &GetParams::default()).await);
+            }
+            DomainCommand::Get { id, json, .. } => {
                 match papers_core::api::domain_get(&client, &id, &GetParams::default()).await {
                     Ok(domain) => {
                         if json {
@@ -741,6 +989,10 @@ async fn papers_main() {
         },
 
         EntityCommand::Field { cmd } => match cmd {
+            FieldCommand::List { args, filters } if args.raw => {
+                let params = field_list_params(&args, &filters);
+                print_raw(papers_core::api::field_list_raw(&client, &params).await);
+            }
             FieldCommand::List { args, filters } => {
                 let params = field_list_params(&args, &filters);
                 match papers_core::api::field_list(&client, &params).await {
@@ -754,7 +1006,10 @@ async fn papers_main() {
                     Err(e) => exit_err(&e.to_string()),
                 }
             }
-            FieldCommand::Get { id, json } => {
+            FieldCommand::Get { id, raw: true, .. } => {
+                print_raw(papers_core::api::field_get_raw(&client, &id, &GetParams::default()).await);
+            }
+            FieldCommand::Get { id, json, .. } => {
                 match papers_core::api::field_get(&client, &id, &GetParams::default()).await {
                     Ok(field) => {
                         if json {
@@ -769,6 +1024,10 @@ async fn papers_main() {
         },
 
         EntityCommand::Subfield { cmd } => match cmd {
+            SubfieldCommand::List { args, filters } if args.raw => {
+                let params = subfield_list_params(&args, &filters);
+                print_raw(papers_core::api::subfield_list_raw(&client, &params).await);
+            }
             SubfieldCommand::List { args, filters } => {
                 let params = subfield_list_params(&args, &filters);
                 match papers_core::api::subfield_list(&client, &params).await {
@@ -782,7 +1041,10 @@ async fn papers_main() {
                     Err(e) => exit_err(&e.to_string()),
                 }
             }
-            SubfieldCommand::Get { id, json } => {
+            SubfieldCommand::Get { id, raw: true, .. } => {
+                print_raw(papers_core::api::subfield_get_raw(&client, &id, &GetParams::default()).await);
+            }
+            SubfieldCommand::Get { id, json, .. } => {
                 match papers_core::api::subfield_get(&client, &id, &GetParams::default()).await {
                     Ok(subfield) => {
                         if json {
@@ -808,13 +1070,28 @@ async fn papers_main() {
             }
         },
 
-        EntityCommand::Zotero { cmd } => {
+        EntityCommand::Zotero { show_version, cmd } => {
+            // Runs before connecting, since diagnosing a failed connection is the point.
+            if let ZoteroCommand::Doctor { json } = cmd {
+                let transport = match ZOTERO_TRANSPORT.get().copied().flatten() {
+                    Some(t) => t,
+                    None => Transport::from_env().unwrap_or_else(|e| exit_err(&e.to_string())),
+                };
+                let diagnosis =
+                    ZoteroClient::diagnose(transport).await.unwrap_or_else(|e| exit_err(&e.to_string()));
+                if json { print_json(&diagnosis); } else { print!("{}", format::format_zotero_doctor(&diagnosis)); }
+                return;
+            }
             let zotero = zotero_client().await.unwrap_or_else(|e| match e {
                 papers_zotero::ZoteroError::NotRunning { path } => exit_err(&format!(
                     "Zotero is installed ({path}) but the local API is not enabled.\n\
                      Fix: Zotero → Settings → Advanced → check \"Enable Local API\".\n\
                      Or set ZOTERO_CHECK_LAUNCHED=0 to skip this check and use the remote web API."
                 )),
+                e @ papers_zotero::ZoteroError::LocalUnavailable { .. } => {
+                    exit_err(&format!("{e}\nDrop --zotero-local (or set ZOTERO_TRANSPORT=auto) to fall back automatically."))
+                }
+                e @ papers_zotero::ZoteroError::InvalidEnv { .. } => exit_err(&e.to_string()),
                 _ => exit_err("Zotero not configured. Set ZOTERO_USER_ID and ZOTERO_API_KEY."),
             });
             match cmd {
@@ -836,6 +1113,7 @@ async fn papers_main() {
                         };
                         match zotero.list_top_items(&params).await {
                             Ok(resp) => {
+                                print_version(show_version, resp.last_modified_version);
                                 if json { print_json(&resp); } else { print!("{}", format::format_zotero_work_list(&resp)); }
                             }
                             Err(e) => exit_err(&e.to_string()),
@@ -845,7 +1123,8 @@ async fn papers_main() {
                         let key = resolve_item_key(&zotero, &key).await.unwrap_or_else(|e| exit_err(&e.to_string()));
                         match zotero.get_item(&key).await {
                             Ok(item) => {
-                                if json { print_json(&item); } else { print!("{}", format::format_zotero_item_get(&item)); }
+                                print_version(show_version, Some(item.version));
+                                if json { print_json(&item); } else { print!("{}", format::format_zotero_item_get(&item, abstract_chars)); }
                             }
                             Err(e) => exit_err(&e.to_string()),
                         }
@@ -868,7 +1147,8 @@ async fn papers_main() {
                         let params = ItemListParams { item_type: Some("note".into()), limit, start, ..Default::default() };
                         match zotero.list_item_children(&key, &params).await {
                             Ok(resp) => {
-                                if json { print_json(&resp); } else { print!("{}", format::format_zotero_note_list(&resp)); }
+                                print_version(show_version, resp.last_modified_version);
+                                if json { print_json(&resp); } else { print!("{}", format::format_zotero_note_list(&resp, abstract_chars)); }
                             }
                             Err(e) => exit_err(&e.to_string()),
                         }
@@ -878,46 +1158,99 @@ async fn papers_main() {
                         let params = ItemListParams { item_type: Some("attachment".into()), limit, start, ..Default::default() };
                         match zotero.list_item_children(&key, &params).await {
                             Ok(resp) => {
+                                print_version(show_version, resp.last_modified_version);
                                 if json { print_json(&resp); } else { print!("{}", format::format_zotero_attachment_list(&resp)); }
                             }
                             Err(e) => exit_err(&e.to_string()),
                         }
                     }
-                    ZoteroWorkCommand::Annotations { key, json } => {
+                    ZoteroWorkCommand::Annotations { key, color, annotation_type, format: output_format, json } => {
                         let key = resolve_item_key(&zotero, &key).await.unwrap_or_else(|e| exit_err(&e.to_string()));
-                        let att_params = ItemListParams { item_type: Some("attachment".into()), ..Default::default() };
-                        let attachments = zotero.list_item_children(&key, &att_params).await
+                        let mut all_annotations = papers_core::zotero::work_annotations(&zotero, &key).await
                             .unwrap_or_else(|e| exit_err(&e.to_string()));
-                        let ann_params = ItemListParams { item_type: Some("annotation".into()), ..Default::default() };
-                        let mut all_annotations = Vec::new();
-                        for att in &attachments.items {
-                            if !is_annotatable_attachment(att) { continue; }
-                            match zotero.list_item_children(&att.key, &ann_params).await {
-                                Ok(r) => all_annotations.extend(r.items),
-                                Err(_) => {},
+                        let annotation_type = annotation_type.map(|t| match t {
+                            AnnotationType::Highlight => "highlight",
+                            AnnotationType::Underline => "underline",
+                            AnnotationType::Note => "note",
+                            AnnotationType::Text => "text",
+                            AnnotationType::Image => "image",
+                            AnnotationType::Ink => "ink",
+                        });
+                        all_annotations.retain(|a| papers_core::zotero::annotation_matches(a, color.as_deref(), annotation_type));
+                        if json {
+                            print_json(&all_annotations);
+                        } else if output_format == AnnotationFormat::Markdown {
+                            print!("{}", format::format_annotations_markdown(&all_annotations));
+                        } else {
+                            print!("{}", format::format_zotero_annotation_list_vec(&all_annotations));
+                        }
+                    }
+                    ZoteroWorkCommand::Review { key, json } => {
+                        let key = resolve_item_key(&zotero, &key).await.unwrap_or_else(|e| exit_err(&e.to_string()));
+                        match papers_core::zotero::work_review(&zotero, &key).await {
+                            Ok(review) => {
+                                if json { print_json(&review); } else { print!("{}", format::format_work_review(&review)); }
                             }
+                            Err(e) => exit_err(&e.to_string()),
+                        }
+                    }
+                    ZoteroWorkCommand::Tag { key, add, remove, json } => {
+                        let key = resolve_item_key(&zotero, &key).await.unwrap_or_else(|e| exit_err(&e.to_string()));
+                        let add: Vec<&str> = add.iter().map(String::as_str).collect();
+                        let remove: Vec<&str> = remove.iter().map(String::as_str).collect();
+                        match zotero.update_item_tags(&key, &add, &remove).await {
+                            Ok(tags) => {
+                                if json {
+                                    print_json(&tags);
+                                } else if tags.is_empty() {
+                                    println!("{key} has no tags");
+                                } else {
+                                    let names: Vec<&str> = tags.iter().map(|t| t.tag.as_str()).collect();
+                                    println!("Tags on {key}: {}", names.join(", "));
+                                }
+                            }
+                            Err(e) => exit_err(&e.to_string()),
                         }
-                        if json { print_json(&all_annotations); } else { print!("{}", format::format_zotero_annotation_list_vec(&all_annotations)); }
                     }
                     ZoteroWorkCommand::Tags { key, search, limit, start, json } => {
                         let key = resolve_item_key(&zotero, &key).await.unwrap_or_else(|e| exit_err(&e.to_string()));
                         let params = TagListParams { q: search, qmode: Some("contains".to_string()), limit, start, ..Default::default() };
                         match zotero.list_item_tags(&key, &params).await {
                             Ok(resp) => {
+                                print_version(show_version, resp.last_modified_version);
                                 if json { print_json(&resp); } else { print!("{}", format::format_zotero_tag_list(&resp)); }
                             }
                             Err(e) => exit_err(&e.to_string()),
                         }
                     }
-                    ZoteroWorkCommand::Extract { key: input, mode } => {
+                    ZoteroWorkCommand::Extract { key: input, mode, format, dry_run, clean } => {
                         let is_exact_key = papers_core::zotero::looks_like_zotero_key(&input);
+                        let html = format == ExtractFormat::Html;
+                        if html && clean {
+                            exit_err("--clean only applies to markdown output");
+                        }
+                        let render = |extraction: papers_core::text::Extraction| match (html, extraction.html) {
+                            (true, Some(html)) => html,
+                            (true, None) => exit_err("DataLab returned no HTML for this item"),
+                            (false, _) if clean => papers_core::text::clean_markdown(&extraction.markdown),
+                            (false, _) => extraction.markdown,
+                        };
 
                         // Resolve to a concrete item key.
                         let key = resolve_item_key(&zotero, &input).await.unwrap_or_else(|e| exit_err(&e.to_string()));
 
                         // Cache hit: return immediately regardless of how the key was specified.
-                        if let Some(markdown) = papers_core::text::datalab_cached_markdown(&key) {
-                            print!("{markdown}");
+                        let cached = if html {
+                            papers_core::text::datalab_cached_html(&key)
+                        } else {
+                            papers_core::text::datalab_cached_markdown(&key)
+                        };
+                        if let Some(text) = cached {
+                            if dry_run {
+                                println!("{key}: already extracted, no credits would be spent");
+                            } else {
+                                print!("{}", if clean { papers_core::text::clean_markdown(&text) } else { text });
+                            }
                         } else {
                             // Cache miss — if the user gave a search string (not an exact key),
                             // error out with suggestions to avoid spending DataLab credits
@@ -938,8 +1271,10 @@ async fn papers_main() {
                                 exit_err(&msg);
                             }
 
-                            // Cache miss: read the PDF from local Zotero storage (no HTTP download).
-                            let att = find_pdf_attachment(&zotero, &key).await.unwrap_or_else(|e| exit_err(&e));
+                            // Cache miss: read the document from local Zotero storage (no HTTP download).
+                            let att = papers_core::text::find_convertible_attachment(&zotero, &key)
+                                .await
+                                .unwrap_or_else(|e| exit_err(&e.to_string()));
                             let filename = att.data.filename.unwrap_or_else(|| exit_err("attachment has no filename"));
                             let local_path = dirs::home_dir()
                                 .unwrap_or_else(|| exit_err("cannot determine home dir"))
@@ -947,24 +1282,73 @@ async fn papers_main() {
                             let pdf_bytes = std::fs::read(&local_path)
                                 .unwrap_or_else(|e| exit_err(&format!("failed to read {}: {e}", local_path.display())));
 
-                            let dl = papers_datalab::DatalabClient::from_env().unwrap_or_else(|e| exit_err(&e.to_string()));
                             let processing_mode = match mode {
                                 AdvancedMode::Fast     => papers_core::text::ProcessingMode::Fast,
                                 AdvancedMode::Balanced => papers_core::text::ProcessingMode::Balanced,
                                 AdvancedMode::Accurate => papers_core::text::ProcessingMode::Accurate,
                             };
-                            let mut source = papers_core::text::PdfSource::ZoteroLocal { path: local_path.to_string_lossy().into_owned() };
-                            match papers_core::text::do_extract(pdf_bytes, &key, Some(&zotero), Some((&dl, processing_mode)), &mut source).await {
-                                Ok(markdown) => print!("{markdown}"),
-                                Err(e) => exit_err(&e.to_string()),
+                            if dry_run {
+                                let pages = papers_core::text::document_page_count(&pdf_bytes);
+                                print!("{}", format::format_cost_estimate(&key, pages, &processing_mode));
+                            } else {
+                                let dl = papers_datalab::DatalabClient::from_env()
+                                    .unwrap_or_else(|e| exit_err(&e.to_string()));
+                                let mut source = papers_core::text::PdfSource::ZoteroLocal { path: local_path.to_string_lossy().into_owned() };
+                                let formats: &[papers_core::text::OutputFormat] = if html {
+                                    &[papers_core::text::OutputFormat::Markdown, papers_core::text::OutputFormat::Json, papers_core::text::OutputFormat::Html]
+                                } else {
+                                    papers_core::text::DEFAULT_EXTRACT_FORMATS
+                                };
+                                match papers_core::text::do_extract_as(pdf_bytes, &key, Some(&zotero), Some((&dl, processing_mode)), formats, &mut source).await {
+                                    Ok(extraction) => print!("{}", render(extraction)),
+                                    Err(e) => exit_err(&e.to_string()),
+                                }
                             }
                         }
                     }
+                    ZoteroWorkCommand::Section { key, name } => {
+                        let key = resolve_item_key(&zotero, &key).await.unwrap_or_else(|e| exit_err(&e.to_string()));
+                        let md = cached_markdown_or_exit(&key);
+                        match papers_core::text::extract_section(&md, &name) {
+                            Some(section) => println!("{section}"),
+                            None => exit_err(&format!(
+                                "No section matching {name:?} in {key}. Run: papers zotero work toc {key}"
+                            )),
+                        }
+                    }
+                    ZoteroWorkCommand::Grep { key, pattern, ignore_case, context, json } => {
+                        let key = resolve_item_key(&zotero, &key).await.unwrap_or_else(|e| exit_err(&e.to_string()));
+                        let md = cached_markdown_or_exit(&key);
+                        match papers_core::text::grep_markdown(&md, &pattern, ignore_case, context) {
+                            Ok(matches) => {
+                                if json { print_json(&matches); } else { print!("{}", format::format_grep_matches(&matches)); }
+                            }
+                            Err(e) => exit_err(&e.to_string()),
+                        }
+                    }
+                    ZoteroWorkCommand::Stats { key, json } => {
+                        let key = resolve_item_key(&zotero, &key).await.unwrap_or_else(|e| exit_err(&e.to_string()));
+                        let stats = papers_core::text::document_stats(&cached_markdown_or_exit(&key));
+                        if json { print_json(&stats); } else { print!("{}", format::format_document_stats(&stats)); }
+                    }
+                    ZoteroWorkCommand::Toc { key, json } => {
+                        let key = resolve_item_key(&zotero, &key).await.unwrap_or_else(|e| exit_err(&e.to_string()));
+                        let toc = papers_core::text::extract_toc(&cached_markdown_or_exit(&key));
+                        if json {
+                            let entries: Vec<_> = toc.iter()
+                                .map(|(level, title)| serde_json::json!({ "level": level, "title": title }))
+                                .collect();
+                            print_json(&entries);
+                        } else {
+                            print!("{}", format::format_toc(&toc));
+                        }
+                    }
                     ZoteroWorkCommand::Text { key, json } => {
                         let key = resolve_item_key(&zotero, &key).await.unwrap_or_else(|e| exit_err(&e.to_string()));
                         let att_key = find_pdf_attachment_key(&zotero, &key).await.unwrap_or_else(|e| exit_err(&e));
                         match zotero.get_item_fulltext(&att_key).await {
                             Ok(resp) => {
+                                print_version(show_version, resp.last_modified_version);
                                 if json { print_json(&resp); } else { print!("{}", format::format_zotero_work_fulltext(&resp)); }
                             }
                             Err(e) => exit_err(&e.to_string()),
@@ -999,7 +1383,7 @@ async fn papers_main() {
                 },
 
                 ZoteroCommand::Extract { cmd } => match cmd {
-                    ZoteroExtractCommand::List { search, limit, json } => {
+                    ZoteroExtractCommand::List { search, limit, sort, json } => {
                         use std::collections::{HashMap, HashSet};
 
                         // 1. Local cache keys (filesystem scan)
@@ -1032,7 +1416,16 @@ async fn papers_main() {
                         // 3. Union of both sources, sorted for deterministic output
                         let mut all_keys: Vec<String> =
                             local_keys.union(&backed_up_keys).cloned().collect();
-                        all_keys.sort();
+                        let sort = match sort {
+                            ExtractSort::Key => papers_core::text::ExtractionSort::Key,
+                            ExtractSort::Date => papers_core::text::ExtractionSort::Date,
+                            ExtractSort::Title => papers_core::text::ExtractionSort::Title,
+                        };
+                        papers_core::text::sort_extraction_keys(
+                            &mut all_keys,
+                            sort,
+                            papers_core::text::read_extraction_meta,
+                        );
 
                         // 4. Apply optional search: intersect union with Zotero search results
                         let filtered_keys: Vec<String> = if let Some(ref q) = search {
@@ -1051,24 +1444,18 @@ async fn papers_main() {
                         };
 
                         // 5. Batch-fetch titles — also tells us which items still exist in Zotero.
-                        //    Use list_items (not list_top_items) so items aren't filtered out by
-                        //    the /items/top "top-level only" constraint when itemKey is specified.
-                        //    Set limit == chunk size so we never silently truncate to the default 25.
+                        //    get_items queries /items (not /items/top) so items aren't filtered out
+                        //    by the "top-level only" constraint when itemKey is specified.
                         let mut title_map: HashMap<String, String> = HashMap::new();
-                        for chunk in filtered_keys.chunks(50) {
-                            let keys_str = chunk.join(",");
-                            let batch_params = ItemListParams {
-                                item_key: Some(keys_str),
-                                limit: Some(chunk.len() as u32),
-                                ..Default::default()
-                            };
-                            if let Ok(resp) = zotero.list_items(&batch_params).await {
-                                for item in resp.items {
-                                    // Always insert — even empty title — so title_map.contains_key()
-                                    // reliably indicates "item exists in Zotero".
-                                    title_map.insert(item.key, item.data.title.unwrap_or_default());
-                                }
-                            }
+                        let key_refs: Vec<&str> = filtered_keys.iter().map(String::as_str).collect();
+                        let batch = zotero.get_items(&key_refs).await;
+                        for item in batch.items {
+                            // Always insert — even empty title — so title_map.contains_key()
+                            // reliably indicates "item exists in Zotero".
+                            title_map.insert(item.key, item.data.title.unwrap_or_default());
+                        }
+                        for failure in &batch.failed {
+                            eprintln!("error: could not fetch {} item(s): {}", failure.keys.len(), failure.error);
                         }
 
                         // 6. Output: [local] [remote]
@@ -1250,16 +1637,69 @@ async fn papers_main() {
                         }
                     }
 
+                    ZoteroExtractCommand::Diff { query, json } => {
+                        let key = smart_resolve_item_key(&zotero, &query)
+                            .await
+                            .unwrap_or_else(|e| exit_err(&e));
+                        match papers_core::text::diff_extraction(&zotero, &key).await {
+                            Ok(diff) => {
+                                if json { print_json(&diff); } else { print!("{}", format::format_extraction_diff(&diff)); }
+                            }
+                            Err(e) => exit_err(&e.to_string()),
+                        }
+                    }
+                    ZoteroExtractCommand::Collection { key, mode, concurrency, dry_run, json } => {
+                        let key = resolve_collection_key(&zotero, &key).await.unwrap_or_else(|e| exit_err(&e.to_string()));
+                        let processing_mode = match mode {
+                            AdvancedMode::Fast     => papers_core::text::ProcessingMode::Fast,
+                            AdvancedMode::Balanced => papers_core::text::ProcessingMode::Balanced,
+                            AdvancedMode::Accurate => papers_core::text::ProcessingMode::Accurate,
+                        };
+                        if dry_run {
+                            let rate = papers_datalab::credits_per_page(&processing_mode);
+                            match papers_core::text::estimate_collection(&zotero, &key, processing_mode).await {
+                                Ok(estimates) => {
+                                    if json { print_json(&estimates); } else { print!("{}", format::format_collection_estimate(&estimates, rate)); }
+                                }
+                                Err(e) => exit_err(&e.to_string()),
+                            }
+                        } else {
+                            let dl = papers_datalab::DatalabClient::from_env()
+                                .unwrap_or_else(|e| exit_err(&e.to_string()));
+                            match papers_core::text::extract_collection(&zotero, &dl, &key, processing_mode, concurrency).await {
+                                Ok(results) => {
+                                    if json { print_json(&results); } else { print!("{}", format::format_batch_extraction(&results)); }
+                                    if results.iter().any(|r| matches!(r.outcome, papers_core::text::BatchOutcome::Failed { .. })) {
+                                        std::process::exit(1);
+                                    }
+                                }
+                                Err(e) => exit_err(&e.to_string()),
+                            }
+                        }
+                    }
+
+                    ZoteroExtractCommand::Export { dir, frontmatter, json } => {
+                        match papers_core::text::export_extractions(&dir, frontmatter) {
+                            Ok(summary) => {
+                                if json { print_json(&summary); } else { print!("{}", format::format_extraction_export(&summary, &dir)); }
+                            }
+                            Err(e) => exit_err(&format!("export to {}: {e}", dir.display())),
+                        }
+                    }
+
                     other => {
                         #[derive(PartialEq)]
-                        enum OutputKind { Text, Json, Get }
+                        enum OutputKind { Text { clean: bool }, Json, Get { json: bool } }
                         let (query, output_kind) = match other {
-                            ZoteroExtractCommand::Text { query } => (query, OutputKind::Text),
+                            ZoteroExtractCommand::Text { query, clean } => (query, OutputKind::Text { clean }),
                             ZoteroExtractCommand::Json { query } => (query, OutputKind::Json),
-                            ZoteroExtractCommand::Get  { query } => (query, OutputKind::Get),
+                            ZoteroExtractCommand::Get  { query, json } => (query, OutputKind::Get { json }),
                             ZoteroExtractCommand::List { .. } => unreachable!(),
                             ZoteroExtractCommand::Upload { .. } => unreachable!(),
                             ZoteroExtractCommand::Download { .. } => unreachable!(),
+                            ZoteroExtractCommand::Diff { .. } => unreachable!(),
+                            ZoteroExtractCommand::Collection { .. } => unreachable!(),
+                            ZoteroExtractCommand::Export { .. } => unreachable!(),
                         };
 
                         let key = smart_resolve_item_key(&zotero, &query)
@@ -1267,51 +1707,42 @@ async fn papers_main() {
                             .unwrap_or_else(|e| exit_err(&e));
 
                         match output_kind {
-                            OutputKind::Text => match papers_core::text::datalab_cached_markdown(&key) {
-                                Some(md) => print!("{md}"),
+                            OutputKind::Text { clean } => match papers_core::text::datalab_cached_markdown(&key) {
+                                Some(md) => print!("{}", if clean { papers_core::text::clean_markdown(&md) } else { md }),
                                 None => exit_err(&format!("No cached extraction for {key}. Run: papers zotero work extract {key}")),
                             },
                             OutputKind::Json => match papers_core::text::datalab_cached_json(&key) {
                                 Some(json_str) => print!("{json_str}"),
+                                None if papers_core::text::datalab_cached_markdown(&key).is_some() => exit_err(&format!(
+                                    "The cached extraction for {key} has no JSON (extracted before JSON was cached)"
+                                )),
                                 None => exit_err(&format!("No cached extraction for {key}. Run: papers zotero work extract {key}")),
                             },
-                            OutputKind::Get => {
-                                let local_dir = papers_core::text::datalab_cache_dir_path(&key);
-                                let local_ok = local_dir.as_ref()
-                                    .map(|d| d.join(format!("{key}.md")).exists())
-                                    .unwrap_or(false);
-                                let item_exists = zotero.get_item(&key).await.is_ok();
-                                // Only check for backup ZIP when the item exists
-                                let remote_col = if item_exists {
-                                    let expected_zip = format!("papers_extract_{key}.zip");
-                                    let backup_ok = match zotero.list_item_children(
-                                        &key,
-                                        &ItemListParams { item_type: Some("attachment".into()), ..Default::default() },
-                                    ).await {
-                                        Ok(children) => children.items.iter().any(|c| {
-                                            c.data.filename.as_deref() == Some(&expected_zip)
-                                                && c.data.link_mode.as_deref() == Some("imported_file")
-                                        }),
-                                        Err(_) => false,
-                                    };
-                                    if backup_ok { "✓" } else { "✗" }
+                            OutputKind::Get { json } => {
+                                let status = papers_core::text::extraction_status(&zotero, &key).await;
+                                if json {
+                                    print_json(&status);
                                 } else {
-                                    "✗  *no item*"
-                                };
-                                println!("{key}");
-                                match &local_dir {
-                                    Some(dir) if local_ok => println!("  local:✓  {}", dir.display()),
-                                    _ => println!("  local:✗"),
-                                }
-                                println!("  remote:{remote_col}");
-                                if let Some(meta) = papers_core::text::read_extraction_meta(&key) {
-                                    if let Some(t) = meta.title { println!("  title: {t}"); }
-                                    if let Some(authors) = meta.authors { println!("  authors: {}", authors.join(", ")); }
-                                    if let Some(et) = meta.extracted_at { println!("  extracted: {et}"); }
-                                    if let Some(mode) = meta.processing_mode { println!("  mode: {mode}"); }
-                                }
-                                if !local_ok && remote_col == "✗" {
-                                    eprintln!("No extraction found. Run: papers zotero work extract {key}");
+                                    let remote_col = match (status.item_exists, status.remote_backup) {
+                                        (true, true) => "✓",
+                                        (true, false) => "✗",
+                                        (false, _) => "✗  *no item*",
+                                    };
+                                    println!("{key}");
+                                    match &status.local_path {
+                                        Some(dir) if status.local_exists => println!("  local:✓  {}", dir.display()),
+                                        _ => println!("  local:✗"),
+                                    }
+                                    println!("  remote:{remote_col}");
+                                    if let Some(meta) = status.meta {
+                                        if let Some(t) = meta.title { println!("  title: {t}"); }
+                                        if let Some(authors) = meta.authors { println!("  authors: {}", authors.join(", ")); }
+                                        if let Some(et) = meta.extracted_at { println!("  extracted: {et}"); }
+                                        if let Some(mode) = meta.processing_mode { println!("  mode: {mode}"); }
+                                    }
+                                    if !status.local_exists && remote_col == "✗" {
+                                        eprintln!("No extraction found. Run: papers zotero work extract {key}");
+                                    }
                                 }
                             }
                         }
@@ -1323,6 +1754,7 @@ async fn papers_main() {
                         let params = ItemListParams { item_type: Some("attachment".into()), q: search, sort, direction, limit: Some(limit), start, ..Default::default() };
                         match zotero.list_items(&params).await {
                             Ok(resp) => {
+                                print_version(show_version, resp.last_modified_version);
                                 if json { print_json(&resp); } else { print!("{}", format::format_zotero_attachment_list(&resp)); }
                             }
                             Err(e) => exit_err(&e.to_string()),
@@ -1332,7 +1764,8 @@ async fn papers_main() {
                         let key = resolve_item_key(&zotero, &key).await.unwrap_or_else(|e| exit_err(&e.to_string()));
                         match zotero.get_item(&key).await {
                             Ok(item) => {
-                                if json { print_json(&item); } else { print!("{}", format::format_zotero_item_get(&item)); }
+                                print_version(show_version, Some(item.version));
+                                if json { print_json(&item); } else { print!("{}", format::format_zotero_item_get(&item, abstract_chars)); }
                             }
                             Err(e) => exit_err(&e.to_string()),
                         }
@@ -1368,6 +1801,7 @@ async fn papers_main() {
                         let params = ItemListParams { item_type: Some("annotation".into()), limit: Some(limit), start, ..Default::default() };
                         match zotero.list_items(&params).await {
                             Ok(resp) => {
+                                print_version(show_version, resp.last_modified_version);
                                 if json { print_json(&resp); } else { print!("{}", format::format_zotero_annotation_list(&resp)); }
                             }
                             Err(e) => exit_err(&e.to_string()),
@@ -1377,7 +1811,8 @@ async fn papers_main() {
                         let key = resolve_item_key(&zotero, &key).await.unwrap_or_else(|e| exit_err(&e.to_string()));
                         match zotero.get_item(&key).await {
                             Ok(item) => {
-                                if json { print_json(&item); } else { print!("{}", format::format_zotero_item_get(&item)); }
+                                print_version(show_version, Some(item.version));
+                                if json { print_json(&item); } else { print!("{}", format::format_zotero_item_get(&item, abstract_chars)); }
                             }
                             Err(e) => exit_err(&e.to_string()),
                         }
@@ -1389,7 +1824,8 @@ async fn papers_main() {
                         let params = ItemListParams { item_type: Some("note".into()), q: search, limit: Some(limit), start, ..Default::default() };
                         match zotero.list_items(&params).await {
                             Ok(resp) => {
-                                if json { print_json(&resp); } else { print!("{}", format::format_zotero_note_list(&resp)); }
+                                print_version(show_version, resp.last_modified_version);
+                                if json { print_json(&resp); } else { print!("{}", format::format_zotero_note_list(&resp, abstract_chars)); }
                             }
                             Err(e) => exit_err(&e.to_string()),
                         }
@@ -1398,7 +1834,8 @@ async fn papers_main() {
                         let key = resolve_item_key(&zotero, &key).await.unwrap_or_else(|e| exit_err(&e.to_string()));
                         match zotero.get_item(&key).await {
                             Ok(item) => {
-                                if json { print_json(&item); } else { print!("{}", format::format_zotero_item_get(&item)); }
+                                print_version(show_version, Some(item.version));
+                                if json { print_json(&item); } else { print!("{}", format::format_zotero_item_get(&item, abstract_chars)); }
                             }
                             Err(e) => exit_err(&e.to_string()),
                         }
@@ -1415,6 +1852,7 @@ async fn papers_main() {
                         };
                         match result {
                             Ok(resp) => {
+                                print_version(show_version, resp.last_modified_version);
                                 if json { print_json(&resp); } else { print!("{}", format::format_zotero_collection_list(&resp)); }
                             }
                             Err(e) => exit_err(&e.to_string()),
@@ -1424,6 +1862,7 @@ async fn papers_main() {
                         let key = resolve_collection_key(&zotero, &key).await.unwrap_or_else(|e| exit_err(&e.to_string()));
                         match zotero.get_collection(&key).await {
                             Ok(coll) => {
+                                print_version(show_version, Some(coll.version));
                                 if json { print_json(&coll); } else { print!("{}", format::format_zotero_collection_get(&coll)); }
                             }
                             Err(e) => exit_err(&e.to_string()),
@@ -1444,6 +1883,7 @@ async fn papers_main() {
                         };
                         match zotero.list_collection_top_items(&key, &params).await {
                             Ok(resp) => {
+                                print_version(show_version, resp.last_modified_version);
                                 if json { print_json(&resp); } else { print!("{}", format::format_zotero_work_list(&resp)); }
                             }
                             Err(e) => exit_err(&e.to_string()),
@@ -1454,6 +1894,7 @@ async fn papers_main() {
                         let params = ItemListParams { item_type: Some("attachment".into()), sort, direction, limit: Some(limit), start, ..Default::default() };
                         match zotero.list_collection_items(&key, &params).await {
                             Ok(resp) => {
+                                print_version(show_version, resp.last_modified_version);
                                 if json { print_json(&resp); } else { print!("{}", format::format_zotero_attachment_list(&resp)); }
                             }
                             Err(e) => exit_err(&e.to_string()),
@@ -1464,25 +1905,16 @@ async fn papers_main() {
                         let params = ItemListParams { item_type: Some("note".into()), q: search, sort, direction, limit: Some(limit), start, ..Default::default() };
                         match zotero.list_collection_items(&key, &params).await {
                             Ok(resp) => {
-                                if json { print_json(&resp); } else { print!("{}", format::format_zotero_note_list(&resp)); }
+                                print_version(show_version, resp.last_modified_version);
+                                if json { print_json(&resp); } else { print!("{}", format::format_zotero_note_list(&resp, abstract_chars)); }
                             }
                             Err(e) => exit_err(&e.to_string()),
                         }
                     }
                     ZoteroCollectionCommand::Annotations { key, json } => {
                         let key = resolve_collection_key(&zotero, &key).await.unwrap_or_else(|e| exit_err(&e.to_string()));
-                        let att_params = ItemListParams { item_type: Some("attachment".into()), ..Default::default() };
-                        let attachments = zotero.list_collection_items(&key, &att_params).await
+                        let all_annotations = papers_core::zotero::collection_annotations(&zotero, &key).await
                             .unwrap_or_else(|e| exit_err(&e.to_string()));
-                        let ann_params = ItemListParams { item_type: Some("annotation".into()), ..Default::default() };
-                        let mut all_annotations = Vec::new();
-                        for att in &attachments.items {
-                            if !is_annotatable_attachment(att) { continue; }
-                            match zotero.list_item_children(&att.key, &ann_params).await {
-                                Ok(r) => all_annotations.extend(r.items),
-                                Err(_) => {},
-                            }
-                        }
                         if json { print_json(&all_annotations); } else { print!("{}", format::format_zotero_annotation_list_vec(&all_annotations)); }
                     }
                     ZoteroCollectionCommand::Subcollections { key, sort, direction, limit, start, json } => {
@@ -1490,6 +1922,7 @@ async fn papers_main() {
                         let params = CollectionListParams { sort, direction, limit: Some(limit), start };
                         match zotero.list_subcollections(&key, &params).await {
                             Ok(resp) => {
+                                print_version(show_version, resp.last_modified_version);
                                 if json { print_json(&resp); } else { print!("{}", format::format_zotero_collection_list(&resp)); }
                             }
                             Err(e) => exit_err(&e.to_string()),
@@ -1505,11 +1938,28 @@ async fn papers_main() {
                         };
                         match result {
                             Ok(resp) => {
+                                print_version(show_version, resp.last_modified_version);
                                 if json { print_json(&resp); } else { print!("{}", format::format_zotero_tag_list(&resp)); }
                             }
                             Err(e) => exit_err(&e.to_string()),
                         }
                     }
+                    ZoteroCollectionCommand::Stats { key, json } => {
+                        let key = resolve_collection_key(&zotero, &key).await.unwrap_or_else(|e| exit_err(&e.to_string()));
+                        match papers_core::zotero::collection_stats(&zotero, &key).await {
+                            Ok(stats) => {
+                                if json { print_json(&stats); } else { print!("{}", format::format_collection_stats(&stats)); }
+                            }
+                            Err(e) => exit_err(&e.to_string()),
+                        }
+                    }
+                    ZoteroCollectionCommand::Export { key, format: ExportFormat::Bibtex } => {
+                        let key = resolve_collection_key(&zotero, &key).await.unwrap_or_else(|e| exit_err(&e.to_string()));
+                        match papers_core::bibtex::collection_to_bibtex(&zotero, &key).await {
+                            Ok(bib) => print!("{bib}"),
+                            Err(e) => exit_err(&e.to_string()),
+                        }
+                    }
                 },
 
                 ZoteroCommand::Tag { cmd } => match cmd {
@@ -1524,6 +1974,7 @@ async fn papers_main() {
                         };
                         match result {
                             Ok(resp) => {
+                                print_version(show_version, resp.last_modified_version);
                                 if json { print_json(&resp); } else { print!("{}", format::format_zotero_tag_list(&resp)); }
                             }
                             Err(e) => exit_err(&e.to_string()),
@@ -1532,17 +1983,41 @@ async fn papers_main() {
                     ZoteroTagCommand::Get { name, json } => {
                         match zotero.get_tag(&name).await {
                             Ok(resp) => {
+                                print_version(show_version, resp.last_modified_version);
                                 if json { print_json(&resp); } else { print!("{}", format::format_zotero_tag_list(&resp)); }
                             }
                             Err(e) => exit_err(&e.to_string()),
                         }
                     }
+                    ZoteroTagCommand::Bulk { search, add, remove, dry_run, json } => {
+                        let add: Vec<&str> = add.iter().map(String::as_str).collect();
+                        let remove: Vec<&str> = remove.iter().map(String::as_str).collect();
+                        let progress = |i: usize, n: usize, item: &Item| {
+                            eprintln!("[{i}/{n}] {}  {}", item.key, item.data.title.as_deref().unwrap_or("(untitled)"));
+                        };
+                        match papers_core::zotero::bulk_tag(&zotero, &search, &add, &remove, dry_run, progress).await {
+                            Ok(outcome) => {
+                                if json {
+                                    print_json(&outcome);
+                                } else if dry_run {
+                                    println!("Would update {} of {} matching item(s)", outcome.changed.len(), outcome.matched);
+                                    for key in &outcome.changed {
+                                        println!("  {key}");
+                                    }
+                                } else {
+                                    println!("Updated {} of {} matching item(s)", outcome.changed.len(), outcome.matched);
+                                }
+                            }
+                            Err(e) => exit_err(&e.to_string()),
+                        }
+                    }
                 },
 
                 ZoteroCommand::Search { cmd } => match cmd {
                     ZoteroSearchCommand::List { json } => {
                         match zotero.list_searches().await {
                             Ok(resp) => {
+                                print_version(show_version, resp.last_modified_version);
                                 if json { print_json(&resp); } else { print!("{}", format::format_zotero_search_list(&resp)); }
                             }
                             Err(e) => exit_err(&e.to_string()),
@@ -1552,6 +2027,7 @@ async fn papers_main() {
                         let key = resolve_search_key(&zotero, &key).await.unwrap_or_else(|e| exit_err(&e.to_string()));
                         match zotero.get_search(&key).await {
                             Ok(search) => {
+                                print_version(show_version, Some(search.version));
                                 if json { print_json(&search); } else { print!("{}", format::format_zotero_search_get(&search)); }
                             }
                             Err(e) => exit_err(&e.to_string()),
@@ -1563,6 +2039,7 @@ async fn papers_main() {
                     ZoteroGroupCommand::List { json } => {
                         match zotero.list_groups().await {
                             Ok(resp) => {
+                                print_version(show_version, resp.last_modified_version);
                                 if json { print_json(&resp); } else { print!("{}", format::format_zotero_group_list(&resp)); }
                             }
                             Err(e) => exit_err(&e.to_string()),
@@ -1574,6 +2051,7 @@ async fn papers_main() {
                     ZoteroSettingCommand::List { json } => {
                         match zotero.get_settings().await {
                             Ok(resp) => {
+                                print_version(show_version, resp.last_modified_version);
                                 if json { print_json(&resp); } else { print!("{}", format::format_zotero_setting_list(&resp)); }
                             }
                             Err(e) => exit_err(&e.to_string()),
@@ -1582,11 +2060,20 @@ async fn papers_main() {
                     ZoteroSettingCommand::Get { key, json } => {
                         match zotero.get_setting(&key).await {
                             Ok(resp) => {
+                                print_version(show_version, resp.last_modified_version);
                                 if json { print_json(&resp); } else { print!("{}", format::format_zotero_setting_get(&key, &resp)); }
                             }
                             Err(e) => exit_err(&e.to_string()),
                         }
                     }
+                    ZoteroSettingCommand::TagColors { json } => {
+                        match zotero.get_tag_colors().await {
+                            Ok(colors) => {
+                                if json { print_json(&colors); } else { print!("{}", format::format_zotero_tag_colors(&colors)); }
+                            }
+                            Err(e) => exit_err(&e.to_string()),
+                        }
+                    }
                 },
 
                 ZoteroCommand::Deleted { cmd } => match cmd {
@@ -1594,6 +2081,7 @@ async fn papers_main() {
                         let params = DeletedParams { since };
                         match zotero.get_deleted(&params).await {
                             Ok(resp) => {
+                                print_version(show_version, resp.last_modified_version);
                                 if json { print_json(&resp); } else { print!("{}", format::format_zotero_deleted_list(&resp)); }
                             }
                             Err(e) => exit_err(&e.to_string()),
@@ -1611,6 +2099,83 @@ async fn papers_main() {
                         }
                     }
                 },
+
+                ZoteroCommand::Fulltext { cmd } => match cmd {
+                    ZoteroFulltextCommand::Status { missing, json } => {
+                        match papers_core::zotero::fulltext_status(&zotero).await {
+                            Ok(mut status) => {
+                                if missing {
+                                    status.indexed.clear();
+                                }
+                                if json { print_json(&status); } else { print!("{}", format::format_fulltext_status(&status, missing)); }
+                            }
+                            Err(e) => exit_err(&e.to_string()),
+                        }
+                    }
+                },
+
+                ZoteroCommand::Stats { json } => match papers_core::zotero::library_stats(&zotero).await {
+                    Ok(stats) => {
+                        if json { print_json(&stats); } else { print!("{}", format::format_library_stats(&stats)); }
+                    }
+                    Err(e) => exit_err(&e.to_string()),
+                },
+                ZoteroCommand::Doctor { .. } => unreachable!("handled before connecting"),
+            }
+        },
+
+        EntityCommand::Grep { pattern, ignore_case, context, json } => {
+            match papers_core::text::grep_cached_extractions(&pattern, ignore_case, context) {
+                Ok(docs) => {
+                    if json { print_json(&docs); } else { print!("{}", format::format_library_grep(&docs)); }
+                }
+                Err(e) => exit_err(&e.to_string()),
+            }
+        }
+
+        EntityCommand::Datalab { cmd } => {
+            let dl = papers_datalab::DatalabClient::from_env()
+                .unwrap_or_else(|e| exit_err(&e.to_string()));
+            match cmd {
+                DatalabCommand::StepTypes { json } => match dl.get_step_types().await {
+                    Ok(resp) => {
+                        if json { print_json(&resp); } else { print!("{}", format::format_datalab_step_types(&resp)); }
+                    }
+                    Err(e) => exit_err(&e.to_string()),
+                },
+                DatalabCommand::Submit { file, mode, json } => {
+                    let bytes = std::fs::read(&file)
+                        .unwrap_or_else(|e| exit_err(&format!("Failed to read {}: {e}", file.display())));
+                    let req = papers_datalab::MarkerRequest {
+                        file: Some(bytes),
+                        filename: file.file_name().map(|n| n.to_string_lossy().into_owned()),
+                        mode: match mode {
+                            AdvancedMode::Fast     => papers_datalab::ProcessingMode::Fast,
+                            AdvancedMode::Balanced => papers_datalab::ProcessingMode::Balanced,
+                            AdvancedMode::Accurate => papers_datalab::ProcessingMode::Accurate,
+                        },
+                        ..Default::default()
+                    };
+                    match dl.submit_marker(req).await {
+                        Ok(resp) => {
+                            if json { print_json(&resp); } else { print!("{}", format::format_datalab_submit(&resp)); }
+                        }
+                        Err(e) => exit_err(&e.to_string()),
+                    }
+                }
+                DatalabCommand::Result { id, json } => match dl.get_marker_result(&id).await {
+                    Ok(resp) if json => print_json(&resp),
+                    Ok(resp) => match resp.status {
+                        papers_datalab::MarkerStatus::Complete => print!("{}", format::format_datalab_result(&resp)),
+                        papers_datalab::MarkerStatus::Failed => {
+                            exit_err(&format!("DataLab job {id} failed: {}", resp.failure_message()))
+                        }
+                        papers_datalab::MarkerStatus::Processing | papers_datalab::MarkerStatus::Unknown => {
+                            print!("{}", format::format_datalab_pending(&id, &resp));
+                        }
+                    },
+                    Err(e) => exit_err(&e.to_string()),
+                },
             }
         },
 
@@ -0,0 +1,159 @@
+//! SQLite export of work lists (`papers work list --to-sqlite`).
+//!
+//! Works land in a `works` table keyed by OpenAlex ID, so exporting the same
+//! work twice updates its row instead of duplicating it. The schema version is
+//! kept in `PRAGMA user_version` and brought up to date when the database is
+//! opened.
+
+use papers_core::summary::WorkSummary;
+use rusqlite::{Connection, params};
+use std::path::Path;
+
+/// Schema migrations, applied in order; a database at `user_version` N has
+/// run the first N.
+const MIGRATIONS: &[&str] = &["CREATE TABLE works (
+        id               TEXT PRIMARY KEY,
+        title            TEXT,
+        doi              TEXT,
+        publication_year INTEGER,
+        type             TEXT,
+        is_retracted     INTEGER NOT NULL,
+        authors          TEXT NOT NULL,
+        journal          TEXT,
+        is_oa            INTEGER,
+        oa_url           TEXT,
+        cited_by_count   INTEGER,
+        primary_topic    TEXT,
+        abstract         TEXT
+    )"];
+
+/// Open (creating if needed) the database at `path` and migrate it to the
+/// current schema.
+pub fn open(path: &Path) -> rusqlite::Result<Connection> {
+    let mut conn = Connection::open(path)?;
+    migrate(&mut conn)?;
+    Ok(conn)
+}
+
+fn migrate(conn: &mut Connection) -> rusqlite::Result<()> {
+    let version: usize = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    let tx = conn.transaction()?;
+    for (i, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+        tx.execute_batch(migration)?;
+        tx.pragma_update(None, "user_version", i + 1)?;
+    }
+    tx.commit()
+}
+
+/// Insert `works`, replacing the columns of any row with the same ID.
+/// Authors are stored as one `; `-separated string.
+pub fn upsert_works(conn: &mut Connection, works: &[WorkSummary]) -> rusqlite::Result<usize> {
+    let tx = conn.transaction()?;
+    {
+        let mut stmt = tx.prepare(
+            "INSERT INTO works (id, title, doi, publication_year, type, is_retracted, authors,
+                                journal, is_oa, oa_url, cited_by_count, primary_topic, abstract)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
+             ON CONFLICT (id) DO UPDATE SET
+                title = excluded.title,
+                doi = excluded.doi,
+                publication_year = excluded.publication_year,
+                type = excluded.type,
+                is_retracted = excluded.is_retracted,
+                authors = excluded.authors,
+                journal = excluded.journal,
+                is_oa = excluded.is_oa,
+                oa_url = excluded.oa_url,
+                cited_by_count = excluded.cited_by_count,
+                primary_topic = excluded.primary_topic,
+                abstract = excluded.abstract",
+        )?;
+        for w in works {
+            stmt.execute(params![
+                w.id,
+                w.title,
+                w.doi,
+                w.publication_year,
+                w.r#type,
+                w.is_retracted,
+                w.authors.join("; "),
+                w.journal,
+                w.is_oa,
+                w.oa_url,
+                w.cited_by_count,
+                w.primary_topic,
+                w.abstract_text,
+            ])?;
+        }
+    }
+    tx.commit()?;
+    Ok(works.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use papers_core::Work;
+
+    /// `id, title, publication_year, cited_by_count, is_retracted, authors`
+    type Row = (String, Option<String>, Option<i32>, Option<i64>, bool, String);
+
+    fn summary(json: serde_json::Value) -> WorkSummary {
+        let work: Work = serde_json::from_value(json).unwrap();
+        WorkSummary::from(work)
+    }
+
+    #[test]
+    fn upserts_and_reads_back_works() {
+        let path = std::env::temp_dir().join(format!("papers-sqlite-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut conn = open(&path).unwrap();
+
+        let attention = |cited_by_count: i64| {
+            summary(serde_json::json!({
+                "id": "https://openalex.org/W1",
+                "display_name": "Attention Is All You Need",
+                "publication_year": 2017,
+                "cited_by_count": cited_by_count,
+                "authorships": [
+                    {"author": {"id": "https://openalex.org/A1", "display_name": "Ashish Vaswani"}},
+                    {"author": {"id": "https://openalex.org/A2", "display_name": "Noam Shazeer"}}
+                ]
+            }))
+        };
+        let retracted = summary(serde_json::json!({"id": "https://openalex.org/W2", "is_retracted": true}));
+        assert_eq!(upsert_works(&mut conn, &[attention(100), retracted]).unwrap(), 2);
+
+        // Re-exporting a work updates its row rather than adding another
+        upsert_works(&mut conn, &[attention(150)]).unwrap();
+        drop(conn);
+
+        let conn = open(&path).unwrap();
+        let rows: Vec<Row> = conn
+            .prepare("SELECT id, title, publication_year, cited_by_count, is_retracted, authors FROM works ORDER BY id")
+            .unwrap()
+            .query_map([], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?, r.get(4)?, r.get(5)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let version: usize = conn.query_row("PRAGMA user_version", [], |r| r.get(0)).unwrap();
+        drop(conn);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(version, MIGRATIONS.len());
+        assert_eq!(
+            rows,
+            [
+                (
+                    "https://openalex.org/W1".to_string(),
+                    Some("Attention Is All You Need".to_string()),
+                    Some(2017),
+                    Some(150),
+                    false,
+                    "Ashish Vaswani; Noam Shazeer".to_string(),
+                ),
+                ("https://openalex.org/W2".to_string(), None, None, None, true, String::new()),
+            ]
+        );
+    }
+}
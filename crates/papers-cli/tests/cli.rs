@@ -4,7 +4,7 @@ use papers_core::{
     SubfieldListParams, TopicListParams, WorkListParams,
 };
 use papers_zotero::{CollectionListParams, ItemListParams, TagListParams, ZoteroClient};
-use wiremock::matchers::{method, path, query_param};
+use wiremock::matchers::{body_string_contains, header, method, path, query_param};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 fn make_client(mock: &MockServer) -> OpenAlexClient {
@@ -800,6 +800,59 @@ fn search_result_json(id: &str) -> String {
     )
 }
 
+fn work_page_body(ids: &[u32], next_cursor: Option<&str>) -> String {
+    let results: Vec<_> = ids
+        .iter()
+        .map(|id| serde_json::json!({"id": format!("https://openalex.org/W{id}"), "display_name": format!("Paper {id}")}))
+        .collect();
+    serde_json::json!({
+        "meta": {"count": 100, "db_response_time_ms": 5, "page": null, "per_page": ids.len(), "next_cursor": next_cursor, "groups_count": null},
+        "results": results,
+        "group_by": []
+    })
+    .to_string()
+}
+
+#[tokio::test]
+async fn test_work_list_limit_total_stops_cursor_walk() {
+    let mock = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/works"))
+        .and(query_param("cursor", "*"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(work_page_body(&[1, 2], Some("c2"))))
+        .expect(1)
+        .mount(&mock)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/works"))
+        .and(query_param("cursor", "c2"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(work_page_body(&[3, 4], Some("c3"))))
+        .expect(1)
+        .mount(&mock)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/works"))
+        .and(query_param("cursor", "c3"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(work_page_body(&[5, 6], None)))
+        .expect(0)
+        .mount(&mock)
+        .await;
+
+    let client = make_client(&mock);
+    let params = WorkListParams { per_page: Some(2), ..Default::default() };
+    let result = papers_core::api::work_list_limited(
+        &client,
+        &params,
+        papers_core::summary::SlimProfile::Standard,
+        3,
+    )
+    .await
+    .unwrap();
+    let text = papers_cli_format::format_work_list(&result);
+    let printed: Vec<_> = text.lines().filter(|l| l.contains("Paper ")).collect();
+    assert_eq!(printed, ["  1  Paper 1", "  2  Paper 2", "  3  Paper 3"]);
+}
+
 #[tokio::test]
 async fn test_work_list_year_flag() {
     let mock = MockServer::start().await;
@@ -920,6 +973,53 @@ async fn test_work_list_overlap_error() {
     assert!(err.contains("publication_year"));
 }
 
+#[tokio::test]
+async fn test_datalab_submit_then_result() {
+    use papers_datalab::{DatalabClient, MarkerRequest, MarkerStatus};
+
+    let mock = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/v1/marker"))
+        .and(header("X-API-Key", "dl-key"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            r#"{"success": true, "request_id": "job-42", "request_check_url": "https://www.datalab.to/api/v1/marker/job-42"}"#,
+        ))
+        .expect(1)
+        .mount(&mock)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/marker/job-42"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"status": "processing"}"#))
+        .up_to_n_times(1)
+        .mount(&mock)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/marker/job-42"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(r##"{"status": "complete", "markdown": "# Converted", "page_count": 3}"##),
+        )
+        .mount(&mock)
+        .await;
+
+    // `datalab submit` and each `datalab result` run as separate invocations,
+    // sharing nothing but the request ID
+    let submit = DatalabClient::new("dl-key").with_base_url(mock.uri());
+    let req = MarkerRequest { file: Some(b"%PDF-1.4".to_vec()), filename: Some("paper.pdf".into()), ..Default::default() };
+    let submitted = submit.submit_marker(req).await.unwrap();
+    assert_eq!(submitted.request_id, "job-42");
+    assert!(submitted.request_check_url.ends_with("/job-42"));
+
+    let early = DatalabClient::new("dl-key").with_base_url(mock.uri());
+    assert_eq!(early.get_marker_result(&submitted.request_id).await.unwrap().status, MarkerStatus::Processing);
+
+    let later = DatalabClient::new("dl-key").with_base_url(mock.uri());
+    let done = later.get_marker_result(&submitted.request_id).await.unwrap();
+    assert_eq!(done.status, MarkerStatus::Complete);
+    assert_eq!(done.markdown.as_deref(), Some("# Converted"));
+    assert_eq!(serde_json::to_value(&done).unwrap()["status"], "complete");
+}
+
 // Format functions exposed for testing (mirrors src/format.rs logic)
 mod papers_cli_format {
     use papers_core::summary::{
@@ -1348,11 +1448,9 @@ mod papers_cli_format {
                 out.push_str(&format!("\nNote:\n  {trimmed}\n"));
             }
         }
-        if let Some(ann_type) = item.data.extra_fields.get("annotationType").and_then(|v| v.as_str()) {
-            out.push_str(&format!("Annotation type: {ann_type}\n"));
-        }
-        if let Some(text) = item.data.extra_fields.get("annotationText").and_then(|v| v.as_str()) {
-            if !text.is_empty() { out.push_str(&format!("Text: {text}\n")); }
+        if let Some(ann) = item.as_annotation() {
+            if let Some(ann_type) = ann.annotation_type { out.push_str(&format!("Annotation type: {ann_type}\n")); }
+            if let Some(text) = ann.text { out.push_str(&format!("Text: {text}\n")); }
         }
         out
     }
@@ -1400,21 +1498,18 @@ mod papers_cli_format {
     }
 
     fn push_annotation_entry(out: &mut String, i: usize, item: &Item) {
-        let ann_type = item.data.extra_fields.get("annotationType").and_then(|v| v.as_str()).unwrap_or("?");
-        let page = item.data.extra_fields.get("annotationPageLabel").and_then(|v| v.as_str()).unwrap_or("");
-        let color = item.data.extra_fields.get("annotationColor").and_then(|v| v.as_str()).unwrap_or("");
+        let ann = item.as_annotation().unwrap_or_default();
         let parent = item.data.parent_item.as_deref().unwrap_or("");
-        out.push_str(&format!("\n {:>2}  [{}] {}", i + 1, item.key, ann_type));
-        if !page.is_empty() { out.push_str(&format!(" (p. {page})")); }
-        if !color.is_empty() { out.push_str(&format!(" {color}")); }
+        let color_name = ann.color.and_then(papers_core::zotero::annotation_color_name);
+        out.push_str(&format!("\n {:>2}  [{}] ", i + 1, item.key));
+        if let Some(name) = color_name { out.push_str(&format!("{name} ")); }
+        out.push_str(ann.annotation_type.unwrap_or("?"));
+        if let Some(page) = ann.page_label { out.push_str(&format!(" (p. {page})")); }
+        if let Some(color) = ann.color.filter(|_| color_name.is_none()) { out.push_str(&format!(" {color}")); }
         out.push('\n');
         if !parent.is_empty() { out.push_str(&format!("     Parent: {parent}\n")); }
-        if let Some(text) = item.data.extra_fields.get("annotationText").and_then(|v| v.as_str()) {
-            if !text.is_empty() { out.push_str(&format!("     \"{text}\"\n")); }
-        }
-        if let Some(comment) = item.data.extra_fields.get("annotationComment").and_then(|v| v.as_str()) {
-            if !comment.is_empty() { out.push_str(&format!("     Note: {comment}\n")); }
-        }
+        if let Some(text) = ann.text { out.push_str(&format!("     \"{text}\"\n")); }
+        if let Some(comment) = ann.comment { out.push_str(&format!("     Note: {comment}\n")); }
     }
 
     pub fn format_zotero_note_list(resp: &PagedResponse<Item>) -> String {
@@ -1639,6 +1734,31 @@ async fn test_zotero_work_list() {
     assert_eq!(result.total_results, Some(1));
 }
 
+#[tokio::test]
+async fn test_zotero_show_version_from_header() {
+    let mock = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/users/test/items/top"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("Total-Results", "1")
+                .insert_header("Last-Modified-Version", "4384")
+                .set_body_string(z_items_body()),
+        )
+        .mount(&mock)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/users/test/settings"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("{}"))
+        .mount(&mock)
+        .await;
+    let client = make_zotero_client(&mock);
+    let list = client.list_top_items(&ItemListParams::default()).await.unwrap();
+    assert_eq!(list.last_modified_version, Some(4384));
+    let settings = client.get_settings().await.unwrap();
+    assert_eq!(settings.last_modified_version, None);
+}
+
 #[tokio::test]
 async fn test_zotero_work_get() {
     let mock = MockServer::start().await;
@@ -2172,11 +2292,7 @@ async fn test_zotero_work_annotations_skips_non_pdf_attachments() {
     let ann_params = ItemListParams { item_type: Some("annotation".into()), ..Default::default() };
     let mut all_annotations = Vec::new();
     for att in &attachments.items {
-        let is_annotatable = matches!(
-            att.data.content_type.as_deref(),
-            Some("application/pdf") | Some("application/epub+zip") | Some("text/html")
-        );
-        if !is_annotatable { continue; }
+        if !att.is_annotatable_attachment() { continue; }
         match client.list_item_children(&att.key, &ann_params).await {
             Ok(r) => all_annotations.extend(r.items),
             Err(_) => {},
@@ -2308,7 +2424,8 @@ async fn test_zotero_annotation_list_text() {
     let params = ItemListParams { item_type: Some("annotation".into()), ..Default::default() };
     let result = client.list_items(&params).await.unwrap();
     let text = papers_cli_format::format_zotero_annotation_list(&result);
-    assert!(text.contains("highlight"), "should contain annotation type");
+    assert!(text.contains("yellow highlight"), "should name the standard color: {text}");
+    assert!(!text.contains("#ffd400"), "standard color should not print as hex");
     assert!(text.contains("ANN12345"), "should contain key");
     assert!(text.contains("p. 5"), "should contain page label");
     assert!(text.contains("GPU sorting outperforms"), "should contain annotation text");
@@ -2516,11 +2633,7 @@ async fn test_zotero_work_annotations_nonempty() {
     let ann_params = ItemListParams { item_type: Some("annotation".into()), ..Default::default() };
     let mut all_annotations = Vec::new();
     for att in &attachments.items {
-        let is_annotatable = matches!(
-            att.data.content_type.as_deref(),
-            Some("application/pdf") | Some("application/epub+zip") | Some("text/html")
-        );
-        if !is_annotatable { continue; }
+        if !att.is_annotatable_attachment() { continue; }
         let ann = client.list_item_children(&att.key, &ann_params).await.unwrap();
         // Verify parent linkage: each annotation's parent_item == the attachment key
         for a in &ann.items {
@@ -2588,11 +2701,7 @@ async fn test_zotero_collection_annotations_nonempty() {
     let ann_params = ItemListParams { item_type: Some("annotation".into()), ..Default::default() };
     let mut all_annotations = Vec::new();
     for att in &attachments.items {
-        let is_annotatable = matches!(
-            att.data.content_type.as_deref(),
-            Some("application/pdf") | Some("application/epub+zip") | Some("text/html")
-        );
-        if !is_annotatable { continue; }
+        if !att.is_annotatable_attachment() { continue; }
         match client.list_item_children(&att.key, &ann_params).await {
             Ok(r) => all_annotations.extend(r.items),
             Err(_) => {}
@@ -2980,3 +3089,83 @@ fn test_datalab_cached_markdown_miss() {
     let result = papers_core::text::datalab_cached_markdown("TSTMISSXX");
     assert!(result.is_none(), "unknown key should return None");
 }
+
+// ── DataLab tests ─────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_datalab_step_types() {
+    let mock = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/workflows/step-types"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "step_types": [{
+                "id": 1,
+                "type": "step",
+                "step_type": "marker_parse",
+                "name": "Marker Parse",
+                "description": "Convert a document to markdown",
+                "settings_schema": {"type": "object"},
+                "version": "1.0",
+                "is_public": true
+            }]
+        })))
+        .expect(1)
+        .mount(&mock)
+        .await;
+
+    let dl = papers_datalab::DatalabClient::new("mock-key").with_base_url(mock.uri());
+    let resp = dl.get_step_types().await.unwrap();
+    assert_eq!(resp.step_types.len(), 1);
+    let step = &resp.step_types[0];
+    assert_eq!(step.step_type, "marker_parse");
+    assert_eq!(step.name, "Marker Parse");
+    assert_eq!(step.type_, "step");
+    assert!(step.is_public);
+}
+
+#[tokio::test]
+async fn test_datalab_sends_user_agent() {
+    let mock = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/workflows/step-types"))
+        .and(header("user-agent", "papers-test/1.0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"step_types": []})))
+        .expect(1)
+        .mount(&mock)
+        .await;
+
+    let dl = papers_datalab::DatalabClient::new("mock-key")
+        .with_base_url(mock.uri())
+        .with_user_agent("papers-test/1.0");
+    dl.get_step_types().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_datalab_submit_uses_content_type_override() {
+    let mock = MockServer::start().await;
+    // The `.html` name alone would be sent as text/html; the override wins
+    Mock::given(method("POST"))
+        .and(path("/api/v1/marker"))
+        .and(body_string_contains(r#"filename="scan.html""#))
+        .and(body_string_contains("Content-Type: application/pdf"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "request_id": "ct-req-1",
+            "request_check_url": "http://mock/api/v1/marker/ct-req-1",
+            "success": true
+        })))
+        .expect(1)
+        .mount(&mock)
+        .await;
+
+    let dl = papers_datalab::DatalabClient::new("mock-key").with_base_url(mock.uri());
+    let resp = dl
+        .submit_marker(papers_datalab::MarkerRequest {
+            file: Some(b"%PDF-1.4 fake".to_vec()),
+            filename: Some("scan.html".into()),
+            content_type: Some("application/pdf".into()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(resp.request_id, "ct-req-1");
+}
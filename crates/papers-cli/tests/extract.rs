@@ -9,7 +9,11 @@
 /// - Fake local DataLab cache written to `papers/test` (NOT `papers/datalab`) by
 ///   setting `PAPERS_DATALAB_CACHE_DIR` to `{cache_dir}/papers/test`.
 /// - All cache dirs are removed after each test via a drop guard.
-use papers_core::text::{datalab_cached_item_keys, read_extraction_meta, ExtractionMeta};
+use papers_core::text::{
+    batch_progress, datalab_cached_item_keys, datalab_cached_markdown, export_extractions, extract_collection, grep_cached_extractions, extraction_status,
+    read_extraction_meta, BatchOutcome, ExtractionMeta, ProcessingMode,
+};
+use papers_datalab::DatalabClient;
 use papers_zotero::{ItemListParams, ZoteroClient};
 use std::collections::HashSet;
 use wiremock::matchers::{method, path, query_param};
@@ -119,6 +123,24 @@ const KEY_META_LIST_PRIO:  &str = "EXT03201"; // meta title differs from Zotero
 const KEY_META_ZIP_A:      &str = "EXT03301"; // meta.json is included in zip content
 const KEY_META_READ_BACK:  &str = "EXT03401"; // read_extraction_meta round-trips
 
+// extract get --json
+const KEY_STATUS_BACKED:   &str = "EXT03501"; // local + meta + backup ZIP → all fields set
+
+// extract collection
+const KEY_BATCH_CACHED:    &str = "EXT03601"; // already cached → skipped, no network
+const KEY_BATCH_NEW:       &str = "EXT03602"; // PDF downloaded and sent to (mock) DataLab
+const KEY_RESUME_DONE:     &str = "EXT03701"; // done in saved progress, no cache → still skipped
+const KEY_RESUME_FAILED:   &str = "EXT03702"; // failed in saved progress → retried
+
+// papers grep
+const KEY_GREP_HIT:        &str = "EXT03801"; // markdown contains the search token
+const KEY_GREP_MISS:       &str = "EXT03802"; // markdown does not
+
+// extract export
+const KEY_EXPORT_META:     &str = "EXT03901"; // title from meta.json, needs sanitizing
+const KEY_EXPORT_HEADING:  &str = "EXT03902"; // no meta; title from first heading
+const KEY_EXPORT_FRONT:    &str = "EXT04001"; // exported with --frontmatter
+
 // ── Helpers ───────────────────────────────────────────────────────────────
 
 fn make_zotero_client(mock: &MockServer) -> ZoteroClient {
@@ -181,6 +203,20 @@ impl Drop for CacheCleanup {
     }
 }
 
+fn remove_progress(collection_key: &str) {
+    if let Some(path) = batch_progress::path(collection_key) {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+/// Drop guard: removes a collection's batch progress file even on panic.
+struct ProgressCleanup(&'static str);
+impl Drop for ProgressCleanup {
+    fn drop(&mut self) {
+        remove_progress(self.0);
+    }
+}
+
 // ── Tests ─────────────────────────────────────────────────────────────────
 
 /// `datalab_cached_item_keys()` must return keys from the redirected test dir.
@@ -1814,3 +1850,319 @@ async fn test_extract_list_mixed_meta_and_zotero_titles() {
     assert_eq!(title_b, "Zotero Title B",
         "KEY_META_LIST_B: Zotero title must be used when meta.json is absent");
 }
+
+/// `extract get --json` for an item cached locally and backed up to Zotero.
+#[tokio::test]
+async fn test_get_json_cached_with_backup() {
+    test_cache_base();
+    remove_cache(KEY_STATUS_BACKED);
+    write_fake_cache(KEY_STATUS_BACKED);
+    let _cleanup = CacheCleanup(vec![KEY_STATUS_BACKED]);
+    write_fake_meta(KEY_STATUS_BACKED, &ExtractionMeta {
+        item_key: KEY_STATUS_BACKED.to_string(),
+        zotero_user_id: None,
+        title: Some("Backed Up Paper".to_string()),
+        authors: Some(vec!["Ada Lovelace".to_string()]),
+        item_type: None,
+        date: None,
+        doi: None,
+        url: None,
+        publication_title: None,
+        extracted_at: Some("2025-03-01T12:00:00Z".to_string()),
+        processing_mode: Some("accurate".to_string()),
+        pdf_source: None,
+    });
+
+    let mock = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/users/test/items/{KEY_STATUS_BACKED}")))
+        .respond_with(ResponseTemplate::new(200)
+            .set_body_string(item_json(KEY_STATUS_BACKED, "Backed Up Paper")))
+        .mount(&mock)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/users/test/items/{KEY_STATUS_BACKED}/children")))
+        .respond_with(zotero_arr(1, &extract_atts_body(&[("ATTSTAT01", KEY_STATUS_BACKED)])))
+        .mount(&mock)
+        .await;
+
+    let zotero = make_zotero_client(&mock);
+    let status = extraction_status(&zotero, KEY_STATUS_BACKED).await;
+    let json = serde_json::to_value(&status).unwrap();
+
+    assert_eq!(json["key"], KEY_STATUS_BACKED);
+    assert_eq!(json["local_path"], cache_dir_for(KEY_STATUS_BACKED).to_str().unwrap());
+    assert_eq!(json["local_exists"], true);
+    assert_eq!(json["item_exists"], true);
+    assert_eq!(json["remote_backup"], true);
+    assert_eq!(json["meta"]["title"], "Backed Up Paper");
+    assert_eq!(json["meta"]["authors"], serde_json::json!(["Ada Lovelace"]));
+    assert_eq!(json["meta"]["extracted_at"], "2025-03-01T12:00:00Z");
+    assert_eq!(json["meta"]["processing_mode"], "accurate");
+}
+
+/// `extract collection` skips the cached item and extracts the other one.
+#[tokio::test]
+async fn test_extract_collection_skips_cached() {
+    test_cache_base();
+    remove_cache(KEY_BATCH_CACHED);
+    remove_cache(KEY_BATCH_NEW);
+    write_fake_cache(KEY_BATCH_CACHED);
+    remove_progress("COLL0001");
+    let _cleanup = CacheCleanup(vec![KEY_BATCH_CACHED, KEY_BATCH_NEW]);
+    let _progress = ProgressCleanup("COLL0001");
+
+    let mock = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/users/test/collections/COLL0001/items/top"))
+        .respond_with(zotero_arr(2, &items_body(&[
+            (KEY_BATCH_CACHED, "Cached Paper"),
+            (KEY_BATCH_NEW, "New Paper"),
+        ])))
+        .mount(&mock)
+        .await;
+    // The cached item must not be looked at
+    Mock::given(method("GET"))
+        .and(path(format!("/users/test/items/{KEY_BATCH_CACHED}/children")))
+        .respond_with(zotero_arr(0, "[]"))
+        .expect(0)
+        .mount(&mock)
+        .await;
+    let pdf_att = format!(r#"[{{"key":"ATTPDF01","version":1,"library":{{"type":"user","id":1,"name":"test","links":{{}}}},"links":{{}},"meta":{{}},"data":{{"key":"ATTPDF01","version":1,"itemType":"attachment","parentItem":"{KEY_BATCH_NEW}","filename":"paper.pdf","linkMode":"imported_file","contentType":"application/pdf"}}}}]"#);
+    Mock::given(method("GET"))
+        .and(path(format!("/users/test/items/{KEY_BATCH_NEW}/children")))
+        .respond_with(zotero_arr(1, &pdf_att))
+        .mount(&mock)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/users/test/items/ATTPDF01/file"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(b"%PDF-1.4 fake".to_vec()))
+        .mount(&mock)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/users/test/items/{KEY_BATCH_NEW}")))
+        .respond_with(ResponseTemplate::new(200).set_body_string(item_json(KEY_BATCH_NEW, "New Paper")))
+        .mount(&mock)
+        .await;
+    // Backup upload after extraction: read-only key, silently skipped
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(403).set_body_string("Forbidden"))
+        .mount(&mock)
+        .await;
+
+    let dl_mock = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/v1/marker"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "request_id": "batch-req-1",
+            "request_check_url": "http://mock/api/v1/marker/batch-req-1",
+            "success": true
+        })))
+        .expect(1)
+        .mount(&dl_mock)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/marker/batch-req-1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "status": "complete",
+            "success": true,
+            "markdown": "# New Paper\n\nBatch extracted.",
+            "json": {"pages": []}
+        })))
+        .mount(&dl_mock)
+        .await;
+
+    let zotero = make_zotero_client(&mock);
+    let dl = DatalabClient::new("mock-key").with_base_url(dl_mock.uri());
+    let results = extract_collection(&zotero, &dl, "COLL0001", ProcessingMode::Fast, 2)
+        .await
+        .unwrap();
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].key, KEY_BATCH_CACHED);
+    assert_eq!(results[0].outcome, BatchOutcome::Cached);
+    assert_eq!(results[1].key, KEY_BATCH_NEW);
+    assert_eq!(results[1].outcome, BatchOutcome::Extracted);
+    assert_eq!(results[1].title.as_deref(), Some("New Paper"));
+    assert_eq!(
+        datalab_cached_markdown(KEY_BATCH_NEW).as_deref(),
+        Some("# New Paper\n\nBatch extracted.")
+    );
+}
+
+/// Progress round-trips through the cache dir; marking moves keys between sets.
+#[test]
+fn test_batch_progress_read_write() {
+    test_cache_base();
+    remove_progress("COLLPRG1");
+    let _progress = ProgressCleanup("COLLPRG1");
+
+    assert_eq!(batch_progress::read("COLLPRG1"), batch_progress::BatchProgress::default());
+
+    let mut progress = batch_progress::BatchProgress::default();
+    progress.mark_failed("AAAA0001", "no PDF attachment");
+    progress.mark_done("AAAA0002");
+    batch_progress::write("COLLPRG1", &progress).unwrap();
+
+    let mut read = batch_progress::read("COLLPRG1");
+    assert_eq!(read, progress);
+    assert_eq!(read.failed.get("AAAA0001").map(String::as_str), Some("no PDF attachment"));
+
+    read.mark_done("AAAA0001");
+    assert!(read.failed.is_empty());
+    assert!(read.done.contains("AAAA0001") && read.done.contains("AAAA0002"));
+}
+
+/// A re-run skips keys recorded as done and retries recorded failures.
+#[tokio::test]
+async fn test_extract_collection_resumes_from_progress() {
+    test_cache_base();
+    remove_cache(KEY_RESUME_DONE);
+    remove_cache(KEY_RESUME_FAILED);
+    remove_progress("COLL0002");
+    let _progress = ProgressCleanup("COLL0002");
+
+    let mut saved = batch_progress::BatchProgress::default();
+    saved.mark_done(KEY_RESUME_DONE);
+    saved.mark_failed(KEY_RESUME_FAILED, "DataLab timed out");
+    batch_progress::write("COLL0002", &saved).unwrap();
+
+    let mock = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/users/test/collections/COLL0002/items/top"))
+        .respond_with(zotero_arr(2, &items_body(&[
+            (KEY_RESUME_DONE, "Done Paper"),
+            (KEY_RESUME_FAILED, "Failed Paper"),
+        ])))
+        .mount(&mock)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/users/test/items/{KEY_RESUME_DONE}/children")))
+        .respond_with(zotero_arr(0, "[]"))
+        .expect(0)
+        .mount(&mock)
+        .await;
+    // Still no attachment, so the retry fails again
+    Mock::given(method("GET"))
+        .and(path(format!("/users/test/items/{KEY_RESUME_FAILED}/children")))
+        .respond_with(zotero_arr(0, "[]"))
+        .expect(1)
+        .mount(&mock)
+        .await;
+
+    let zotero = make_zotero_client(&mock);
+    let dl = DatalabClient::new("mock-key").with_base_url(mock.uri());
+    let results = extract_collection(&zotero, &dl, "COLL0002", ProcessingMode::Fast, 2)
+        .await
+        .unwrap();
+
+    assert_eq!(results[0].outcome, BatchOutcome::Skipped);
+    assert!(matches!(&results[1].outcome, BatchOutcome::Failed { error } if error.contains("no PDF, EPUB, or HTML attachment")));
+
+    let progress = batch_progress::read("COLL0002");
+    assert!(progress.done.contains(KEY_RESUME_DONE));
+    assert!(progress.failed[KEY_RESUME_FAILED].contains("no PDF, EPUB, or HTML attachment"));
+}
+
+/// Library-wide grep returns only the cached document that matches, titled
+/// from its first heading when there is no meta.json.
+#[test]
+fn test_grep_cached_extractions_only_matching_doc() {
+    test_cache_base();
+    let _cleanup = CacheCleanup(vec![KEY_GREP_HIT, KEY_GREP_MISS]);
+    for (key, body) in [
+        (KEY_GREP_HIT, "# Bitonic Sorting\n\nWe use a zqxgrep network.\nUnrelated line.\n"),
+        (KEY_GREP_MISS, "# Radix Sorting\n\nNothing to see here.\n"),
+    ] {
+        remove_cache(key);
+        let dir = cache_dir_for(key);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(format!("{key}.md")), body).unwrap();
+    }
+
+    let docs = grep_cached_extractions("ZQXGREP", true, 1).unwrap();
+    let hits: Vec<_> = docs.iter().filter(|d| d.key == KEY_GREP_HIT || d.key == KEY_GREP_MISS).collect();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].key, KEY_GREP_HIT);
+    assert_eq!(hits[0].title.as_deref(), Some("Bitonic Sorting"));
+    assert_eq!(hits[0].matches.len(), 1);
+    assert_eq!(hits[0].matches[0].line_number, 3);
+    assert_eq!(hits[0].matches[0].after, vec!["Unrelated line.".to_string()]);
+
+    assert!(grep_cached_extractions("ZQXGREP", false, 0).unwrap().iter().all(|d| d.key != KEY_GREP_HIT));
+}
+
+#[test]
+fn test_export_extractions_writes_sanitized_filenames() {
+    test_cache_base();
+    let _cleanup = CacheCleanup(vec![KEY_EXPORT_META, KEY_EXPORT_HEADING]);
+    for key in [KEY_EXPORT_META, KEY_EXPORT_HEADING] {
+        remove_cache(key);
+        write_fake_cache(key);
+    }
+    let meta = serde_json::json!({"item_key": KEY_EXPORT_META, "title": "GPU Sorting: A/B Study?"});
+    std::fs::write(cache_dir_for(KEY_EXPORT_META).join("meta.json"), meta.to_string()).unwrap();
+
+    let out = std::env::temp_dir().join(format!("papers-export-test-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&out);
+    let summary = export_extractions(&out, false).unwrap();
+
+    let meta_file = out.join(format!("{KEY_EXPORT_META}-GPU Sorting AB Study.md"));
+    let heading_file = out.join(format!("{KEY_EXPORT_HEADING}-Paper {KEY_EXPORT_HEADING}.md"));
+    assert_eq!(
+        std::fs::read_to_string(&meta_file).unwrap(),
+        format!("# Paper {KEY_EXPORT_META}\n\nExtracted content.\n")
+    );
+    assert!(heading_file.is_file());
+    assert!(summary.exported.iter().any(|f| f.key == KEY_EXPORT_META && f.path == meta_file));
+    assert!(summary.exported.iter().any(|f| f.key == KEY_EXPORT_HEADING && f.path == heading_file));
+
+    std::fs::remove_dir_all(&out).unwrap();
+}
+
+#[test]
+fn test_export_extractions_frontmatter_is_parseable() {
+    test_cache_base();
+    let _cleanup = CacheCleanup(vec![KEY_EXPORT_FRONT]);
+    remove_cache(KEY_EXPORT_FRONT);
+    write_fake_cache(KEY_EXPORT_FRONT);
+    let meta = serde_json::json!({
+        "item_key": KEY_EXPORT_FRONT,
+        "title": "Sorting: \"Fast\" & #Parallel",
+        "authors": ["Ada Lovelace", "Alan Turing"],
+        "doi": "10.1234/sort",
+        "extracted_at": "2026-01-02T03:04:05Z",
+    });
+    std::fs::write(cache_dir_for(KEY_EXPORT_FRONT).join("meta.json"), meta.to_string()).unwrap();
+
+    let out = std::env::temp_dir().join(format!("papers-export-front-test-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&out);
+    let summary = export_extractions(&out, true).unwrap();
+    let file = summary.exported.iter().find(|f| f.key == KEY_EXPORT_FRONT).expect("exported");
+    let text = std::fs::read_to_string(&file.path).unwrap();
+    std::fs::remove_dir_all(&out).unwrap();
+
+    // Minimal YAML reader for the subset we emit: `k: "v"` scalars and
+    // `  - "v"` list items under the preceding key
+    let body = text.strip_prefix("---\n").expect("opening fence");
+    let (yaml, rest) = body.split_once("\n---\n").expect("closing fence");
+    assert_eq!(rest, format!("\n# Paper {KEY_EXPORT_FRONT}\n\nExtracted content.\n"));
+    let mut fields: std::collections::HashMap<String, Vec<String>> = Default::default();
+    let mut last = String::new();
+    for line in yaml.lines() {
+        if let Some(item) = line.strip_prefix("  - ") {
+            fields.get_mut(&last).unwrap().push(serde_json::from_str(item).unwrap());
+        } else {
+            let (k, v) = line.split_once(':').expect("key: value");
+            last = k.to_string();
+            let v = v.trim();
+            let values = if v.is_empty() { vec![] } else { vec![serde_json::from_str(v).unwrap()] };
+            fields.insert(last.clone(), values);
+        }
+    }
+    assert_eq!(fields["title"], vec!["Sorting: \"Fast\" & #Parallel".to_string()]);
+    assert_eq!(fields["authors"], vec!["Ada Lovelace".to_string(), "Alan Turing".to_string()]);
+    assert_eq!(fields["doi"], vec!["10.1234/sort".to_string()]);
+    assert_eq!(fields["extracted"], vec!["2026-01-02T03:04:05Z".to_string()]);
+    assert_eq!(fields["key"], vec![KEY_EXPORT_FRONT.to_string()]);
+}
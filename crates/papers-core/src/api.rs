@@ -1,20 +1,22 @@
 use papers_openalex::{
     Author, AutocompleteResponse, Domain, Field, FindWorksParams, FindWorksResponse, Funder,
-    GetParams, Institution, OpenAlexClient, OpenAlexError, Publisher, Source, Subfield,
+    GetParams, Institution, ListMeta, ListParams, OpenAlexClient, OpenAlexError, Publisher, Source, Subfield,
     Topic, Work,
 };
+use futures::{Stream, TryStreamExt as _};
 use papers_zotero::ZoteroClient;
 use serde::Serialize;
+use std::collections::{HashMap, HashSet};
 
 use crate::filter::{
     AuthorListParams, DomainListParams, FieldListParams, FilterError, FunderListParams,
     InstitutionListParams, PublisherListParams, SourceListParams, SubfieldListParams,
-    TopicListParams, WorkListParams, is_openalex_id, resolve_entity_id,
-    resolve_filters, WORK_ALIASES,
+    TopicListParams, WorkListParams, is_openalex_id, normalize_id, resolve_entity_id,
+    resolve_filter_entity, resolve_filters, WORK_ALIASES, WORK_FILTER_KEYS,
 };
 use crate::summary::{
     AuthorSummary, DomainSummary, FieldSummary, FunderSummary, InstitutionSummary,
-    PublisherSummary, SlimListResponse, SourceSummary, SubfieldSummary, TopicSummary, WorkSummary,
+    PublisherSummary, SlimListResponse, SlimProfile, SourceSummary, SubfieldSummary, TopicSummary, WorkSummary,
     summary_list_result,
 };
 
@@ -24,38 +26,172 @@ pub async fn work_list(
     client: &OpenAlexClient,
     params: &WorkListParams,
 ) -> Result<SlimListResponse<WorkSummary>, FilterError> {
+    work_list_with_profile(client, params, SlimProfile::Standard).await
+}
+
+/// [`work_list`] without slimming: the OpenAlex response body exactly as
+/// returned, for callers that need fields the summaries drop.
+pub async fn work_list_raw(client: &OpenAlexClient, params: &WorkListParams) -> Result<String, FilterError> {
     let (alias_values, mut list_params) = params.into_aliases_and_list_params();
-    list_params.filter = resolve_filters(client, WORK_ALIASES, &alias_values, list_params.filter.as_deref()).await?;
-    Ok(summary_list_result(client.list_works(&list_params).await, WorkSummary::from)?)
+    list_params.filter = resolve_filters(client, WORK_ALIASES, WORK_FILTER_KEYS, &alias_values, list_params.filter.as_deref()).await?;
+    Ok(client.list_raw("works", &list_params).await?)
+}
+
+/// Works funded by `funder`, an OpenAlex funder ID or a name (resolved to the
+/// most-cited match). Adds `grants.funder:<id>` to any filters in `params`.
+pub async fn funder_works(
+    client: &OpenAlexClient,
+    funder: &str,
+    params: &WorkListParams,
+) -> Result<SlimListResponse<WorkSummary>, FilterError> {
+    work_list(client, &with_funder_grant(client, funder, params).await?).await
+}
+
+/// [`funder_works`] as the unmodified OpenAlex response body.
+pub async fn funder_works_raw(
+    client: &OpenAlexClient,
+    funder: &str,
+    params: &WorkListParams,
+) -> Result<String, FilterError> {
+    work_list_raw(client, &with_funder_grant(client, funder, params).await?).await
+}
+
+async fn with_funder_grant(
+    client: &OpenAlexClient,
+    funder: &str,
+    params: &WorkListParams,
+) -> Result<WorkListParams, FilterError> {
+    let funder_id = resolve_filter_entity(client, "funders", funder).await?;
+    let grant = format!("grants.funder:{funder_id}");
+    let mut params = params.clone();
+    params.filter = Some(match params.filter.as_deref().filter(|f| !f.is_empty()) {
+        Some(filter) => format!("{filter},{grant}"),
+        None => grant,
+    });
+    Ok(params)
+}
+
+/// [`work_list`] with summaries slimmed to `profile`.
+pub async fn work_list_with_profile(
+    client: &OpenAlexClient,
+    params: &WorkListParams,
+    profile: SlimProfile,
+) -> Result<SlimListResponse<WorkSummary>, FilterError> {
+    let (alias_values, mut list_params) = params.into_aliases_and_list_params();
+    list_params.filter = resolve_filters(client, WORK_ALIASES, WORK_FILTER_KEYS, &alias_values, list_params.filter.as_deref()).await?;
+    Ok(summary_list_result(client.list_works(&list_params).await, |w| WorkSummary::from_work(w, profile))?)
+}
+
+/// Successive pages of a [`work_list_with_profile`] query.
+///
+/// Filter aliases are resolved once, before the first page. With `page` set
+/// the walk continues page by page until [`ListMeta::is_last_page`];
+/// otherwise it uses cursor pagination (starting from `cursor`, or `*`) until
+/// OpenAlex returns no further cursor.
+///
+/// [`ListMeta::is_last_page`]: papers_openalex::ListMeta::is_last_page
+pub fn work_list_pages<'a>(
+    client: &'a OpenAlexClient,
+    params: &WorkListParams,
+    profile: SlimProfile,
+) -> impl Stream<Item = Result<SlimListResponse<WorkSummary>, FilterError>> + 'a {
+    let (alias_values, mut list_params) = params.into_aliases_and_list_params();
+    if list_params.page.is_none() && list_params.cursor.is_none() {
+        list_params.cursor = Some("*".to_string());
+    }
+    let first = (Some(alias_values), list_params);
+    futures::stream::try_unfold(Some(first), move |state| async move {
+        let Some((alias_values, mut list_params)) = state else { return Ok(None) };
+        if let Some(alias_values) = alias_values {
+            list_params.filter = resolve_filters(client, WORK_ALIASES, WORK_FILTER_KEYS, &alias_values, list_params.filter.as_deref()).await?;
+        }
+        let resp = client.list_works(&list_params).await?;
+        let more = !resp.results.is_empty() && !resp.meta.is_last_page();
+        match (list_params.page, &resp.meta.next_cursor) {
+            (Some(page), _) => list_params.page = Some(page + 1),
+            (None, cursor) => list_params.cursor = cursor.clone(),
+        }
+        let page = SlimListResponse {
+            meta: resp.meta,
+            results: resp.results.into_iter().map(|w| WorkSummary::from_work(w, profile)).collect(),
+        };
+        Ok(Some((page, more.then_some((None, list_params)))))
+    })
+}
+
+/// Collect at most `limit_total` works from [`work_list_pages`], fetching no
+/// more pages than needed. The returned `meta` is the first page's, with
+/// `per_page` set to the number of works collected.
+pub async fn work_list_limited(
+    client: &OpenAlexClient,
+    params: &WorkListParams,
+    profile: SlimProfile,
+    limit_total: usize,
+) -> Result<SlimListResponse<WorkSummary>, FilterError> {
+    let mut pages = std::pin::pin!(work_list_pages(client, params, profile));
+    let mut meta = None;
+    let mut results = Vec::new();
+    while results.len() < limit_total {
+        let Some(page) = pages.try_next().await? else { break };
+        meta.get_or_insert(page.meta);
+        results.extend(page.results);
+    }
+    results.truncate(limit_total);
+    let mut meta = meta.unwrap_or(ListMeta {
+        count: 0,
+        db_response_time_ms: 0,
+        page: None,
+        per_page: None,
+        next_cursor: None,
+        groups_count: None,
+    });
+    meta.per_page = Some(results.len() as i32);
+    Ok(SlimListResponse { meta, results })
 }
 
 macro_rules! entity_list_fn {
-    ($fn_name:ident, $params_type:ident, $summary_type:ident, $client_method:ident) => {
+    ($fn_name:ident, $raw_fn_name:ident, $params_type:ident, $summary_type:ident, $client_method:ident, $entity_type:literal) => {
         pub async fn $fn_name(
             client: &OpenAlexClient,
             params: &$params_type,
         ) -> Result<SlimListResponse<$summary_type>, FilterError> {
-            let (alias_values, mut list_params) = params.into_aliases_and_list_params();
-            list_params.filter = resolve_filters(
-                client,
-                $params_type::alias_specs(),
-                &alias_values,
-                list_params.filter.as_deref(),
-            ).await?;
+            let list_params = resolved_list_params!(client, params, $params_type);
             Ok(summary_list_result(client.$client_method(&list_params).await, $summary_type::from)?)
         }
+
+        #[doc = concat!("[`", stringify!($fn_name), "`] as the unmodified OpenAlex response body.")]
+        pub async fn $raw_fn_name(client: &OpenAlexClient, params: &$params_type) -> Result<String, FilterError> {
+            let list_params = resolved_list_params!(client, params, $params_type);
+            Ok(client.list_raw($entity_type, &list_params).await?)
+        }
     };
 }
 
-entity_list_fn!(author_list, AuthorListParams, AuthorSummary, list_authors);
-entity_list_fn!(source_list, SourceListParams, SourceSummary, list_sources);
-entity_list_fn!(institution_list, InstitutionListParams, InstitutionSummary, list_institutions);
-entity_list_fn!(topic_list, TopicListParams, TopicSummary, list_topics);
-entity_list_fn!(publisher_list, PublisherListParams, PublisherSummary, list_publishers);
-entity_list_fn!(funder_list, FunderListParams, FunderSummary, list_funders);
-entity_list_fn!(domain_list, DomainListParams, DomainSummary, list_domains);
-entity_list_fn!(field_list, FieldListParams, FieldSummary, list_fields);
-entity_list_fn!(subfield_list, SubfieldListParams, SubfieldSummary, list_subfields);
+/// `params` as OpenAlex list parameters, with its filter aliases resolved
+/// into the filter expression.
+macro_rules! resolved_list_params {
+    ($client:expr, $params:expr, $params_type:ident) => {{
+        let (alias_values, mut list_params) = $params.into_aliases_and_list_params();
+        list_params.filter = resolve_filters(
+            $client,
+            $params_type::alias_specs(),
+            $params_type::filter_keys(),
+            &alias_values,
+            list_params.filter.as_deref(),
+        ).await?;
+        list_params
+    }};
+}
+
+entity_list_fn!(author_list, author_list_raw, AuthorListParams, AuthorSummary, list_authors, "authors");
+entity_list_fn!(source_list, source_list_raw, SourceListParams, SourceSummary, list_sources, "sources");
+entity_list_fn!(institution_list, institution_list_raw, InstitutionListParams, InstitutionSummary, list_institutions, "institutions");
+entity_list_fn!(topic_list, topic_list_raw, TopicListParams, TopicSummary, list_topics, "topics");
+entity_list_fn!(publisher_list, publisher_list_raw, PublisherListParams, PublisherSummary, list_publishers, "publishers");
+entity_list_fn!(funder_list, funder_list_raw, FunderListParams, FunderSummary, list_funders, "funders");
+entity_list_fn!(domain_list, domain_list_raw, DomainListParams, DomainSummary, list_domains, "domains");
+entity_list_fn!(field_list, field_list_raw, FieldListParams, FieldSummary, list_fields, "fields");
+entity_list_fn!(subfield_list, subfield_list_raw, SubfieldListParams, SubfieldSummary, list_subfields, "subfields");
 
 // ── Get (smart ID resolution) ────────────────────────────────────────────
 
@@ -68,10 +204,8 @@ fn looks_like_identifier(input: &str, entity_type: &str) -> bool {
     }
 
     // DOIs (works only, but safe to pass through for any entity — API will 404 gracefully)
-    if input.starts_with("https://doi.org/")
-        || input.starts_with("doi:")
-        || (input.starts_with("10.") && input.contains('/'))
-    {
+    let doi = strip_doi_prefix(input);
+    if doi.starts_with("10.") && doi.contains('/') {
         return true;
     }
 
@@ -123,13 +257,35 @@ fn bare_id_for_get(input: &str, entity_type: &str) -> String {
         _ => id,
     };
     // Bare DOIs (e.g. "10.1234/foo") need the "doi:" prefix for the OpenAlex /works/{id}
-    // endpoint. DOIs already prefixed with "doi:" or "https://doi.org/" are left unchanged.
-    if id.starts_with("10.") && id.contains('/') {
-        return format!("doi:{id}");
+    // endpoint. DOIs prefixed with "https://doi.org/" are left unchanged; any other
+    // prefix ("DOI:", "http://dx.doi.org/", ...) is rewritten to "doi:".
+    if id.starts_with("https://doi.org/") {
+        return id.to_string();
+    }
+    let doi = strip_doi_prefix(id);
+    if doi.starts_with("10.") && doi.contains('/') {
+        return format!("doi:{doi}");
     }
     id.to_string()
 }
 
+/// Strip a `doi:` or `doi.org` resolver prefix from `input`, ignoring case:
+/// `DOI:10.1/x`, `https://doi.org/10.1/x` and `http://dx.doi.org/10.1/x` all
+/// become `10.1/x`. Input without such a prefix is returned unchanged.
+fn strip_doi_prefix(input: &str) -> &str {
+    const PREFIXES: [&str; 5] = [
+        "doi:",
+        "https://doi.org/",
+        "http://doi.org/",
+        "https://dx.doi.org/",
+        "http://dx.doi.org/",
+    ];
+    PREFIXES
+        .iter()
+        .find(|p| input.get(..p.len()).is_some_and(|head| head.eq_ignore_ascii_case(p)))
+        .map_or(input, |p| &input[p.len()..])
+}
+
 /// Resolve an input string to an entity ID suitable for the get endpoint.
 ///
 /// If the input looks like a known identifier, it is returned in bare form.
@@ -150,7 +306,7 @@ async fn resolve_get_id(
 }
 
 macro_rules! entity_get_fn {
-    ($fn_name:ident, $return_type:ident, $client_method:ident, $entity_type:literal) => {
+    ($fn_name:ident, $raw_fn_name:ident, $return_type:ident, $client_method:ident, $entity_type:literal) => {
         pub async fn $fn_name(
             client: &OpenAlexClient,
             id: &str,
@@ -159,19 +315,25 @@ macro_rules! entity_get_fn {
             let resolved = resolve_get_id(client, id, $entity_type).await?;
             Ok(client.$client_method(&resolved, params).await?)
         }
+
+        #[doc = concat!("[`", stringify!($fn_name), "`] as the unmodified OpenAlex response body.")]
+        pub async fn $raw_fn_name(client: &OpenAlexClient, id: &str, params: &GetParams) -> Result<String, FilterError> {
+            let resolved = resolve_get_id(client, id, $entity_type).await?;
+            Ok(client.get_raw($entity_type, &resolved, params).await?)
+        }
     };
 }
 
-entity_get_fn!(work_get, Work, get_work, "works");
-entity_get_fn!(author_get, Author, get_author, "authors");
-entity_get_fn!(source_get, Source, get_source, "sources");
-entity_get_fn!(institution_get, Institution, get_institution, "institutions");
-entity_get_fn!(topic_get, Topic, get_topic, "topics");
-entity_get_fn!(publisher_get, Publisher, get_publisher, "publishers");
-entity_get_fn!(funder_get, Funder, get_funder, "funders");
-entity_get_fn!(domain_get, Domain, get_domain, "domains");
-entity_get_fn!(field_get, Field, get_field, "fields");
-entity_get_fn!(subfield_get, Subfield, get_subfield, "subfields");
+entity_get_fn!(work_get, work_get_raw, Work, get_work, "works");
+entity_get_fn!(author_get, author_get_raw, Author, get_author, "authors");
+entity_get_fn!(source_get, source_get_raw, Source, get_source, "sources");
+entity_get_fn!(institution_get, institution_get_raw, Institution, get_institution, "institutions");
+entity_get_fn!(topic_get, topic_get_raw, Topic, get_topic, "topics");
+entity_get_fn!(publisher_get, publisher_get_raw, Publisher, get_publisher, "publishers");
+entity_get_fn!(funder_get, funder_get_raw, Funder, get_funder, "funders");
+entity_get_fn!(domain_get, domain_get_raw, Domain, get_domain, "domains");
+entity_get_fn!(field_get, field_get_raw, Field, get_field, "fields");
+entity_get_fn!(subfield_get, subfield_get_raw, Subfield, get_subfield, "subfields");
 
 /// Combined work response including optional Zotero library metadata.
 #[derive(Debug, Clone, Serialize)]
@@ -189,7 +351,18 @@ pub async fn work_get_response(
     params: &GetParams,
 ) -> Result<WorkGetResponse, FilterError> {
     let t0 = std::time::Instant::now();
-    let work = work_get(client, id, params).await?;
+    let work = match work_get(client, id, params).await {
+        Ok(work) => work,
+        // A DOI/ID that OpenAlex doesn't know: offer close matches instead of a bare 404
+        Err(FilterError::Api(OpenAlexError::Api { status: 404, message })) => {
+            let suggestions = work_suggestions(client, id).await.unwrap_or_default();
+            if suggestions.is_empty() {
+                return Err(OpenAlexError::Api { status: 404, message }.into());
+            }
+            return Err(FilterError::Suggestions { query: id.to_string(), suggestions });
+        }
+        Err(e) => return Err(e),
+    };
     eprintln!("[timing] openalex work_get: {:?}", t0.elapsed());
 
     let zotero_info = if let Some(z) = zotero {
@@ -208,6 +381,105 @@ pub async fn work_get_response(
     })
 }
 
+// ── Coauthors ───────────────────────────────────────────────────────────
+
+/// Most recent works scanned when tallying coauthors (one OpenAlex page).
+const COAUTHOR_MAX_WORKS: u32 = 200;
+
+/// Upper bound on collaborators resolved in one call; OpenAlex caps OR filters
+/// at 100 values.
+const COAUTHOR_MAX_RESULTS: usize = 100;
+
+/// Find an author's most frequent collaborators.
+///
+/// Scans the author's `COAUTHOR_MAX_WORKS` most recent works, counts how many of
+/// them each other author appears on, and resolves the top `depth` collaborators
+/// (at most `COAUTHOR_MAX_RESULTS`) to full [`Author`] records in one request.
+/// Results are ordered by shared work count, highest first.
+///
+/// `author_id` accepts anything `author_get` does (OpenAlex ID, ORCID, or a
+/// name to search for).
+pub async fn coauthors(
+    client: &OpenAlexClient,
+    author_id: &str,
+    depth: usize,
+) -> Result<Vec<(Author, usize)>, FilterError> {
+    let author = author_get(client, author_id, &GetParams::builder().select("id").build()).await?;
+    let self_id = normalize_id(&author.id, "authors");
+
+    let works = client
+        .list_works(&ListParams {
+            filter: Some(format!("authorships.author.id:{self_id}")),
+            sort: Some("publication_date:desc".to_string()),
+            per_page: Some(COAUTHOR_MAX_WORKS),
+            select: Some("id,authorships".to_string()),
+            ..Default::default()
+        })
+        .await?;
+
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for work in &works.results {
+        // Count each collaborator once per work, even if listed twice
+        let ids: HashSet<String> = work
+            .authorships
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|a| a.author.as_ref()?.id.as_deref())
+            .map(|id| normalize_id(id, "authors"))
+            .filter(|id| *id != self_id)
+            .collect();
+        for id in ids {
+            *counts.entry(id).or_default() += 1;
+        }
+    }
+
+    let mut ranked: Vec<(String, usize)> = counts.into_iter().collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    ranked.truncate(depth.min(COAUTHOR_MAX_RESULTS));
+    if ranked.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let ids: Vec<&str> = ranked.iter().map(|(id, _)| id.as_str()).collect();
+    let authors = client
+        .list_authors(&ListParams {
+            filter: Some(format!("openalex:{}", ids.join("|"))),
+            per_page: Some(ids.len() as u32),
+            ..Default::default()
+        })
+        .await?;
+    let mut by_id: HashMap<String, Author> = authors
+        .results
+        .into_iter()
+        .map(|a| (normalize_id(&a.id, "authors"), a))
+        .collect();
+
+    Ok(ranked
+        .into_iter()
+        .filter_map(|(id, shared)| Some((by_id.remove(&id)?, shared)))
+        .collect())
+}
+
+/// Maximum number of candidates returned when a work lookup misses.
+const WORK_SUGGESTION_LIMIT: usize = 5;
+
+/// Autocomplete `query` against works and return the top candidates as
+/// `(display_name, cited_by_count)` pairs.
+async fn work_suggestions(
+    client: &OpenAlexClient,
+    query: &str,
+) -> Result<Vec<(String, u64)>, OpenAlexError> {
+    let query = strip_doi_prefix(query);
+    let resp = client.autocomplete_works(query).await?;
+    Ok(resp
+        .results
+        .into_iter()
+        .take(WORK_SUGGESTION_LIMIT)
+        .map(|r| (r.display_name, r.cited_by_count.unwrap_or(0).max(0) as u64))
+        .collect())
+}
+
 // ── Autocomplete ─────────────────────────────────────────────────────────
 
 pub async fn work_autocomplete(
@@ -231,11 +503,17 @@ pub async fn source_autocomplete(
     client.autocomplete_sources(q).await
 }
 
+/// Institution type-ahead, narrowed to one country when `country` (an ISO
+/// alpha-2 code) is given.
 pub async fn institution_autocomplete(
     client: &OpenAlexClient,
     q: &str,
+    country: Option<&str>,
 ) -> Result<AutocompleteResponse, OpenAlexError> {
-    client.autocomplete_institutions(q).await
+    match country {
+        Some(country) => client.autocomplete_institutions_in_country(q, country).await,
+        None => client.autocomplete_institutions(q).await,
+    }
 }
 
 pub async fn publisher_autocomplete(
@@ -338,6 +616,31 @@ mod tests {
         assert!(looks_like_identifier("10.1109/ipdps.2012.30", "works"));
     }
 
+    #[test]
+    fn id_doi_prefix_any_case() {
+        assert!(looks_like_identifier("DOI:10.1109/ipdps.2012.30", "works"));
+        assert!(looks_like_identifier("http://dx.doi.org/10.1109/ipdps.2012.30", "works"));
+        assert!(looks_like_identifier("HTTPS://DOI.ORG/10.1109/ipdps.2012.30", "works"));
+    }
+
+    #[test]
+    fn strip_doi_prefix_forms() {
+        assert_eq!(strip_doi_prefix("doi:10.1/x"), "10.1/x");
+        assert_eq!(strip_doi_prefix("DOI:10.1/x"), "10.1/x");
+        assert_eq!(strip_doi_prefix("https://doi.org/10.1/x"), "10.1/x");
+        assert_eq!(strip_doi_prefix("Https://Doi.org/10.1/x"), "10.1/x");
+        assert_eq!(strip_doi_prefix("http://dx.doi.org/10.1/x"), "10.1/x");
+        assert_eq!(strip_doi_prefix("10.1/x"), "10.1/x");
+        assert_eq!(strip_doi_prefix("attention is all you need"), "attention is all you need");
+    }
+
+    #[test]
+    fn bare_id_rewrites_doi_prefixes() {
+        assert_eq!(bare_id_for_get("DOI:10.1/x", "works"), "doi:10.1/x");
+        assert_eq!(bare_id_for_get("http://dx.doi.org/10.1/x", "works"), "doi:10.1/x");
+        assert_eq!(bare_id_for_get("https://doi.org/10.1/x", "works"), "https://doi.org/10.1/x");
+    }
+
     // ── looks_like_identifier: PubMed IDs ────────────────────────────────
 
     #[test]
@@ -0,0 +1,101 @@
+use std::collections::HashSet;
+
+use papers_zotero::{ItemListParams, ZoteroClient, ZoteroError};
+
+/// Item types that carry no bibliographic record of their own.
+const NON_BIBLIOGRAPHIC_TYPES: &[&str] = &["attachment", "note", "annotation"];
+
+/// Export every bibliographic item in a collection as BibTeX.
+///
+/// Lists every page of the collection,
+/// skips non-bibliographic items, and drops items already seen (the same key can
+/// reappear across pages if the library changes mid-export). The entries
+/// themselves come from Zotero's own BibTeX translator (`format=bibtex`), so
+/// escaping and entry types match a Zotero export.
+///
+/// Zotero only disambiguates cite keys within one request, so keys that
+/// collide across requests get the same `-1`, `-2`, ... suffix Zotero uses.
+pub async fn collection_to_bibtex(
+    client: &ZoteroClient,
+    collection_key: &str,
+) -> Result<String, ZoteroError> {
+    let items = crate::zotero::all_items(ItemListParams::default(), |params| async move {
+        client.list_collection_items(collection_key, &params).await
+    })
+    .await?;
+    let mut seen_items = HashSet::new();
+    let keys: Vec<String> = items
+        .into_iter()
+        .filter(|item| !NON_BIBLIOGRAPHIC_TYPES.contains(&item.data.item_type.as_str()))
+        .filter_map(|item| seen_items.insert(item.key.clone()).then_some(item.key))
+        .collect();
+    if keys.is_empty() {
+        return Ok(String::new());
+    }
+    let key_refs: Vec<&str> = keys.iter().map(String::as_str).collect();
+    let bib = client.export_items(&key_refs, "bibtex").await?;
+    Ok(dedup_cite_keys(&bib))
+}
+
+/// Rename repeated cite keys in concatenated BibTeX output to `key-1`,
+/// `key-2`, ... (skipping any suffix that is already taken).
+fn dedup_cite_keys(bib: &str) -> String {
+    let mut seen = HashSet::new();
+    let mut out = String::with_capacity(bib.len());
+    for line in bib.split_inclusive('\n') {
+        let Some((head, key)) = entry_header(line) else {
+            out.push_str(line);
+            continue;
+        };
+        let mut unique = key.to_string();
+        let mut n = 0;
+        while !seen.insert(unique.clone()) {
+            n += 1;
+            unique = format!("{key}-{n}");
+        }
+        out.push_str(head);
+        out.push_str(&unique);
+        out.push_str(&line[head.len() + key.len()..]);
+    }
+    out
+}
+
+/// Split an entry header line `@type{key,` into `("@type{", "key")`.
+fn entry_header(line: &str) -> Option<(&str, &str)> {
+    let rest = line.strip_prefix('@')?;
+    let brace = rest.find('{')?;
+    if !rest[..brace].chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    let after = &rest[brace + 1..];
+    let key = &after[..after.find(',')?];
+    (!key.is_empty()).then(|| (&line[..brace + 2], key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dedup_cite_keys() {
+        let bib = "\n@article{smith_same_2020,\n\ttitle = {A},\n}\n\n@article{smith_same_2020,\n\ttitle = {B},\n}\n\n@book{smith_same_2020-1,\n}\n\n@misc{smith_same_2020,\n}\n";
+        let out = dedup_cite_keys(bib);
+        assert!(out.contains("@article{smith_same_2020,\n\ttitle = {A}"));
+        assert!(out.contains("@article{smith_same_2020-1,\n\ttitle = {B}"));
+        assert!(out.contains("@book{smith_same_2020-1-1,\n"));
+        assert!(out.contains("@misc{smith_same_2020-2,\n"));
+    }
+
+    #[test]
+    fn test_entry_header() {
+        assert_eq!(entry_header("@article{lecun_deep_2015,\n"), Some(("@article{", "lecun_deep_2015")));
+        assert_eq!(entry_header("\ttitle = {@home{x,y}},\n"), None);
+        assert_eq!(entry_header("@comment{no key here}\n"), None);
+    }
+
+    #[test]
+    fn test_dedup_leaves_unique_keys_alone() {
+        let bib = "\n@article{a_2020,\n\ttitle = {{GPU} {Rendering}},\n}\n\n@phdthesis{b_2021,\n}\n";
+        assert_eq!(dedup_cite_keys(bib), bib);
+    }
+}
@@ -0,0 +1,113 @@
+//! Opening works in the user's browser.
+
+use std::fmt;
+use std::io;
+use std::process::Command;
+
+use papers_openalex::Work;
+
+/// Which kind of link [`work_open_url`] picked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkLink {
+    /// The PDF link of the best open-access location.
+    OaPdf,
+    /// The work's open-access URL (`open_access.oa_url`), typically a
+    /// landing page when no PDF link is known.
+    OaPage,
+    /// The DOI resolver URL.
+    Doi,
+    /// The work's OpenAlex page.
+    OpenAlex,
+}
+
+impl fmt::Display for WorkLink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            WorkLink::OaPdf => "open-access PDF",
+            WorkLink::OaPage => "open-access page",
+            WorkLink::Doi => "DOI",
+            WorkLink::OpenAlex => "OpenAlex page",
+        })
+    }
+}
+
+/// The best URL to open for `work`: its open-access PDF, else its
+/// open-access URL, else its DOI, else its OpenAlex page (which every work
+/// has).
+pub fn work_open_url(work: &Work) -> (WorkLink, String) {
+    if let Some(url) = work.best_oa_location.as_ref().and_then(|l| l.pdf_url.as_deref()) {
+        return (WorkLink::OaPdf, url.to_string());
+    }
+    if let Some(url) = work.open_access.as_ref().and_then(|oa| oa.oa_url.as_deref()) {
+        return (WorkLink::OaPage, url.to_string());
+    }
+    if let Some(doi) = work.doi.as_deref().filter(|d| !d.is_empty()) {
+        let bare = doi.strip_prefix("https://doi.org/").unwrap_or(doi);
+        return (WorkLink::Doi, format!("https://doi.org/{bare}"));
+    }
+    (WorkLink::OpenAlex, work.id.clone())
+}
+
+/// Open `url` in the default browser without waiting for it to exit.
+///
+/// Uses `start` on Windows, `open` on macOS, and `xdg-open` elsewhere.
+pub fn open_url(url: &str) -> io::Result<()> {
+    browser_command(url).spawn().map(drop)
+}
+
+#[cfg(target_os = "windows")]
+fn browser_command(url: &str) -> Command {
+    use std::os::windows::process::CommandExt;
+    // `start` treats its first quoted argument as the window title, hence the
+    // empty one. The URL is quoted so cmd doesn't split it at `&`; a literal
+    // `"` can't appear inside cmd quotes, so it is percent-encoded.
+    let mut command = Command::new("cmd");
+    command
+        .args(["/c", "start"])
+        .raw_arg("\"\"")
+        .raw_arg(format!("\"{}\"", url.replace('"', "%22")));
+    command
+}
+
+#[cfg(not(target_os = "windows"))]
+fn browser_command(url: &str) -> Command {
+    let program = if cfg!(target_os = "macos") { "open" } else { "xdg-open" };
+    let mut command = Command::new(program);
+    command.arg(url);
+    command
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn work(json: serde_json::Value) -> Work {
+        let mut json = json;
+        json["id"] = "https://openalex.org/W1".into();
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_work_open_url_precedence() {
+        let pdf = work(serde_json::json!({
+            "doi": "https://doi.org/10.1/x",
+            "best_oa_location": {"pdf_url": "https://example.org/x.pdf"}
+        }));
+        assert_eq!(work_open_url(&pdf), (WorkLink::OaPdf, "https://example.org/x.pdf".into()));
+
+        let page = work(serde_json::json!({
+            "doi": "https://doi.org/10.1/x",
+            "open_access": {"is_oa": true, "oa_url": "https://example.org/landing"}
+        }));
+        assert_eq!(work_open_url(&page), (WorkLink::OaPage, "https://example.org/landing".into()));
+
+        let doi = work(serde_json::json!({"doi": "https://doi.org/10.1/x"}));
+        assert_eq!(work_open_url(&doi), (WorkLink::Doi, "https://doi.org/10.1/x".into()));
+
+        let bare_doi = work(serde_json::json!({"doi": "10.1/y"}));
+        assert_eq!(work_open_url(&bare_doi), (WorkLink::Doi, "https://doi.org/10.1/y".into()));
+
+        let neither = work(serde_json::json!({}));
+        assert_eq!(work_open_url(&neither), (WorkLink::OpenAlex, "https://openalex.org/W1".into()));
+    }
+}
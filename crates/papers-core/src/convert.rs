@@ -0,0 +1,269 @@
+//! Conversion between OpenAlex works and Zotero items.
+//!
+//! Zotero validates fields per item type (a `conferencePaper` has no
+//! `publicationTitle`, a `book` no `issue`), so the type mapping and the
+//! fields each type accepts live together here.
+
+use papers_openalex::Work;
+use papers_zotero::{Creator, ItemData};
+
+/// Zotero item type used when a work carries no type information.
+const DEFAULT_ITEM_TYPE: &str = "journalArticle";
+
+/// Zotero item type for an OpenAlex work.
+///
+/// Prefers the granular Crossref type (`proceedings-article`,
+/// `posted-content`, ...) and falls back to OpenAlex's simplified `type`.
+/// Works with neither are assumed to be journal articles; types Zotero has no
+/// counterpart for map to `document`.
+pub fn zotero_item_type(work: &Work) -> &'static str {
+    let crossref = work.type_crossref.as_deref().and_then(|t| match t {
+        "journal-article" => Some("journalArticle"),
+        "proceedings-article" => Some("conferencePaper"),
+        "book-chapter" | "book-section" | "book-part" | "reference-entry" => Some("bookSection"),
+        "book" | "monograph" | "edited-book" | "reference-book" | "book-set" => Some("book"),
+        "dissertation" => Some("thesis"),
+        "posted-content" => Some("preprint"),
+        "report" | "report-component" => Some("report"),
+        "dataset" => Some("dataset"),
+        "standard" => Some("standard"),
+        _ => None,
+    });
+    if let Some(item_type) = crossref {
+        return item_type;
+    }
+    match work.r#type.as_deref() {
+        None => DEFAULT_ITEM_TYPE,
+        Some("article" | "review" | "letter" | "editorial" | "erratum" | "retraction") => "journalArticle",
+        Some("preprint") => "preprint",
+        Some("book") => "book",
+        Some("book-chapter") => "bookSection",
+        Some("dissertation") => "thesis",
+        Some("report") => "report",
+        Some("dataset") => "dataset",
+        Some("standard") => "standard",
+        Some(_) => "document",
+    }
+}
+
+/// Zotero field holding the venue name for `item_type`, if it has one.
+fn container_field(item_type: &str) -> Option<&'static str> {
+    match item_type {
+        "journalArticle" => Some("publicationTitle"),
+        "conferencePaper" => Some("proceedingsTitle"),
+        "bookSection" => Some("bookTitle"),
+        "preprint" => Some("repository"),
+        _ => None,
+    }
+}
+
+/// Whether Zotero accepts `field` on `item_type`, for the optional
+/// bibliographic fields [`work_to_item_data`] fills in.
+fn has_field(item_type: &str, field: &str) -> bool {
+    match field {
+        "volume" => matches!(item_type, "journalArticle" | "conferencePaper" | "bookSection" | "book"),
+        "issue" => item_type == "journalArticle",
+        "pages" => matches!(item_type, "journalArticle" | "conferencePaper" | "bookSection"),
+        "DOI" => matches!(item_type, "journalArticle" | "conferencePaper" | "preprint" | "dataset" | "standard"),
+        _ => false,
+    }
+}
+
+/// Build Zotero item data for an OpenAlex work.
+///
+/// Maps title, authors, abstract, publication date (falling back to year),
+/// venue, volume/issue/pages, and DOI (bare form; the `https://doi.org/` URL
+/// goes in `url`). The item type comes from [`zotero_item_type`], and only
+/// fields valid for that type are set: the venue lands in the type's
+/// container field (`publicationTitle`, `proceedingsTitle`, ...) and a DOI on
+/// a type without a `DOI` field is recorded as a `DOI: ...` line in `extra`,
+/// as Zotero itself does.
+///
+/// `key` and `version` are left empty; the result describes an item not yet
+/// in any library.
+pub fn work_to_item_data(work: &Work) -> ItemData {
+    let item_type = zotero_item_type(work);
+    let mut data = ItemData { item_type: item_type.to_string(), ..Default::default() };
+
+    data.title = work.display_name.clone().or_else(|| work.title.clone());
+    data.creators = work
+        .authorships
+        .iter()
+        .flatten()
+        .filter_map(|a| a.author.as_ref()?.display_name.as_deref())
+        .map(author_creator)
+        .collect();
+    data.abstract_note = work.abstract_text.clone();
+    data.date = work
+        .publication_date
+        .clone()
+        .or_else(|| work.publication_year.map(|y| y.to_string()));
+
+    let venue = work
+        .primary_location
+        .as_ref()
+        .and_then(|l| l.source.as_ref())
+        .and_then(|s| s.display_name.clone());
+    match (container_field(item_type), venue) {
+        (Some("publicationTitle"), venue) => data.publication_title = venue,
+        (Some(field), Some(venue)) => {
+            data.extra_fields.insert(field.to_string(), venue.into());
+        }
+        _ => {}
+    }
+
+    let biblio = work.biblio.as_ref();
+    if has_field(item_type, "volume") {
+        data.volume = biblio.and_then(|b| b.volume.clone());
+    }
+    if has_field(item_type, "issue") {
+        data.issue = biblio.and_then(|b| b.issue.clone());
+    }
+    if has_field(item_type, "pages") {
+        data.pages = biblio.and_then(|b| match (b.first_page.as_deref(), b.last_page.as_deref()) {
+            (Some(first), Some(last)) if first != last => Some(format!("{first}-{last}")),
+            (Some(first), _) => Some(first.to_string()),
+            _ => None,
+        });
+    }
+
+    let doi = work
+        .doi
+        .as_deref()
+        .map(|d| d.strip_prefix("https://doi.org/").unwrap_or(d).to_string());
+    if has_field(item_type, "DOI") {
+        data.doi = doi;
+    } else if let Some(doi) = doi {
+        data.extra = Some(format!("DOI: {doi}"));
+    }
+    data.url = work.doi.clone();
+    data
+}
+
+/// Split an OpenAlex display name into a Zotero two-field creator.
+///
+/// The last whitespace-separated token becomes the last name; single-token
+/// names use Zotero's single-field `name` form.
+fn author_creator(display_name: &str) -> Creator {
+    let display_name = display_name.trim();
+    match display_name.rsplit_once(' ') {
+        Some((first, last)) => Creator {
+            creator_type: "author".into(),
+            first_name: Some(first.trim().to_string()),
+            last_name: Some(last.to_string()),
+            name: None,
+        },
+        None => Creator {
+            creator_type: "author".into(),
+            first_name: None,
+            last_name: None,
+            name: Some(display_name.to_string()),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn work(json: serde_json::Value) -> Work {
+        let mut json = json;
+        json["id"] = "https://openalex.org/W1".into();
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_author_creator_two_field() {
+        let c = author_creator("Ashish  Vaswani");
+        assert_eq!(c.first_name.as_deref(), Some("Ashish"));
+        assert_eq!(c.last_name.as_deref(), Some("Vaswani"));
+        assert!(c.name.is_none());
+    }
+
+    #[test]
+    fn test_author_creator_single_name() {
+        let c = author_creator("OpenAI");
+        assert_eq!(c.name.as_deref(), Some("OpenAI"));
+        assert!(c.first_name.is_none());
+    }
+
+    #[test]
+    fn test_zotero_item_type_mapping() {
+        let cases = [
+            (None, None, "journalArticle"),
+            (Some("article"), None, "journalArticle"),
+            (Some("article"), Some("proceedings-article"), "conferencePaper"),
+            (Some("preprint"), Some("posted-content"), "preprint"),
+            (Some("book-chapter"), None, "bookSection"),
+            (Some("dissertation"), None, "thesis"),
+            (Some("paratext"), None, "document"),
+            (Some("review"), Some("unknown-crossref-type"), "journalArticle"),
+        ];
+        for (ty, crossref, expected) in cases {
+            let w = work(serde_json::json!({"type": ty, "type_crossref": crossref}));
+            assert_eq!(zotero_item_type(&w), expected, "{ty:?} / {crossref:?}");
+        }
+    }
+
+    #[test]
+    fn test_work_to_item_data_journal_article() {
+        let w = work(serde_json::json!({
+            "display_name": "A Great Paper",
+            "doi": "https://doi.org/10.1234/test",
+            "type": "article",
+            "type_crossref": "journal-article",
+            "publication_year": 2020,
+            "publication_date": "2020-05-01",
+            "authorships": [
+                {"author": {"id": "https://openalex.org/A1", "display_name": "Alice B. Smith"}},
+                {"author": {"id": "https://openalex.org/A2", "display_name": "OpenAI"}}
+            ],
+            "primary_location": {"source": {"id": "https://openalex.org/S1", "display_name": "Nature"}},
+            "biblio": {"volume": "7", "issue": "2", "first_page": "10", "last_page": "20"}
+        }));
+        let data = work_to_item_data(&w);
+        assert_eq!(data.item_type, "journalArticle");
+        assert_eq!(data.key, "");
+        assert_eq!(data.title.as_deref(), Some("A Great Paper"));
+        assert_eq!(data.date.as_deref(), Some("2020-05-01"));
+        assert_eq!(data.publication_title.as_deref(), Some("Nature"));
+        assert_eq!(data.volume.as_deref(), Some("7"));
+        assert_eq!(data.issue.as_deref(), Some("2"));
+        assert_eq!(data.pages.as_deref(), Some("10-20"));
+        assert_eq!(data.doi.as_deref(), Some("10.1234/test"));
+        assert_eq!(data.url.as_deref(), Some("https://doi.org/10.1234/test"));
+        let names: Vec<_> = data.authors().iter().filter_map(|c| c.display_name()).collect();
+        assert_eq!(names, ["Alice B. Smith", "OpenAI"]);
+    }
+
+    #[test]
+    fn test_work_to_item_data_uses_type_specific_fields() {
+        let w = work(serde_json::json!({
+            "display_name": "Fast Sorting",
+            "doi": "https://doi.org/10.1145/1",
+            "type": "article",
+            "type_crossref": "proceedings-article",
+            "publication_year": 2019,
+            "primary_location": {"source": {"id": "https://openalex.org/S2", "display_name": "SIGGRAPH"}},
+            "biblio": {"volume": "3", "issue": "4", "first_page": "5", "last_page": "5"}
+        }));
+        let data = work_to_item_data(&w);
+        assert_eq!(data.item_type, "conferencePaper");
+        assert!(data.publication_title.is_none());
+        assert_eq!(data.extra_fields["proceedingsTitle"], "SIGGRAPH");
+        assert_eq!(data.date.as_deref(), Some("2019"));
+        assert_eq!(data.volume.as_deref(), Some("3"));
+        assert!(data.issue.is_none());
+        assert_eq!(data.pages.as_deref(), Some("5"));
+        assert_eq!(data.doi.as_deref(), Some("10.1145/1"));
+
+        let thesis = work_to_item_data(&work(serde_json::json!({
+            "display_name": "My Thesis",
+            "doi": "https://doi.org/10.5555/t",
+            "type": "dissertation"
+        })));
+        assert_eq!(thesis.item_type, "thesis");
+        assert!(thesis.doi.is_none());
+        assert_eq!(thesis.extra.as_deref(), Some("DOI: 10.5555/t"));
+    }
+}
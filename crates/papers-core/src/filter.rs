@@ -1,5 +1,7 @@
 use papers_openalex::{ListParams, OpenAlexClient, OpenAlexError};
 
+use crate::geo;
+
 // ── Alias specification types ────────────────────────────────────────────
 
 /// Alias definition mapping a user-facing name to its OpenAlex filter key.
@@ -17,6 +19,12 @@ pub(crate) enum AliasKind {
     Direct,
     /// Boolean flag — when present and true, emits `filter_key:true`.
     Boolean,
+    /// Yes/no filter — emits `filter_key:{yes}` for true and
+    /// `filter_key:{no}` for false.
+    Either { yes: &'static str, no: &'static str },
+    /// Pass value through after checking each `|` segment against a vocabulary;
+    /// the checker returns suggestions on failure.
+    Checked(fn(&str) -> Result<(), Vec<String>>),
 }
 
 // ── Error type ───────────────────────────────────────────────────────────
@@ -29,6 +37,11 @@ pub enum FilterError {
         alias: &'static str,
         filter_key: &'static str,
     },
+    #[error("Conflict: '{first}' and '{second}' aliases cannot be combined")]
+    AliasConflict {
+        first: &'static str,
+        second: &'static str,
+    },
     #[error("No {entity_type} found matching \"{query}\"")]
     NotFound {
         entity_type: &'static str,
@@ -40,6 +53,24 @@ pub enum FilterError {
         /// Each entry is (display_name, cited_by_count).
         suggestions: Vec<(String, u64)>,
     },
+    #[error("Unknown filter key '{key}'{}", format_key_suggestions(.suggestions))]
+    UnknownKey {
+        key: String,
+        /// Valid keys for the entity that are close to `key`, best match first.
+        suggestions: Vec<String>,
+    },
+    #[error("Invalid {alias} '{value}'{}", format_key_suggestions(.suggestions))]
+    InvalidValue {
+        alias: &'static str,
+        value: String,
+        /// Accepted values the input most likely meant, best match first.
+        suggestions: Vec<String>,
+    },
+    #[error("Invalid filter clause '{clause}': {reason}")]
+    InvalidSyntax {
+        clause: String,
+        reason: &'static str,
+    },
     #[error(transparent)]
     Api(#[from] OpenAlexError),
 }
@@ -52,6 +83,14 @@ fn format_suggestions(suggestions: &[(String, u64)]) -> String {
         .join("\n")
 }
 
+fn format_key_suggestions(suggestions: &[String]) -> String {
+    if suggestions.is_empty() {
+        String::new()
+    } else {
+        format!(" (did you mean: {}?)", suggestions.join(", "))
+    }
+}
+
 // ── Alias constant arrays ────────────────────────────────────────────────
 
 pub(crate) const WORK_ALIASES: &[AliasSpec] = &[
@@ -64,17 +103,20 @@ pub(crate) const WORK_ALIASES: &[AliasSpec] = &[
     AliasSpec { name: "source",      filter_key: "primary_location.source.id",                entity_type: "sources",       kind: AliasKind::Entity },
     AliasSpec { name: "institution", filter_key: "authorships.institutions.lineage",          entity_type: "institutions",  kind: AliasKind::Entity },
     AliasSpec { name: "year",        filter_key: "publication_year",                          entity_type: "",              kind: AliasKind::Direct },
+    AliasSpec { name: "from_publication_date", filter_key: "from_publication_date",         entity_type: "",              kind: AliasKind::Checked(check_date) },
+    AliasSpec { name: "to_publication_date",   filter_key: "to_publication_date",           entity_type: "",              kind: AliasKind::Checked(check_date) },
     AliasSpec { name: "citations",   filter_key: "cited_by_count",                            entity_type: "",              kind: AliasKind::Direct },
-    AliasSpec { name: "country",     filter_key: "authorships.institutions.country_code",     entity_type: "",              kind: AliasKind::Direct },
-    AliasSpec { name: "continent",   filter_key: "authorships.institutions.continent",        entity_type: "",              kind: AliasKind::Direct },
-    AliasSpec { name: "type",        filter_key: "type",                                      entity_type: "",              kind: AliasKind::Direct },
+    AliasSpec { name: "country",     filter_key: "authorships.institutions.country_code",     entity_type: "",              kind: AliasKind::Checked(geo::check_country) },
+    AliasSpec { name: "continent",   filter_key: "authorships.institutions.continent",        entity_type: "",              kind: AliasKind::Checked(geo::check_continent) },
+    AliasSpec { name: "type",        filter_key: "type",                                      entity_type: "",              kind: AliasKind::Checked(check_work_type) },
     AliasSpec { name: "open",        filter_key: "is_oa",                                     entity_type: "",              kind: AliasKind::Boolean },
+    AliasSpec { name: "exclude_retracted", filter_key: "is_retracted",                      entity_type: "",              kind: AliasKind::Either { yes: "false", no: "true" } },
 ];
 
 pub(crate) const AUTHOR_ALIASES: &[AliasSpec] = &[
     AliasSpec { name: "institution", filter_key: "last_known_institutions.id",                entity_type: "institutions",  kind: AliasKind::Entity },
-    AliasSpec { name: "country",     filter_key: "last_known_institutions.country_code",      entity_type: "",              kind: AliasKind::Direct },
-    AliasSpec { name: "continent",   filter_key: "last_known_institutions.continent",         entity_type: "",              kind: AliasKind::Direct },
+    AliasSpec { name: "country",     filter_key: "last_known_institutions.country_code",      entity_type: "",              kind: AliasKind::Checked(geo::check_country) },
+    AliasSpec { name: "continent",   filter_key: "last_known_institutions.continent",         entity_type: "",              kind: AliasKind::Checked(geo::check_continent) },
     AliasSpec { name: "citations",   filter_key: "cited_by_count",                            entity_type: "",              kind: AliasKind::Direct },
     AliasSpec { name: "works",       filter_key: "works_count",                               entity_type: "",              kind: AliasKind::Direct },
     AliasSpec { name: "h_index",     filter_key: "summary_stats.h_index",                     entity_type: "",              kind: AliasKind::Direct },
@@ -82,17 +124,19 @@ pub(crate) const AUTHOR_ALIASES: &[AliasSpec] = &[
 
 pub(crate) const SOURCE_ALIASES: &[AliasSpec] = &[
     AliasSpec { name: "publisher",   filter_key: "host_organization_lineage",                 entity_type: "publishers",    kind: AliasKind::Entity },
-    AliasSpec { name: "country",     filter_key: "country_code",                              entity_type: "",              kind: AliasKind::Direct },
-    AliasSpec { name: "continent",   filter_key: "continent",                                 entity_type: "",              kind: AliasKind::Direct },
+    AliasSpec { name: "country",     filter_key: "country_code",                              entity_type: "",              kind: AliasKind::Checked(geo::check_country) },
+    AliasSpec { name: "continent",   filter_key: "continent",                                 entity_type: "",              kind: AliasKind::Checked(geo::check_continent) },
     AliasSpec { name: "type",        filter_key: "type",                                      entity_type: "",              kind: AliasKind::Direct },
     AliasSpec { name: "open",        filter_key: "is_oa",                                     entity_type: "",              kind: AliasKind::Boolean },
     AliasSpec { name: "citations",   filter_key: "cited_by_count",                            entity_type: "",              kind: AliasKind::Direct },
     AliasSpec { name: "works",       filter_key: "works_count",                               entity_type: "",              kind: AliasKind::Direct },
+    AliasSpec { name: "is_in_doaj",  filter_key: "is_in_doaj",                                entity_type: "",              kind: AliasKind::Either { yes: "true", no: "false" } },
+    AliasSpec { name: "has_apc",     filter_key: "apc_usd",                                   entity_type: "",              kind: AliasKind::Either { yes: ">0", no: "0" } },
 ];
 
 pub(crate) const INSTITUTION_ALIASES: &[AliasSpec] = &[
-    AliasSpec { name: "country",     filter_key: "country_code",                              entity_type: "",              kind: AliasKind::Direct },
-    AliasSpec { name: "continent",   filter_key: "continent",                                 entity_type: "",              kind: AliasKind::Direct },
+    AliasSpec { name: "country",     filter_key: "country_code",                              entity_type: "",              kind: AliasKind::Checked(geo::check_country) },
+    AliasSpec { name: "continent",   filter_key: "continent",                                 entity_type: "",              kind: AliasKind::Checked(geo::check_continent) },
     AliasSpec { name: "type",        filter_key: "type",                                      entity_type: "",              kind: AliasKind::Direct },
     AliasSpec { name: "citations",   filter_key: "cited_by_count",                            entity_type: "",              kind: AliasKind::Direct },
     AliasSpec { name: "works",       filter_key: "works_count",                               entity_type: "",              kind: AliasKind::Direct },
@@ -107,15 +151,15 @@ pub(crate) const TOPIC_ALIASES: &[AliasSpec] = &[
 ];
 
 pub(crate) const PUBLISHER_ALIASES: &[AliasSpec] = &[
-    AliasSpec { name: "country",     filter_key: "country_codes",                             entity_type: "",              kind: AliasKind::Direct },
-    AliasSpec { name: "continent",   filter_key: "continent",                                 entity_type: "",              kind: AliasKind::Direct },
+    AliasSpec { name: "country",     filter_key: "country_codes",                             entity_type: "",              kind: AliasKind::Checked(geo::check_country) },
+    AliasSpec { name: "continent",   filter_key: "continent",                                 entity_type: "",              kind: AliasKind::Checked(geo::check_continent) },
     AliasSpec { name: "citations",   filter_key: "cited_by_count",                            entity_type: "",              kind: AliasKind::Direct },
     AliasSpec { name: "works",       filter_key: "works_count",                               entity_type: "",              kind: AliasKind::Direct },
 ];
 
 pub(crate) const FUNDER_ALIASES: &[AliasSpec] = &[
-    AliasSpec { name: "country",     filter_key: "country_code",                              entity_type: "",              kind: AliasKind::Direct },
-    AliasSpec { name: "continent",   filter_key: "continent",                                 entity_type: "",              kind: AliasKind::Direct },
+    AliasSpec { name: "country",     filter_key: "country_code",                              entity_type: "",              kind: AliasKind::Checked(geo::check_country) },
+    AliasSpec { name: "continent",   filter_key: "continent",                                 entity_type: "",              kind: AliasKind::Checked(geo::check_continent) },
     AliasSpec { name: "citations",   filter_key: "cited_by_count",                            entity_type: "",              kind: AliasKind::Direct },
     AliasSpec { name: "works",       filter_key: "works_count",                               entity_type: "",              kind: AliasKind::Direct },
 ];
@@ -135,17 +179,199 @@ pub(crate) const SUBFIELD_ALIASES: &[AliasSpec] = &[
     AliasSpec { name: "works",       filter_key: "works_count",                               entity_type: "",              kind: AliasKind::Direct },
 ];
 
+// ── Filter key allowlists ────────────────────────────────────────────────
+//
+// Left-hand keys accepted in a raw `--filter` string, per entity. These mirror
+// the filterable fields documented by OpenAlex; a key missing from the list is
+// rejected locally instead of round-tripping to a 400 from the API.
+
+/// Alias pairs that constrain the same field and may not be set together.
+const EXCLUSIVE_ALIASES: &[(&str, &str)] = &[
+    ("year", "from_publication_date"),
+    ("year", "to_publication_date"),
+];
+
+/// Check a `YYYY-MM-DD` calendar date. A date in another common layout
+/// (`2020/5/1`, `2020.05.01`) gets the canonical form as its suggestion.
+fn check_date(value: &str) -> Result<(), Vec<String>> {
+    fn parse(value: &str, sep: char) -> Option<(u32, u32, u32)> {
+        let mut parts = value.split(sep);
+        let (y, m, d) = (parts.next()?, parts.next()?, parts.next()?);
+        if parts.next().is_some() || y.len() != 4 || m.len() > 2 || d.len() > 2 {
+            return None;
+        }
+        let (y, m, d) = (y.parse().ok()?, m.parse().ok()?, d.parse().ok()?);
+        let leap = (y % 4 == 0 && y % 100 != 0) || y % 400 == 0;
+        let days = match m {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if leap => 29,
+            2 => 28,
+            _ => return None,
+        };
+        (1..=days).contains(&d).then_some((y, m, d))
+    }
+
+    let canonical = value.len() == 10 && parse(value, '-').is_some();
+    if canonical {
+        return Ok(());
+    }
+    Err(['-', '/', '.']
+        .into_iter()
+        .find_map(|sep| parse(value, sep))
+        .map(|(y, m, d)| vec![format!("{y:04}-{m:02}-{d:02}")])
+        .unwrap_or_default())
+}
+
+/// Work `type` values OpenAlex assigns (`type` filter / `--type`).
+pub(crate) const WORK_TYPES: &[&str] = &[
+    "article", "book", "book-chapter", "dataset", "dissertation", "editorial", "erratum",
+    "grant", "letter", "libguides", "other", "paratext", "peer-review", "preprint",
+    "reference-entry", "report", "retraction", "review", "standard", "supplementary-materials",
+];
+
+/// Check a work type (case-insensitive), suggesting the closest known types
+/// for a typo. Spaces and underscores are read as hyphens (`book chapter`).
+fn check_work_type(value: &str) -> Result<(), Vec<String>> {
+    let lower = value.to_lowercase();
+    if WORK_TYPES.contains(&lower.as_str()) {
+        return Ok(());
+    }
+    let normalized = lower.replace([' ', '_'], "-");
+    let mut scored: Vec<(usize, &str)> = WORK_TYPES
+        .iter()
+        .map(|t| (edit_distance(&normalized, t), *t))
+        .filter(|&(d, _)| d <= 2)
+        .collect();
+    scored.sort();
+    Err(scored.into_iter().take(3).map(|(_, t)| t.to_string()).collect())
+}
+
+pub(crate) const WORK_FILTER_KEYS: &[&str] = &[
+    "abstract.search", "apc_list.currency", "apc_list.value", "apc_list.value_usd",
+    "apc_paid.currency", "apc_paid.value", "apc_paid.value_usd",
+    "authors_count", "authorships.affiliations.institution_ids", "authorships.author.id",
+    "authorships.author.orcid", "authorships.countries", "authorships.institutions.continent",
+    "authorships.institutions.country_code", "authorships.institutions.id",
+    "authorships.institutions.is_global_south", "authorships.institutions.lineage",
+    "authorships.institutions.ror", "authorships.institutions.type", "authorships.is_corresponding",
+    "best_oa_location.is_accepted", "best_oa_location.is_published", "best_oa_location.license",
+    "best_oa_location.source.host_organization", "best_oa_location.source.id",
+    "best_oa_location.source.is_in_doaj", "best_oa_location.source.issn",
+    "best_oa_location.source.type", "best_oa_location.version",
+    "citation_normalized_percentile.is_in_top_1_percent",
+    "citation_normalized_percentile.is_in_top_10_percent", "citation_normalized_percentile.value",
+    "cited_by", "cited_by_count", "cited_by_percentile_year.max", "cited_by_percentile_year.min",
+    "cites", "concepts.id", "concepts_count", "corresponding_author_ids",
+    "corresponding_institution_ids", "countries_distinct_count", "default.search",
+    "display_name", "display_name.search", "doi", "doi_starts_with", "from_created_date",
+    "from_publication_date", "from_updated_date", "fulltext.search", "fulltext_origin",
+    "funders.id", "fwci", "grants.award_id", "grants.funder", "has_abstract", "has_doi",
+    "has_fulltext", "has_oa_accepted_or_published_version", "has_oa_submitted_version",
+    "has_orcid", "has_pmcid", "has_pmid", "has_references", "ids.mag", "ids.openalex",
+    "ids.pmcid", "ids.pmid", "indexed_in", "institutions_distinct_count", "is_corresponding",
+    "is_oa", "is_paratext", "is_retracted", "keywords.id", "language",
+    "locations.is_accepted", "locations.is_oa", "locations.is_published", "locations.license",
+    "locations.source.host_organization", "locations.source.host_organization_lineage",
+    "locations.source.id", "locations.source.is_in_doaj", "locations.source.is_oa",
+    "locations.source.issn", "locations.source.publisher_lineage", "locations.source.type",
+    "locations.version", "locations_count", "mag", "oa_status",
+    "open_access.any_repository_has_fulltext", "open_access.is_oa", "open_access.oa_status",
+    "openalex", "openalex_id", "pmcid", "pmid", "primary_location.is_accepted",
+    "primary_location.is_oa", "primary_location.is_published", "primary_location.license",
+    "primary_location.source.has_issn", "primary_location.source.host_organization",
+    "primary_location.source.host_organization_lineage", "primary_location.source.id",
+    "primary_location.source.is_in_doaj", "primary_location.source.is_oa",
+    "primary_location.source.issn", "primary_location.source.publisher_lineage",
+    "primary_location.source.type", "primary_location.version", "primary_topic.domain.id",
+    "primary_topic.field.id", "primary_topic.id", "primary_topic.subfield.id",
+    "publication_date", "publication_year", "raw_affiliation_strings.search",
+    "raw_author_name.search", "referenced_works", "referenced_works_count", "related_to",
+    "repository", "sustainable_development_goals.id", "sustainable_development_goals.score",
+    "title.search", "title_and_abstract.search", "to_created_date", "to_publication_date",
+    "to_updated_date", "topics.domain.id", "topics.field.id", "topics.id", "topics.subfield.id",
+    "topics_count", "type", "type_crossref", "version",
+];
+
+pub(crate) const AUTHOR_FILTER_KEYS: &[&str] = &[
+    "affiliations.institution.continent", "affiliations.institution.country_code",
+    "affiliations.institution.id", "affiliations.institution.is_global_south",
+    "affiliations.institution.lineage", "affiliations.institution.ror",
+    "affiliations.institution.type", "cited_by_count", "default.search", "display_name",
+    "display_name.search", "has_orcid", "ids.openalex", "ids.orcid",
+    "last_known_institutions.continent", "last_known_institutions.country_code",
+    "last_known_institutions.id", "last_known_institutions.is_global_south",
+    "last_known_institutions.lineage", "last_known_institutions.ror",
+    "last_known_institutions.type", "openalex", "orcid", "summary_stats.2yr_mean_citedness",
+    "summary_stats.h_index", "summary_stats.i10_index", "topic_share.id", "topics.domain.id",
+    "topics.field.id", "topics.id", "topics.subfield.id", "works_count",
+];
+
+pub(crate) const SOURCE_FILTER_KEYS: &[&str] = &[
+    "apc_prices.currency", "apc_prices.price", "apc_usd", "cited_by_count", "continent",
+    "country_code", "default.search", "display_name", "display_name.search", "has_issn",
+    "host_organization", "host_organization_lineage", "ids.openalex", "is_core",
+    "is_global_south", "is_in_doaj", "is_oa", "issn", "openalex",
+    "summary_stats.2yr_mean_citedness", "summary_stats.h_index", "summary_stats.i10_index",
+    "topic_share.id", "topics.id", "type", "works_count",
+];
+
+pub(crate) const INSTITUTION_FILTER_KEYS: &[&str] = &[
+    "cited_by_count", "continent", "country_code", "default.search", "display_name",
+    "display_name.search", "has_ror", "ids.openalex", "ids.ror", "is_global_south", "lineage",
+    "openalex", "repositories.host_organization", "repositories.id", "roles.id", "ror",
+    "summary_stats.2yr_mean_citedness", "summary_stats.h_index", "summary_stats.i10_index",
+    "topic_share.id", "topics.id", "type", "works_count",
+];
+
+pub(crate) const TOPIC_FILTER_KEYS: &[&str] = &[
+    "cited_by_count", "default.search", "display_name", "display_name.search", "domain.id",
+    "field.id", "ids.openalex", "openalex", "subfield.id", "works_count",
+];
+
+pub(crate) const PUBLISHER_FILTER_KEYS: &[&str] = &[
+    "cited_by_count", "continent", "country_codes", "default.search", "display_name",
+    "display_name.search", "hierarchy_level", "ids.openalex", "ids.ror", "lineage", "openalex",
+    "parent_publisher", "ror", "summary_stats.2yr_mean_citedness", "summary_stats.h_index",
+    "summary_stats.i10_index", "works_count",
+];
+
+pub(crate) const FUNDER_FILTER_KEYS: &[&str] = &[
+    "cited_by_count", "continent", "country_code", "default.search", "display_name",
+    "display_name.search", "grants_count", "ids.openalex", "ids.ror", "is_global_south",
+    "openalex", "ror", "summary_stats.2yr_mean_citedness", "summary_stats.h_index",
+    "summary_stats.i10_index", "works_count",
+];
+
+pub(crate) const DOMAIN_FILTER_KEYS: &[&str] = &[
+    "cited_by_count", "default.search", "display_name", "display_name.search", "ids.openalex",
+    "openalex", "works_count",
+];
+
+pub(crate) const FIELD_FILTER_KEYS: &[&str] = &[
+    "cited_by_count", "default.search", "display_name", "display_name.search", "domain.id",
+    "ids.openalex", "openalex", "works_count",
+];
+
+pub(crate) const SUBFIELD_FILTER_KEYS: &[&str] = &[
+    "cited_by_count", "default.search", "display_name", "display_name.search", "domain.id",
+    "field.id", "ids.openalex", "openalex", "works_count",
+];
+
 // ── Generic resolve_filters ──────────────────────────────────────────────
 
 /// Resolves filter aliases + raw filter into a single filter string.
 ///
-/// Each alias is resolved (IDs passed through, search strings resolved via API),
-/// then combined with the raw filter using comma-separated AND logic.
+/// The raw filter is validated against `filter_keys` first, so typos fail before
+/// any request is made. Each alias is then resolved (IDs passed through, search
+/// strings resolved via API) and combined with the raw filter using
+/// comma-separated AND logic.
 ///
 /// Returns `None` if no filters are active.
 pub(crate) async fn resolve_filters(
     client: &OpenAlexClient,
     alias_specs: &[AliasSpec],
+    filter_keys: &[&str],
     alias_values: &[Option<String>],
     raw_filter: Option<&str>,
 ) -> Result<Option<String>, FilterError> {
@@ -159,9 +385,18 @@ pub(crate) async fn resolve_filters(
         .map(|(spec, _)| (spec.name, spec.filter_key))
         .collect();
 
-    // Check for overlap with raw filter
+    for &(first, second) in EXCLUSIVE_ALIASES {
+        let is_active = |name| active.iter().any(|&(n, _)| n == name);
+        if is_active(first) && is_active(second) {
+            return Err(FilterError::AliasConflict { first, second });
+        }
+    }
+    validate_checked_aliases(alias_specs, alias_values)?;
+
+    // Validate and check for overlap with raw filter
     if let Some(raw) = raw_filter {
         if !raw.is_empty() {
+            validate_filter(raw, filter_keys)?;
             check_filter_overlap(raw, &active)?;
         }
     }
@@ -172,11 +407,14 @@ pub(crate) async fn resolve_filters(
     for (spec, value) in alias_specs.iter().zip(alias_values.iter()) {
         if let Some(val) = value {
             let resolved_value = match spec.kind {
-                AliasKind::Direct => val.clone(),
+                AliasKind::Direct | AliasKind::Checked(_) => val.clone(),
                 AliasKind::Entity => {
-                    resolve_alias_value(client, val, spec.entity_type).await?
+                    resolve_filter_entity(client, spec.entity_type, val).await?
                 }
                 AliasKind::Boolean => "true".to_string(),
+                AliasKind::Either { yes, no } => {
+                    if val == "true" { yes } else { no }.to_string()
+                }
             };
             conditions.push(format!("{}:{}", spec.filter_key, resolved_value));
         }
@@ -196,6 +434,27 @@ pub(crate) async fn resolve_filters(
     }
 }
 
+/// Check [`AliasKind::Checked`] values (`country`, `continent`, work `type`)
+/// locally, since the API answers a bad one with a bare 400. Each
+/// `|`-separated segment is checked, ignoring a leading `!` negation.
+fn validate_checked_aliases(
+    alias_specs: &[AliasSpec],
+    alias_values: &[Option<String>],
+) -> Result<(), FilterError> {
+    for (spec, value) in alias_specs.iter().zip(alias_values) {
+        let (AliasKind::Checked(check), Some(value)) = (&spec.kind, value) else { continue };
+        for segment in value.split('|').map(str::trim) {
+            let segment = segment.strip_prefix('!').unwrap_or(segment);
+            check(segment).map_err(|suggestions| FilterError::InvalidValue {
+                alias: spec.name,
+                value: segment.to_string(),
+                suggestions,
+            })?;
+        }
+    }
+    Ok(())
+}
+
 // ── WorkListParams ───────────────────────────────────────────────────────
 
 /// Combined parameters for `work_list`, including both standard list parameters
@@ -228,11 +487,17 @@ pub struct WorkListParams {
     pub source: Option<String>,
     pub institution: Option<String>,
     pub year: Option<String>,
+    /// Earliest publication date, `YYYY-MM-DD`. Cannot be combined with `year`.
+    pub from_publication_date: Option<String>,
+    /// Latest publication date, `YYYY-MM-DD`. Cannot be combined with `year`.
+    pub to_publication_date: Option<String>,
     pub citations: Option<String>,
     pub country: Option<String>,
     pub continent: Option<String>,
     pub r#type: Option<String>,
     pub open: Option<bool>,
+    /// Drop retracted works (`is_retracted:false`).
+    pub exclude_retracted: Option<bool>,
 }
 
 impl WorkListParams {
@@ -247,11 +512,14 @@ impl WorkListParams {
             self.source.clone(),
             self.institution.clone(),
             self.year.clone(),
+            self.from_publication_date.clone(),
+            self.to_publication_date.clone(),
             self.citations.clone(),
             self.country.clone(),
             self.continent.clone(),
             self.r#type.clone(),
             if self.open == Some(true) { Some("true".to_string()) } else { None },
+            if self.exclude_retracted == Some(true) { Some("true".to_string()) } else { None },
         ];
         let list_params = ListParams {
             filter: self.filter.clone(),
@@ -285,13 +553,16 @@ pub async fn resolve_work_filters(
         aliases.source.clone(),
         aliases.institution.clone(),
         aliases.year.clone(),
+        aliases.from_publication_date.clone(),
+        aliases.to_publication_date.clone(),
         aliases.citations.clone(),
         aliases.country.clone(),
         aliases.continent.clone(),
         aliases.r#type.clone(),
         if aliases.open == Some(true) { Some("true".to_string()) } else { None },
+        if aliases.exclude_retracted == Some(true) { Some("true".to_string()) } else { None },
     ];
-    resolve_filters(client, WORK_ALIASES, &alias_values, raw_filter).await
+    resolve_filters(client, WORK_ALIASES, WORK_FILTER_KEYS, &alias_values, raw_filter).await
 }
 
 /// Alias fields for work list filters (kept for backward compatibility with tests).
@@ -306,11 +577,17 @@ pub struct WorkFilterAliases {
     pub source: Option<String>,
     pub institution: Option<String>,
     pub year: Option<String>,
+    /// Earliest publication date, `YYYY-MM-DD`. Cannot be combined with `year`.
+    pub from_publication_date: Option<String>,
+    /// Latest publication date, `YYYY-MM-DD`. Cannot be combined with `year`.
+    pub to_publication_date: Option<String>,
     pub citations: Option<String>,
     pub country: Option<String>,
     pub continent: Option<String>,
     pub r#type: Option<String>,
     pub open: Option<bool>,
+    /// Drop retracted works (`is_retracted:false`).
+    pub exclude_retracted: Option<bool>,
 }
 
 // ── Macro for entity list params ─────────────────────────────────────────
@@ -319,6 +596,7 @@ macro_rules! entity_list_params {
     (
         $name:ident,
         aliases: $aliases:ident,
+        filter_keys: $filter_keys:ident,
         fields: [ $( $field:ident : $kind:ident ),* $(,)? ]
     ) => {
         #[derive(Debug, Default, Clone)]
@@ -361,11 +639,16 @@ macro_rules! entity_list_params {
             pub(crate) fn alias_specs() -> &'static [AliasSpec] {
                 $aliases
             }
+
+            pub(crate) fn filter_keys() -> &'static [&'static str] {
+                $filter_keys
+            }
         }
     };
 
     (@field_type string) => { Option<String> };
     (@field_type bool) => { Option<bool> };
+    (@field_type either) => { Option<bool> };
 
     (@to_option_string $self:ident, $field:ident, string) => {
         $self.$field.clone()
@@ -373,9 +656,12 @@ macro_rules! entity_list_params {
     (@to_option_string $self:ident, $field:ident, bool) => {
         if $self.$field == Some(true) { Some("true".to_string()) } else { None }
     };
+    (@to_option_string $self:ident, $field:ident, either) => {
+        $self.$field.map(|b| b.to_string())
+    };
 }
 
-entity_list_params!(AuthorListParams, aliases: AUTHOR_ALIASES, fields: [
+entity_list_params!(AuthorListParams, aliases: AUTHOR_ALIASES, filter_keys: AUTHOR_FILTER_KEYS, fields: [
     institution: string,
     country: string,
     continent: string,
@@ -384,7 +670,7 @@ entity_list_params!(AuthorListParams, aliases: AUTHOR_ALIASES, fields: [
     h_index: string,
 ]);
 
-entity_list_params!(SourceListParams, aliases: SOURCE_ALIASES, fields: [
+entity_list_params!(SourceListParams, aliases: SOURCE_ALIASES, filter_keys: SOURCE_FILTER_KEYS, fields: [
     publisher: string,
     country: string,
     continent: string,
@@ -392,9 +678,26 @@ entity_list_params!(SourceListParams, aliases: SOURCE_ALIASES, fields: [
     open: bool,
     citations: string,
     works: string,
+    is_in_doaj: either,
+    has_apc: either,
 ]);
 
-entity_list_params!(InstitutionListParams, aliases: INSTITUTION_ALIASES, fields: [
+impl SourceListParams {
+    /// Only sources that are (`true`) or are not (`false`) indexed in DOAJ.
+    pub fn is_in_doaj(mut self, in_doaj: bool) -> Self {
+        self.is_in_doaj = Some(in_doaj);
+        self
+    }
+
+    /// Only sources that charge an APC (`apc_usd:>0`) or that list an APC of
+    /// zero (`apc_usd:0`). Sources with no APC data match neither.
+    pub fn has_apc(mut self, has_apc: bool) -> Self {
+        self.has_apc = Some(has_apc);
+        self
+    }
+}
+
+entity_list_params!(InstitutionListParams, aliases: INSTITUTION_ALIASES, filter_keys: INSTITUTION_FILTER_KEYS, fields: [
     country: string,
     continent: string,
     r#type: string,
@@ -402,7 +705,7 @@ entity_list_params!(InstitutionListParams, aliases: INSTITUTION_ALIASES, fields:
     works: string,
 ]);
 
-entity_list_params!(TopicListParams, aliases: TOPIC_ALIASES, fields: [
+entity_list_params!(TopicListParams, aliases: TOPIC_ALIASES, filter_keys: TOPIC_FILTER_KEYS, fields: [
     domain: string,
     field: string,
     subfield: string,
@@ -410,30 +713,30 @@ entity_list_params!(TopicListParams, aliases: TOPIC_ALIASES, fields: [
     works: string,
 ]);
 
-entity_list_params!(PublisherListParams, aliases: PUBLISHER_ALIASES, fields: [
+entity_list_params!(PublisherListParams, aliases: PUBLISHER_ALIASES, filter_keys: PUBLISHER_FILTER_KEYS, fields: [
     country: string,
     continent: string,
     citations: string,
     works: string,
 ]);
 
-entity_list_params!(FunderListParams, aliases: FUNDER_ALIASES, fields: [
+entity_list_params!(FunderListParams, aliases: FUNDER_ALIASES, filter_keys: FUNDER_FILTER_KEYS, fields: [
     country: string,
     continent: string,
     citations: string,
     works: string,
 ]);
 
-entity_list_params!(DomainListParams, aliases: DOMAIN_ALIASES, fields: [
+entity_list_params!(DomainListParams, aliases: DOMAIN_ALIASES, filter_keys: DOMAIN_FILTER_KEYS, fields: [
     works: string,
 ]);
 
-entity_list_params!(FieldListParams, aliases: FIELD_ALIASES, fields: [
+entity_list_params!(FieldListParams, aliases: FIELD_ALIASES, filter_keys: FIELD_FILTER_KEYS, fields: [
     domain: string,
     works: string,
 ]);
 
-entity_list_params!(SubfieldListParams, aliases: SUBFIELD_ALIASES, fields: [
+entity_list_params!(SubfieldListParams, aliases: SUBFIELD_ALIASES, filter_keys: SUBFIELD_FILTER_KEYS, fields: [
     domain: string,
     field: string,
     works: string,
@@ -606,6 +909,34 @@ pub(crate) async fn resolve_entity_id(
     Ok(normalize_id(id, entity_type))
 }
 
+/// Resolve an author name in a filter alias via `GET /autocomplete/authors?q={query}`.
+///
+/// Common names match many people, so the top hit is not trusted blindly: the
+/// sole candidate, or the sole candidate whose `display_name` equals the query
+/// (case-insensitive), is accepted. Anything else is ambiguous and returns
+/// [`FilterError::Suggestions`] listing the candidates.
+async fn resolve_author_id(client: &OpenAlexClient, query: &str) -> Result<String, FilterError> {
+    let candidates = client.autocomplete_authors(query).await?.results;
+    let query_lower = query.to_lowercase();
+    let mut exact = candidates.iter().filter(|c| c.display_name.to_lowercase() == query_lower);
+    let chosen = match (candidates.as_slice(), exact.next(), exact.next()) {
+        ([], _, _) => {
+            return Err(FilterError::NotFound { entity_type: "authors", query: query.to_string() });
+        }
+        ([only], _, _) | (_, Some(only), None) => only,
+        _ => {
+            return Err(FilterError::Suggestions {
+                query: query.to_string(),
+                suggestions: candidates
+                    .iter()
+                    .map(|c| (c.display_name.clone(), c.cited_by_count.unwrap_or(0).max(0) as u64))
+                    .collect(),
+            });
+        }
+    };
+    Ok(normalize_id(&chosen.id, "authors"))
+}
+
 /// Convert a typed ListResponse into a ListResponse<serde_json::Value>.
 pub(crate) fn transmute_list<T: serde::Serialize>(
     resp: papers_openalex::ListResponse<T>,
@@ -621,6 +952,129 @@ pub(crate) fn transmute_list<T: serde::Serialize>(
     }
 }
 
+/// Comparison operator of a single filter clause.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterOp {
+    /// `key:value`
+    Eq,
+    /// `key:!value`
+    Ne,
+    /// `key:>value`
+    Gt,
+    /// `key:<value`
+    Lt,
+}
+
+/// One `key:value` condition from a comma-separated filter string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterClause<'a> {
+    pub key: &'a str,
+    pub op: FilterOp,
+    /// The value with the operator stripped; may still contain `|` alternatives.
+    pub value: &'a str,
+}
+
+/// Parse a raw OpenAlex filter string into its clauses, checking operator syntax.
+///
+/// Empty clauses (e.g. from a trailing comma) are skipped. A leading `!` on the
+/// key is accepted as negation, matching what `check_filter_overlap` tolerates.
+pub fn parse_filter(raw: &str) -> Result<Vec<FilterClause<'_>>, FilterError> {
+    let mut clauses = Vec::new();
+    for clause in raw.split(',') {
+        let clause = clause.trim();
+        if clause.is_empty() {
+            continue;
+        }
+        let invalid = |reason| FilterError::InvalidSyntax { clause: clause.to_string(), reason };
+
+        let (key, value) = clause.split_once(':').ok_or_else(|| invalid("expected key:value"))?;
+        let (key, key_negated) = match key.strip_prefix('!') {
+            Some(k) => (k.trim(), true),
+            None => (key.trim(), false),
+        };
+        if key.is_empty() {
+            return Err(invalid("missing filter key"));
+        }
+        if !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.') {
+            return Err(invalid("filter keys may only contain letters, digits, '_' and '.'"));
+        }
+
+        let value = value.trim();
+        let (op, value) = if let Some(v) = value.strip_prefix('>') {
+            (FilterOp::Gt, v)
+        } else if let Some(v) = value.strip_prefix('<') {
+            (FilterOp::Lt, v)
+        } else if let Some(v) = value.strip_prefix('!') {
+            (FilterOp::Ne, v)
+        } else {
+            (if key_negated { FilterOp::Ne } else { FilterOp::Eq }, value)
+        };
+        if value.starts_with('=') {
+            return Err(invalid("OpenAlex has no '>=' or '<=' operator; use '>' or '<'"));
+        }
+        if value.starts_with(['>', '<', '!']) {
+            return Err(invalid("only one operator is allowed per clause"));
+        }
+        if value.is_empty() {
+            return Err(invalid("missing filter value"));
+        }
+        clauses.push(FilterClause { key, op, value });
+    }
+    Ok(clauses)
+}
+
+/// Parse `raw` and check every key against `allowed`.
+///
+/// Unknown keys produce [`FilterError::UnknownKey`] with up to three close
+/// matches from `allowed`.
+pub(crate) fn validate_filter(raw: &str, allowed: &[&str]) -> Result<(), FilterError> {
+    for clause in parse_filter(raw)? {
+        if !allowed.contains(&clause.key) {
+            return Err(FilterError::UnknownKey {
+                key: clause.key.to_string(),
+                suggestions: suggest_filter_keys(clause.key, allowed),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Rank `allowed` keys by edit distance to `key`, keeping only plausible typos.
+///
+/// Keys whose last dotted segment equals `key` (e.g. `h_index` for
+/// `summary_stats.h_index`) are always included.
+fn suggest_filter_keys(key: &str, allowed: &[&str]) -> Vec<String> {
+    let max_distance = (key.chars().count() / 4).max(2);
+    let mut ranked: Vec<(usize, &str)> = allowed
+        .iter()
+        .filter_map(|&candidate| {
+            if candidate.rsplit('.').next() == Some(key) {
+                return Some((0, candidate));
+            }
+            let d = edit_distance(key, candidate);
+            (d <= max_distance).then_some((d, candidate))
+        })
+        .collect();
+    ranked.sort();
+    ranked.into_iter().take(3).map(|(_, k)| k.to_string()).collect()
+}
+
+/// Levenshtein distance over chars.
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut cur = vec![0; b.len() + 1];
+    for (i, ca) in a.chars().enumerate() {
+        cur[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != cb);
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    prev[b.len()]
+}
+
 /// Check that alias filter keys don't overlap with keys in the raw filter string.
 fn check_filter_overlap(
     raw_filter: &str,
@@ -652,11 +1106,18 @@ fn check_filter_overlap(
     Ok(())
 }
 
-/// Resolve a pipe-separated alias value where each segment may be an ID or search string.
-async fn resolve_alias_value(
+/// Resolve an entity filter value to the ID list OpenAlex expects.
+///
+/// `kind` is the entity type (`"authors"`, `"institutions"`, `"sources"`, ...).
+/// `value` is pipe-separated; each segment may be an ID (kept, normalized to
+/// short form) or a name. Author names go through autocomplete with an
+/// ambiguity check; other names resolve to the most-cited search match. Every
+/// Entity alias on every list endpoint goes through here, so `--institution mit`
+/// and `--source nature` behave the same way as `--publisher acm`.
+pub async fn resolve_filter_entity(
     client: &OpenAlexClient,
+    kind: &'static str,
     value: &str,
-    entity_type: &'static str,
 ) -> Result<String, FilterError> {
     let mut resolved = Vec::new();
     for segment in value.split('|') {
@@ -664,10 +1125,12 @@ async fn resolve_alias_value(
         if segment.is_empty() {
             continue;
         }
-        if is_openalex_id(segment, entity_type) {
-            resolved.push(normalize_id(segment, entity_type));
+        if is_openalex_id(segment, kind) {
+            resolved.push(normalize_id(segment, kind));
+        } else if kind == "authors" {
+            resolved.push(resolve_author_id(client, segment).await?);
         } else {
-            resolved.push(resolve_entity_id(client, segment, entity_type).await?);
+            resolved.push(resolve_entity_id(client, segment, kind).await?);
         }
     }
     Ok(resolved.join("|"))
@@ -782,6 +1245,85 @@ mod tests {
         assert_eq!(normalize_id("domains/3", "domains"), "domains/3");
     }
 
+    // ── parse_filter / validate_filter tests ────────────────────────────
+
+    #[test]
+    fn test_parse_filter_operators() {
+        let clauses = parse_filter("publication_year:>2020,cited_by_count:<10,type:!article,is_oa:true,").unwrap();
+        assert_eq!(
+            clauses,
+            vec![
+                FilterClause { key: "publication_year", op: FilterOp::Gt, value: "2020" },
+                FilterClause { key: "cited_by_count", op: FilterOp::Lt, value: "10" },
+                FilterClause { key: "type", op: FilterOp::Ne, value: "article" },
+                FilterClause { key: "is_oa", op: FilterOp::Eq, value: "true" },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_filter_value_keeps_colons() {
+        let clauses = parse_filter("doi:https://doi.org/10.1234/x").unwrap();
+        assert_eq!(clauses[0].key, "doi");
+        assert_eq!(clauses[0].value, "https://doi.org/10.1234/x");
+    }
+
+    #[test]
+    fn test_parse_filter_syntax_errors() {
+        for bad in ["publication_year", ":2020", "publication_year:", "cited_by_count:>=10", "cited_by_count:><10", "bad key:1"] {
+            let err = parse_filter(bad).unwrap_err();
+            assert!(matches!(err, FilterError::InvalidSyntax { .. }), "{bad}: {err:?}");
+        }
+    }
+
+    #[test]
+    fn test_validate_filter_valid() {
+        validate_filter("publication_year:>2020,is_oa:true,authorships.author.id:A1|A2", WORK_FILTER_KEYS).unwrap();
+        validate_filter("summary_stats.h_index:>50", AUTHOR_FILTER_KEYS).unwrap();
+    }
+
+    #[test]
+    fn test_validate_filter_typo_suggests_key() {
+        let err = validate_filter("is_oa:true,publication_yaer:2020", WORK_FILTER_KEYS).unwrap_err();
+        match &err {
+            FilterError::UnknownKey { key, suggestions } => {
+                assert_eq!(key, "publication_yaer");
+                assert_eq!(suggestions.first().map(String::as_str), Some("publication_year"));
+            }
+            other => panic!("expected UnknownKey, got {other:?}"),
+        }
+        assert!(err.to_string().contains("did you mean: publication_year"));
+    }
+
+    #[test]
+    fn test_validate_filter_suggests_by_last_segment() {
+        let err = validate_filter("h_index:>10", AUTHOR_FILTER_KEYS).unwrap_err();
+        let FilterError::UnknownKey { suggestions, .. } = err else { panic!("expected UnknownKey") };
+        assert_eq!(suggestions, vec!["summary_stats.h_index".to_string()]);
+    }
+
+    #[test]
+    fn test_validate_filter_is_per_entity() {
+        // Valid for works but not for authors
+        validate_filter("publication_year:2020", WORK_FILTER_KEYS).unwrap();
+        assert!(matches!(
+            validate_filter("publication_year:2020", AUTHOR_FILTER_KEYS),
+            Err(FilterError::UnknownKey { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_key_rejected_before_alias_resolution() {
+        // "einstein" would need an API call to resolve; the bad key must fail first
+        let client = OpenAlexClient::new().with_base_url("http://127.0.0.1:9");
+        let aliases = WorkFilterAliases {
+            author: Some("einstein".to_string()),
+            ..Default::default()
+        };
+        let result = resolve_work_filters(&client, &aliases, Some("cited_by_cnt:>10")).await;
+        assert!(matches!(result, Err(FilterError::UnknownKey { .. })));
+    }
+
     // ── check_filter_overlap tests ──────────────────────────────────────
 
     #[test]
@@ -1089,6 +1631,21 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[tokio::test]
+    async fn test_exclude_retracted() {
+        let client = OpenAlexClient::new();
+        let aliases = WorkFilterAliases {
+            exclude_retracted: Some(true),
+            open: Some(true),
+            ..Default::default()
+        };
+        let result = resolve_work_filters(&client, &aliases, None).await.unwrap();
+        assert_eq!(result.as_deref(), Some("is_oa:true,is_retracted:false"));
+
+        let aliases = WorkFilterAliases { exclude_retracted: Some(false), ..Default::default() };
+        assert!(resolve_work_filters(&client, &aliases, None).await.unwrap().is_none());
+    }
+
     // ── Per-entity alias unit tests ─────────────────────────────────────
 
     #[tokio::test]
@@ -1099,7 +1656,7 @@ mod tests {
             ..Default::default()
         };
         let (values, list_params) = params.into_aliases_and_list_params();
-        let result = resolve_filters(&client, AuthorListParams::alias_specs(), &values, list_params.filter.as_deref()).await.unwrap();
+        let result = resolve_filters(&client, AuthorListParams::alias_specs(), AuthorListParams::filter_keys(), &values, list_params.filter.as_deref()).await.unwrap();
         assert_eq!(result.as_deref(), Some("cited_by_count:>1000"));
     }
 
@@ -1111,7 +1668,7 @@ mod tests {
             ..Default::default()
         };
         let (values, list_params) = params.into_aliases_and_list_params();
-        let result = resolve_filters(&client, AuthorListParams::alias_specs(), &values, list_params.filter.as_deref()).await.unwrap();
+        let result = resolve_filters(&client, AuthorListParams::alias_specs(), AuthorListParams::filter_keys(), &values, list_params.filter.as_deref()).await.unwrap();
         assert_eq!(result.as_deref(), Some("summary_stats.h_index:>50"));
     }
 
@@ -1123,7 +1680,7 @@ mod tests {
             ..Default::default()
         };
         let (values, list_params) = params.into_aliases_and_list_params();
-        let result = resolve_filters(&client, AuthorListParams::alias_specs(), &values, list_params.filter.as_deref()).await.unwrap();
+        let result = resolve_filters(&client, AuthorListParams::alias_specs(), AuthorListParams::filter_keys(), &values, list_params.filter.as_deref()).await.unwrap();
         assert_eq!(result.as_deref(), Some("works_count:>500"));
     }
 
@@ -1135,7 +1692,7 @@ mod tests {
             ..Default::default()
         };
         let (values, list_params) = params.into_aliases_and_list_params();
-        let result = resolve_filters(&client, AuthorListParams::alias_specs(), &values, list_params.filter.as_deref()).await.unwrap();
+        let result = resolve_filters(&client, AuthorListParams::alias_specs(), AuthorListParams::filter_keys(), &values, list_params.filter.as_deref()).await.unwrap();
         assert_eq!(result.as_deref(), Some("last_known_institutions.id:I136199984"));
     }
 
@@ -1147,10 +1704,27 @@ mod tests {
             ..Default::default()
         };
         let (values, list_params) = params.into_aliases_and_list_params();
-        let result = resolve_filters(&client, SourceListParams::alias_specs(), &values, list_params.filter.as_deref()).await.unwrap();
+        let result = resolve_filters(&client, SourceListParams::alias_specs(), SourceListParams::filter_keys(), &values, list_params.filter.as_deref()).await.unwrap();
         assert_eq!(result.as_deref(), Some("is_oa:true"));
     }
 
+    #[tokio::test]
+    async fn test_source_doaj_and_apc() {
+        let client = OpenAlexClient::new();
+        let cases = [
+            (SourceListParams::default().is_in_doaj(true), "is_in_doaj:true"),
+            (SourceListParams::default().is_in_doaj(false), "is_in_doaj:false"),
+            (SourceListParams::default().has_apc(true), "apc_usd:>0"),
+            (SourceListParams::default().has_apc(false), "apc_usd:0"),
+            (SourceListParams::default().is_in_doaj(true).has_apc(false), "is_in_doaj:true,apc_usd:0"),
+        ];
+        for (params, expected) in cases {
+            let (values, list_params) = params.into_aliases_and_list_params();
+            let result = resolve_filters(&client, SourceListParams::alias_specs(), SourceListParams::filter_keys(), &values, list_params.filter.as_deref()).await.unwrap();
+            assert_eq!(result.as_deref(), Some(expected));
+        }
+    }
+
     #[tokio::test]
     async fn test_source_type_direct() {
         let client = OpenAlexClient::new();
@@ -1159,7 +1733,7 @@ mod tests {
             ..Default::default()
         };
         let (values, list_params) = params.into_aliases_and_list_params();
-        let result = resolve_filters(&client, SourceListParams::alias_specs(), &values, list_params.filter.as_deref()).await.unwrap();
+        let result = resolve_filters(&client, SourceListParams::alias_specs(), SourceListParams::filter_keys(), &values, list_params.filter.as_deref()).await.unwrap();
         assert_eq!(result.as_deref(), Some("type:journal"));
     }
 
@@ -1171,7 +1745,7 @@ mod tests {
             ..Default::default()
         };
         let (values, list_params) = params.into_aliases_and_list_params();
-        let result = resolve_filters(&client, InstitutionListParams::alias_specs(), &values, list_params.filter.as_deref()).await.unwrap();
+        let result = resolve_filters(&client, InstitutionListParams::alias_specs(), InstitutionListParams::filter_keys(), &values, list_params.filter.as_deref()).await.unwrap();
         assert_eq!(result.as_deref(), Some("country_code:US"));
     }
 
@@ -1183,7 +1757,7 @@ mod tests {
             ..Default::default()
         };
         let (values, list_params) = params.into_aliases_and_list_params();
-        let result = resolve_filters(&client, InstitutionListParams::alias_specs(), &values, list_params.filter.as_deref()).await.unwrap();
+        let result = resolve_filters(&client, InstitutionListParams::alias_specs(), InstitutionListParams::filter_keys(), &values, list_params.filter.as_deref()).await.unwrap();
         assert_eq!(result.as_deref(), Some("type:education"));
     }
 
@@ -1195,7 +1769,7 @@ mod tests {
             ..Default::default()
         };
         let (values, list_params) = params.into_aliases_and_list_params();
-        let result = resolve_filters(&client, TopicListParams::alias_specs(), &values, list_params.filter.as_deref()).await.unwrap();
+        let result = resolve_filters(&client, TopicListParams::alias_specs(), TopicListParams::filter_keys(), &values, list_params.filter.as_deref()).await.unwrap();
         assert_eq!(result.as_deref(), Some("domain.id:domains/3"));
     }
 
@@ -1207,7 +1781,7 @@ mod tests {
             ..Default::default()
         };
         let (values, list_params) = params.into_aliases_and_list_params();
-        let result = resolve_filters(&client, TopicListParams::alias_specs(), &values, list_params.filter.as_deref()).await.unwrap();
+        let result = resolve_filters(&client, TopicListParams::alias_specs(), TopicListParams::filter_keys(), &values, list_params.filter.as_deref()).await.unwrap();
         assert_eq!(result.as_deref(), Some("field.id:fields/17"));
     }
 
@@ -1219,7 +1793,7 @@ mod tests {
             ..Default::default()
         };
         let (values, list_params) = params.into_aliases_and_list_params();
-        let result = resolve_filters(&client, TopicListParams::alias_specs(), &values, list_params.filter.as_deref()).await.unwrap();
+        let result = resolve_filters(&client, TopicListParams::alias_specs(), TopicListParams::filter_keys(), &values, list_params.filter.as_deref()).await.unwrap();
         assert_eq!(result.as_deref(), Some("subfield.id:subfields/1702"));
     }
 
@@ -1231,7 +1805,7 @@ mod tests {
             ..Default::default()
         };
         let (values, list_params) = params.into_aliases_and_list_params();
-        let result = resolve_filters(&client, PublisherListParams::alias_specs(), &values, list_params.filter.as_deref()).await.unwrap();
+        let result = resolve_filters(&client, PublisherListParams::alias_specs(), PublisherListParams::filter_keys(), &values, list_params.filter.as_deref()).await.unwrap();
         assert_eq!(result.as_deref(), Some("country_codes:US"));
     }
 
@@ -1243,7 +1817,7 @@ mod tests {
             ..Default::default()
         };
         let (values, list_params) = params.into_aliases_and_list_params();
-        let result = resolve_filters(&client, FunderListParams::alias_specs(), &values, list_params.filter.as_deref()).await.unwrap();
+        let result = resolve_filters(&client, FunderListParams::alias_specs(), FunderListParams::filter_keys(), &values, list_params.filter.as_deref()).await.unwrap();
         assert_eq!(result.as_deref(), Some("country_code:US"));
     }
 
@@ -1255,7 +1829,7 @@ mod tests {
             ..Default::default()
         };
         let (values, list_params) = params.into_aliases_and_list_params();
-        let result = resolve_filters(&client, DomainListParams::alias_specs(), &values, list_params.filter.as_deref()).await.unwrap();
+        let result = resolve_filters(&client, DomainListParams::alias_specs(), DomainListParams::filter_keys(), &values, list_params.filter.as_deref()).await.unwrap();
         assert_eq!(result.as_deref(), Some("works_count:>100000000"));
     }
 
@@ -1267,7 +1841,7 @@ mod tests {
             ..Default::default()
         };
         let (values, list_params) = params.into_aliases_and_list_params();
-        let result = resolve_filters(&client, FieldListParams::alias_specs(), &values, list_params.filter.as_deref()).await.unwrap();
+        let result = resolve_filters(&client, FieldListParams::alias_specs(), FieldListParams::filter_keys(), &values, list_params.filter.as_deref()).await.unwrap();
         assert_eq!(result.as_deref(), Some("domain.id:domains/3"));
     }
 
@@ -1279,7 +1853,7 @@ mod tests {
             ..Default::default()
         };
         let (values, list_params) = params.into_aliases_and_list_params();
-        let result = resolve_filters(&client, SubfieldListParams::alias_specs(), &values, list_params.filter.as_deref()).await.unwrap();
+        let result = resolve_filters(&client, SubfieldListParams::alias_specs(), SubfieldListParams::filter_keys(), &values, list_params.filter.as_deref()).await.unwrap();
         assert_eq!(result.as_deref(), Some("field.id:fields/17"));
     }
 
@@ -1292,7 +1866,7 @@ mod tests {
             ..Default::default()
         };
         let (values, list_params) = params.into_aliases_and_list_params();
-        let result = resolve_filters(&client, AuthorListParams::alias_specs(), &values, list_params.filter.as_deref()).await;
+        let result = resolve_filters(&client, AuthorListParams::alias_specs(), AuthorListParams::filter_keys(), &values, list_params.filter.as_deref()).await;
         assert!(result.is_err());
         let err = result.unwrap_err().to_string();
         assert!(err.contains("citations"));
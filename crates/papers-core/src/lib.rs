@@ -1,5 +1,9 @@
 pub mod api;
+pub mod bibtex;
+pub mod browser;
+pub mod convert;
 pub mod filter;
+mod geo;
 pub mod summary;
 pub mod text;
 pub mod zotero;
@@ -14,7 +18,7 @@ pub use text::ZoteroItemInfo;
 pub use papers_openalex::{
     Author, Domain, Field, Funder, HierarchyEntity, HierarchyIds, Institution, Publisher, Source,
     Subfield, Topic, Work,
-    DiskCache,
+    DiskCache, CACHE_DIR_ENV,
     OpenAlexClient, OpenAlexError, Result,
     ListParams, GetParams, FindWorksParams,
     ListMeta, ListResponse,
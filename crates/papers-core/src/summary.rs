@@ -1,6 +1,6 @@
-use papers_openalex::{Author, Domain, Field, Funder, Institution, ListMeta, ListResponse, Publisher, Source, Subfield, Topic, Work};
+use papers_openalex::{Author, Domain, Field, FindWorksResponse, Funder, Institution, ListMeta, ListResponse, Publisher, Source, Subfield, Topic, Work};
 use papers_openalex::OpenAlexError;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 /// Slim wrapper returned by all list functions — keeps meta but drops group_by
 /// and maps full entities to their summary equivalents.
@@ -22,61 +22,175 @@ pub fn summary_list_result<T, S: Serialize>(
 
 // ── WorkSummary ───────────────────────────────────────────────────────────
 
-#[derive(Serialize)]
+/// Keywords kept per work by [`SlimProfile::Detailed`], highest score first.
+pub const WORK_TOP_KEYWORDS: usize = 5;
+
+/// How much of each work a [`WorkSummary`] keeps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SlimProfile {
+    /// Identification only: id, title, DOI, year, type, retraction status,
+    /// citation count, and `has_abstract`.
+    Minimal,
+    /// Minimal plus authors, journal, open-access status, primary topic, and
+    /// abstract.
+    #[default]
+    Standard,
+    /// Standard plus every topic and the top-scoring keywords.
+    Detailed,
+}
+
+/// Work fields kept by list tools; which of them are filled and serialized
+/// depends on [`profile`](Self::profile). Fields a profile leaves out are
+/// empty in the struct and absent from the JSON.
 pub struct WorkSummary {
+    pub profile: SlimProfile,
     pub id: String,
     pub title: Option<String>,
     pub doi: Option<String>,
     pub publication_year: Option<i32>,
     pub r#type: Option<String>,
+    pub is_retracted: bool,
     pub authors: Vec<String>,
     pub journal: Option<String>,
     pub is_oa: Option<bool>,
     pub oa_url: Option<String>,
     pub cited_by_count: Option<i64>,
     pub primary_topic: Option<String>,
+    /// Whether OpenAlex has an abstract (`abstract_inverted_index`) for the
+    /// work, so callers can filter without inspecting `abstract_text`.
+    pub has_abstract: bool,
     pub abstract_text: Option<String>,
+    /// All topic names, best match first ([`SlimProfile::Detailed`] only).
+    pub topics: Vec<String>,
+    /// Up to [`WORK_TOP_KEYWORDS`] keyword names, highest score first
+    /// ([`SlimProfile::Detailed`] only).
+    pub keywords: Vec<String>,
 }
 
-impl From<Work> for WorkSummary {
-    fn from(w: Work) -> Self {
-        let authors = w
+impl WorkSummary {
+    pub fn from_work(w: Work, profile: SlimProfile) -> Self {
+        let has_abstract = w.abstract_text.as_deref().is_some_and(|a| !a.trim().is_empty());
+        let mut summary = WorkSummary {
+            profile,
+            id: w.id,
+            title: w.display_name,
+            doi: w.doi,
+            publication_year: w.publication_year,
+            r#type: w.r#type,
+            is_retracted: w.is_retracted.unwrap_or(false),
+            authors: Vec::new(),
+            journal: None,
+            is_oa: None,
+            oa_url: None,
+            cited_by_count: w.cited_by_count,
+            primary_topic: None,
+            has_abstract,
+            abstract_text: None,
+            topics: Vec::new(),
+            keywords: Vec::new(),
+        };
+        if profile == SlimProfile::Minimal {
+            return summary;
+        }
+
+        summary.authors = w
             .authorships
             .unwrap_or_default()
             .into_iter()
             .filter_map(|a| a.author.and_then(|au| au.display_name))
             .collect();
-
-        let journal = w
+        summary.journal = w
             .primary_location
             .as_ref()
             .and_then(|l| l.source.as_ref())
             .and_then(|s| s.display_name.clone());
+        summary.is_oa = w.open_access.as_ref().and_then(|oa| oa.is_oa);
+        summary.oa_url = w.open_access.and_then(|oa| oa.oa_url);
+        summary.primary_topic = w.primary_topic.and_then(|t| t.display_name);
+        summary.abstract_text = w.abstract_text;
+
+        if profile == SlimProfile::Detailed {
+            summary.topics = w
+                .topics
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|t| t.display_name)
+                .collect();
+            let mut keywords = w.keywords.unwrap_or_default();
+            keywords.sort_by(|a, b| b.score.unwrap_or(0.0).total_cmp(&a.score.unwrap_or(0.0)));
+            summary.keywords = keywords
+                .into_iter()
+                .filter_map(|k| k.display_name)
+                .take(WORK_TOP_KEYWORDS)
+                .collect();
+        }
+        summary
+    }
+}
 
-        let is_oa = w.open_access.as_ref().and_then(|oa| oa.is_oa);
-        let oa_url = w.open_access.and_then(|oa| oa.oa_url);
-
-        let primary_topic = w
-            .primary_topic
-            .and_then(|t| t.display_name);
+impl From<Work> for WorkSummary {
+    fn from(w: Work) -> Self {
+        WorkSummary::from_work(w, SlimProfile::Standard)
+    }
+}
 
-        WorkSummary {
-            id: w.id,
-            title: w.display_name,
-            doi: w.doi,
-            publication_year: w.publication_year,
-            r#type: w.r#type,
-            authors,
-            journal,
-            is_oa,
-            oa_url,
-            cited_by_count: w.cited_by_count,
-            primary_topic,
-            abstract_text: w.abstract_text,
+impl Serialize for WorkSummary {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+        let standard = self.profile != SlimProfile::Minimal;
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("id", &self.id)?;
+        map.serialize_entry("title", &self.title)?;
+        map.serialize_entry("doi", &self.doi)?;
+        map.serialize_entry("publication_year", &self.publication_year)?;
+        map.serialize_entry("type", &self.r#type)?;
+        map.serialize_entry("is_retracted", &self.is_retracted)?;
+        if standard {
+            map.serialize_entry("authors", &self.authors)?;
+            map.serialize_entry("journal", &self.journal)?;
+            map.serialize_entry("is_oa", &self.is_oa)?;
+            map.serialize_entry("oa_url", &self.oa_url)?;
         }
+        map.serialize_entry("cited_by_count", &self.cited_by_count)?;
+        if standard {
+            map.serialize_entry("primary_topic", &self.primary_topic)?;
+        }
+        if self.profile == SlimProfile::Detailed {
+            map.serialize_entry("topics", &self.topics)?;
+            map.serialize_entry("keywords", &self.keywords)?;
+        }
+        map.serialize_entry("has_abstract", &self.has_abstract)?;
+        if standard {
+            map.serialize_entry("abstract_text", &self.abstract_text)?;
+        }
+        map.end()
     }
 }
 
+// ── ScoredWorkSummary ─────────────────────────────────────────────────────
+
+/// A semantic search hit: the similarity score plus the work's summary fields.
+#[derive(Serialize)]
+pub struct ScoredWorkSummary {
+    pub score: f64,
+    #[serde(flatten)]
+    pub work: WorkSummary,
+}
+
+/// Slim a `find/works` response to scored summaries, best match first.
+///
+/// Results whose work data does not parse as a [`Work`] are dropped.
+pub fn scored_work_summaries(resp: FindWorksResponse) -> Vec<ScoredWorkSummary> {
+    resp.results
+        .into_iter()
+        .filter_map(|r| {
+            let work: Work = serde_json::from_value(r.work).ok()?;
+            Some(ScoredWorkSummary { score: r.score, work: work.into() })
+        })
+        .collect()
+}
+
 // ── AuthorSummary ─────────────────────────────────────────────────────────
 
 #[derive(Serialize)]
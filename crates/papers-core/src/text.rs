@@ -1,6 +1,6 @@
-pub use papers_datalab::ProcessingMode;
+pub use papers_datalab::{OutputFormat, ProcessingMode};
 use base64::Engine as _;
-use papers_datalab::{DatalabClient, MarkerRequest, OutputFormat};
+use papers_datalab::{DatalabClient, MarkerRequest};
 use papers_openalex::{GetParams, OpenAlexClient, Work};
 use papers_zotero::{ItemListParams, ZoteroClient};
 use serde::{Deserialize, Serialize};
@@ -45,6 +45,10 @@ pub enum WorkTextError {
     #[error("PDF extraction error: {0}")]
     PdfExtract(String),
 
+    /// Reading or writing a `papers_extract_*.zip` archive failed.
+    #[error("Extraction archive error: {0}")]
+    Archive(String),
+
     #[error(transparent)]
     DataLab(#[from] papers_datalab::DatalabError),
 
@@ -109,11 +113,82 @@ fn zotero_data_dir() -> Option<PathBuf> {
     dirs::home_dir().map(|h| h.join("Zotero"))
 }
 
-fn datalab_cache_dir(short_id: &str) -> Option<PathBuf> {
+/// Base directory holding one `<key>/` subdirectory per DataLab extraction.
+fn datalab_cache_base() -> Option<PathBuf> {
     if let Ok(base) = std::env::var("PAPERS_DATALAB_CACHE_DIR") {
-        return Some(PathBuf::from(base).join(short_id));
+        return Some(PathBuf::from(base));
+    }
+    dirs::cache_dir().map(|d| d.join("papers").join("datalab"))
+}
+
+fn datalab_cache_dir(short_id: &str) -> Option<PathBuf> {
+    let base = datalab_cache_base()?;
+    migrate_cache_once(&base);
+    Some(base.join(short_id))
+}
+
+/// Cache file extensions that older versions wrote flat into the cache base
+/// as `<key>.<ext>`, before each extraction got its own directory.
+const LEGACY_CACHE_EXTENSIONS: &[&str] = &["md", "json", "html"];
+
+/// Move extractions in the legacy flat layout into the current `<key>/`
+/// layout, returning how many keys were migrated.
+///
+/// Runs lazily (once per process and base directory) the first time the
+/// cache is read, so calling this directly is only needed to migrate eagerly.
+pub fn migrate_cache() -> std::io::Result<usize> {
+    match datalab_cache_base() {
+        Some(base) => migrate_cache_dir(&base),
+        None => Ok(0),
+    }
+}
+
+fn migrate_cache_once(base: &std::path::Path) {
+    static MIGRATED: std::sync::Mutex<Vec<PathBuf>> = std::sync::Mutex::new(Vec::new());
+    let mut migrated = MIGRATED.lock().unwrap_or_else(|e| e.into_inner());
+    if migrated.iter().any(|b| b == base) {
+        return;
+    }
+    migrated.push(base.to_path_buf());
+    if let Err(e) = migrate_cache_dir(base) {
+        eprintln!("[papers] DataLab cache migration failed: {e}");
+    }
+}
+
+/// Move each flat `<key>.<ext>` file in `base` to `<key>/<key>.<ext>`. A file
+/// whose destination already exists is left where it is, so a newer
+/// extraction is never overwritten.
+fn migrate_cache_dir(base: &std::path::Path) -> std::io::Result<usize> {
+    let entries = match std::fs::read_dir(base) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e),
+    };
+    let mut keys = std::collections::BTreeSet::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        let (Some(key), Some(ext)) = (
+            path.file_stem().and_then(|s| s.to_str()),
+            path.extension().and_then(|e| e.to_str()),
+        ) else {
+            continue;
+        };
+        if key.starts_with('.') || !LEGACY_CACHE_EXTENSIONS.contains(&ext) {
+            continue;
+        }
+        let dir = base.join(key);
+        let dest = dir.join(format!("{key}.{ext}"));
+        if dest.exists() {
+            continue;
+        }
+        std::fs::create_dir_all(&dir)?;
+        std::fs::rename(&path, &dest)?;
+        keys.insert(key.to_string());
     }
-    dirs::cache_dir().map(|d| d.join("papers").join("datalab").join(short_id))
+    Ok(keys.len())
 }
 
 /// Upload the local DataLab cache for `item_key` to Zotero as
@@ -150,7 +225,7 @@ pub async fn download_extraction_from_zotero(
     }
     let dir = datalab_cache_dir(item_key)
         .ok_or_else(|| WorkTextError::PdfExtract("cannot determine cache directory".into()))?;
-    unzip_to_cache_dir(&zip_bytes, &dir).map_err(|e| WorkTextError::PdfExtract(e.to_string()))
+    unzip_to_cache_dir(&zip_bytes, &dir).map_err(|e| WorkTextError::Archive(e.to_string()))
 }
 
 /// Return the cached markdown for `cache_id` if it exists, otherwise `None`.
@@ -164,14 +239,10 @@ pub fn datalab_cached_markdown(cache_id: &str) -> Option<String> {
 /// Scans the DataLab cache base directory and returns the name of every
 /// subdirectory that contains a `{key}.md` file.
 pub fn datalab_cached_item_keys() -> Vec<String> {
-    let base = if let Ok(base_str) = std::env::var("PAPERS_DATALAB_CACHE_DIR") {
-        PathBuf::from(base_str)
-    } else {
-        match dirs::cache_dir() {
-            Some(d) => d.join("papers").join("datalab"),
-            None => return vec![],
-        }
+    let Some(base) = datalab_cache_base() else {
+        return vec![];
     };
+    migrate_cache_once(&base);
     if !base.is_dir() {
         return vec![];
     }
@@ -196,6 +267,14 @@ pub fn datalab_cached_json(cache_id: &str) -> Option<String> {
     std::fs::read_to_string(dir.join(format!("{cache_id}.json"))).ok()
 }
 
+/// Return the cached HTML for `cache_id` if it exists, otherwise `None`.
+///
+/// Only extractions run with [`OutputFormat::Html`] requested have one.
+pub fn datalab_cached_html(cache_id: &str) -> Option<String> {
+    let dir = datalab_cache_dir(cache_id)?;
+    std::fs::read_to_string(dir.join(format!("{cache_id}.html"))).ok()
+}
+
 /// Return the local cache directory path for `cache_id` if determinable.
 pub fn datalab_cache_dir_path(cache_id: &str) -> Option<std::path::PathBuf> {
     datalab_cache_dir(cache_id)
@@ -228,6 +307,229 @@ pub fn read_extraction_meta(cache_id: &str) -> Option<ExtractionMeta> {
     serde_json::from_slice(&bytes).ok()
 }
 
+/// Sort order for listing cached extractions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExtractionSort {
+    /// Item key, ascending.
+    #[default]
+    Key,
+    /// `extracted_at`, most recent first.
+    Date,
+    /// Title, case-insensitive.
+    Title,
+}
+
+/// Order extraction `keys` by `sort`, looking up each key's metadata with
+/// `meta_for` (normally [`read_extraction_meta`]).
+///
+/// Keys whose meta is missing, or lacks the sorted field, go last; ties and
+/// the missing group are ordered by key so output stays stable.
+pub fn sort_extraction_keys(
+    keys: &mut [String],
+    sort: ExtractionSort,
+    meta_for: impl Fn(&str) -> Option<ExtractionMeta>,
+) {
+    keys.sort();
+    let field = |key: &str| -> Option<String> {
+        let meta = meta_for(key)?;
+        match sort {
+            ExtractionSort::Key => None,
+            ExtractionSort::Date => meta.extracted_at,
+            ExtractionSort::Title => meta.title.map(|t| t.to_lowercase()),
+        }
+    };
+    match sort {
+        ExtractionSort::Key => {}
+        ExtractionSort::Date => keys.sort_by_cached_key(|k| {
+            let date = field(k);
+            (date.is_none(), std::cmp::Reverse(date))
+        }),
+        ExtractionSort::Title => keys.sort_by_cached_key(|k| {
+            let title = field(k).filter(|t| !t.is_empty());
+            (title.is_none(), title)
+        }),
+    }
+}
+
+/// The parts of an extraction that `diff_extraction` compares.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExtractionSnapshot {
+    /// Markdown length in bytes.
+    pub markdown_len: usize,
+    /// Hex MD5 of the markdown.
+    pub markdown_md5: String,
+    pub processing_mode: Option<String>,
+    pub extracted_at: Option<String>,
+}
+
+impl ExtractionSnapshot {
+    fn new(markdown: &[u8], meta: Option<ExtractionMeta>) -> Self {
+        use md5::{Digest, Md5};
+        let (processing_mode, extracted_at) = meta
+            .map(|m| (m.processing_mode, m.extracted_at))
+            .unwrap_or_default();
+        Self {
+            markdown_len: markdown.len(),
+            markdown_md5: format!("{:x}", Md5::digest(markdown)),
+            processing_mode,
+            extracted_at,
+        }
+    }
+}
+
+/// One way a local extraction differs from its Zotero backup.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "field", rename_all = "snake_case")]
+pub enum ExtractionDivergence {
+    ProcessingMode { local: Option<String>, remote: Option<String> },
+    MarkdownLength { local: usize, remote: usize },
+    /// Same length, different bytes.
+    MarkdownContent,
+    ExtractedAt { local: Option<String>, remote: Option<String> },
+}
+
+/// Result of comparing the local cache for an item with its `papers_extract_*.zip`.
+///
+/// `local`/`remote` are `None` when that side has no extraction; `divergences`
+/// is only populated when both exist.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExtractionDiff {
+    pub item_key: String,
+    pub local: Option<ExtractionSnapshot>,
+    pub remote: Option<ExtractionSnapshot>,
+    pub divergences: Vec<ExtractionDivergence>,
+}
+
+impl ExtractionDiff {
+    /// True when both sides exist and nothing differs.
+    pub fn is_match(&self) -> bool {
+        self.local.is_some() && self.remote.is_some() && self.divergences.is_empty()
+    }
+}
+
+/// Snapshot the extraction stored in `dir` (`{cache_id}.md` plus optional `meta.json`).
+pub fn extraction_snapshot_from_dir(dir: &std::path::Path, cache_id: &str) -> Option<ExtractionSnapshot> {
+    let markdown = std::fs::read(dir.join(format!("{cache_id}.md"))).ok()?;
+    let meta = std::fs::read(dir.join("meta.json"))
+        .ok()
+        .and_then(|b| serde_json::from_slice(&b).ok());
+    Some(ExtractionSnapshot::new(&markdown, meta))
+}
+
+/// Snapshot the extraction inside a `papers_extract_*.zip` without unpacking it to disk.
+///
+/// Returns `Ok(None)` if the archive has no `{cache_id}.md`.
+pub fn extraction_snapshot_from_zip(
+    zip_bytes: &[u8],
+    cache_id: &str,
+) -> Result<Option<ExtractionSnapshot>, WorkTextError> {
+    use std::io::Read as _;
+    let zip_err = |e: zip::result::ZipError| WorkTextError::Archive(format!("invalid extraction zip: {e}"));
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(zip_bytes)).map_err(zip_err)?;
+    let mut read_entry = |name: &str| -> Result<Option<Vec<u8>>, WorkTextError> {
+        match archive.by_name(name) {
+            Ok(mut file) => {
+                let mut buf = Vec::new();
+                file.read_to_end(&mut buf)
+                    .map_err(|e| WorkTextError::Archive(format!("cannot read {name} from extraction zip: {e}")))?;
+                Ok(Some(buf))
+            }
+            Err(zip::result::ZipError::FileNotFound) => Ok(None),
+            Err(e) => Err(zip_err(e)),
+        }
+    };
+    let Some(markdown) = read_entry(&format!("{cache_id}.md"))? else {
+        return Ok(None);
+    };
+    let meta = read_entry("meta.json")?.and_then(|b| serde_json::from_slice(&b).ok());
+    Ok(Some(ExtractionSnapshot::new(&markdown, meta)))
+}
+
+/// List every field on which two extraction snapshots disagree.
+pub fn compare_extractions(
+    local: &ExtractionSnapshot,
+    remote: &ExtractionSnapshot,
+) -> Vec<ExtractionDivergence> {
+    let mut out = Vec::new();
+    if local.processing_mode != remote.processing_mode {
+        out.push(ExtractionDivergence::ProcessingMode {
+            local: local.processing_mode.clone(),
+            remote: remote.processing_mode.clone(),
+        });
+    }
+    if local.markdown_len != remote.markdown_len {
+        out.push(ExtractionDivergence::MarkdownLength {
+            local: local.markdown_len,
+            remote: remote.markdown_len,
+        });
+    } else if local.markdown_md5 != remote.markdown_md5 {
+        out.push(ExtractionDivergence::MarkdownContent);
+    }
+    if local.extracted_at != remote.extracted_at {
+        out.push(ExtractionDivergence::ExtractedAt {
+            local: local.extracted_at.clone(),
+            remote: remote.extracted_at.clone(),
+        });
+    }
+    out
+}
+
+/// Compare the local DataLab cache for `item_key` with the Zotero-backed ZIP.
+///
+/// Downloads `papers_extract_{item_key}.zip` (if the item has one) and reads it
+/// in memory; the local cache is never modified.
+pub async fn diff_extraction(zc: &ZoteroClient, item_key: &str) -> Result<ExtractionDiff, WorkTextError> {
+    let local = datalab_cache_dir(item_key).and_then(|dir| extraction_snapshot_from_dir(&dir, item_key));
+    let remote = match find_papers_zip_key(zc, item_key).await? {
+        Some(att_key) => extraction_snapshot_from_zip(&zc.download_item_file(&att_key).await?, item_key)?,
+        None => None,
+    };
+    let divergences = match (&local, &remote) {
+        (Some(l), Some(r)) => compare_extractions(l, r),
+        _ => Vec::new(),
+    };
+    Ok(ExtractionDiff { item_key: item_key.to_string(), local, remote, divergences })
+}
+
+/// Where an item's extraction lives, as reported by `papers zotero extract get`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExtractionStatus {
+    pub key: String,
+    /// Local cache directory for the key, whether or not it holds an extraction.
+    pub local_path: Option<std::path::PathBuf>,
+    /// `{key}.md` exists under `local_path`.
+    pub local_exists: bool,
+    /// The Zotero item itself exists.
+    pub item_exists: bool,
+    /// The item has a `papers_extract_{key}.zip` backup attachment.
+    pub remote_backup: bool,
+    /// The local `meta.json`, if any.
+    pub meta: Option<ExtractionMeta>,
+}
+
+/// Gather the local and Zotero-side extraction state for `item_key`.
+///
+/// Zotero lookups that fail are reported as absent rather than as errors, so
+/// this works offline for the local half. The backup is only looked for when
+/// the item exists.
+pub async fn extraction_status(zc: &ZoteroClient, item_key: &str) -> ExtractionStatus {
+    let local_path = datalab_cache_dir(item_key);
+    let local_exists = local_path
+        .as_ref()
+        .is_some_and(|d| d.join(format!("{item_key}.md")).exists());
+    let item_exists = zc.get_item(item_key).await.is_ok();
+    let remote_backup = item_exists
+        && matches!(find_papers_zip_key(zc, item_key).await, Ok(Some(_)));
+    ExtractionStatus {
+        key: item_key.to_string(),
+        local_path,
+        local_exists,
+        item_exists,
+        remote_backup,
+        meta: read_extraction_meta(item_key),
+    }
+}
+
 /// Return an ISO 8601 UTC timestamp for the current moment (no external deps).
 fn iso_now() -> String {
     let secs = std::time::SystemTime::now()
@@ -280,28 +582,17 @@ async fn write_extraction_meta(
 
     if let Some(zc) = zotero {
         if let Ok(item) = zc.get_item(item_key).await {
+            let authors: Vec<String> =
+                item.data.authors().iter().filter_map(|c| c.display_name()).collect();
+            if !authors.is_empty() {
+                meta.authors = Some(authors);
+            }
             meta.title = item.data.title;
             meta.item_type = Some(item.data.item_type);
             meta.date = item.data.date;
             meta.doi = item.data.doi;
             meta.url = item.data.url;
             meta.publication_title = item.data.publication_title;
-            let authors: Vec<String> = item
-                .data
-                .creators
-                .iter()
-                .filter(|c| c.creator_type == "author")
-                .map(|c| match (&c.first_name, &c.last_name, &c.name) {
-                    (Some(f), Some(l), _) if !l.is_empty() => format!("{f} {l}"),
-                    (_, Some(l), _) if !l.is_empty() => l.clone(),
-                    (_, _, Some(n)) if !n.is_empty() => n.clone(),
-                    _ => String::new(),
-                })
-                .filter(|s| !s.is_empty())
-                .collect();
-            if !authors.is_empty() {
-                meta.authors = Some(authors);
-            }
         }
     }
 
@@ -310,14 +601,13 @@ async fn write_extraction_meta(
     }
 }
 
-/// Collect all pdf_url values from an OpenAlex Work's locations.
+/// Collect candidate PDF URLs for an OpenAlex Work: the best open-access
+/// URL ([`Work::best_oa_pdf_url`]) first, then every location's pdf_url.
 fn collect_pdf_urls(work: &Work) -> Vec<String> {
     let mut urls = Vec::new();
 
-    if let Some(loc) = &work.best_oa_location {
-        if let Some(url) = &loc.pdf_url {
-            urls.push(url.clone());
-        }
+    if let Some(url) = work.best_oa_pdf_url() {
+        urls.push(url.to_string());
     }
     if let Some(loc) = &work.primary_location {
         if let Some(url) = &loc.pdf_url {
@@ -564,6 +854,142 @@ async fn try_direct_urls(
     Ok(None)
 }
 
+/// Environment variable overriding the OA PDF size cap, in megabytes.
+pub const OA_PDF_MAX_MB_ENV: &str = "PAPERS_OA_PDF_MAX_MB";
+
+/// Default cap on an OA PDF download, in megabytes.
+const OA_PDF_DEFAULT_MAX_MB: usize = 50;
+
+/// Time limit for a whole [`try_oa_pdf`] download, headers through body.
+const OA_PDF_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Minimum gap between OA PDF downloads from the same host. Publishers
+/// commonly block clients that fetch several PDFs back to back.
+const OA_PDF_HOST_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Why [`try_oa_pdf`] abandoned a download that was otherwise a PDF.
+#[derive(Debug, thiserror::Error)]
+pub enum OaPdfError {
+    #[error("OA PDF at {url} exceeds the {limit_bytes}-byte limit (set {OA_PDF_MAX_MB_ENV} to raise it)")]
+    TooLarge { url: String, limit_bytes: usize },
+
+    #[error("OA PDF download from {url} timed out after {}s", OA_PDF_TIMEOUT.as_secs())]
+    Timeout { url: String },
+
+    #[error("OA PDF download from {url} failed: {source}")]
+    Http {
+        url: String,
+        #[source]
+        source: reqwest::Error,
+    },
+}
+
+/// Limits applied by [`work_text_with`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkTextConfig {
+    /// Largest open-access PDF [`try_oa_pdf`] downloads, in bytes. Default
+    /// 50 MB.
+    pub oa_pdf_max_bytes: usize,
+}
+
+impl Default for WorkTextConfig {
+    fn default() -> Self {
+        Self { oa_pdf_max_bytes: OA_PDF_DEFAULT_MAX_MB * 1024 * 1024 }
+    }
+}
+
+impl WorkTextConfig {
+    /// The default, with `oa_pdf_max_bytes` from [`OA_PDF_MAX_MB_ENV`] when it
+    /// holds a positive number of megabytes.
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+        if let Some(mb) = std::env::var(OA_PDF_MAX_MB_ENV)
+            .ok()
+            .and_then(|v| v.trim().parse::<usize>().ok())
+            .filter(|&mb| mb > 0)
+        {
+            config.oa_pdf_max_bytes = mb * 1024 * 1024;
+        }
+        config
+    }
+}
+
+/// Wait until `url`'s host may be contacted again, per
+/// [`OA_PDF_HOST_INTERVAL`]. Slots are reserved before sleeping, so
+/// concurrent downloads from one host are spaced out rather than released
+/// together.
+async fn throttle_host(url: &str) {
+    type Slots = std::collections::HashMap<String, std::time::Instant>;
+    static NEXT_SLOT: std::sync::OnceLock<std::sync::Mutex<Slots>> = std::sync::OnceLock::new();
+    let Some(host) = reqwest::Url::parse(url)
+        .ok()
+        .and_then(|u| Some(format!("{}:{}", u.host_str()?, u.port_or_known_default()?)))
+    else {
+        return;
+    };
+    let wait = {
+        let mut slots = NEXT_SLOT.get_or_init(Default::default).lock().unwrap_or_else(|e| e.into_inner());
+        let now = std::time::Instant::now();
+        let slot = slots.get(&host).copied().filter(|&t| t > now).unwrap_or(now);
+        slots.insert(host, slot + OA_PDF_HOST_INTERVAL);
+        slot - now
+    };
+    if !wait.is_zero() {
+        tokio::time::sleep(wait).await;
+    }
+}
+
+/// Download a work's best open-access PDF ([`Work::best_oa_pdf_url`]) from
+/// wherever it is hosted, unlike [`try_direct_urls`] which only trusts
+/// whitelisted domains.
+///
+/// Returns `Ok(None)` when there is no OA URL or it doesn't answer with
+/// `application/pdf` (OA URLs often lead to landing pages or bot checks).
+/// A PDF larger than `max_bytes` (see [`WorkTextConfig::oa_pdf_max_bytes`]),
+/// slower than [`OA_PDF_TIMEOUT`], or cut off mid-body is abandoned with an
+/// [`OaPdfError`]. Downloads from
+/// the same host are spaced [`OA_PDF_HOST_INTERVAL`] apart.
+pub async fn try_oa_pdf(
+    client: &reqwest::Client,
+    work: &Work,
+    max_bytes: usize,
+) -> Result<Option<Vec<u8>>, OaPdfError> {
+    let Some(url) = work.best_oa_pdf_url() else { return Ok(None) };
+    let too_large = || OaPdfError::TooLarge { url: url.to_string(), limit_bytes: max_bytes };
+    throttle_host(url).await;
+    let download = async {
+        let Some(mut resp) = client.get(url).send().await.ok().and_then(|r| r.error_for_status().ok()) else {
+            return Ok(None);
+        };
+        let is_pdf = resp
+            .headers()
+            .get("content-type")
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|ct| ct.contains("application/pdf"));
+        if !is_pdf {
+            return Ok(None);
+        }
+        if resp.content_length().is_some_and(|n| n > max_bytes as u64) {
+            return Err(too_large());
+        }
+        let mut bytes = Vec::new();
+        while let Some(chunk) = resp
+            .chunk()
+            .await
+            .map_err(|source| OaPdfError::Http { url: url.to_string(), source })?
+        {
+            if bytes.len() + chunk.len() > max_bytes {
+                return Err(too_large());
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+        Ok((!bytes.is_empty()).then_some(bytes))
+    };
+    tokio::time::timeout(OA_PDF_TIMEOUT, download)
+        .await
+        .unwrap_or_else(|_| Err(OaPdfError::Timeout { url: url.to_string() }))
+}
+
 /// Try downloading from the OpenAlex Content API.
 async fn try_openalex_content(
     http: &reqwest::Client,
@@ -661,11 +1087,13 @@ fn zip_cache_dir(dir: &std::path::Path, id: &str) -> std::io::Result<Vec<u8>> {
         zip.write_all(&std::fs::read(&md_path)?)?;
     }
 
-    // Add {id}.json (if present)
-    let json_path = dir.join(format!("{id}.json"));
-    if json_path.exists() {
-        zip.start_file(format!("{id}.json"), opts)?;
-        zip.write_all(&std::fs::read(&json_path)?)?;
+    // Add {id}.json and {id}.html (if present)
+    for ext in ["json", "html"] {
+        let path = dir.join(format!("{id}.{ext}"));
+        if path.exists() {
+            zip.start_file(format!("{id}.{ext}"), opts)?;
+            zip.write_all(&std::fs::read(&path)?)?;
+        }
     }
 
     // Add meta.json (if present)
@@ -723,7 +1151,7 @@ async fn upload_papers_zip(
     id: &str,
 ) -> Result<(), WorkTextError> {
     let filename = papers_extract_filename(id);
-    let zip_bytes = zip_cache_dir(dir, id).map_err(|e| WorkTextError::PdfExtract(e.to_string()))?;
+    let zip_bytes = zip_cache_dir(dir, id).map_err(|e| WorkTextError::Archive(e.to_string()))?;
     let att_key = zc
         .create_imported_attachment(parent_key, &filename, "application/zip")
         .await?;
@@ -732,6 +1160,114 @@ async fn upload_papers_zip(
     Ok(())
 }
 
+/// Write the formats of one DataLab result into the cache dir `dir`.
+///
+/// Each file goes through a `.tmp` + rename, and `{id}.md` is renamed last:
+/// its presence is what marks a cache hit, so a crash part-way never leaves
+/// markdown without the JSON or HTML converted alongside it.
+fn write_cached_formats(dir: &std::path::Path, id: &str, markdown: &str, json: Option<&str>, html: Option<&str>) {
+    let files = [("json", json), ("html", html), ("md", Some(markdown))];
+    for (ext, contents) in files {
+        let Some(contents) = contents else { continue };
+        let tmp_path = dir.join(format!("{id}.{ext}.tmp"));
+        let written = std::fs::write(&tmp_path, contents)
+            .and_then(|()| std::fs::rename(&tmp_path, dir.join(format!("{id}.{ext}"))));
+        if written.is_err() {
+            let _ = std::fs::remove_file(&tmp_path);
+        }
+    }
+}
+
+/// Lock file in a key's cache dir, held while that key is being converted.
+const EXTRACT_LOCK_FILE: &str = ".extract.lock";
+
+/// How long an extraction lock may go without being refreshed before it is
+/// assumed abandoned and taken over.
+const EXTRACT_LOCK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(600);
+
+const EXTRACT_LOCK_POLL: std::time::Duration = std::time::Duration::from_millis(250);
+
+/// Removes its lock file when dropped. Until then a background task keeps
+/// the file's mtime fresh, so a long extraction is never mistaken for an
+/// abandoned one.
+#[derive(Debug)]
+struct ExtractLock {
+    path: PathBuf,
+    heartbeat: tokio::task::JoinHandle<()>,
+}
+
+impl ExtractLock {
+    /// Wrap a just-created lock file, refreshing its mtime every quarter of
+    /// `timeout`.
+    fn new(path: PathBuf, timeout: std::time::Duration) -> Self {
+        let touched = path.clone();
+        let heartbeat = tokio::spawn(async move {
+            let mut ticks = tokio::time::interval(timeout / 4);
+            ticks.tick().await;
+            loop {
+                ticks.tick().await;
+                let _ = std::fs::File::options()
+                    .write(true)
+                    .open(&touched)
+                    .and_then(|f| f.set_modified(std::time::SystemTime::now()));
+            }
+        });
+        Self { path, heartbeat }
+    }
+}
+
+impl Drop for ExtractLock {
+    fn drop(&mut self) {
+        self.heartbeat.abort();
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Take the extraction lock in `dir`, waiting while another extraction holds
+/// it. Returns the lock (`None` if the lock file can't be created at all, in
+/// which case the caller extracts unlocked) and whether it had to wait, in
+/// which case the other extraction has likely filled the cache.
+///
+/// A lock whose mtime is older than `timeout` is abandoned (live holders
+/// refresh it) and is taken over: it is renamed aside and the lock file
+/// re-created with `create_new`, so of several waiters only one wins.
+async fn acquire_extract_lock(dir: &std::path::Path, timeout: std::time::Duration) -> (Option<ExtractLock>, bool) {
+    if std::fs::create_dir_all(dir).is_err() {
+        return (None, false);
+    }
+    let path = dir.join(EXTRACT_LOCK_FILE);
+    let mut waited = false;
+    loop {
+        match std::fs::OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(_) => return (Some(ExtractLock::new(path, timeout)), waited),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                let stale = std::fs::metadata(&path)
+                    .and_then(|m| m.modified())
+                    .ok()
+                    .and_then(|t| t.elapsed().ok())
+                    .is_some_and(|age| age > timeout);
+                waited = true;
+                if stale {
+                    // Only one waiter's rename succeeds; everyone then races
+                    // on `create_new` above.
+                    let aside = dir.join(format!("{EXTRACT_LOCK_FILE}.stale-{}", std::process::id()));
+                    if std::fs::rename(&path, &aside).is_ok() {
+                        eprintln!("[papers] Taking over extraction lock {}", path.display());
+                        let _ = std::fs::remove_file(&aside);
+                    }
+                    continue;
+                }
+                tokio::time::sleep(EXTRACT_LOCK_POLL).await;
+            }
+            Err(_) => return (None, waited),
+        }
+    }
+}
+
+/// Output formats [`do_extract`] requests from DataLab: markdown, plus the
+/// block-level JSON cached alongside it.
+pub const DEFAULT_EXTRACT_FORMATS: &[OutputFormat] = &[OutputFormat::Markdown, OutputFormat::Json];
+
 /// Extract text from PDF bytes, routing through DataLab if `datalab` is `Some`.
 ///
 /// `zotero_id` is the Zotero parent item key (or OpenAlex short ID for non-Zotero sources)
@@ -744,6 +1280,44 @@ pub async fn do_extract(
     datalab: Option<(&DatalabClient, ProcessingMode)>,
     source: &mut PdfSource,
 ) -> Result<String, WorkTextError> {
+    do_extract_as(pdf_bytes, zotero_id, zotero, datalab, DEFAULT_EXTRACT_FORMATS, source)
+        .await
+        .map(|extraction| extraction.markdown)
+}
+
+/// What [`do_extract_as`] produced: the markdown, plus the block-level JSON
+/// and HTML when they were requested (or are already cached).
+#[derive(Debug, Clone)]
+pub struct Extraction {
+    pub markdown: String,
+    /// DataLab's block-level JSON, serialized as cached in `{id}.json`.
+    pub json: Option<String>,
+    pub html: Option<String>,
+}
+
+/// [`do_extract`] requesting `formats` from DataLab (markdown is always
+/// included). Each format DataLab returns is cached as `{zotero_id}.{md,json,html}`.
+///
+/// A cached extraction only counts as a hit if it has HTML whenever
+/// [`OutputFormat::Html`] is requested, so asking for HTML on an item
+/// extracted as markdown alone converts it again. A missing `{id}.json` does
+/// not, since older caches predate it.
+pub async fn do_extract_as(
+    pdf_bytes: Vec<u8>,
+    zotero_id: &str,
+    zotero: Option<&ZoteroClient>,
+    datalab: Option<(&DatalabClient, ProcessingMode)>,
+    formats: &[OutputFormat],
+    source: &mut PdfSource,
+) -> Result<Extraction, WorkTextError> {
+    let wants_json = formats.iter().any(|f| matches!(f, OutputFormat::Json));
+    let wants_html = formats.iter().any(|f| matches!(f, OutputFormat::Html));
+    let read_cached = |dir: &std::path::Path| {
+        let markdown = std::fs::read_to_string(dir.join(format!("{zotero_id}.md"))).ok()?;
+        let json = std::fs::read_to_string(dir.join(format!("{zotero_id}.json"))).ok();
+        let html = std::fs::read_to_string(dir.join(format!("{zotero_id}.html"))).ok();
+        (!wants_html || html.is_some()).then_some(Extraction { markdown, json, html })
+    };
     if let Some((dl, mode)) = datalab {
         // Validate key if Zotero sync is requested
         if let Some(zc) = zotero {
@@ -756,35 +1330,34 @@ pub async fn do_extract(
         let cache_dir = datalab_cache_dir(zotero_id);
 
         // --- local cache check ---
-        if let Some(ref dir) = cache_dir {
-            let md_path = dir.join(format!("{zotero_id}.md"));
-            if let Ok(text) = std::fs::read_to_string(&md_path) {
-                *source = PdfSource::DataLab;
-                // Best-effort: upload to Zotero if no Papers.zip exists yet
-                if let Some(zc) = zotero {
-                    let zc = zc.clone();
-                    let dir = dir.clone();
-                    let id = zotero_id.to_string();
-                    tokio::spawn(async move {
-                        match find_papers_zip_key(&zc, &id).await {
-                            Ok(None) => {
-                                if let Err(e) = upload_papers_zip(&zc, &id, &dir, &id).await {
-                                    if !is_zotero_write_denied(&e) {
-                                        eprintln!("[papers] Zotero backup upload failed: {e}");
-                                    }
-                                }
-                            }
-                            Ok(Some(_)) => {} // already present
-                            Err(e) => {
+        if let Some(ref dir) = cache_dir
+            && let Some(extraction) = read_cached(dir)
+        {
+            *source = PdfSource::DataLab;
+            // Best-effort: upload to Zotero if no Papers.zip exists yet
+            if let Some(zc) = zotero {
+                let zc = zc.clone();
+                let dir = dir.clone();
+                let id = zotero_id.to_string();
+                tokio::spawn(async move {
+                    match find_papers_zip_key(&zc, &id).await {
+                        Ok(None) => {
+                            if let Err(e) = upload_papers_zip(&zc, &id, &dir, &id).await {
                                 if !is_zotero_write_denied(&e) {
-                                    eprintln!("[papers] Zotero children check failed: {e}");
+                                    eprintln!("[papers] Zotero backup upload failed: {e}");
                                 }
                             }
                         }
-                    });
-                }
-                return Ok(text);
+                        Ok(Some(_)) => {} // already present
+                        Err(e) => {
+                            if !is_zotero_write_denied(&e) {
+                                eprintln!("[papers] Zotero children check failed: {e}");
+                            }
+                        }
+                    }
+                });
             }
+            return Ok(extraction);
         }
 
         // --- Zotero cache check (Papers.zip) ---
@@ -793,12 +1366,11 @@ pub async fn do_extract(
                 match zc.download_item_file(&att_key).await {
                     Ok(zip_bytes) if !zip_bytes.is_empty() => {
                         if let Some(ref dir) = cache_dir {
-                            if unzip_to_cache_dir(&zip_bytes, dir).is_ok() {
-                                let md_path = dir.join(format!("{zotero_id}.md"));
-                                if let Ok(text) = std::fs::read_to_string(&md_path) {
-                                    *source = PdfSource::DataLab;
-                                    return Ok(text);
-                                }
+                            if unzip_to_cache_dir(&zip_bytes, dir).is_ok()
+                                && let Some(extraction) = read_cached(dir)
+                            {
+                                *source = PdfSource::DataLab;
+                                return Ok(extraction);
                             }
                         }
                     }
@@ -810,17 +1382,35 @@ pub async fn do_extract(
             }
         }
 
+        // --- per-key lock: wait out a concurrent extraction of the same key ---
+        let _lock = match cache_dir {
+            Some(ref dir) => {
+                let (lock, waited) = acquire_extract_lock(dir, EXTRACT_LOCK_TIMEOUT).await;
+                if waited && let Some(extraction) = read_cached(dir) {
+                    *source = PdfSource::DataLab;
+                    return Ok(extraction);
+                }
+                lock
+            }
+            None => None,
+        };
+
         // --- DataLab API call ---
         // Capture mode string and original source before they are moved/overwritten.
         let mode_str_opt = serde_json::to_value(&mode)
             .ok()
             .and_then(|v| v.as_str().map(String::from));
         let original_source = source.clone();
+        let ext = document_extension(&pdf_bytes);
+        let mut output_format = formats.to_vec();
+        if !output_format.iter().any(|f| matches!(f, OutputFormat::Markdown)) {
+            output_format.insert(0, OutputFormat::Markdown);
+        }
         let dl_result = dl
             .convert_document(MarkerRequest {
                 file: Some(pdf_bytes),
-                filename: Some(format!("{zotero_id}.pdf")),
-                output_format: vec![OutputFormat::Markdown, OutputFormat::Json],
+                filename: Some(format!("{zotero_id}.{ext}")),
+                output_format,
                 mode,
                 ..Default::default()
             })
@@ -828,18 +1418,13 @@ pub async fn do_extract(
 
         *source = PdfSource::DataLab;
         let markdown = dl_result.markdown.clone().unwrap_or_default();
+        let json = dl_result.json.as_ref().filter(|_| wants_json).map(|v| v.to_string());
+        let html = dl_result.html.clone().filter(|_| wants_html);
 
         // --- write local cache (best-effort) ---
         if let Some(ref dir) = cache_dir {
             let _ = std::fs::create_dir_all(dir);
-
-            let md_path = dir.join(format!("{zotero_id}.md"));
-            let _ = std::fs::write(&md_path, &markdown);
-
-            if let Some(ref json_val) = dl_result.json {
-                let json_path = dir.join(format!("{zotero_id}.json"));
-                let _ = std::fs::write(&json_path, json_val.to_string());
-            }
+            write_cached_formats(dir, zotero_id, &markdown, json.as_deref(), html.as_deref());
 
             if let Some(ref images) = dl_result.images {
                 if !images.is_empty() {
@@ -879,79 +1464,927 @@ pub async fn do_extract(
             }
         }
 
-        Ok(markdown)
+        Ok(Extraction { markdown, json, html })
     } else {
-        extract_text(&pdf_bytes)
+        extract_text(&pdf_bytes).map(|markdown| Extraction { markdown, json: None, html: None })
     }
 }
 
-/// Download and extract the full text of a scholarly work.
-///
-/// Tries multiple sources in priority order:
-/// 1. Local Zotero storage (filesystem)
-/// 2. Remote Zotero API (if credentials available)
-/// 3. Direct PDF URLs from OpenAlex locations (whitelisted domains)
-/// 4. OpenAlex Content API (requires `OPENALEX_API_KEY`)
-///
-/// When `datalab` is `Some`, the final extraction step uses the DataLab Marker
-/// API instead of local pdfium extraction, producing higher-quality markdown.
-/// The `ProcessingMode` controls quality vs. speed: `Fast` < `Balanced` < `Accurate`.
-pub async fn work_text(
-    openalex: &OpenAlexClient,
-    zotero: Option<&ZoteroClient>,
-    datalab: Option<(&DatalabClient, ProcessingMode)>,
-    work_id: &str,
-) -> Result<WorkTextResult, WorkTextError> {
-    // 1. Fetch work metadata from OpenAlex
-    let work = crate::api::work_get(openalex, work_id, &GetParams::default()).await?;
+// ── Markdown post-processing ──────────────────────────────────────────────
 
-    let title = work.title.clone().or_else(|| work.display_name.clone());
-    let doi_raw = work.doi.as_deref();
-    let doi = doi_raw.map(bare_doi);
-    let short_id = short_openalex_id(&work.id);
+/// Standalone lines at most this long are header/footer candidates.
+const MAX_RUNNING_HEADER_LEN: usize = 80;
 
-    let http = reqwest::Client::new();
+/// A standalone line repeated at least this often is treated as a running
+/// page header or footer.
+const MIN_RUNNING_HEADER_REPEATS: usize = 3;
 
-    // 2. Try Zotero (local then remote)
-    if let (Some(zotero), Some(doi)) = (zotero, doi) {
-        if let Some((bytes, mut source, zotero_key)) = try_zotero(zotero, doi, title.as_deref()).await? {
-            let text = do_extract(bytes, &zotero_key, Some(zotero), datalab, &mut source).await?;
-            return Ok(WorkTextResult {
-                text,
-                source,
-                work_id: work.id.clone(),
-                title,
-                doi: doi_raw.map(String::from),
-            });
+/// Remove common DataLab/Marker artifacts from extracted markdown.
+///
+/// The rules are deliberately conservative and never touch fenced code:
+/// - form-feed characters are dropped;
+/// - empty anchors such as `<span id="page-3-0"></span>` are dropped;
+/// - images with an empty target (`![Figure]()`) are dropped;
+/// - short standalone lines repeated three or more times (running headers and
+///   footers) are dropped;
+/// - standalone numbers of up to four digits are dropped only as the first or
+///   last line of a page (next to a form feed, a `page-N` anchor, a Marker
+///   `{N}---` separator, a running header, or the start or end of the text),
+///   so years and table values elsewhere survive;
+/// - runs of blank lines left behind are collapsed to one.
+pub fn clean_markdown(md: &str) -> String {
+    let form_feeds: Vec<bool> = md.lines().map(|l| l.contains('\u{c}')).collect();
+    let md = md.replace('\u{c}', "");
+    let lines: Vec<&str> = md.lines().collect();
+
+    let mut in_fence = vec![false; lines.len()];
+    let mut fenced = false;
+    for (i, line) in lines.iter().enumerate() {
+        let is_fence = line.trim_start().starts_with("```");
+        in_fence[i] = fenced || is_fence;
+        if is_fence {
+            fenced = !fenced;
         }
     }
+    let blank = |i: usize| lines.get(i).is_none_or(|l| l.trim().is_empty());
+    let standalone = |i: usize| !in_fence[i] && (i == 0 || blank(i - 1)) && blank(i + 1);
 
-    // 3. Try direct PDF URLs from OpenAlex locations
-    let pdf_urls = collect_pdf_urls(&work);
-    if let Some((bytes, mut source)) = try_direct_urls(&http, &pdf_urls).await? {
-        let text = do_extract(bytes, short_id, None, datalab, &mut source).await?;
-        return Ok(WorkTextResult {
-            text,
-            source,
-            work_id: work.id.clone(),
-            title,
-            doi: doi_raw.map(String::from),
-        });
+    let mut repeats: std::collections::HashMap<&str, usize> = std::collections::HashMap::new();
+    for (i, line) in lines.iter().enumerate() {
+        if standalone(i) && is_running_header_candidate(line) {
+            *repeats.entry(line.trim()).or_default() += 1;
+        }
     }
 
-    // 4. Try OpenAlex Content API
-    if let Some((bytes, mut source)) = try_openalex_content(&http, &work).await? {
-        let text = do_extract(bytes, short_id, None, datalab, &mut source).await?;
-        return Ok(WorkTextResult {
-            text,
-            source,
-            work_id: work.id.clone(),
-            title,
-            doi: doi_raw.map(String::from),
-        });
+    let running_header =
+        |i: usize| standalone(i) && repeats.get(lines[i].trim()).is_some_and(|&n| n >= MIN_RUNNING_HEADER_REPEATS);
+    // Lines that open a new page, and those that hold nothing but that marker
+    let starts_page = |i: usize| {
+        !in_fence[i]
+            && (form_feeds[i]
+                || lines[i].contains("<span id=\"page-")
+                || is_page_separator(lines[i])
+                || running_header(i))
+    };
+    let bare_marker = |i: usize| {
+        starts_page(i)
+            && (running_header(i) || is_page_separator(lines[i]) || strip_empty_spans(lines[i]).trim().is_empty())
+    };
+    let at_page_edge = |i: usize| {
+        let prev = (0..i).rev().find(|&j| !blank(j));
+        let next = (i + 1..lines.len()).find(|&j| !blank(j));
+        starts_page(i) || prev.is_none_or(bare_marker) || next.is_none_or(starts_page)
+    };
+
+    let mut out = String::new();
+    let mut last_blank = true;
+    for (i, line) in lines.iter().enumerate() {
+        let line = if in_fence[i] {
+            line.to_string()
+        } else {
+            let trimmed = line.trim();
+            let page_number = trimmed.len() <= 4 && trimmed.bytes().all(|b| b.is_ascii_digit());
+            if standalone(i) && (running_header(i) || (page_number && !trimmed.is_empty() && at_page_edge(i))) {
+                continue;
+            }
+            let cleaned = strip_dangling_images(&strip_empty_spans(line));
+            if cleaned.trim().is_empty() && !trimmed.is_empty() {
+                continue;
+            }
+            cleaned
+        };
+        let is_blank = line.trim().is_empty() && !in_fence[i];
+        if is_blank && last_blank {
+            continue;
+        }
+        out.push_str(line.trim_end());
+        out.push('\n');
+        last_blank = is_blank;
+    }
+    let trimmed_len = out.trim_end().len();
+    out.truncate(trimmed_len);
+    if !out.is_empty() {
+        out.push('\n');
     }
+    out
+}
 
-    // 5. No PDF found
+/// Marker's paginated-output page separator: `{N}` followed by dashes.
+fn is_page_separator(line: &str) -> bool {
+    let Some(rest) = line.trim().strip_prefix('{') else { return false };
+    let Some((num, dashes)) = rest.split_once('}') else { return false };
+    !num.is_empty() && num.bytes().all(|b| b.is_ascii_digit()) && dashes.len() >= 3 && dashes.bytes().all(|b| b == b'-')
+}
+
+/// Plain-text lines that could be a running header: short, and not a heading,
+/// list item, table row, quote, image, or math block.
+fn is_running_header_candidate(line: &str) -> bool {
+    let t = line.trim();
+    !t.is_empty()
+        && t.len() <= MAX_RUNNING_HEADER_LEN
+        && !t.starts_with(['#', '|', '>', '-', '*', '+', '!', '$', '<'])
+}
+
+/// Remove `<span ...></span>` elements with no content (Marker page anchors).
+fn strip_empty_spans(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(start) = rest.find("<span") {
+        let after = &rest[start..];
+        let empty_len = after.find('>').and_then(|open_end| {
+            let inner = &after[open_end + 1..];
+            let ws = inner.len() - inner.trim_start().len();
+            inner[ws..].starts_with("</span>").then_some(open_end + 1 + ws + "</span>".len())
+        });
+        match empty_len {
+            Some(len) => {
+                out.push_str(&rest[..start]);
+                rest = &rest[start + len..];
+            }
+            None => {
+                out.push_str(&rest[..start + "<span".len()]);
+                rest = &rest[start + "<span".len()..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Remove images whose target is empty, e.g. `![Figure 2]()`.
+fn strip_dangling_images(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(start) = rest.find("![") {
+        let after = &rest[start..];
+        let dangling_len = after.find("](").and_then(|mid| {
+            let target = &after[mid + 2..];
+            let close = target.find(')')?;
+            target[..close].trim().is_empty().then_some(mid + 2 + close + 1)
+        });
+        match dangling_len {
+            Some(len) => {
+                out.push_str(&rest[..start]);
+                rest = &rest[start + len..];
+            }
+            None => {
+                out.push_str(&rest[..start + 2]);
+                rest = &rest[start + 2..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Outline of a markdown document: `(level, title)` for every ATX heading
+/// (`# Title` … `###### Title`) outside fenced code, in document order.
+pub fn extract_toc(md: &str) -> Vec<(usize, String)> {
+    headings(md).map(|(_, level, title)| (level, title)).collect()
+}
+
+/// One matching line from [`grep_markdown`], with surrounding context.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Match {
+    /// 1-based line number of the match.
+    pub line_number: usize,
+    pub line: String,
+    /// Up to `context` lines immediately before the match.
+    pub before: Vec<String>,
+    /// Up to `context` lines immediately after the match.
+    pub after: Vec<String>,
+}
+
+/// Search markdown line by line for a regular expression.
+///
+/// Every line containing a match is returned in order, with up to `context`
+/// lines on each side (context may overlap between nearby matches).
+pub fn grep_markdown(
+    md: &str,
+    pattern: &str,
+    case_insensitive: bool,
+    context: usize,
+) -> Result<Vec<Match>, regex::Error> {
+    let re = regex::RegexBuilder::new(pattern).case_insensitive(case_insensitive).build()?;
+    Ok(grep_lines(md, &re, context))
+}
+
+fn grep_lines(md: &str, re: &regex::Regex, context: usize) -> Vec<Match> {
+    let lines: Vec<&str> = md.lines().collect();
+    let owned = |range: &[&str]| range.iter().map(|l| l.to_string()).collect();
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| re.is_match(line))
+        .map(|(i, line)| Match {
+            line_number: i + 1,
+            line: line.to_string(),
+            before: owned(&lines[i.saturating_sub(context)..i]),
+            after: owned(&lines[i + 1..(i + 1 + context).min(lines.len())]),
+        })
+        .collect()
+}
+
+/// All matches of a pattern within one cached extraction.
+#[derive(Debug, Clone, Serialize)]
+pub struct DocumentMatches {
+    pub key: String,
+    /// Title from `meta.json`, else the document's first heading.
+    pub title: Option<String>,
+    pub matches: Vec<Match>,
+}
+
+/// Run [`grep_markdown`] over every cached extraction.
+///
+/// Documents are enumerated with [`datalab_cached_item_keys`] and scanned on
+/// a small pool of threads. Only documents with at least one match are
+/// returned, sorted by key.
+pub fn grep_cached_extractions(
+    pattern: &str,
+    case_insensitive: bool,
+    context: usize,
+) -> Result<Vec<DocumentMatches>, regex::Error> {
+    let re = regex::RegexBuilder::new(pattern).case_insensitive(case_insensitive).build()?;
+    let mut keys = datalab_cached_item_keys();
+    keys.sort();
+    let workers = std::thread::available_parallelism().map_or(4, |n| n.get()).min(keys.len().max(1));
+    let chunk_len = keys.len().div_ceil(workers).max(1);
+
+    let scan = |key: &String| -> Option<DocumentMatches> {
+        let md = datalab_cached_markdown(key)?;
+        let matches = grep_lines(&md, &re, context);
+        if matches.is_empty() {
+            return None;
+        }
+        let title = read_extraction_meta(key)
+            .and_then(|m| m.title)
+            .or_else(|| extract_toc(&md).into_iter().next().map(|(_, t)| t));
+        Some(DocumentMatches { key: key.clone(), title, matches })
+    };
+    let results = std::thread::scope(|s| {
+        let handles: Vec<_> = keys
+            .chunks(chunk_len)
+            .map(|chunk| s.spawn(|| chunk.iter().filter_map(scan).collect::<Vec<_>>()))
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap_or_else(|e| std::panic::resume_unwind(e)))
+            .collect()
+    });
+    Ok(results)
+}
+
+/// Longest title fragment kept in an exported filename, in characters.
+const EXPORT_TITLE_MAX_CHARS: usize = 80;
+
+/// One extraction written by [`export_extractions`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExportedExtraction {
+    pub key: String,
+    pub path: PathBuf,
+}
+
+/// Result of [`export_extractions`].
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ExportSummary {
+    pub exported: Vec<ExportedExtraction>,
+    /// Keys listed in the cache whose markdown could not be read.
+    pub skipped: Vec<String>,
+}
+
+/// Copy every cached extraction's markdown into `dir` as `{key}-{title}.md`.
+///
+/// The title comes from `meta.json`, else the document's first heading, and
+/// is reduced to characters that are safe in filenames on every platform
+/// (and in Obsidian wiki links). Keys without a usable title are written as
+/// `{key}.md`. The key prefix keeps names unique however titles sanitise.
+/// `dir` is created if missing; existing files are overwritten so re-running
+/// the export refreshes the folder.
+///
+/// With `frontmatter`, each file starts with the block from
+/// [`extraction_frontmatter`].
+pub fn export_extractions(dir: &std::path::Path, frontmatter: bool) -> std::io::Result<ExportSummary> {
+    std::fs::create_dir_all(dir)?;
+    let mut keys = datalab_cached_item_keys();
+    keys.sort();
+    let mut summary = ExportSummary::default();
+    for key in keys {
+        let Some(md) = datalab_cached_markdown(&key) else {
+            summary.skipped.push(key);
+            continue;
+        };
+        let meta = read_extraction_meta(&key);
+        let title = meta
+            .as_ref()
+            .and_then(|m| m.title.clone())
+            .or_else(|| extract_toc(&md).into_iter().next().map(|(_, t)| t))
+            .map(|t| sanitize_filename_part(&t))
+            .filter(|t| !t.is_empty());
+        let stem = match title {
+            Some(title) => format!("{key}-{title}"),
+            None => key.clone(),
+        };
+        let path = dir.join(format!("{stem}.md"));
+        if frontmatter {
+            std::fs::write(&path, extraction_frontmatter(&key, meta.as_ref()) + &md)?;
+        } else {
+            std::fs::write(&path, md)?;
+        }
+        summary.exported.push(ExportedExtraction { key, path });
+    }
+    Ok(summary)
+}
+
+/// YAML frontmatter block (`---` fenced, trailing newline) describing an
+/// extraction: `title`, `authors`, `doi`, `extracted` (the `extracted_at`
+/// timestamp), and `key`. Fields absent from `meta` are omitted; `key` is
+/// always written.
+///
+/// Values are emitted as JSON string literals, which YAML reads as
+/// double-quoted scalars, so titles containing `:` or `#` stay intact.
+pub fn extraction_frontmatter(key: &str, meta: Option<&ExtractionMeta>) -> String {
+    let quote = |s: &str| serde_json::Value::from(s).to_string();
+    let mut out = String::from("---\n");
+    if let Some(meta) = meta {
+        if let Some(title) = &meta.title {
+            out.push_str(&format!("title: {}\n", quote(title)));
+        }
+        if let Some(authors) = meta.authors.as_ref().filter(|a| !a.is_empty()) {
+            out.push_str("authors:\n");
+            for author in authors {
+                out.push_str(&format!("  - {}\n", quote(author)));
+            }
+        }
+        if let Some(doi) = &meta.doi {
+            out.push_str(&format!("doi: {}\n", quote(doi)));
+        }
+        if let Some(at) = &meta.extracted_at {
+            out.push_str(&format!("extracted: {}\n", quote(at)));
+        }
+    }
+    out.push_str(&format!("key: {}\n---\n\n", quote(key)));
+    out
+}
+
+/// Reduce `s` to a filename fragment: characters reserved by Windows, macOS,
+/// or Obsidian links are dropped, whitespace runs collapse to one space, and
+/// the result is trimmed and capped at [`EXPORT_TITLE_MAX_CHARS`].
+fn sanitize_filename_part(s: &str) -> String {
+    let cleaned: String = s
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .filter(|c| !matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' | '#' | '^' | '[' | ']'))
+        .collect();
+    let collapsed = cleaned.split_whitespace().collect::<Vec<_>>().join(" ");
+    let truncated: String = collapsed.chars().take(EXPORT_TITLE_MAX_CHARS).collect();
+    // Trailing dots and spaces are stripped by Windows
+    truncated.trim_end_matches(['.', ' ']).to_string()
+}
+
+/// Reading speed assumed by [`document_stats`], in words per minute. Slower
+/// than the usual prose figure because papers are dense.
+const READING_WORDS_PER_MINUTE: usize = 200;
+
+/// Size statistics for an extracted document, from [`document_stats`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DocumentStats {
+    /// Whitespace-separated tokens containing at least one letter or digit
+    /// (bare markdown syntax such as `#`, `|`, or `---` is not a word).
+    pub words: usize,
+    /// Characters in the markdown, including whitespace and markup.
+    pub chars: usize,
+    /// ATX headings, as counted by [`extract_toc`].
+    pub headings: usize,
+    /// `words` at [`READING_WORDS_PER_MINUTE`], rounded up.
+    pub est_reading_minutes: usize,
+}
+
+/// Count words, characters, and headings in extracted markdown.
+pub fn document_stats(md: &str) -> DocumentStats {
+    let words = md
+        .split_whitespace()
+        .filter(|w| w.chars().any(char::is_alphanumeric))
+        .count();
+    DocumentStats {
+        words,
+        chars: md.chars().count(),
+        headings: extract_toc(md).len(),
+        est_reading_minutes: words.div_ceil(READING_WORDS_PER_MINUTE),
+    }
+}
+
+/// Content under the heading that best matches `heading_query`.
+///
+/// Matching ignores case, punctuation, and section numbering (`"2.1 Methods"`
+/// matches `"methods"`). An exact match beats a heading that starts with the
+/// query, which beats one that merely contains it, which beats a near-miss
+/// spelling. The section runs until the next heading of the same or a higher
+/// level; its own heading line is not included. When the best-matching title
+/// appears more than once, every such section is returned, joined by a blank
+/// line. `None` when no heading matches.
+pub fn extract_section(md: &str, heading_query: &str) -> Option<String> {
+    let query = normalize_heading(heading_query);
+    if query.is_empty() {
+        return None;
+    }
+    let all: Vec<(usize, usize, String)> = headings(md).collect();
+    let (_, best_title) = all
+        .iter()
+        .filter_map(|(_, _, title)| {
+            let norm = normalize_heading(title);
+            heading_match_rank(&norm, &query).map(|rank| (rank, norm))
+        })
+        .min_by_key(|(rank, _)| *rank)?;
+
+    let lines: Vec<&str> = md.lines().collect();
+    let mut sections = Vec::new();
+    for (idx, &(line, level, ref title)) in all.iter().enumerate() {
+        if normalize_heading(title) != best_title {
+            continue;
+        }
+        let end = all[idx + 1..]
+            .iter()
+            .find(|(_, l, _)| *l <= level)
+            .map_or(lines.len(), |(next, _, _)| *next);
+        let body = lines[line + 1..end].join("\n");
+        let body = body.trim();
+        if !body.is_empty() {
+            sections.push(body.to_string());
+        }
+    }
+    Some(sections.join("\n\n"))
+}
+
+/// Lowercase words of a heading with numbering and punctuation removed.
+fn normalize_heading(title: &str) -> String {
+    let words: Vec<String> = title
+        .split_whitespace()
+        .map(|w| w.chars().filter(|c| c.is_alphanumeric()).collect::<String>().to_lowercase())
+        .filter(|w| !w.is_empty())
+        .collect();
+    // Drop a leading section number such as "2", "2.1", "iv", or "a"
+    let numbered = words.len() > 1
+        && (words[0].chars().all(|c| c.is_ascii_digit())
+            || words[0].len() == 1
+            || words[0].chars().all(|c| matches!(c, 'i' | 'v' | 'x')));
+    words[usize::from(numbered)..].join(" ")
+}
+
+/// How well a normalized heading matches a normalized query; lower is better.
+fn heading_match_rank(heading: &str, query: &str) -> Option<u8> {
+    if heading == query {
+        Some(0)
+    } else if heading.starts_with(query) {
+        Some(1)
+    } else if heading.contains(query) {
+        Some(2)
+    } else if crate::filter::edit_distance(heading, query) <= (query.len() / 4).max(1) {
+        Some(3)
+    } else {
+        None
+    }
+}
+
+/// `(line index, level, title)` of each ATX heading outside fenced code.
+fn headings(md: &str) -> impl Iterator<Item = (usize, usize, String)> + '_ {
+    let mut fenced = false;
+    md.lines().enumerate().filter_map(move |(i, line)| {
+        if line.trim_start().starts_with("```") {
+            fenced = !fenced;
+            return None;
+        }
+        if fenced {
+            return None;
+        }
+        parse_atx_heading(line).map(|(level, title)| (i, level, title))
+    })
+}
+
+/// Parse one ATX heading line into its level and cleaned-up title.
+fn parse_atx_heading(line: &str) -> Option<(usize, String)> {
+    // Up to three leading spaces are allowed; more makes it a code block
+    let indent = line.len() - line.trim_start_matches(' ').len();
+    if indent > 3 {
+        return None;
+    }
+    let rest = &line[indent..];
+    let level = rest.len() - rest.trim_start_matches('#').len();
+    let body = &rest[level..];
+    if !(1..=6).contains(&level) || !(body.is_empty() || body.starts_with([' ', '\t'])) {
+        return None;
+    }
+    // A closing sequence of #s is only stripped when preceded by whitespace
+    let body = body.trim();
+    let stripped = body.trim_end_matches('#');
+    let body = if stripped.is_empty() || stripped.ends_with([' ', '\t']) { stripped } else { body };
+    let title = strip_empty_spans(body).trim().to_string();
+    (!title.is_empty()).then_some((level, title))
+}
+
+// ── Batch extraction ──────────────────────────────────────────────────────
+
+/// What happened to one item in [`extract_collection`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum BatchOutcome {
+    /// Already in the local cache; nothing was sent to DataLab.
+    Cached,
+    /// Not in the local cache, but recorded as done in the [`batch_progress`]
+    /// file by an earlier run, so it was not extracted again.
+    Skipped,
+    /// Extracted (or restored from the Zotero backup) in this run.
+    Extracted,
+    /// Extraction failed; the item can be retried.
+    Failed { error: String },
+}
+
+/// Per-item result of [`extract_collection`].
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchItemResult {
+    pub key: String,
+    pub title: Option<String>,
+    #[serde(flatten)]
+    pub outcome: BatchOutcome,
+}
+
+/// Progress of a collection batch, persisted so an interrupted run can resume.
+///
+/// Stored as `.batch/{collection_key}.json` under the DataLab cache directory.
+pub mod batch_progress {
+    use std::collections::{BTreeMap, BTreeSet};
+    use std::path::PathBuf;
+
+    use serde::{Deserialize, Serialize};
+
+    /// Item keys finished so far, and the last error of each failed key.
+    #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct BatchProgress {
+        #[serde(default)]
+        pub done: BTreeSet<String>,
+        #[serde(default)]
+        pub failed: BTreeMap<String, String>,
+    }
+
+    impl BatchProgress {
+        pub fn mark_done(&mut self, key: &str) {
+            self.failed.remove(key);
+            self.done.insert(key.to_string());
+        }
+
+        pub fn mark_failed(&mut self, key: &str, error: &str) {
+            self.done.remove(key);
+            self.failed.insert(key.to_string(), error.to_string());
+        }
+    }
+
+    /// Location of the progress file for `collection_key`.
+    pub fn path(collection_key: &str) -> Option<PathBuf> {
+        super::datalab_cache_dir(".batch").map(|d| d.join(format!("{collection_key}.json")))
+    }
+
+    /// Read the saved progress for `collection_key`; empty when there is none
+    /// or the file cannot be parsed.
+    pub fn read(collection_key: &str) -> BatchProgress {
+        path(collection_key)
+            .and_then(|p| std::fs::read(p).ok())
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    /// Save `progress` for `collection_key`, creating the directory if needed.
+    pub fn write(collection_key: &str, progress: &BatchProgress) -> std::io::Result<()> {
+        let path = path(collection_key).ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::NotFound, "no cache directory")
+        })?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let json = serde_json::to_vec_pretty(progress).map_err(std::io::Error::other)?;
+        std::fs::write(path, json)
+    }
+}
+
+/// Extract the PDF of every top-level item in a Zotero collection.
+///
+/// Items already in the local cache are reported as [`BatchOutcome::Cached`],
+/// and items recorded as done in the collection's [`batch_progress`] file by
+/// an earlier run as [`BatchOutcome::Skipped`], without any network call. An
+/// item listed twice (the collection changed while paging) is reported once.
+/// The rest run through
+/// [`do_extract`] at most `concurrency` at a time; one item failing (no attachment,
+/// DataLab error) does not stop the others. Progress is saved after every item,
+/// so a re-run after an interruption retries only what is left or failed.
+/// Results are returned in collection order.
+pub async fn extract_collection(
+    zc: &ZoteroClient,
+    dl: &DatalabClient,
+    collection_key: &str,
+    mode: ProcessingMode,
+    concurrency: usize,
+) -> Result<Vec<BatchItemResult>, WorkTextError> {
+    use futures::StreamExt as _;
+
+    let mut seen = std::collections::HashSet::new();
+    let items: Vec<_> = collection_documents(zc, collection_key)
+        .await?
+        .into_iter()
+        .filter(|item| seen.insert(item.key.clone()))
+        .collect();
+    let saved = batch_progress::read(collection_key);
+    // Only what earlier runs finished counts as skipped, not this run's own progress
+    let done_before = saved.done.clone();
+    let progress = std::sync::Mutex::new(saved);
+    let results = futures::stream::iter(items)
+        .map(|item| {
+            let mode = mode.clone();
+            let progress = &progress;
+            let done_before = &done_before;
+            async move {
+                if datalab_cached_markdown(&item.key).is_some() {
+                    return BatchItemResult { key: item.key, title: item.data.title, outcome: BatchOutcome::Cached };
+                }
+                if done_before.contains(&item.key) {
+                    return BatchItemResult { key: item.key, title: item.data.title, outcome: BatchOutcome::Skipped };
+                }
+                let outcome = match extract_item_document(zc, dl, &item.key, mode).await {
+                    Ok(()) => BatchOutcome::Extracted,
+                    Err(e) => BatchOutcome::Failed { error: e.to_string() },
+                };
+                {
+                    // A poisoned lock still holds every recorded key, so one panic must not end the batch
+                    let mut progress = progress.lock().unwrap_or_else(|e| e.into_inner());
+                    match &outcome {
+                        BatchOutcome::Failed { error } => progress.mark_failed(&item.key, error),
+                        _ => progress.mark_done(&item.key),
+                    }
+                    // Best effort: a progress file that can't be written only costs resumability
+                    let _ = batch_progress::write(collection_key, &progress);
+                }
+                BatchItemResult { key: item.key, title: item.data.title, outcome }
+            }
+        })
+        .buffered(concurrency.max(1))
+        .collect()
+        .await;
+    Ok(results)
+}
+
+/// Attachment content types DataLab can convert, in order of preference.
+pub const CONVERTIBLE_CONTENT_TYPES: &[&str] =
+    &["application/pdf", "application/epub+zip", "text/html"];
+
+/// Find the attachment of `item_key` best suited for DataLab conversion.
+///
+/// A PDF wins over an EPUB, which wins over an HTML snapshot; within one
+/// content type the first attachment listed is used.
+pub async fn find_convertible_attachment(
+    zc: &ZoteroClient,
+    item_key: &str,
+) -> Result<papers_zotero::Item, WorkTextError> {
+    let params = ItemListParams { item_type: Some("attachment".into()), ..Default::default() };
+    let mut children = zc.list_item_children(item_key, &params).await?.items;
+    let idx = CONVERTIBLE_CONTENT_TYPES
+        .iter()
+        .find_map(|ct| children.iter().position(|a| a.data.content_type.as_deref() == Some(ct)))
+        .ok_or_else(|| {
+            WorkTextError::PdfExtract(format!("no PDF, EPUB, or HTML attachment for {item_key}"))
+        })?;
+    Ok(children.swap_remove(idx))
+}
+
+/// File extension DataLab should see for a document, sniffed from its bytes.
+///
+/// EPUBs are ZIP archives and HTML snapshots (XHTML included) start with
+/// markup; anything else is treated as a PDF.
+fn document_extension(bytes: &[u8]) -> &'static str {
+    if bytes.starts_with(b"PK\x03\x04") {
+        return "epub";
+    }
+    let head = String::from_utf8_lossy(&bytes[..bytes.len().min(64)]);
+    if head.trim_start_matches('\u{feff}').trim_start().starts_with('<') { "html" } else { "pdf" }
+}
+
+/// Page count of a PDF; `None` for EPUB/HTML documents or unreadable PDFs.
+pub fn document_page_count(bytes: &[u8]) -> Option<u32> {
+    if document_extension(bytes) != "pdf" {
+        return None;
+    }
+    pdf_extract::Document::load_mem(bytes).ok().map(|doc| doc.get_pages().len() as u32)
+}
+
+/// Every top-level item of a collection, minus attachments, notes, and annotations.
+async fn collection_documents(
+    zc: &ZoteroClient,
+    collection_key: &str,
+) -> Result<Vec<papers_zotero::Item>, WorkTextError> {
+    let items = crate::zotero::all_items(ItemListParams::default(), |params| async move {
+        zc.list_collection_top_items(collection_key, &params).await
+    })
+    .await?;
+    Ok(items
+        .into_iter()
+        .filter(|i| !matches!(i.data.item_type.as_str(), "attachment" | "note" | "annotation"))
+        .collect())
+}
+
+/// Projected DataLab cost of extracting one item, from [`estimate_collection`].
+#[derive(Debug, Clone, Serialize)]
+pub struct CostEstimate {
+    pub key: String,
+    pub title: Option<String>,
+    /// Already extracted; nothing would be sent to DataLab.
+    pub cached: bool,
+    /// Page count, when the document is a readable PDF.
+    pub pages: Option<u32>,
+    /// Estimated credits; `None` when the page count is unknown.
+    pub credits: Option<u64>,
+    /// Why the document could not be read, if it couldn't.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Estimate what [`extract_collection`] would spend, without calling DataLab.
+///
+/// Items skipped by `extract_collection` (cached or recorded as done) cost
+/// nothing. For the rest the attachment is read to count its pages.
+pub async fn estimate_collection(
+    zc: &ZoteroClient,
+    collection_key: &str,
+    mode: ProcessingMode,
+) -> Result<Vec<CostEstimate>, WorkTextError> {
+    let progress = batch_progress::read(collection_key);
+    let req = MarkerRequest { mode, ..Default::default() };
+    let mut out = Vec::new();
+    for item in collection_documents(zc, collection_key).await? {
+        let mut est = CostEstimate {
+            key: item.key,
+            title: item.data.title,
+            cached: false,
+            pages: None,
+            credits: None,
+            error: None,
+        };
+        if progress.done.contains(&est.key) || datalab_cached_markdown(&est.key).is_some() {
+            est.cached = true;
+            est.credits = Some(0);
+        } else {
+            match read_convertible_attachment(zc, &est.key).await {
+                Ok((bytes, _)) => {
+                    est.pages = document_page_count(&bytes);
+                    est.credits = est.pages.map(|p| DatalabClient::estimate_cost(&req, p));
+                }
+                Err(e) => est.error = Some(e.to_string()),
+            }
+        }
+        out.push(est);
+    }
+    Ok(out)
+}
+
+/// Find `item_key`'s convertible attachment and read it, from local Zotero
+/// storage when present and the API otherwise.
+pub async fn read_convertible_attachment(
+    zc: &ZoteroClient,
+    item_key: &str,
+) -> Result<(Vec<u8>, PdfSource), WorkTextError> {
+    let att = &find_convertible_attachment(zc, item_key).await?;
+
+    let local_path = att
+        .data
+        .filename
+        .as_ref()
+        .zip(zotero_data_dir())
+        .map(|(filename, dir)| dir.join("storage").join(&att.key).join(filename))
+        .filter(|p| p.exists());
+    Ok(match local_path {
+        Some(path) => {
+            let bytes = tokio::fs::read(&path)
+                .await
+                .map_err(|e| WorkTextError::PdfExtract(format!("Failed to read local file: {e}")))?;
+            (bytes, PdfSource::ZoteroLocal { path: path.to_string_lossy().into_owned() })
+        }
+        None => (
+            zc.download_item_file(&att.key).await?,
+            PdfSource::ZoteroRemote { item_key: att.key.clone() },
+        ),
+    })
+}
+
+/// Read `item_key`'s convertible attachment and run it through [`do_extract`].
+async fn extract_item_document(
+    zc: &ZoteroClient,
+    dl: &DatalabClient,
+    item_key: &str,
+    mode: ProcessingMode,
+) -> Result<(), WorkTextError> {
+    let (bytes, mut source) = read_convertible_attachment(zc, item_key).await?;
+    do_extract(bytes, item_key, Some(zc), Some((dl, mode)), &mut source).await?;
+    Ok(())
+}
+
+/// Download and extract the full text of a scholarly work.
+///
+/// Tries multiple sources in priority order:
+/// 1. Local Zotero storage (filesystem)
+/// 2. Remote Zotero API (if credentials available)
+/// 3. Direct PDF URLs from OpenAlex locations (whitelisted domains)
+/// 4. The best open-access PDF, on any domain ([`try_oa_pdf`])
+/// 5. OpenAlex Content API (requires `OPENALEX_API_KEY`)
+///
+/// When `datalab` is `Some`, the final extraction step uses the DataLab Marker
+/// API instead of local pdfium extraction, producing higher-quality markdown.
+/// The `ProcessingMode` controls quality vs. speed: `Fast` < `Balanced` < `Accurate`.
+///
+/// Limits come from [`WorkTextConfig::from_env`]; use [`work_text_with`] to
+/// set them directly.
+pub async fn work_text(
+    openalex: &OpenAlexClient,
+    zotero: Option<&ZoteroClient>,
+    datalab: Option<(&DatalabClient, ProcessingMode)>,
+    work_id: &str,
+) -> Result<WorkTextResult, WorkTextError> {
+    work_text_with(openalex, zotero, datalab, work_id, &WorkTextConfig::from_env()).await
+}
+
+/// [`work_text`] with explicit limits.
+pub async fn work_text_with(
+    openalex: &OpenAlexClient,
+    zotero: Option<&ZoteroClient>,
+    datalab: Option<(&DatalabClient, ProcessingMode)>,
+    work_id: &str,
+    config: &WorkTextConfig,
+) -> Result<WorkTextResult, WorkTextError> {
+    // 1. Fetch work metadata from OpenAlex
+    let work = crate::api::work_get(openalex, work_id, &GetParams::default()).await?;
+
+    let title = work.title.clone().or_else(|| work.display_name.clone());
+    let doi_raw = work.doi.as_deref();
+    let doi = doi_raw.map(bare_doi);
+    let short_id = short_openalex_id(&work.id);
+
+    let http = reqwest::Client::new();
+
+    // 2. Try Zotero (local then remote)
+    if let (Some(zotero), Some(doi)) = (zotero, doi) {
+        if let Some((bytes, mut source, zotero_key)) = try_zotero(zotero, doi, title.as_deref()).await? {
+            let text = do_extract(bytes, &zotero_key, Some(zotero), datalab, &mut source).await?;
+            return Ok(WorkTextResult {
+                text,
+                source,
+                work_id: work.id.clone(),
+                title,
+                doi: doi_raw.map(String::from),
+            });
+        }
+    }
+
+    // 3. Try direct PDF URLs from OpenAlex locations
+    let pdf_urls = collect_pdf_urls(&work);
+    if let Some((bytes, mut source)) = try_direct_urls(&http, &pdf_urls).await? {
+        let text = do_extract(bytes, short_id, None, datalab, &mut source).await?;
+        return Ok(WorkTextResult {
+            text,
+            source,
+            work_id: work.id.clone(),
+            title,
+            doi: doi_raw.map(String::from),
+        });
+    }
+
+    // 4. Try the best OA PDF even off the whitelist (step 3 covered it otherwise)
+    // Oversize or stalled downloads fall through to the remaining sources
+    if let Some(url) = work.best_oa_pdf_url().filter(|u| !is_whitelisted_url(u))
+        && let Some(bytes) = try_oa_pdf(&http, &work, config.oa_pdf_max_bytes)
+            .await
+            .unwrap_or_else(|e| {
+                eprintln!("[papers] {e}");
+                None
+            })
+    {
+        let mut source = PdfSource::DirectUrl { url: url.to_string() };
+        let text = do_extract(bytes, short_id, None, datalab, &mut source).await?;
+        return Ok(WorkTextResult {
+            text,
+            source,
+            work_id: work.id.clone(),
+            title,
+            doi: doi_raw.map(String::from),
+        });
+    }
+
+    // 5. Try OpenAlex Content API
+    if let Some((bytes, mut source)) = try_openalex_content(&http, &work).await? {
+        let text = do_extract(bytes, short_id, None, datalab, &mut source).await?;
+        return Ok(WorkTextResult {
+            text,
+            source,
+            work_id: work.id.clone(),
+            title,
+            doi: doi_raw.map(String::from),
+        });
+    }
+
+    // 6. No PDF found
     Err(WorkTextError::NoPdfFound {
         work_id: work.id.clone(),
         title,
@@ -997,6 +2430,196 @@ pub async fn poll_zotero_for_work(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_sanitize_filename_part() {
+        assert_eq!(sanitize_filename_part("A/B: C?  [draft]\n v2."), "AB C draft v2");
+        assert_eq!(sanitize_filename_part("***"), "");
+        assert_eq!(sanitize_filename_part(&"x".repeat(200)).len(), EXPORT_TITLE_MAX_CHARS);
+    }
+
+    #[test]
+    fn test_sort_extraction_keys() {
+        let meta = |key: &str, title: Option<&str>, at: Option<&str>| -> ExtractionMeta {
+            serde_json::from_value(serde_json::json!({
+                "item_key": key, "title": title, "extracted_at": at,
+            }))
+            .unwrap()
+        };
+        let metas: std::collections::HashMap<&str, ExtractionMeta> = [
+            ("AAAA0001", meta("AAAA0001", Some("zebra"), Some("2024-01-01T00:00:00Z"))),
+            ("BBBB0002", meta("BBBB0002", Some("Apple"), None)),
+            ("CCCC0003", meta("CCCC0003", None, Some("2025-06-01T12:00:00Z"))),
+        ]
+        .into_iter()
+        .collect();
+        let lookup = |k: &str| metas.get(k).cloned();
+        // DDDD0004 has no meta.json at all
+        let fresh = || ["DDDD0004", "CCCC0003", "BBBB0002", "AAAA0001"].map(String::from);
+
+        let mut keys = fresh();
+        sort_extraction_keys(&mut keys, ExtractionSort::Key, lookup);
+        assert_eq!(keys, ["AAAA0001", "BBBB0002", "CCCC0003", "DDDD0004"]);
+
+        let mut keys = fresh();
+        sort_extraction_keys(&mut keys, ExtractionSort::Date, lookup);
+        assert_eq!(keys, ["CCCC0003", "AAAA0001", "BBBB0002", "DDDD0004"]);
+
+        let mut keys = fresh();
+        sort_extraction_keys(&mut keys, ExtractionSort::Title, lookup);
+        assert_eq!(keys, ["BBBB0002", "AAAA0001", "CCCC0003", "DDDD0004"]);
+    }
+
+    #[test]
+    fn test_extract_toc_nested_headings() {
+        let md = "# Title\n\nIntro text.\n\n## 1 Introduction\n\n### 1.1 Background ###\n\n## <span id=\"page-3-0\"></span>2 Methods\n\n```\n# not a heading\n```\n\n####### too deep\n#hashtag\n    # indented code\n## C# ##\n";
+        assert_eq!(
+            extract_toc(md),
+            vec![
+                (1, "Title".to_string()),
+                (2, "1 Introduction".to_string()),
+                (3, "1.1 Background".to_string()),
+                (2, "2 Methods".to_string()),
+                (2, "C#".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_toc_empty() {
+        assert!(extract_toc("No headings here.\n").is_empty());
+        assert!(extract_toc("#\n## \n").is_empty());
+    }
+
+    const SECTIONED_PAPER: &str = "# A Paper\n\n## Abstract\n\nWe study things.\n\n## 1 Introduction\n\nThings matter.\n\n### 1.1 Scope\n\nOnly some things.\n\n## 2 Conclusions\n\nThings were studied.\n\n# Appendix\n\n## Conclusions\n\nMore conclusions.\n";
+
+    #[test]
+    fn test_extract_section_abstract() {
+        assert_eq!(extract_section(SECTIONED_PAPER, "abstract").as_deref(), Some("We study things."));
+        assert_eq!(extract_section(SECTIONED_PAPER, "ABSTRACT:").as_deref(), Some("We study things."));
+    }
+
+    #[test]
+    fn test_extract_section_includes_subsections() {
+        assert_eq!(
+            extract_section(SECTIONED_PAPER, "introduction").as_deref(),
+            Some("Things matter.\n\n### 1.1 Scope\n\nOnly some things.")
+        );
+    }
+
+    #[test]
+    fn test_extract_section_fuzzy_and_duplicates() {
+        // "conclusion" is a prefix of "conclusions"; both copies are returned
+        assert_eq!(
+            extract_section(SECTIONED_PAPER, "conclusion").as_deref(),
+            Some("Things were studied.\n\nMore conclusions.")
+        );
+        assert_eq!(extract_section(SECTIONED_PAPER, "introdution").as_deref().map(|s| &s[..14]), Some("Things matter."));
+    }
+
+    #[test]
+    fn test_extract_section_missing() {
+        assert_eq!(extract_section(SECTIONED_PAPER, "acknowledgements"), None);
+        assert_eq!(extract_section(SECTIONED_PAPER, "  "), None);
+    }
+
+    #[test]
+    fn test_grep_markdown_with_context() {
+        let md = "# Intro\nline two\nGPU sorting is fast\nline four\nline five\n";
+        let matches = grep_markdown(md, "gpu", true, 1).unwrap();
+        assert_eq!(
+            matches,
+            vec![Match {
+                line_number: 3,
+                line: "GPU sorting is fast".to_string(),
+                before: vec!["line two".to_string()],
+                after: vec!["line four".to_string()],
+            }]
+        );
+        // Context is clipped at the document edges
+        let first = grep_markdown(md, r"^# ", false, 2).unwrap();
+        assert!(first[0].before.is_empty());
+        assert_eq!(first[0].after.len(), 2);
+    }
+
+    #[test]
+    fn test_grep_markdown_no_match_and_case_sensitivity() {
+        let md = "GPU sorting\n";
+        assert!(grep_markdown(md, "gpu", false, 2).unwrap().is_empty());
+        assert!(grep_markdown(md, "bitonic", true, 2).unwrap().is_empty());
+        assert!(grep_markdown(md, "(unclosed", true, 0).is_err());
+    }
+
+    #[test]
+    fn test_document_stats() {
+        let md = "# Title\n\nThe quick brown fox.\n\n| a | b |\n|---|---|\n\n## Déjà vu\n";
+        assert_eq!(
+            document_stats(md),
+            DocumentStats { words: 9, chars: 63, headings: 2, est_reading_minutes: 1 }
+        );
+    }
+
+    #[test]
+    fn test_document_stats_reading_time_rounds_up() {
+        let md = "word ".repeat(401);
+        assert_eq!(document_stats(&md).words, 401);
+        assert_eq!(document_stats(&md).est_reading_minutes, 3);
+        assert_eq!(document_stats("").est_reading_minutes, 0);
+    }
+
+    #[test]
+    fn test_document_extension() {
+        assert_eq!(document_extension(b"%PDF-1.7\n..."), "pdf");
+        assert_eq!(document_extension(b"PK\x03\x04\x14\x00mimetypeapplication/epub+zip"), "epub");
+        assert_eq!(document_extension(b"\n  <!DOCTYPE html><html></html>"), "html");
+        assert_eq!(document_extension(b"<?xml version=\"1.0\"?><html xmlns=\"\"></html>"), "html");
+        assert_eq!(document_extension(b"\0"), "pdf");
+    }
+
+    #[test]
+    fn test_clean_markdown_strips_running_headers_and_page_numbers() {
+        let dirty = "# Deep Learning\n\nJournal of Things, Vol. 3\n\nFirst paragraph.\n\n12\n\n\u{c}Journal of Things, Vol. 3\n\nSecond paragraph.\n\n13\n\nJournal of Things, Vol. 3\n\nThird paragraph.\n";
+        assert_eq!(
+            clean_markdown(dirty),
+            "# Deep Learning\n\nFirst paragraph.\n\nSecond paragraph.\n\nThird paragraph.\n"
+        );
+    }
+
+    #[test]
+    fn test_clean_markdown_keeps_numbers_inside_a_page() {
+        let md = "Intro.\n\n1999\n\nThe year above is content.\n\n| a |\n|---|\n\n42\n\nMore text.\n";
+        assert_eq!(clean_markdown(md), md);
+    }
+
+    #[test]
+    fn test_clean_markdown_strips_numbers_at_page_markers() {
+        let dirty = "Body one.\n\n7\n\n<span id=\"page-8-0\"></span>Body two.\n\n{8}------------------------------------------------\n\n9\n\nBody three.\n\n10\n";
+        assert_eq!(
+            clean_markdown(dirty),
+            "Body one.\n\nBody two.\n\n{8}------------------------------------------------\n\nBody three.\n"
+        );
+    }
+
+    #[test]
+    fn test_clean_markdown_keeps_lines_repeated_twice() {
+        let md = "Note\n\nBody.\n\nNote\n";
+        assert_eq!(clean_markdown(md), md);
+    }
+
+    #[test]
+    fn test_clean_markdown_strips_anchors_and_dangling_images() {
+        let dirty = "## <span id=\"page-2-0\"></span>2 Methods\n\n![Figure 1]()\n\nSee ![](fig.png) and ![x]( ) here.\n";
+        assert_eq!(
+            clean_markdown(dirty),
+            "## 2 Methods\n\nSee ![](fig.png) and  here.\n"
+        );
+    }
+
+    #[test]
+    fn test_clean_markdown_leaves_code_fences_alone() {
+        let md = "```\n1\n\n\n![a]()\n```\n\n```\n1\n```\n\n1\n";
+        assert_eq!(clean_markdown(md), "```\n1\n\n\n![a]()\n```\n\n```\n1\n```\n");
+    }
+
     #[test]
     fn test_bare_doi() {
         assert_eq!(bare_doi("https://doi.org/10.1234/test"), "10.1234/test");
@@ -1047,4 +2670,185 @@ mod tests {
         assert_eq!(urls[0], "https://arxiv.org/pdf/1234");
         assert_eq!(urls[1], "https://europepmc.org/pdf/5678");
     }
+
+    #[test]
+    fn test_collect_pdf_urls_tries_oa_url_first() {
+        let work: Work = serde_json::from_value(serde_json::json!({
+            "id": "https://openalex.org/W1",
+            "open_access": { "is_oa": true, "oa_url": "https://europepmc.org/pdf/5678" },
+            "locations": [{ "pdf_url": "https://arxiv.org/pdf/1234" }]
+        }))
+        .unwrap();
+        assert_eq!(collect_pdf_urls(&work), ["https://europepmc.org/pdf/5678", "https://arxiv.org/pdf/1234"]);
+    }
+
+    // ── Cache migration ─────────────────────────────────────────────────
+
+    #[test]
+    fn test_migrate_cache_dir_moves_flat_files() {
+        let base = std::env::temp_dir().join(format!("papers-migrate-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&base);
+        std::fs::create_dir_all(base.join("NEWER001")).unwrap();
+        std::fs::write(base.join("LEGACY01.md"), "# Legacy").unwrap();
+        std::fs::write(base.join("LEGACY01.json"), "{}").unwrap();
+        std::fs::write(base.join("LEGACY02.md"), "# Other").unwrap();
+        // Already migrated: the flat copy must not clobber it
+        std::fs::write(base.join("NEWER001").join("NEWER001.md"), "# Newer").unwrap();
+        std::fs::write(base.join("NEWER001.md"), "# Stale").unwrap();
+        std::fs::write(base.join("notes.txt"), "unrelated").unwrap();
+
+        assert_eq!(migrate_cache_dir(&base).unwrap(), 2);
+        let read = |p: &str| std::fs::read_to_string(base.join(p)).unwrap();
+        assert_eq!(read("LEGACY01/LEGACY01.md"), "# Legacy");
+        assert_eq!(read("LEGACY01/LEGACY01.json"), "{}");
+        assert_eq!(read("LEGACY02/LEGACY02.md"), "# Other");
+        assert_eq!(read("NEWER001/NEWER001.md"), "# Newer");
+        assert!(!base.join("LEGACY01.md").exists());
+        assert!(base.join("NEWER001.md").exists());
+        assert!(base.join("notes.txt").exists());
+
+        // Nothing left to do the second time
+        assert_eq!(migrate_cache_dir(&base).unwrap(), 0);
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn test_migrate_cache_dir_missing_base() {
+        let base = std::env::temp_dir().join(format!("papers-migrate-missing-{}", std::process::id()));
+        assert_eq!(migrate_cache_dir(&base).unwrap(), 0);
+    }
+
+    // ── Extraction lock ─────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_extract_lock_released_on_drop() {
+        let dir = std::env::temp_dir().join(format!("papers-lock-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let (lock, waited) = acquire_extract_lock(&dir, std::time::Duration::from_secs(5)).await;
+        assert!(lock.is_some() && !waited);
+        assert!(dir.join(EXTRACT_LOCK_FILE).exists());
+        drop(lock);
+        assert!(!dir.join(EXTRACT_LOCK_FILE).exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_extract_lock_taken_over_when_stale() {
+        let dir = std::env::temp_dir().join(format!("papers-lock-stale-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let stale = std::fs::File::create(dir.join(EXTRACT_LOCK_FILE)).unwrap();
+        stale.set_modified(std::time::SystemTime::now() - std::time::Duration::from_secs(3600)).unwrap();
+
+        let (lock, waited) = acquire_extract_lock(&dir, std::time::Duration::from_secs(5)).await;
+        assert!(lock.is_some() && waited);
+        let age = std::fs::metadata(dir.join(EXTRACT_LOCK_FILE)).unwrap().modified().unwrap().elapsed().unwrap();
+        assert!(age < std::time::Duration::from_secs(5), "lock file not re-created: {age:?}");
+        drop(lock);
+        assert!(!dir.join(EXTRACT_LOCK_FILE).exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_extract_lock_refreshed_while_held() {
+        let dir = std::env::temp_dir().join(format!("papers-lock-refresh-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let timeout = std::time::Duration::from_millis(400);
+        let (lock, _) = acquire_extract_lock(&dir, timeout).await;
+        assert!(lock.is_some());
+        let path = dir.join(EXTRACT_LOCK_FILE);
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(std::time::SystemTime::now() - std::time::Duration::from_secs(3600))
+            .unwrap();
+        tokio::time::sleep(timeout / 2 + timeout / 4).await;
+        let age = std::fs::metadata(&path).unwrap().modified().unwrap().elapsed().unwrap();
+        assert!(age < timeout, "lock not refreshed: {age:?}");
+
+        // A second taker sees a live lock and keeps waiting.
+        let second = tokio::time::timeout(timeout * 2, acquire_extract_lock(&dir, timeout)).await;
+        assert!(second.is_err(), "refreshed lock was taken over");
+        drop(lock);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    // ── Extraction diff ─────────────────────────────────────────────────
+
+    /// Fresh temp cache dir holding `{id}.md` and a `meta.json` with `mode`.
+    fn temp_extraction(name: &str, id: &str, markdown: &str, mode: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("papers-diff-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(format!("{id}.md")), markdown).unwrap();
+        write_mode(&dir, id, mode);
+        dir
+    }
+
+    fn write_mode(dir: &std::path::Path, id: &str, mode: &str) {
+        let meta = serde_json::json!({
+            "item_key": id,
+            "processing_mode": mode,
+            "extracted_at": "2024-01-01T00:00:00Z",
+        });
+        std::fs::write(dir.join("meta.json"), meta.to_string()).unwrap();
+    }
+
+    #[test]
+    fn test_extraction_diff_matching() {
+        let dir = temp_extraction("match", "ABCD1234", "# Paper\n\nBody.", "balanced");
+        let zip = zip_cache_dir(&dir, "ABCD1234").unwrap();
+        let local = extraction_snapshot_from_dir(&dir, "ABCD1234").unwrap();
+        let remote = extraction_snapshot_from_zip(&zip, "ABCD1234").unwrap().unwrap();
+        assert_eq!(local, remote);
+        assert!(compare_extractions(&local, &remote).is_empty());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_extraction_diff_diverging_mode_and_length() {
+        let dir = temp_extraction("diverge", "ABCD1234", "# Paper\n\nBody.", "fast");
+        let zip = zip_cache_dir(&dir, "ABCD1234").unwrap();
+        // Re-extract locally with a different mode and longer output
+        std::fs::write(dir.join("ABCD1234.md"), "# Paper\n\nBody, with tables.").unwrap();
+        write_mode(&dir, "ABCD1234", "accurate");
+
+        let local = extraction_snapshot_from_dir(&dir, "ABCD1234").unwrap();
+        let remote = extraction_snapshot_from_zip(&zip, "ABCD1234").unwrap().unwrap();
+        let diffs = compare_extractions(&local, &remote);
+        assert_eq!(
+            diffs,
+            vec![
+                ExtractionDivergence::ProcessingMode {
+                    local: Some("accurate".into()),
+                    remote: Some("fast".into()),
+                },
+                ExtractionDivergence::MarkdownLength { local: 27, remote: 14 },
+            ]
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_extraction_diff_same_length_different_content() {
+        let dir = temp_extraction("content", "ABCD1234", "abc", "balanced");
+        let zip = zip_cache_dir(&dir, "ABCD1234").unwrap();
+        std::fs::write(dir.join("ABCD1234.md"), "abd").unwrap();
+        let local = extraction_snapshot_from_dir(&dir, "ABCD1234").unwrap();
+        let remote = extraction_snapshot_from_zip(&zip, "ABCD1234").unwrap().unwrap();
+        assert_eq!(compare_extractions(&local, &remote), vec![ExtractionDivergence::MarkdownContent]);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_extraction_snapshot_from_zip_without_markdown() {
+        let dir = temp_extraction("nomd", "ABCD1234", "x", "balanced");
+        let zip = zip_cache_dir(&dir, "OTHER123").unwrap();
+        assert!(extraction_snapshot_from_zip(&zip, "ABCD1234").unwrap().is_none());
+        let err = extraction_snapshot_from_zip(b"not a zip", "ABCD1234").unwrap_err();
+        assert!(matches!(err, WorkTextError::Archive(_)), "got {err:?}");
+        assert!(err.to_string().starts_with("Extraction archive error: invalid extraction zip"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
@@ -1,4 +1,10 @@
-use papers_zotero::{CollectionListParams, ItemListParams, ZoteroClient, ZoteroError};
+use papers_openalex::Work;
+use papers_zotero::{
+    CollectionListParams, FulltextParams, ITEM_TYPES, Item, ItemListParams, NewItem, PagedResponse, TagListParams, ZoteroClient,
+    ZoteroError,
+};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
 
 /// Returns `true` if `input` looks like a Zotero key.
 ///
@@ -7,6 +13,99 @@ pub fn looks_like_zotero_key(input: &str) -> bool {
     input.len() == 8 && input.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
 }
 
+/// Zotero's standard annotation palette, as `(hex, name)` in the order the
+/// reader offers them.
+pub const ANNOTATION_COLORS: &[(&str, &str)] = &[
+    ("#ffd400", "yellow"),
+    ("#ff6666", "red"),
+    ("#5fb236", "green"),
+    ("#2ea8e5", "blue"),
+    ("#a28ae5", "purple"),
+    ("#e56eee", "magenta"),
+    ("#f19837", "orange"),
+    ("#aaaaaa", "gray"),
+];
+
+/// Name of a standard Zotero annotation color (`#ffd400` → `yellow`),
+/// matching the hex case-insensitively. `None` for any other color.
+pub fn annotation_color_name(hex: &str) -> Option<&'static str> {
+    ANNOTATION_COLORS
+        .iter()
+        .find(|(h, _)| h.eq_ignore_ascii_case(hex.trim()))
+        .map(|&(_, name)| name)
+}
+
+/// Whether annotation `item` has the given color and type; `None` matches
+/// anything.
+///
+/// `color` is a hex (`#ffd400`) or a name from [`ANNOTATION_COLORS`]
+/// (`yellow`); both, like `annotation_type`, compare case-insensitively.
+/// Items that aren't annotations never match a filter.
+pub fn annotation_matches(item: &Item, color: Option<&str>, annotation_type: Option<&str>) -> bool {
+    let ann = item.as_annotation().unwrap_or_default();
+    let color_ok = color.is_none_or(|want| {
+        ann.color.is_some_and(|hex| {
+            hex.eq_ignore_ascii_case(want) || annotation_color_name(hex).is_some_and(|n| n.eq_ignore_ascii_case(want))
+        })
+    });
+    let type_ok = annotation_type.is_none_or(|want| ann.annotation_type.is_some_and(|t| t.eq_ignore_ascii_case(want)));
+    color_ok && type_ok
+}
+
+const MONTH_NAMES: [&str; 12] = [
+    "january", "february", "march", "april", "may", "june",
+    "july", "august", "september", "october", "november", "december",
+];
+
+/// Parse a free-form Zotero `date` field into `(year, month, day)`.
+///
+/// Handles ISO-style dates (`2024`, `2024-03`, `2024/3/15`), month names
+/// (`March 2024`, `15 Mar 2024`, `March 15, 2024`), and numeric dates with the
+/// year last (`3/15/2024`, month first unless the first number can't be a
+/// month). Anything else containing a four-digit year yields just the year
+/// (`Spring 2019`). Returns `None` when no year is found.
+///
+/// The result orders chronologically, with a bare year sorting before any
+/// date within that year.
+pub fn parse_date(date: &str) -> Option<(i32, Option<u32>, Option<u32>)> {
+    let tokens: Vec<&str> = date
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .collect();
+    let year_idx = tokens
+        .iter()
+        .position(|t| t.len() == 4 && t.chars().all(|c| c.is_ascii_digit()))?;
+    let year: i32 = tokens[year_idx].parse().ok()?;
+
+    // Full names and abbreviations of at least three letters ("Mar", "Sept")
+    let month_name = tokens.iter().find_map(|t| {
+        let lower = t.to_ascii_lowercase();
+        let idx = MONTH_NAMES.iter().position(|m| lower.len() >= 3 && m.starts_with(&lower))?;
+        Some(idx as u32 + 1)
+    });
+    let small: Vec<u32> = tokens
+        .iter()
+        .enumerate()
+        .filter(|&(i, t)| i != year_idx && t.len() <= 2 && t.chars().all(|c| c.is_ascii_digit()))
+        .filter_map(|(_, t)| t.parse().ok())
+        .collect();
+
+    let (month, day) = if let Some(month) = month_name {
+        (Some(month), small.first().copied())
+    } else if year_idx == 0 {
+        (small.first().copied(), small.get(1).copied())
+    } else {
+        match small[..] {
+            [a, b, ..] if a > 12 => (Some(b), Some(a)),
+            [a, b, ..] => (Some(a), Some(b)),
+            _ => (None, None),
+        }
+    };
+    let month = month.filter(|m| (1..=12).contains(m));
+    let day = month.and(day).filter(|d| (1..=31).contains(d));
+    Some((year, month, day))
+}
+
 /// Resolve an item key or title/search string to a Zotero item key.
 ///
 /// If `input` looks like a Zotero key (8 uppercase alphanumeric chars), it is
@@ -77,6 +176,372 @@ pub async fn resolve_search_key(
         })
 }
 
+/// Annotations on every annotatable attachment (PDF, EPUB, HTML snapshot) of
+/// `item_key`, in reading order (see [`sort_annotations`]). An attachment
+/// whose annotations can't be listed is skipped.
+pub async fn work_annotations(client: &ZoteroClient, item_key: &str) -> Result<Vec<Item>, ZoteroError> {
+    let att_params = ItemListParams { item_type: Some("attachment".into()), ..Default::default() };
+    let attachments =
+        all_items(att_params, |params| async move { client.list_item_children(item_key, &params).await }).await?;
+    let mut annotations = Vec::new();
+    for att in attachments.iter().filter(|a| a.is_annotatable_attachment()) {
+        annotations.extend(attachment_annotations(client, &att.key).await);
+    }
+    sort_annotations(&mut annotations);
+    Ok(annotations)
+}
+
+/// Every annotation on attachment `attachment_key`, unsorted; empty if they
+/// can't be listed.
+async fn attachment_annotations(client: &ZoteroClient, attachment_key: &str) -> Vec<Item> {
+    let params = ItemListParams { item_type: Some("annotation".into()), ..Default::default() };
+    all_items(params, |params| async move { client.list_item_children(attachment_key, &params).await })
+        .await
+        .unwrap_or_default()
+}
+
+/// Annotations on every top-level item of `collection_key`, item by item,
+/// each item's in reading order: [`work_annotations`] for regular items, and
+/// the item's own annotations for a standalone PDF, EPUB, or snapshot.
+pub async fn collection_annotations(client: &ZoteroClient, collection_key: &str) -> Result<Vec<Item>, ZoteroError> {
+    let items = all_items(ItemListParams::default(), |params| async move {
+        client.list_collection_top_items(collection_key, &params).await
+    })
+    .await?;
+    let mut annotations = Vec::new();
+    for item in &items {
+        if item.is_annotatable_attachment() {
+            let mut own = attachment_annotations(client, &item.key).await;
+            sort_annotations(&mut own);
+            annotations.extend(own);
+        } else {
+            annotations.extend(work_annotations(client, &item.key).await?);
+        }
+    }
+    Ok(annotations)
+}
+
+/// Order annotations for reading: by numeric page label, then by Zotero's
+/// `annotationSortIndex` (position within the page), falling back to the
+/// page index in `annotationPosition` when there is no sort index.
+///
+/// Labels that aren't plain numbers (`iv`, `A-3`) sort after numbered pages,
+/// by sort index and then label; ties keep their original order.
+pub fn compare_annotations(a: &Item, b: &Item) -> std::cmp::Ordering {
+    fn key(item: &Item) -> (bool, Option<u32>, Option<&str>, Option<u64>, Option<&str>) {
+        let ann = item.as_annotation().unwrap_or_default();
+        let page = ann.page_label.and_then(|l| l.trim().parse::<u32>().ok());
+        let position_page = item
+            .data
+            .extra_fields
+            .get("annotationPosition")
+            .and_then(|v| v.as_str())
+            .and_then(|p| serde_json::from_str::<serde_json::Value>(p).ok())
+            .and_then(|p| p.get("pageIndex")?.as_u64());
+        (page.is_none(), page, ann.sort_index, position_page, ann.page_label)
+    }
+    key(a).cmp(&key(b))
+}
+
+/// Sort `items` into reading order (see [`compare_annotations`]).
+pub fn sort_annotations(items: &mut [Item]) {
+    items.sort_by(compare_annotations);
+}
+
+/// A work with its child notes and annotations, gathered for review.
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkReview {
+    pub item: Item,
+    pub notes: Vec<Item>,
+    pub annotations: Vec<Item>,
+}
+
+/// Gather the item `item_key`, all its notes, and its annotations.
+pub async fn work_review(client: &ZoteroClient, item_key: &str) -> Result<WorkReview, ZoteroError> {
+    let item = client.get_item(item_key).await?;
+    let note_params = ItemListParams { item_type: Some("note".into()), ..Default::default() };
+    let notes =
+        all_items(note_params, |params| async move { client.list_item_children(item_key, &params).await }).await?;
+    let annotations = work_annotations(client, item_key).await?;
+    Ok(WorkReview { item, notes, annotations })
+}
+
+/// Counts describing a collection, from [`collection_stats`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct CollectionStats {
+    pub key: String,
+    pub name: String,
+    /// Top-level items directly in the collection (children such as
+    /// attachments and notes are not counted).
+    pub items: usize,
+    /// Top-level items per Zotero item type.
+    pub by_type: BTreeMap<String, usize>,
+    /// PDF attachments, whether children or standalone.
+    pub pdfs: usize,
+    /// Top-level items with at least one annotated attachment.
+    pub with_annotations: usize,
+    /// Direct sub-collections, from the collection's `numCollections` meta.
+    pub subcollections: u64,
+}
+
+/// Items fetched per request by [`all_items`].
+const PAGE_SIZE: u32 = 100;
+
+/// Every item `fetch` returns for `params`, requesting pages of [`PAGE_SIZE`]
+/// until `Total-Results` is reached (or a short page, without that header).
+pub(crate) async fn all_items<F, Fut>(params: ItemListParams, fetch: F) -> Result<Vec<Item>, ZoteroError>
+where
+    F: Fn(ItemListParams) -> Fut,
+    Fut: Future<Output = Result<PagedResponse<Item>, ZoteroError>>,
+{
+    let mut items = Vec::new();
+    let mut start = 0;
+    loop {
+        let page = fetch(ItemListParams { limit: Some(PAGE_SIZE), start: Some(start), ..params.clone() }).await?;
+        let fetched = page.items.len() as u32;
+        items.extend(page.items);
+        start += fetched;
+        let done = match page.total_results {
+            Some(total) => u64::from(start) >= total,
+            None => fetched < PAGE_SIZE,
+        };
+        if done || fetched == 0 {
+            return Ok(items);
+        }
+    }
+}
+
+/// Gather [`CollectionStats`] for `collection_key`.
+///
+/// Lists every item in the collection (all pages), then asks each annotatable
+/// attachment for a single annotation to learn whether it has any; only
+/// attachments of items not already known to be annotated are asked.
+pub async fn collection_stats(client: &ZoteroClient, collection_key: &str) -> Result<CollectionStats, ZoteroError> {
+    let collection = client.get_collection(collection_key).await?;
+    let items = all_items(ItemListParams::default(), |params| async move {
+        client.list_collection_items(collection_key, &params).await
+    })
+    .await?;
+
+    let mut stats = CollectionStats {
+        key: collection.key,
+        name: collection.data.name,
+        subcollections: collection.meta.num_collections.unwrap_or(0),
+        ..Default::default()
+    };
+    for item in items.iter().filter(|i| i.data.parent_item.is_none()) {
+        stats.items += 1;
+        *stats.by_type.entry(item.data.item_type.clone()).or_default() += 1;
+    }
+    stats.pdfs = items
+        .iter()
+        .filter(|i| i.data.item_type == "attachment" && i.data.content_type.as_deref() == Some("application/pdf"))
+        .count();
+
+    let ann_params = ItemListParams { item_type: Some("annotation".into()), limit: Some(1), ..Default::default() };
+    let mut annotated: HashSet<&str> = HashSet::new();
+    for att in items.iter().filter(|i| i.is_annotatable_attachment()) {
+        let owner = att.data.parent_item.as_deref().unwrap_or(&att.key);
+        if annotated.contains(owner) {
+            continue;
+        }
+        if client.list_item_children(&att.key, &ann_params).await?.items.is_empty() {
+            continue;
+        }
+        annotated.insert(owner);
+    }
+    stats.with_annotations = annotated.len();
+    Ok(stats)
+}
+
+/// Library-wide counts, from [`library_stats`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct LibraryStats {
+    /// Top-level items (standalone notes and attachments included).
+    pub items: u64,
+    /// Top-level items per Zotero item type; types with no items are omitted.
+    pub by_type: BTreeMap<String, u64>,
+    pub collections: u64,
+    pub tags: u64,
+    /// Top-level items with at least one PDF attachment (or that are one).
+    pub items_with_pdfs: usize,
+}
+
+/// Count queries [`library_stats`] keeps in flight at once.
+const LIBRARY_STATS_CONCURRENCY: usize = 8;
+
+/// Gather [`LibraryStats`] for the library.
+///
+/// Totals come from the `Total-Results` header of `limit=1` queries, one per
+/// item type for the breakdown. Only the PDF count needs whole pages: every
+/// attachment is listed to see its content type.
+pub async fn library_stats(client: &ZoteroClient) -> Result<LibraryStats, ZoteroError> {
+    use futures::{StreamExt as _, TryStreamExt as _};
+
+    fn total<T>(page: &PagedResponse<T>) -> u64 {
+        page.total_results.unwrap_or(page.items.len() as u64)
+    }
+    let count_one = ItemListParams { limit: Some(1), ..Default::default() };
+    let items = total(&client.list_top_items(&count_one).await?);
+    let collection_params = CollectionListParams { limit: Some(1), ..Default::default() };
+    let collections = total(&client.list_collections(&collection_params).await?);
+    let tags = total(&client.list_tags(&TagListParams { limit: Some(1), ..Default::default() }).await?);
+
+    let by_type: BTreeMap<String, u64> = futures::stream::iter(ITEM_TYPES.iter().filter(|t| **t != "annotation"))
+        .map(|item_type| {
+            let params = ItemListParams { item_type: Some(item_type.to_string()), ..count_one.clone() };
+            async move {
+                let n = total(&client.list_top_items(&params).await?);
+                Ok::<_, ZoteroError>((item_type.to_string(), n))
+            }
+        })
+        .buffer_unordered(LIBRARY_STATS_CONCURRENCY)
+        .try_filter(|(_, n)| futures::future::ready(*n > 0))
+        .try_collect()
+        .await?;
+
+    let att_params = ItemListParams { item_type: Some("attachment".into()), ..Default::default() };
+    let attachments = all_items(att_params, |params| async move { client.list_items(&params).await }).await?;
+    let with_pdfs: HashSet<&str> = attachments
+        .iter()
+        .filter(|a| a.data.content_type.as_deref() == Some("application/pdf"))
+        .map(|a| a.data.parent_item.as_deref().unwrap_or(&a.key))
+        .collect();
+
+    Ok(LibraryStats { items, by_type, collections, tags, items_with_pdfs: with_pdfs.len() })
+}
+
+/// An attachment listed by [`fulltext_status`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FulltextEntry {
+    pub key: String,
+    pub title: Option<String>,
+    pub parent_item: Option<String>,
+    /// Full-text version Zotero reports, for indexed attachments.
+    pub version: Option<u64>,
+}
+
+/// The library's file attachments, split by whether their full text is indexed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct FulltextStatus {
+    pub indexed: Vec<FulltextEntry>,
+    pub missing: Vec<FulltextEntry>,
+}
+
+/// Which attachments Zotero has indexed full text for, and which it hasn't.
+///
+/// Compares the indexed versions from `GET /fulltext` against every
+/// attachment in the library. Linked URLs have no file to index and are left
+/// out; both lists keep the library's attachment order.
+pub async fn fulltext_status(client: &ZoteroClient) -> Result<FulltextStatus, ZoteroError> {
+    let versions = client.list_fulltext_versions(&FulltextParams::default()).await?.data;
+    let att_params = ItemListParams { item_type: Some("attachment".into()), ..Default::default() };
+    let attachments = all_items(att_params, |params| async move { client.list_items(&params).await }).await?;
+    let mut status = FulltextStatus::default();
+    for att in attachments {
+        if att.data.link_mode.as_deref() == Some("linked_url") {
+            continue;
+        }
+        let version = versions.get(&att.key).copied();
+        let entry = FulltextEntry { key: att.key, title: att.data.title, parent_item: att.data.parent_item, version };
+        if version.is_some() {
+            status.indexed.push(entry);
+        } else {
+            status.missing.push(entry);
+        }
+    }
+    Ok(status)
+}
+
+/// Result of [`bulk_tag`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct BulkTagOutcome {
+    /// Number of top-level items the search matched.
+    pub matched: usize,
+    /// Keys of the matched items whose tags changed (or would, on a dry run).
+    pub changed: Vec<String>,
+}
+
+/// Add and remove tags on every top-level item matching the quick search
+/// `query`.
+///
+/// All result pages are read first; items whose tags would not change are
+/// skipped, and each of the rest is updated with
+/// [`ZoteroClient::update_item_tags`]. `progress` is called before each update
+/// with the 1-based position, the number of items to update, and the item.
+/// With `dry_run` nothing is written and `progress` is not called.
+pub async fn bulk_tag(
+    client: &ZoteroClient,
+    query: &str,
+    add: &[&str],
+    remove: &[&str],
+    dry_run: bool,
+    mut progress: impl FnMut(usize, usize, &Item),
+) -> Result<BulkTagOutcome, ZoteroError> {
+    let search = ItemListParams { q: Some(query.to_string()), ..Default::default() };
+    let matched = all_items(search, |params| async move { client.list_top_items(&params).await }).await?;
+
+    let to_change: Vec<&Item> = matched.iter().filter(|i| i.data.tags_with(add, remove) != i.data.tags).collect();
+    let mut outcome = BulkTagOutcome { matched: matched.len(), changed: Vec::new() };
+    for (i, item) in to_change.iter().enumerate() {
+        if !dry_run {
+            progress(i + 1, to_change.len(), item);
+            client.update_item_tags(&item.key, add, remove).await?;
+        }
+        outcome.changed.push(item.key.clone());
+    }
+    Ok(outcome)
+}
+
+/// Outcome of [`import_work`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", content = "key", rename_all = "snake_case")]
+pub enum ImportOutcome {
+    /// A new item was created with this key.
+    Created(String),
+    /// An item with the same DOI is already in the library; nothing was created.
+    AlreadyExists(String),
+}
+
+/// Build a Zotero item to create from an OpenAlex work.
+///
+/// Fields come from [`crate::convert::work_to_item_data`], including `extra`
+/// (where a DOI goes on types without a `DOI` field) and type-specific
+/// container fields such as `proceedingsTitle`.
+pub fn work_to_new_item(work: &Work) -> NewItem {
+    let data = crate::convert::work_to_item_data(work);
+    NewItem {
+        item_type: data.item_type,
+        title: data.title,
+        creators: data.creators,
+        abstract_note: data.abstract_note,
+        publication_title: data.publication_title,
+        date: data.date,
+        volume: data.volume,
+        issue: data.issue,
+        pages: data.pages,
+        doi: data.doi,
+        url: data.url,
+        extra: data.extra,
+        extra_fields: data.extra_fields,
+        ..Default::default()
+    }
+}
+
+/// Create a Zotero item for an OpenAlex work, skipping works already in the
+/// library.
+///
+/// Duplicates are detected by DOI; works without a DOI are always created.
+pub async fn import_work(client: &ZoteroClient, work: &Work) -> Result<ImportOutcome, ZoteroError> {
+    if let Some(doi) = &work.doi
+        && let Some(existing) = client.find_by_doi(doi).await?
+    {
+        return Ok(ImportOutcome::AlreadyExists(existing.key));
+    }
+    let key = client.create_item(&work_to_new_item(work)).await?;
+    Ok(ImportOutcome::Created(key))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -103,4 +568,125 @@ mod tests {
         assert!(!looks_like_zotero_key("Test Paper")); // space
         assert!(!looks_like_zotero_key("GPU Papers")); // collection name
     }
+
+    #[test]
+    fn test_annotation_color_name_palette() {
+        assert_eq!(annotation_color_name("#ffd400"), Some("yellow"));
+        assert_eq!(annotation_color_name("#ff6666"), Some("red"));
+        assert_eq!(annotation_color_name("#5fb236"), Some("green"));
+        assert_eq!(annotation_color_name("#2ea8e5"), Some("blue"));
+        assert_eq!(annotation_color_name("#a28ae5"), Some("purple"));
+        assert_eq!(annotation_color_name("#e56eee"), Some("magenta"));
+        assert_eq!(annotation_color_name("#f19837"), Some("orange"));
+        assert_eq!(annotation_color_name("#aaaaaa"), Some("gray"));
+    }
+
+    #[test]
+    fn test_annotation_color_name_case_and_unknown() {
+        assert_eq!(annotation_color_name("#FFD400"), Some("yellow"));
+        assert_eq!(annotation_color_name("#123456"), None);
+        assert_eq!(annotation_color_name("yellow"), None);
+        assert_eq!(annotation_color_name(""), None);
+    }
+
+    fn annotation(key: &str, annotation_type: &str, color: &str) -> Item {
+        serde_json::from_value(serde_json::json!({
+            "key": key, "version": 1,
+            "library": {"type": "user", "id": 1, "name": "test", "links": {}},
+            "data": {
+                "key": key, "version": 1, "itemType": "annotation",
+                "annotationType": annotation_type, "annotationColor": color
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_annotation_matches_filters_mixed_set() {
+        let items = [
+            annotation("ANN00001", "highlight", "#ffd400"),
+            annotation("ANN00002", "highlight", "#ff6666"),
+            annotation("ANN00003", "underline", "#ffd400"),
+            annotation("ANN00004", "note", "#2ea8e5"),
+        ];
+        let keys = |color: Option<&str>, ty: Option<&str>| -> Vec<&str> {
+            items.iter().filter(|i| annotation_matches(i, color, ty)).map(|i| i.key.as_str()).collect()
+        };
+        assert_eq!(keys(Some("yellow"), Some("highlight")), ["ANN00001"]);
+        assert_eq!(keys(Some("#FFD400"), None), ["ANN00001", "ANN00003"]);
+        assert_eq!(keys(None, Some("Note")), ["ANN00004"]);
+        assert_eq!(keys(None, None).len(), 4);
+        assert!(keys(Some("green"), None).is_empty());
+    }
+
+    #[test]
+    fn test_sort_annotations_reading_order() {
+        let with = |key: &str, fields: serde_json::Value| {
+            let mut item = annotation(key, "highlight", "#ffd400");
+            for (k, v) in fields.as_object().unwrap() {
+                item.data.extra_fields.insert(k.clone(), v.clone());
+            }
+            item
+        };
+        let mut items = vec![
+            with("P10", serde_json::json!({"annotationPageLabel": "10"})),
+            with("ROMAN", serde_json::json!({"annotationPageLabel": "iv", "annotationSortIndex": "00003|000000|00000"})),
+            with("P2LOW", serde_json::json!({"annotationPageLabel": "2", "annotationSortIndex": "00001|000500|00600"})),
+            with("NOPAGE", serde_json::json!({"annotationPosition": "{\"pageIndex\":0,\"rects\":[]}"})),
+            with("P2TOP", serde_json::json!({"annotationPageLabel": "2", "annotationSortIndex": "00001|000010|00050"})),
+            with("ROMAN2", serde_json::json!({"annotationPageLabel": "ii", "annotationSortIndex": "00001|000000|00000"})),
+        ];
+        sort_annotations(&mut items);
+        let keys: Vec<&str> = items.iter().map(|i| i.key.as_str()).collect();
+        assert_eq!(keys, ["P2TOP", "P2LOW", "P10", "NOPAGE", "ROMAN2", "ROMAN"]);
+    }
+
+    #[test]
+    fn test_parse_date_formats() {
+        assert_eq!(parse_date("2024"), Some((2024, None, None)));
+        assert_eq!(parse_date("2024-03"), Some((2024, Some(3), None)));
+        assert_eq!(parse_date("2024-03-15"), Some((2024, Some(3), Some(15))));
+        assert_eq!(parse_date("2024/3/15"), Some((2024, Some(3), Some(15))));
+        assert_eq!(parse_date("March 2024"), Some((2024, Some(3), None)));
+        assert_eq!(parse_date("15 Mar 2024"), Some((2024, Some(3), Some(15))));
+        assert_eq!(parse_date("March 15, 2024"), Some((2024, Some(3), Some(15))));
+        assert_eq!(parse_date("Sept. 2021"), Some((2021, Some(9), None)));
+        assert_eq!(parse_date("3/15/2024"), Some((2024, Some(3), Some(15))));
+        assert_eq!(parse_date("15/3/2024"), Some((2024, Some(3), Some(15))));
+        assert_eq!(parse_date("Spring 2019"), Some((2019, None, None)));
+        assert_eq!(parse_date("Marine Biology 2019"), Some((2019, None, None)));
+        assert_eq!(parse_date("2024-13-40"), Some((2024, None, None)));
+        assert_eq!(parse_date("n.d."), None);
+        assert_eq!(parse_date(""), None);
+    }
+
+    #[test]
+    fn test_parse_date_sorts_chronologically() {
+        let mut dates = ["2024-03-15", "2023", "2024", "Jan 2024"].map(|d| parse_date(d).unwrap());
+        dates.sort();
+        assert_eq!(dates, [(2023, None, None), (2024, None, None), (2024, Some(1), None), (2024, Some(3), Some(15))]);
+    }
+
+    #[test]
+    fn test_work_to_new_item() {
+        let work: Work = serde_json::from_str(r#"{
+            "id": "https://openalex.org/W1",
+            "display_name": "A Great Paper",
+            "doi": "https://doi.org/10.1234/test",
+            "publication_year": 2020,
+            "authorships": [{"author": {"id": "https://openalex.org/A1", "display_name": "Alice B. Smith"}}],
+            "primary_location": {"source": {"id": "https://openalex.org/S1", "display_name": "Nature"}},
+            "biblio": {"volume": "7", "issue": "2", "first_page": "10", "last_page": "20"}
+        }"#).unwrap();
+        let item = work_to_new_item(&work);
+        assert_eq!(item.item_type, "journalArticle");
+        assert_eq!(item.title.as_deref(), Some("A Great Paper"));
+        assert_eq!(item.doi.as_deref(), Some("10.1234/test"));
+        assert_eq!(item.date.as_deref(), Some("2020"));
+        assert_eq!(item.publication_title.as_deref(), Some("Nature"));
+        assert_eq!(item.pages.as_deref(), Some("10-20"));
+        assert_eq!(item.creators.len(), 1);
+        assert_eq!(item.creators[0].first_name.as_deref(), Some("Alice B."));
+        assert_eq!(item.creators[0].last_name.as_deref(), Some("Smith"));
+    }
 }
@@ -223,6 +223,32 @@ async fn test_work_list_applies_summary() {
     assert!(!json.contains("authorships"));
 }
 
+#[tokio::test]
+async fn test_work_list_raw_keeps_dropped_fields() {
+    let mock = MockServer::start().await;
+    let body = list_response(work_json());
+    Mock::given(method("GET"))
+        .and(path("/works"))
+        .and(query_param("filter", "publication_year:2020"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body.clone()))
+        .mount(&mock)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/works/W1"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(work_json()))
+        .mount(&mock)
+        .await;
+
+    let client = make_client(&mock);
+    let params = WorkListParams { year: Some("2020".into()), ..Default::default() };
+    let raw = api::work_list_raw(&client, &params).await.unwrap();
+    assert_eq!(raw, body);
+    assert!(raw.contains("\"referenced_works\": [\"https://openalex.org/W2\"]"));
+
+    let raw = api::work_get_raw(&client, "W1", &GetParams::default()).await.unwrap();
+    assert_eq!(raw, work_json());
+}
+
 #[tokio::test]
 async fn test_work_list_abstract_preserved() {
     let mock = MockServer::start().await;
@@ -490,6 +516,46 @@ async fn test_funder_list_applies_summary() {
     assert!(!json.contains("counts_by_year"));
 }
 
+#[tokio::test]
+async fn test_funder_works_by_id() {
+    let mock = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/works"))
+        .and(query_param("filter", "publication_year:2020,grants.funder:F1"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(list_response(work_json())))
+        .expect(1)
+        .mount(&mock)
+        .await;
+
+    let client = make_client(&mock);
+    let params = WorkListParams { filter: Some("publication_year:2020".into()), ..Default::default() };
+    let result = api::funder_works(&client, "F1", &params).await.unwrap();
+    assert_eq!(result.results[0].title.as_deref(), Some("A Great Paper"));
+}
+
+#[tokio::test]
+async fn test_funder_works_resolves_name() {
+    let mock = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/funders"))
+        .and(query_param("filter", "display_name.search:nih"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(list_response(funder_json())))
+        .expect(1)
+        .mount(&mock)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/works"))
+        .and(query_param("filter", "grants.funder:F1"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(list_response(work_json())))
+        .expect(1)
+        .mount(&mock)
+        .await;
+
+    let client = make_client(&mock);
+    let result = api::funder_works(&client, "nih", &WorkListParams::default()).await.unwrap();
+    assert_eq!(result.results.len(), 1);
+}
+
 #[tokio::test]
 async fn test_funder_get_returns_full() {
     let mock = MockServer::start().await;
@@ -891,6 +957,100 @@ async fn test_work_get_returns_suggestions_when_no_exact_match() {
     }
 }
 
+#[tokio::test]
+async fn test_work_get_response_unknown_doi_returns_top_suggestions() {
+    let mock = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/works/doi:10.9999/missing"))
+        .respond_with(ResponseTemplate::new(404).set_body_string("Not Found"))
+        .mount(&mock)
+        .await;
+    let results: Vec<String> = (1..=7)
+        .map(|i| format!(
+            r#"{{"id": "https://openalex.org/W{i}", "short_id": "works/W{i}", "display_name": "Candidate {i}", "hint": null, "cited_by_count": {c}, "works_count": null, "entity_type": "work", "external_id": null, "filter_key": "openalex"}}"#,
+            c = i * 10
+        ))
+        .collect();
+    Mock::given(method("GET"))
+        .and(path("/autocomplete/works"))
+        .and(query_param("q", "10.9999/missing"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+            r#"{{"meta": {{"count": 7, "db_response_time_ms": 5, "page": 1, "per_page": 10}}, "results": [{}]}}"#,
+            results.join(",")
+        )))
+        .mount(&mock)
+        .await;
+
+    let client = make_client(&mock);
+    let err = api::work_get_response(&client, None, "10.9999/missing", &GetParams::default())
+        .await
+        .unwrap_err();
+    match err {
+        papers_core::FilterError::Suggestions { query, suggestions } => {
+            assert_eq!(query, "10.9999/missing");
+            assert_eq!(suggestions.len(), 5);
+            assert_eq!(suggestions[0], ("Candidate 1".to_string(), 10));
+        }
+        other => panic!("expected FilterError::Suggestions, got: {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_work_get_response_suggestions_strip_uppercase_doi_prefix() {
+    let mock = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/works/doi:10.9999/missing"))
+        .respond_with(ResponseTemplate::new(404).set_body_string("Not Found"))
+        .mount(&mock)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/autocomplete/works"))
+        .and(query_param("q", "10.9999/missing"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            r#"{"meta": {"count": 1, "db_response_time_ms": 5, "page": 1, "per_page": 10}, "results": [{"id": "https://openalex.org/W1", "short_id": "works/W1", "display_name": "Candidate", "hint": null, "cited_by_count": 3, "works_count": null, "entity_type": "work", "external_id": null, "filter_key": "openalex"}]}"#,
+        ))
+        .expect(1)
+        .mount(&mock)
+        .await;
+
+    let client = make_client(&mock);
+    let err = api::work_get_response(&client, None, "DOI:10.9999/missing", &GetParams::default())
+        .await
+        .unwrap_err();
+    match err {
+        papers_core::FilterError::Suggestions { suggestions, .. } => {
+            assert_eq!(suggestions, vec![("Candidate".to_string(), 3)]);
+        }
+        other => panic!("expected FilterError::Suggestions, got: {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_work_get_response_unknown_id_without_candidates_keeps_404() {
+    let mock = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/works/W404"))
+        .respond_with(ResponseTemplate::new(404).set_body_string("Not Found"))
+        .mount(&mock)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/autocomplete/works"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            r#"{"meta": {"count": 0, "db_response_time_ms": 5, "page": 1, "per_page": 10}, "results": []}"#,
+        ))
+        .mount(&mock)
+        .await;
+
+    let client = make_client(&mock);
+    let err = api::work_get_response(&client, None, "W404", &GetParams::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        papers_core::FilterError::Api(papers_core::OpenAlexError::Api { status: 404, .. })
+    ));
+}
+
 #[tokio::test]
 async fn test_work_get_suggestions_error_message_format() {
     let mock = MockServer::start().await;
@@ -959,6 +1119,90 @@ async fn test_work_get_single_candidate_auto_selected() {
     assert!(result.is_ok());
 }
 
+// ── Coauthors ────────────────────────────────────────────────────────────
+
+fn authorship_work_json(id: &str, author_ids: &[&str]) -> String {
+    let authorships: Vec<String> = author_ids
+        .iter()
+        .map(|a| format!(r#"{{"author": {{"id": "https://openalex.org/{a}", "display_name": "{a}"}}}}"#))
+        .collect();
+    format!(r#"{{"id": "https://openalex.org/{id}", "authorships": [{}]}}"#, authorships.join(", "))
+}
+
+#[tokio::test]
+async fn test_coauthors_tallies_shared_works() {
+    let mock = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/authors/A1"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(author_json()))
+        .mount(&mock)
+        .await;
+    // A2 appears on both works (listed twice on W2), A3 on one
+    Mock::given(method("GET"))
+        .and(path("/works"))
+        .and(query_param("filter", "authorships.author.id:A1"))
+        .and(query_param("per-page", "200"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(list_response(&format!(
+            "{}, {}",
+            authorship_work_json("W1", &["A1", "A2", "A3"]),
+            authorship_work_json("W2", &["A2", "A1", "A2"]),
+        ))))
+        .expect(1)
+        .mount(&mock)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/authors"))
+        .and(query_param("filter", "openalex:A2|A3"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(multi_result_list_response(&[
+            ("https://openalex.org/A3", "Carol", 5),
+            ("https://openalex.org/A2", "Bob", 7),
+        ])))
+        .expect(1)
+        .mount(&mock)
+        .await;
+
+    let client = make_client(&mock);
+    let result = api::coauthors(&client, "A1", 10).await.unwrap();
+    let summary: Vec<(&str, usize)> = result
+        .iter()
+        .map(|(a, n)| (a.display_name.as_deref().unwrap(), *n))
+        .collect();
+    assert_eq!(summary, vec![("Bob", 2), ("Carol", 1)]);
+}
+
+#[tokio::test]
+async fn test_coauthors_depth_limits_resolved_authors() {
+    let mock = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/authors/A1"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(author_json()))
+        .mount(&mock)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/works"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(list_response(&format!(
+            "{}, {}",
+            authorship_work_json("W1", &["A1", "A2", "A3"]),
+            authorship_work_json("W2", &["A1", "A2"]),
+        ))))
+        .mount(&mock)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/authors"))
+        .and(query_param("filter", "openalex:A2"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(multi_result_list_response(&[
+            ("https://openalex.org/A2", "Bob", 7),
+        ])))
+        .expect(1)
+        .mount(&mock)
+        .await;
+
+    let client = make_client(&mock);
+    let result = api::coauthors(&client, "A1", 1).await.unwrap();
+    assert_eq!(result.len(), 1);
+    assert_eq!(result[0].1, 2);
+}
+
 // ── work_text tests ──────────────────────────────────────────────────────
 
 #[tokio::test]
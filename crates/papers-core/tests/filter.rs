@@ -1,4 +1,4 @@
-use papers_core::filter::{WorkFilterAliases, resolve_work_filters};
+use papers_core::filter::{FilterError, WorkFilterAliases, resolve_filter_entity, resolve_work_filters};
 use papers_core::{
     AuthorListParams, FieldListParams, FunderListParams, InstitutionListParams, OpenAlexClient,
     PublisherListParams, SourceListParams, SubfieldListParams, TopicListParams,
@@ -22,13 +22,21 @@ fn search_result_json(id: &str) -> String {
     )
 }
 
-/// Empty list response (no results).
-fn empty_search_result_json() -> String {
-    r#"{
-        "meta": {"count": 0, "db_response_time_ms": 5, "page": 1, "per_page": 1, "next_cursor": null, "groups_count": null},
-        "results": [],
-        "group_by": []
-    }"#
+/// Author autocomplete response with the given `(id, display_name)` candidates.
+fn author_autocomplete_json(candidates: &[(&str, &str)]) -> String {
+    let results: Vec<_> = candidates
+        .iter()
+        .map(|(id, name)| {
+            serde_json::json!({
+                "id": id, "display_name": name, "hint": null, "cited_by_count": 100,
+                "works_count": 10, "entity_type": "author", "external_id": null,
+            })
+        })
+        .collect();
+    serde_json::json!({
+        "meta": {"count": results.len(), "db_response_time_ms": 5, "page": 1, "per_page": 10},
+        "results": results,
+    })
     .to_string()
 }
 
@@ -36,13 +44,11 @@ fn empty_search_result_json() -> String {
 async fn test_resolve_author_search() {
     let mock = MockServer::start().await;
     Mock::given(method("GET"))
-        .and(path("/authors"))
-        .and(query_param("filter", "display_name.search:einstein"))
-        .and(query_param("sort", "cited_by_count:desc"))
-        .and(query_param("per-page", "1"))
+        .and(path("/autocomplete/authors"))
+        .and(query_param("q", "einstein"))
         .respond_with(
             ResponseTemplate::new(200)
-                .set_body_string(search_result_json("https://openalex.org/A5083138872")),
+                .set_body_string(author_autocomplete_json(&[("https://openalex.org/A5083138872", "Albert Einstein")])),
         )
         .mount(&mock)
         .await;
@@ -61,6 +67,57 @@ async fn test_resolve_author_search() {
     );
 }
 
+#[tokio::test]
+async fn test_resolve_author_name_picks_exact_match() {
+    let mock = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/autocomplete/authors"))
+        .and(query_param("q", "geoffrey hinton"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(author_autocomplete_json(&[
+            ("https://openalex.org/A5108093963", "Geoffrey E. Hinton"),
+            ("https://openalex.org/A5046878462", "Geoffrey Hinton"),
+        ])))
+        .mount(&mock)
+        .await;
+
+    let client = make_client(&mock);
+    let aliases = WorkFilterAliases {
+        author: Some("geoffrey hinton".to_string()),
+        ..Default::default()
+    };
+    let result = resolve_work_filters(&client, &aliases, None).await.unwrap();
+    assert_eq!(result.as_deref(), Some("authorships.author.id:A5046878462"));
+}
+
+#[tokio::test]
+async fn test_resolve_author_name_ambiguous_lists_candidates() {
+    let mock = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/autocomplete/authors"))
+        .and(query_param("q", "smith"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(author_autocomplete_json(&[
+            ("https://openalex.org/A1", "John Smith"),
+            ("https://openalex.org/A2", "Jane Smith"),
+        ])))
+        .mount(&mock)
+        .await;
+
+    let client = make_client(&mock);
+    let aliases = WorkFilterAliases {
+        author: Some("smith".to_string()),
+        ..Default::default()
+    };
+    let err = resolve_work_filters(&client, &aliases, None).await.unwrap_err();
+    match err {
+        FilterError::Suggestions { query, suggestions } => {
+            assert_eq!(query, "smith");
+            let names: Vec<_> = suggestions.iter().map(|(n, _)| n.as_str()).collect();
+            assert_eq!(names, ["John Smith", "Jane Smith"]);
+        }
+        other => panic!("expected Suggestions, got {other:?}"),
+    }
+}
+
 #[tokio::test]
 async fn test_resolve_publisher_search() {
     let mock = MockServer::start().await;
@@ -118,6 +175,55 @@ async fn test_resolve_source_search() {
     );
 }
 
+#[tokio::test]
+async fn test_resolve_institution_search() {
+    let mock = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/institutions"))
+        .and(query_param("filter", "display_name.search:MIT"))
+        .and(query_param("sort", "cited_by_count:desc"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(search_result_json("https://openalex.org/I63966007")),
+        )
+        .mount(&mock)
+        .await;
+
+    let client = make_client(&mock);
+    let aliases = WorkFilterAliases {
+        institution: Some("MIT".to_string()),
+        ..Default::default()
+    };
+    let result = resolve_work_filters(&client, &aliases, None)
+        .await
+        .unwrap();
+    assert_eq!(
+        result.as_deref(),
+        Some("authorships.institutions.lineage:I63966007")
+    );
+}
+
+#[tokio::test]
+async fn test_resolve_filter_entity_source_name_and_id() {
+    let mock = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/sources"))
+        .and(query_param("filter", "display_name.search:Nature"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(search_result_json("https://openalex.org/S137773608")),
+        )
+        .expect(1)
+        .mount(&mock)
+        .await;
+
+    let client = make_client(&mock);
+    let result = resolve_filter_entity(&client, "sources", "Nature|https://openalex.org/S4210172589")
+        .await
+        .unwrap();
+    assert_eq!(result, "S137773608|S4210172589");
+}
+
 #[tokio::test]
 async fn test_resolve_topic_search() {
     let mock = MockServer::start().await;
@@ -261,8 +367,8 @@ async fn test_resolve_mixed_id_and_search() {
 async fn test_resolve_not_found_error() {
     let mock = MockServer::start().await;
     Mock::given(method("GET"))
-        .and(path("/authors"))
-        .respond_with(ResponseTemplate::new(200).set_body_string(empty_search_result_json()))
+        .and(path("/autocomplete/authors"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(author_autocomplete_json(&[])))
         .mount(&mock)
         .await;
 
@@ -306,11 +412,11 @@ async fn test_resolve_all_aliases_combined() {
 
     // Mock author search
     Mock::given(method("GET"))
-        .and(path("/authors"))
-        .and(query_param("filter", "display_name.search:einstein"))
+        .and(path("/autocomplete/authors"))
+        .and(query_param("q", "einstein"))
         .respond_with(
             ResponseTemplate::new(200)
-                .set_body_string(search_result_json("https://openalex.org/A5083138872")),
+                .set_body_string(author_autocomplete_json(&[("https://openalex.org/A5083138872", "Albert Einstein")])),
         )
         .mount(&mock)
         .await;
@@ -413,6 +519,123 @@ async fn test_author_list_country_direct() {
     assert_eq!(result.meta.count, 0);
 }
 
+#[tokio::test]
+async fn test_work_filter_country_codes_valid() {
+    let client = make_client(&MockServer::start().await);
+    let aliases = WorkFilterAliases {
+        country: Some("US|gb".to_string()),
+        continent: Some("!Europe".to_string()),
+        ..Default::default()
+    };
+    let result = resolve_work_filters(&client, &aliases, None).await.unwrap();
+    assert_eq!(
+        result.as_deref(),
+        Some("authorships.institutions.country_code:US|gb,authorships.institutions.continent:!Europe")
+    );
+}
+
+#[tokio::test]
+async fn test_work_filter_type_valid() {
+    let client = make_client(&MockServer::start().await);
+    let aliases = WorkFilterAliases {
+        r#type: Some("article|Book-Chapter".to_string()),
+        ..Default::default()
+    };
+    let result = resolve_work_filters(&client, &aliases, None).await.unwrap();
+    assert_eq!(result.as_deref(), Some("type:article|Book-Chapter"));
+}
+
+#[tokio::test]
+async fn test_work_filter_type_typo_suggests() {
+    let client = make_client(&MockServer::start().await);
+    let aliases = WorkFilterAliases {
+        r#type: Some("book chaptr".to_string()),
+        ..Default::default()
+    };
+    let err = resolve_work_filters(&client, &aliases, None).await.unwrap_err();
+    assert_eq!(err.to_string(), "Invalid type 'book chaptr' (did you mean: book-chapter?)");
+}
+
+#[tokio::test]
+async fn test_work_filter_publication_date_range() {
+    let client = make_client(&MockServer::start().await);
+    let aliases = WorkFilterAliases {
+        from_publication_date: Some("2020-01-01".to_string()),
+        to_publication_date: Some("2020-02-29".to_string()),
+        ..Default::default()
+    };
+    let result = resolve_work_filters(&client, &aliases, None).await.unwrap();
+    assert_eq!(
+        result.as_deref(),
+        Some("from_publication_date:2020-01-01,to_publication_date:2020-02-29")
+    );
+}
+
+#[tokio::test]
+async fn test_work_filter_invalid_publication_date() {
+    let client = make_client(&MockServer::start().await);
+    let aliases = WorkFilterAliases {
+        to_publication_date: Some("2021-02-29".to_string()),
+        ..Default::default()
+    };
+    let err = resolve_work_filters(&client, &aliases, None).await.unwrap_err();
+    assert_eq!(err.to_string(), "Invalid to_publication_date '2021-02-29'");
+
+    let aliases = WorkFilterAliases {
+        from_publication_date: Some("2020/5/1".to_string()),
+        ..Default::default()
+    };
+    let err = resolve_work_filters(&client, &aliases, None).await.unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Invalid from_publication_date '2020/5/1' (did you mean: 2020-05-01?)"
+    );
+}
+
+#[tokio::test]
+async fn test_work_filter_publication_date_conflicts_with_year() {
+    let client = make_client(&MockServer::start().await);
+    let aliases = WorkFilterAliases {
+        year: Some("2020".to_string()),
+        from_publication_date: Some("2020-01-01".to_string()),
+        ..Default::default()
+    };
+    let err = resolve_work_filters(&client, &aliases, None).await.unwrap_err();
+    assert!(matches!(
+        err,
+        FilterError::AliasConflict { first: "year", second: "from_publication_date" }
+    ));
+}
+
+#[tokio::test]
+async fn test_author_list_invalid_country_suggests_code() {
+    let mock = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/authors"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(empty_list_response_json()))
+        .expect(0)
+        .mount(&mock)
+        .await;
+
+    let client = make_client(&mock);
+    let params = AuthorListParams {
+        country: Some("USA".into()),
+        ..Default::default()
+    };
+    let Err(err) = api::author_list(&client, &params).await else {
+        panic!("expected an error for country 'USA'");
+    };
+    match &err {
+        FilterError::InvalidValue { alias, value, suggestions } => {
+            assert_eq!(*alias, "country");
+            assert_eq!(value, "USA");
+            assert_eq!(suggestions, &["US"]);
+        }
+        other => panic!("expected InvalidValue, got {other:?}"),
+    }
+    assert_eq!(err.to_string(), "Invalid country 'USA' (did you mean: US?)");
+}
+
 #[tokio::test]
 async fn test_author_list_citations_direct() {
     let mock = MockServer::start().await;
@@ -5,11 +5,13 @@
 ///
 /// **DataLab is always mocked with wiremock** — never call the real DataLab API
 /// from tests. Real calls spend credits and take ~25 seconds.
-use papers_core::text::{do_extract, read_extraction_meta, PdfSource, ProcessingMode};
+use papers_core::text::{
+    datalab_cached_html, datalab_cached_json, do_extract, do_extract_as, read_extraction_meta, OutputFormat, PdfSource, ProcessingMode,
+};
 use papers_datalab::DatalabClient;
 use papers_zotero::{ItemListParams, ZoteroClient};
 use std::path::PathBuf;
-use wiremock::matchers::{method, path, path_regex};
+use wiremock::matchers::{body_string_contains, method, path, path_regex};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 /// Zotero parent item key for "Augmented Vertex Block Descent" (Giles et al.)
@@ -570,3 +572,401 @@ async fn test_meta_json_enriched_with_mock_zotero() {
 
     let _ = std::fs::remove_dir_all(&cdir);
 }
+
+/// Verify that EPUB bytes reach DataLab under an `.epub` filename and MIME type.
+#[tokio::test]
+async fn test_epub_sent_with_epub_filename() {
+    let key = "EPUB0001";
+    let cdir = dirs::cache_dir()
+        .expect("no cache dir")
+        .join("papers")
+        .join("datalab")
+        .join(key);
+    let _ = std::fs::remove_dir_all(&cdir);
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/v1/marker"))
+        .and(body_string_contains(r#"filename="EPUB0001.epub""#))
+        .and(body_string_contains("Content-Type: application/epub+zip"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "request_id": "epub-req-1",
+            "request_check_url": "http://mock/api/v1/marker/epub-req-1",
+            "success": true
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/marker/epub-req-1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "status": "complete",
+            "success": true,
+            "markdown": "# Book"
+        })))
+        .mount(&server)
+        .await;
+
+    let dl = DatalabClient::new("mock-key").with_base_url(server.uri());
+    let mut source = PdfSource::ZoteroRemote { item_key: key.to_string() };
+    let epub = b"PK\x03\x04\x14\x00\x00\x00mimetypeapplication/epub+zip".to_vec();
+    let md = do_extract(epub, key, None, Some((&dl, ProcessingMode::Fast)), &mut source)
+        .await
+        .expect("do_extract failed");
+    assert_eq!(md, "# Book");
+
+    let _ = std::fs::remove_dir_all(&cdir);
+}
+
+/// Two concurrent extractions of the same uncached key convert it once: the
+/// second waits on the first's lock and then reads its cache.
+#[tokio::test]
+async fn test_concurrent_extractions_share_one_conversion() {
+    let key = "LOCK0001";
+    let cdir = dirs::cache_dir()
+        .expect("no cache dir")
+        .join("papers")
+        .join("datalab")
+        .join(key);
+    let _ = std::fs::remove_dir_all(&cdir);
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/v1/marker"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({
+                    "request_id": "lock-req-1",
+                    "request_check_url": "http://mock/api/v1/marker/lock-req-1",
+                    "success": true
+                }))
+                .set_delay(std::time::Duration::from_millis(500)),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/marker/lock-req-1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "status": "complete",
+            "success": true,
+            "markdown": "# Locked"
+        })))
+        .mount(&server)
+        .await;
+
+    let dl = DatalabClient::new("mock-key").with_base_url(server.uri());
+    let extract = || async {
+        let mut source = PdfSource::ZoteroRemote { item_key: key.to_string() };
+        let text = do_extract(vec![0u8], key, None, Some((&dl, ProcessingMode::Fast)), &mut source).await;
+        (text, source)
+    };
+    let ((first, first_source), (second, second_source)) = tokio::join!(extract(), extract());
+
+    assert_eq!(first.expect("first extraction failed"), "# Locked");
+    assert_eq!(second.expect("second extraction failed"), "# Locked");
+    assert!(matches!(first_source, PdfSource::DataLab));
+    assert!(matches!(second_source, PdfSource::DataLab));
+    assert!(!cdir.join(".extract.lock").exists(), "lock file left behind");
+
+    let _ = std::fs::remove_dir_all(&cdir);
+}
+
+/// Requesting JSON sends it to DataLab alongside markdown and caches the
+/// block-level result as `{key}.json`, which `datalab_cached_json` reads back.
+#[tokio::test]
+async fn test_json_output_written_to_cache() {
+    let key = "JSON0001";
+    let cdir = dirs::cache_dir()
+        .expect("no cache dir")
+        .join("papers")
+        .join("datalab")
+        .join(key);
+    let _ = std::fs::remove_dir_all(&cdir);
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/v1/marker"))
+        .and(body_string_contains("markdown,json"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "request_id": "json-req-1",
+            "request_check_url": "http://mock/api/v1/marker/json-req-1",
+            "success": true
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/marker/json-req-1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "status": "complete",
+            "success": true,
+            "markdown": "# Paper",
+            "json": {"pages": [{"blocks": [{"block_type": "SectionHeader"}]}]}
+        })))
+        .mount(&server)
+        .await;
+
+    let dl = DatalabClient::new("mock-key").with_base_url(server.uri());
+    let mut source = PdfSource::ZoteroRemote { item_key: key.to_string() };
+    let extraction = do_extract_as(vec![0u8], key, None, Some((&dl, ProcessingMode::Fast)), &[OutputFormat::Json], &mut source)
+        .await
+        .expect("do_extract_as failed");
+
+    let expected = r#"{"pages":[{"blocks":[{"block_type":"SectionHeader"}]}]}"#;
+    assert_eq!(extraction.markdown, "# Paper");
+    assert_eq!(extraction.json.as_deref(), Some(expected));
+    assert!(cdir.join(format!("{key}.json")).exists(), ".json cache file missing");
+    assert_eq!(datalab_cached_json(key).as_deref(), Some(expected));
+
+    let _ = std::fs::remove_dir_all(&cdir);
+}
+
+/// The default extraction asks for markdown and JSON in a single DataLab job
+/// and writes both cache files from that one result, leaving no `.tmp` files.
+#[tokio::test]
+async fn test_markdown_and_json_from_one_job() {
+    let key = "MULTI001";
+    let cdir = dirs::cache_dir()
+        .expect("no cache dir")
+        .join("papers")
+        .join("datalab")
+        .join(key);
+    let _ = std::fs::remove_dir_all(&cdir);
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/v1/marker"))
+        .and(body_string_contains("markdown,json"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "request_id": "multi-req-1",
+            "request_check_url": "http://mock/api/v1/marker/multi-req-1",
+            "success": true
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/marker/multi-req-1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "status": "complete",
+            "success": true,
+            "output_format": "markdown,json",
+            "markdown": "# Multi\n\nBody.",
+            "json": {"pages": []}
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let dl = DatalabClient::new("mock-key").with_base_url(server.uri());
+    let mut source = PdfSource::ZoteroRemote { item_key: key.to_string() };
+    let text = do_extract(vec![0u8], key, None, Some((&dl, ProcessingMode::Fast)), &mut source)
+        .await
+        .expect("do_extract failed");
+
+    assert_eq!(text, "# Multi\n\nBody.");
+    assert_eq!(std::fs::read_to_string(cdir.join(format!("{key}.md"))).unwrap(), text);
+    assert_eq!(std::fs::read_to_string(cdir.join(format!("{key}.json"))).unwrap(), r#"{"pages":[]}"#);
+    let leftovers: Vec<_> = std::fs::read_dir(&cdir)
+        .unwrap()
+        .flatten()
+        .filter(|e| e.file_name().to_string_lossy().ends_with(".tmp"))
+        .collect();
+    assert!(leftovers.is_empty(), "temporary files left behind: {leftovers:?}");
+
+    let _ = std::fs::remove_dir_all(&cdir);
+}
+
+/// Requesting HTML sends `html` in `output_format`, caches `{key}.html`, and
+/// re-extracts an item whose cache only has markdown.
+#[tokio::test]
+async fn test_html_output_cached_and_returned() {
+    let key = "HTML0001";
+    let cdir = dirs::cache_dir()
+        .expect("no cache dir")
+        .join("papers")
+        .join("datalab")
+        .join(key);
+    let _ = std::fs::remove_dir_all(&cdir);
+    std::fs::create_dir_all(&cdir).unwrap();
+    std::fs::write(cdir.join(format!("{key}.md")), "# Markdown only").unwrap();
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/v1/marker"))
+        .and(body_string_contains("markdown,json,html"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "request_id": "html-req-1",
+            "request_check_url": "http://mock/api/v1/marker/html-req-1",
+            "success": true
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/marker/html-req-1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "status": "complete",
+            "success": true,
+            "markdown": "# Paper",
+            "html": "<h1>Paper</h1>"
+        })))
+        .mount(&server)
+        .await;
+
+    let dl = DatalabClient::new("mock-key").with_base_url(server.uri());
+    let formats = [OutputFormat::Markdown, OutputFormat::Json, OutputFormat::Html];
+    let mut source = PdfSource::ZoteroRemote { item_key: key.to_string() };
+    let extraction = do_extract_as(vec![0u8], key, None, Some((&dl, ProcessingMode::Fast)), &formats, &mut source)
+        .await
+        .expect("do_extract_as failed");
+    assert_eq!(extraction.markdown, "# Paper");
+    assert_eq!(extraction.html.as_deref(), Some("<h1>Paper</h1>"));
+    assert_eq!(datalab_cached_html(key).as_deref(), Some("<h1>Paper</h1>"));
+
+    // Now cached: a second request is served locally (the submit mock expects one call)
+    let mut source = PdfSource::ZoteroRemote { item_key: key.to_string() };
+    let again = do_extract_as(vec![0u8], key, None, Some((&dl, ProcessingMode::Fast)), &formats, &mut source)
+        .await
+        .expect("cached do_extract_as failed");
+    assert_eq!(again.html.as_deref(), Some("<h1>Paper</h1>"));
+
+    let _ = std::fs::remove_dir_all(&cdir);
+}
+
+/// A work with no Zotero copy and an off-whitelist OA PDF is downloaded
+/// directly and sent through DataLab.
+#[tokio::test]
+async fn test_work_text_downloads_oa_pdf_for_extraction() {
+    let id = "W6530001";
+    let cdir = dirs::cache_dir()
+        .expect("no cache dir")
+        .join("papers")
+        .join("datalab")
+        .join(id);
+    let _ = std::fs::remove_dir_all(&cdir);
+
+    let (server, dl) = setup_datalab_mock().await;
+    let pdf_url = format!("{}/oa/paper.pdf", server.uri());
+    Mock::given(method("GET"))
+        .and(path(format!("/works/{id}")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": format!("https://openalex.org/{id}"),
+            "display_name": "Open Paper",
+            "best_oa_location": {"pdf_url": pdf_url},
+            "open_access": {"is_oa": true, "oa_url": pdf_url}
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/oa/paper.pdf"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "application/pdf")
+                .set_body_bytes(b"%PDF-1.4 fake".to_vec()),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let openalex = papers_core::OpenAlexClient::new().with_base_url(server.uri());
+    let result = papers_core::text::work_text(&openalex, None, Some((&dl, ProcessingMode::Fast)), id)
+        .await
+        .expect("work_text failed");
+    assert_eq!(result.text, "# Test Paper\n\nExtracted content for testing.");
+    assert!(matches!(result.source, PdfSource::DataLab));
+    assert!(cdir.join(format!("{id}.md")).exists());
+
+    let _ = std::fs::remove_dir_all(&cdir);
+}
+
+/// An OA URL that serves a landing page instead of a PDF is skipped.
+#[tokio::test]
+async fn test_try_oa_pdf_rejects_non_pdf() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/landing"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/html")
+                .set_body_string("<html>Download here</html>"),
+        )
+        .mount(&server)
+        .await;
+    let work: papers_core::Work = serde_json::from_value(serde_json::json!({
+        "id": "https://openalex.org/W6530002",
+        "open_access": {"is_oa": true, "oa_url": format!("{}/landing", server.uri())}
+    }))
+    .unwrap();
+    let result = papers_core::text::try_oa_pdf(&reqwest::Client::new(), &work, 1024).await;
+    assert!(matches!(result, Ok(None)));
+}
+
+/// A PDF over the size cap is abandoned with a typed error.
+#[tokio::test]
+async fn test_try_oa_pdf_size_cap() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/big.pdf"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "application/pdf")
+                .set_body_bytes(vec![b'x'; 4096]),
+        )
+        .mount(&server)
+        .await;
+    let work: papers_core::Work = serde_json::from_value(serde_json::json!({
+        "id": "https://openalex.org/W6540001",
+        "best_oa_location": {"pdf_url": format!("{}/big.pdf", server.uri())}
+    }))
+    .unwrap();
+    let err = papers_core::text::try_oa_pdf(&reqwest::Client::new(), &work, 1024).await.unwrap_err();
+    assert!(matches!(err, papers_core::text::OaPdfError::TooLarge { limit_bytes: 1024, .. }), "{err}");
+}
+
+/// When the OA PDF exceeds the cap, `work_text` skips it (no DataLab call)
+/// and ends in `NoPdfFound`, which callers turn into the DOI prompt.
+#[tokio::test]
+async fn test_work_text_oversize_oa_pdf_falls_back() {
+    let id = "W6540002";
+    let server = MockServer::start().await;
+    let pdf_url = format!("{}/supplement.pdf", server.uri());
+    Mock::given(method("GET"))
+        .and(path(format!("/works/{id}")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": format!("https://openalex.org/{id}"),
+            "display_name": "Huge Supplement",
+            "best_oa_location": {"pdf_url": pdf_url}
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/supplement.pdf"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "application/pdf")
+                .set_body_bytes(vec![b'x'; 2 * 1024 * 1024]),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v1/marker"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let dl = DatalabClient::new("mock-key").with_base_url(server.uri());
+    let openalex = papers_core::OpenAlexClient::new().with_base_url(server.uri());
+    let config = papers_core::text::WorkTextConfig { oa_pdf_max_bytes: 1024 * 1024 };
+    let result =
+        papers_core::text::work_text_with(&openalex, None, Some((&dl, ProcessingMode::Fast)), id, &config).await;
+    match result {
+        Err(papers_core::text::WorkTextError::NoPdfFound { work_id, .. }) => {
+            assert_eq!(work_id, format!("https://openalex.org/{id}"));
+        }
+        other => panic!("expected NoPdfFound, got {other:?}"),
+    }
+}
@@ -1,6 +1,12 @@
-use papers_core::zotero::{resolve_collection_key, resolve_item_key, resolve_search_key};
+use papers_core::Work;
+use papers_core::bibtex::collection_to_bibtex;
+use papers_core::text::find_convertible_attachment;
+use papers_core::zotero::{
+    BulkTagOutcome, FulltextEntry, ImportOutcome, bulk_tag, collection_annotations, collection_stats, fulltext_status, import_work,
+    library_stats, resolve_collection_key, resolve_item_key, resolve_search_key, work_review,
+};
 use papers_zotero::ZoteroClient;
-use wiremock::matchers::{method, path, query_param};
+use wiremock::matchers::{body_json, method, path, query_param, query_param_is_missing};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 fn make_client(mock: &MockServer) -> ZoteroClient {
@@ -256,3 +262,766 @@ async fn test_resolve_search_key_not_found() {
     let msg = result.unwrap_err().to_string();
     assert!(msg.contains("nonexistent search"));
 }
+
+// ── import_work ───────────────────────────────────────────────────────
+
+fn openalex_work() -> Work {
+    serde_json::from_str(
+        r#"{
+            "id": "https://openalex.org/W1",
+            "display_name": "Attention Is All You Need",
+            "doi": "https://doi.org/10.1234/attention",
+            "publication_year": 2017,
+            "authorships": [{"author": {"id": "https://openalex.org/A1", "display_name": "Ashish Vaswani"}}]
+        }"#,
+    )
+    .unwrap()
+}
+
+fn empty_array_response() -> ResponseTemplate {
+    ResponseTemplate::new(200)
+        .insert_header("Total-Results", "0")
+        .insert_header("Last-Modified-Version", "1")
+        .set_body_string("[]")
+}
+
+#[tokio::test]
+async fn test_import_work_creates_item() {
+    let mock = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/users/test/items/top"))
+        .respond_with(empty_array_response())
+        .mount(&mock)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/users/test/items"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            r#"{"successful":{"0":{"key":"NEW12345","version":2}},"unchanged":{},"failed":{}}"#,
+        ))
+        .expect(1)
+        .mount(&mock)
+        .await;
+
+    let client = make_client(&mock);
+    let outcome = import_work(&client, &openalex_work()).await.unwrap();
+    assert_eq!(outcome, ImportOutcome::Created("NEW12345".into()));
+
+    let post = mock
+        .received_requests()
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|r| r.method == wiremock::http::Method::POST)
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&post.body).unwrap();
+    assert_eq!(body[0]["itemType"], "journalArticle");
+    assert_eq!(body[0]["title"], "Attention Is All You Need");
+    assert_eq!(body[0]["DOI"], "10.1234/attention");
+    assert_eq!(body[0]["date"], "2017");
+    assert_eq!(body[0]["creators"][0]["lastName"], "Vaswani");
+}
+
+#[tokio::test]
+async fn test_import_work_skips_existing_doi() {
+    let mock = MockServer::start().await;
+    let existing = item_list_json().replace(
+        r#""title": "Attention Is All You Need","#,
+        r#""title": "Attention Is All You Need", "DOI": "10.1234/ATTENTION","#,
+    );
+    Mock::given(method("GET"))
+        .and(path("/users/test/items/top"))
+        .and(query_param("q", "10.1234/attention"))
+        .and(query_param("qmode", "everything"))
+        .respond_with(array_response(&existing))
+        .mount(&mock)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/users/test/items"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&mock)
+        .await;
+
+    let client = make_client(&mock);
+    let outcome = import_work(&client, &openalex_work()).await.unwrap();
+    assert_eq!(outcome, ImportOutcome::AlreadyExists("ABC12345".into()));
+}
+
+fn thesis_work() -> Work {
+    serde_json::from_str(
+        r#"{
+            "id": "https://openalex.org/W2",
+            "display_name": "Sorting on GPUs",
+            "doi": "https://doi.org/10.5555/thesis",
+            "type": "dissertation",
+            "publication_year": 2012
+        }"#,
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_import_work_sends_doi_in_extra_for_thesis() {
+    let mock = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/users/test/items/top"))
+        .respond_with(empty_array_response())
+        .mount(&mock)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/users/test/items"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            r#"{"successful":{"0":{"key":"NEW12345","version":2}},"unchanged":{},"failed":{}}"#,
+        ))
+        .expect(1)
+        .mount(&mock)
+        .await;
+
+    let client = make_client(&mock);
+    import_work(&client, &thesis_work()).await.unwrap();
+
+    let post = mock
+        .received_requests()
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|r| r.method == wiremock::http::Method::POST)
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&post.body).unwrap();
+    assert_eq!(body[0]["itemType"], "thesis");
+    assert!(body[0].get("DOI").is_none());
+    assert_eq!(body[0]["extra"], "DOI: 10.5555/thesis");
+}
+
+#[tokio::test]
+async fn test_import_work_sends_proceedings_title() {
+    let mock = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/users/test/items/top"))
+        .respond_with(empty_array_response())
+        .mount(&mock)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/users/test/items"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            r#"{"successful":{"0":{"key":"NEW12345","version":2}},"unchanged":{},"failed":{}}"#,
+        ))
+        .expect(1)
+        .mount(&mock)
+        .await;
+
+    let work: Work = serde_json::from_str(
+        r#"{
+            "id": "https://openalex.org/W3",
+            "display_name": "Fast Sorting",
+            "type": "article",
+            "type_crossref": "proceedings-article",
+            "primary_location": {"source": {"id": "https://openalex.org/S2", "display_name": "SIGGRAPH"}}
+        }"#,
+    )
+    .unwrap();
+    let client = make_client(&mock);
+    import_work(&client, &work).await.unwrap();
+
+    let post = mock
+        .received_requests()
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|r| r.method == wiremock::http::Method::POST)
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&post.body).unwrap();
+    assert_eq!(body[0]["itemType"], "conferencePaper");
+    assert_eq!(body[0]["proceedingsTitle"], "SIGGRAPH");
+    assert!(body[0].get("publicationTitle").is_none());
+}
+
+#[tokio::test]
+async fn test_import_work_skips_existing_thesis() {
+    let mock = MockServer::start().await;
+    // Zotero keeps a thesis DOI in `extra`, as the first import wrote it.
+    let existing = item_list_json().replace(
+        r#""title": "Attention Is All You Need","#,
+        r#""title": "Sorting on GPUs", "extra": "DOI: 10.5555/thesis","#,
+    );
+    Mock::given(method("GET"))
+        .and(path("/users/test/items/top"))
+        .and(query_param("q", "10.5555/thesis"))
+        .respond_with(array_response(&existing))
+        .mount(&mock)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/users/test/items"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&mock)
+        .await;
+
+    let client = make_client(&mock);
+    let outcome = import_work(&client, &thesis_work()).await.unwrap();
+    assert_eq!(outcome, ImportOutcome::AlreadyExists("ABC12345".into()));
+}
+
+// ── collection_to_bibtex ──────────────────────────────────────────────
+
+fn collection_item_json(key: &str, data: &str) -> String {
+    format!(
+        r#"{{
+            "key": "{key}",
+            "version": 1,
+            "library": {{"type": "user", "id": 1, "name": "test", "links": {{}}}},
+            "links": {{}},
+            "meta": {{}},
+            "data": {{"key": "{key}", "version": 1, {data}}}
+        }}"#
+    )
+}
+
+#[tokio::test]
+async fn test_collection_to_bibtex_two_items() {
+    let mock = MockServer::start().await;
+    let body = format!(
+        "[{}, {}, {}, {}]",
+        collection_item_json("ITEMAAAA", r#""itemType": "journalArticle", "title": "Deep Learning""#),
+        collection_item_json("ITEMBBBB", r#""itemType": "thesis", "title": "A {GPU} Thesis""#),
+        collection_item_json(
+            "ATTACH01",
+            r#""itemType": "attachment", "parentItem": "ITEMAAAA", "linkMode": "imported_file""#,
+        ),
+        collection_item_json("NOTE0001", r#""itemType": "note", "note": "<p>read later</p>""#),
+    );
+    Mock::given(method("GET"))
+        .and(path("/users/test/collections/COL12345/items"))
+        .and(query_param("limit", "100"))
+        .and(query_param("start", "0"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("Total-Results", "4")
+                .insert_header("Last-Modified-Version", "100")
+                .set_body_string(body),
+        )
+        .expect(1)
+        .mount(&mock)
+        .await;
+    // Only the two bibliographic items are exported, through Zotero's translator
+    let zotero_bib = "\n@article{lecun_deep_2015,\n\ttitle = {Deep {Learning}},\n}\n\n@mastersthesis{doe_gpu_2020,\n\ttitle = {A \\{GPU\\} {Thesis}},\n}\n";
+    Mock::given(method("GET"))
+        .and(path("/users/test/items"))
+        .and(query_param("itemKey", "ITEMAAAA,ITEMBBBB"))
+        .and(query_param("format", "bibtex"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(zotero_bib))
+        .expect(1)
+        .mount(&mock)
+        .await;
+
+    let client = make_client(&mock);
+    let bib = collection_to_bibtex(&client, "COL12345").await.unwrap();
+    assert_eq!(bib, zotero_bib);
+}
+
+#[tokio::test]
+async fn test_collection_to_bibtex_pages_and_dedups() {
+    let mock = MockServer::start().await;
+    let item = |key: &str| collection_item_json(key, r#""itemType": "journalArticle", "title": "Same Title""#);
+    let first_page: Vec<String> = (0..100).map(|i| item(&format!("K{i:07}"))).collect();
+    Mock::given(method("GET"))
+        .and(path("/users/test/collections/COL12345/items"))
+        .and(query_param("start", "0"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("Total-Results", "102")
+                .set_body_string(format!("[{}]", first_page.join(","))),
+        )
+        .mount(&mock)
+        .await;
+    // The second page repeats the last item of the first page
+    Mock::given(method("GET"))
+        .and(path("/users/test/collections/COL12345/items"))
+        .and(query_param("start", "100"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("Total-Results", "102")
+                .set_body_string(format!("[{}, {}]", item("K0000099"), item("K0000100"))),
+        )
+        .expect(1)
+        .mount(&mock)
+        .await;
+    // 101 unique keys → export chunks of 50, 50 and 1, each with the same cite key
+    Mock::given(method("GET"))
+        .and(path("/users/test/items"))
+        .and(query_param("format", "bibtex"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("\n@article{smith_same_2020,\n}\n"))
+        .expect(3)
+        .mount(&mock)
+        .await;
+
+    let client = make_client(&mock);
+    let bib = collection_to_bibtex(&client, "COL12345").await.unwrap();
+    assert_eq!(bib.matches("@article{").count(), 3);
+    // Cite keys that collide across requests get Zotero-style suffixes
+    assert!(bib.contains("@article{smith_same_2020,\n"));
+    assert!(bib.contains("@article{smith_same_2020-1,\n"));
+    assert!(bib.contains("@article{smith_same_2020-2,\n"));
+
+    let requests = mock.received_requests().await.unwrap();
+    let exported: usize = requests
+        .iter()
+        .filter(|r| r.url.path() == "/users/test/items")
+        .flat_map(|r| r.url.query_pairs().filter(|(k, _)| k == "itemKey").map(|(_, v)| v.split(',').count()).collect::<Vec<_>>())
+        .sum();
+    assert_eq!(exported, 101);
+}
+
+/// One attachment child of `PARENT01` with the given key and content type.
+fn attachment_json(key: &str, content_type: &str, filename: &str) -> String {
+    format!(
+        r#"{{"key":"{key}","version":1,"library":{{"type":"user","id":1,"name":"test","links":{{}}}},"links":{{}},"meta":{{}},"data":{{"key":"{key}","version":1,"itemType":"attachment","parentItem":"PARENT01","linkMode":"imported_file","contentType":"{content_type}","filename":"{filename}"}}}}"#
+    )
+}
+
+async fn mock_attachments(attachments: &[String]) -> MockServer {
+    let mock = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/users/test/items/PARENT01/children"))
+        .respond_with(array_response(&format!("[{}]", attachments.join(","))))
+        .mount(&mock)
+        .await;
+    mock
+}
+
+#[tokio::test]
+async fn test_find_convertible_attachment_selects_epub() {
+    let mock = mock_attachments(&[
+        attachment_json("ATTIMG01", "image/png", "figure.png"),
+        attachment_json("ATTEPUB1", "application/epub+zip", "book.epub"),
+    ])
+    .await;
+    let att = find_convertible_attachment(&make_client(&mock), "PARENT01").await.unwrap();
+    assert_eq!(att.key, "ATTEPUB1");
+    assert_eq!(att.data.filename.as_deref(), Some("book.epub"));
+}
+
+#[tokio::test]
+async fn test_find_convertible_attachment_selects_html_snapshot() {
+    let mock = mock_attachments(&[attachment_json("ATTHTML1", "text/html", "Snapshot.html")]).await;
+    let att = find_convertible_attachment(&make_client(&mock), "PARENT01").await.unwrap();
+    assert_eq!(att.key, "ATTHTML1");
+}
+
+#[tokio::test]
+async fn test_find_convertible_attachment_prefers_pdf() {
+    let mock = mock_attachments(&[
+        attachment_json("ATTHTML1", "text/html", "Snapshot.html"),
+        attachment_json("ATTPDF01", "application/pdf", "paper.pdf"),
+    ])
+    .await;
+    let att = find_convertible_attachment(&make_client(&mock), "PARENT01").await.unwrap();
+    assert_eq!(att.key, "ATTPDF01");
+}
+
+#[tokio::test]
+async fn test_find_convertible_attachment_no_match() {
+    let mock = mock_attachments(&[attachment_json("ATTIMG01", "image/png", "figure.png")]).await;
+    let err = find_convertible_attachment(&make_client(&mock), "PARENT01").await.unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "PDF extraction error: no PDF, EPUB, or HTML attachment for PARENT01"
+    );
+}
+
+// ── work_review ───────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_work_review_gathers_notes_and_annotations() {
+    let mock = MockServer::start().await;
+    let parent = collection_item_json("PARENT01", r#""itemType": "journalArticle", "title": "Vertex Block Descent""#);
+    Mock::given(method("GET"))
+        .and(path("/users/test/items/PARENT01"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(parent))
+        .mount(&mock)
+        .await;
+    let note = collection_item_json(
+        "NOTE0001",
+        r#""itemType": "note", "parentItem": "PARENT01", "note": "<p>Stable at large steps.</p>""#,
+    );
+    Mock::given(method("GET"))
+        .and(path("/users/test/items/PARENT01/children"))
+        .and(query_param("itemType", "note"))
+        .respond_with(array_response(&format!("[{note}]")))
+        .mount(&mock)
+        .await;
+    let attachments = [
+        attachment_json("ATTPDF01", "application/pdf", "paper.pdf"),
+        attachment_json("ATTIMG01", "image/png", "figure.png"),
+    ];
+    Mock::given(method("GET"))
+        .and(path("/users/test/items/PARENT01/children"))
+        .and(query_param("itemType", "attachment"))
+        .respond_with(array_response(&format!("[{}]", attachments.join(","))))
+        .mount(&mock)
+        .await;
+    let annotation = collection_item_json(
+        "ANN00001",
+        r#""itemType": "annotation", "parentItem": "ATTPDF01", "annotationType": "highlight",
+           "annotationText": "Converges quickly", "annotationPageLabel": "3""#,
+    );
+    Mock::given(method("GET"))
+        .and(path("/users/test/items/ATTPDF01/children"))
+        .and(query_param("itemType", "annotation"))
+        .respond_with(array_response(&format!("[{annotation}]")))
+        .expect(1)
+        .mount(&mock)
+        .await;
+
+    let review = work_review(&make_client(&mock), "PARENT01").await.unwrap();
+    assert_eq!(review.item.data.title.as_deref(), Some("Vertex Block Descent"));
+    assert_eq!(review.notes.len(), 1);
+    assert_eq!(review.notes[0].data.note.as_deref(), Some("<p>Stable at large steps.</p>"));
+    assert_eq!(review.annotations.len(), 1);
+    assert_eq!(review.annotations[0].as_annotation().unwrap().text, Some("Converges quickly"));
+}
+
+#[tokio::test]
+async fn test_work_review_pages_through_notes_and_annotations() {
+    let mock = MockServer::start().await;
+    let parent = collection_item_json("PARENT01", r#""itemType": "journalArticle", "title": "Vertex Block Descent""#);
+    Mock::given(method("GET"))
+        .and(path("/users/test/items/PARENT01"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(parent))
+        .mount(&mock)
+        .await;
+    let attachment = attachment_json("ATTPDF01", "application/pdf", "paper.pdf");
+    Mock::given(method("GET"))
+        .and(path("/users/test/items/PARENT01/children"))
+        .and(query_param("itemType", "attachment"))
+        .respond_with(array_response(&format!("[{attachment}]")))
+        .mount(&mock)
+        .await;
+    // Each listing reports two results but returns one per page.
+    for (parent_key, item_type, keys) in [
+        ("PARENT01", "note", ["NOTE0001", "NOTE0002"]),
+        ("ATTPDF01", "annotation", ["ANN00001", "ANN00002"]),
+    ] {
+        for (start, key) in keys.iter().enumerate() {
+            let child = collection_item_json(
+                key,
+                &format!(r#""itemType": "{item_type}", "parentItem": "{parent_key}", "annotationType": "highlight""#),
+            );
+            Mock::given(method("GET"))
+                .and(path(format!("/users/test/items/{parent_key}/children")))
+                .and(query_param("itemType", item_type))
+                .and(query_param("start", start.to_string()))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .insert_header("Total-Results", "2")
+                        .set_body_string(format!("[{child}]")),
+                )
+                .expect(1)
+                .mount(&mock)
+                .await;
+        }
+    }
+
+    let review = work_review(&make_client(&mock), "PARENT01").await.unwrap();
+    let keys = |items: &[papers_zotero::Item]| items.iter().map(|i| i.key.clone()).collect::<Vec<_>>();
+    assert_eq!(keys(&review.notes), ["NOTE0001", "NOTE0002"]);
+    assert_eq!(keys(&review.annotations), ["ANN00001", "ANN00002"]);
+}
+
+#[tokio::test]
+async fn test_collection_annotations_covers_children_and_standalone_pdfs() {
+    let mock = MockServer::start().await;
+    let items = [
+        collection_item_json("PARENT01", r#""itemType": "journalArticle", "title": "Vertex Block Descent""#),
+        collection_item_json(
+            "STANDPDF",
+            r#""itemType": "attachment", "linkMode": "imported_file", "contentType": "application/pdf""#,
+        ),
+    ];
+    Mock::given(method("GET"))
+        .and(path("/users/test/collections/COL12345/items/top"))
+        .respond_with(array_response(&format!("[{}]", items.join(","))))
+        .mount(&mock)
+        .await;
+    let attachment = attachment_json("ATTPDF01", "application/pdf", "paper.pdf");
+    Mock::given(method("GET"))
+        .and(path("/users/test/items/PARENT01/children"))
+        .and(query_param("itemType", "attachment"))
+        .respond_with(array_response(&format!("[{attachment}]")))
+        .mount(&mock)
+        .await;
+    for (parent, key) in [("ATTPDF01", "ANN00001"), ("STANDPDF", "ANN00002")] {
+        let annotation = collection_item_json(
+            key,
+            &format!(r#""itemType": "annotation", "parentItem": "{parent}", "annotationType": "highlight""#),
+        );
+        Mock::given(method("GET"))
+            .and(path(format!("/users/test/items/{parent}/children")))
+            .and(query_param("itemType", "annotation"))
+            .respond_with(array_response(&format!("[{annotation}]")))
+            .expect(1)
+            .mount(&mock)
+            .await;
+    }
+
+    let annotations = collection_annotations(&make_client(&mock), "COL12345").await.unwrap();
+    let keys: Vec<_> = annotations.iter().map(|a| a.key.as_str()).collect();
+    assert_eq!(keys, ["ANN00001", "ANN00002"]);
+}
+
+// ── bulk_tag ──────────────────────────────────────────────────────────
+
+/// Mock a `gpu` search matching two items, each fetchable by key and
+/// accepting `patches` `PATCH`es that add `Reading`.
+async fn mock_bulk_tag_search(patches: u64) -> MockServer {
+    let mock = MockServer::start().await;
+    let items = [
+        collection_item_json("GPU00001", r#""itemType": "journalArticle", "title": "GPU Cloth", "tags": [{"tag": "Survey"}]"#),
+        collection_item_json("GPU00002", r#""itemType": "conferencePaper", "title": "GPU Fluids", "tags": []"#),
+    ];
+    Mock::given(method("GET"))
+        .and(path("/users/test/items/top"))
+        .and(query_param("q", "gpu"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("Total-Results", "2")
+                .set_body_string(format!("[{}]", items.join(","))),
+        )
+        .mount(&mock)
+        .await;
+    for (key, item) in ["GPU00001", "GPU00002"].iter().zip(&items) {
+        Mock::given(method("GET"))
+            .and(path(format!("/users/test/items/{key}")))
+            .respond_with(ResponseTemplate::new(200).set_body_string(item.clone()))
+            .mount(&mock)
+            .await;
+    }
+    Mock::given(method("PATCH"))
+        .and(path("/users/test/items/GPU00001"))
+        .and(body_json(serde_json::json!({"tags": [{"tag": "Survey"}, {"tag": "Reading"}]})))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(patches)
+        .mount(&mock)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path("/users/test/items/GPU00002"))
+        .and(body_json(serde_json::json!({"tags": [{"tag": "Reading"}]})))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(patches)
+        .mount(&mock)
+        .await;
+    mock
+}
+
+#[tokio::test]
+async fn test_bulk_tag_adds_tag_to_every_match() {
+    let mock = mock_bulk_tag_search(1).await;
+    let mut seen = Vec::new();
+    let outcome = bulk_tag(&make_client(&mock), "gpu", &["Reading"], &[], false, |i, n, item| {
+        seen.push(format!("{i}/{n} {}", item.key));
+    })
+    .await
+    .unwrap();
+    assert_eq!(outcome.matched, 2);
+    assert_eq!(outcome.changed, ["GPU00001", "GPU00002"]);
+    assert_eq!(seen, ["1/2 GPU00001", "2/2 GPU00002"]);
+}
+
+#[tokio::test]
+async fn test_bulk_tag_dry_run_writes_nothing() {
+    let mock = mock_bulk_tag_search(0).await;
+    let outcome = bulk_tag(&make_client(&mock), "gpu", &["Reading"], &[], true, |_, _, _| {
+        panic!("dry run reported progress")
+    })
+    .await
+    .unwrap();
+    assert_eq!(outcome.changed, ["GPU00001", "GPU00002"]);
+
+    // Items that already carry the tag are left alone
+    let outcome = bulk_tag(&make_client(&mock), "gpu", &["Survey"], &[], true, |_, _, _| {}).await.unwrap();
+    assert_eq!(outcome, BulkTagOutcome { matched: 2, changed: vec!["GPU00002".into()] });
+}
+
+// ── collection_stats ──────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_collection_stats_mixed_item_types() {
+    let mock = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/users/test/collections/COLL0001"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            r#"{"key": "COLL0001", "version": 1, "library": {"type": "user", "id": 1, "name": "test", "links": {}},
+                "links": {}, "meta": {"numCollections": 2, "numItems": 5},
+                "data": {"key": "COLL0001", "version": 1, "name": "Simulation", "parentCollection": false}}"#,
+        ))
+        .mount(&mock)
+        .await;
+    let pdf = |key: &str, parent: &str| {
+        collection_item_json(
+            key,
+            &format!(r#""itemType": "attachment", "parentItem": "{parent}", "contentType": "application/pdf""#),
+        )
+    };
+    let items = [
+        collection_item_json("ART00001", r#""itemType": "journalArticle""#),
+        collection_item_json("ART00002", r#""itemType": "journalArticle""#),
+        collection_item_json("CONF0001", r#""itemType": "conferencePaper""#),
+        collection_item_json("BOOK0001", r#""itemType": "book""#),
+        collection_item_json("STAND001", r#""itemType": "attachment", "contentType": "application/pdf""#),
+        pdf("PDF00001", "ART00001"),
+        pdf("PDF00002", "ART00001"),
+        pdf("PDF00003", "CONF0001"),
+        collection_item_json("SNAP0001", r#""itemType": "attachment", "parentItem": "BOOK0001", "contentType": "text/html""#),
+        collection_item_json("NOTE0001", r#""itemType": "note", "parentItem": "ART00002", "note": "<p>Read</p>""#),
+    ];
+    Mock::given(method("GET"))
+        .and(path("/users/test/collections/COLL0001/items"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("Total-Results", "10")
+                .set_body_string(format!("[{}]", items.join(","))),
+        )
+        .mount(&mock)
+        .await;
+    let annotation = collection_item_json("ANN00001", r#""itemType": "annotation", "annotationType": "highlight""#);
+    for (key, annotations, calls) in [
+        ("PDF00001", format!("[{annotation}]"), 1),
+        ("PDF00002", format!("[{annotation}]"), 0), // ART00001 is already known to be annotated
+        ("PDF00003", "[]".to_string(), 1),
+        ("SNAP0001", format!("[{annotation}]"), 1),
+        ("STAND001", "[]".to_string(), 1),
+    ] {
+        Mock::given(method("GET"))
+            .and(path(format!("/users/test/items/{key}/children")))
+            .and(query_param("itemType", "annotation"))
+            .respond_with(array_response(&annotations))
+            .expect(calls)
+            .mount(&mock)
+            .await;
+    }
+
+    let stats = collection_stats(&make_client(&mock), "COLL0001").await.unwrap();
+    assert_eq!(stats.name, "Simulation");
+    assert_eq!(stats.items, 5);
+    let by_type: Vec<(&str, usize)> = stats.by_type.iter().map(|(t, n)| (t.as_str(), *n)).collect();
+    assert_eq!(by_type, [("attachment", 1), ("book", 1), ("conferencePaper", 1), ("journalArticle", 2)]);
+    assert_eq!(stats.pdfs, 4);
+    assert_eq!(stats.with_annotations, 2);
+    assert_eq!(stats.subcollections, 2);
+}
+
+// ── library_stats ─────────────────────────────────────────────────────
+
+/// An empty page reporting `total` matches, as a `limit=1` count query sees it.
+fn count_response(total: u64) -> ResponseTemplate {
+    ResponseTemplate::new(200).insert_header("Total-Results", total.to_string()).set_body_string("[]")
+}
+
+#[tokio::test]
+async fn test_library_stats_aggregates_counts() {
+    let mock = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/users/test/items/top"))
+        .and(query_param_is_missing("itemType"))
+        .respond_with(count_response(7))
+        .expect(1)
+        .mount(&mock)
+        .await;
+    for (item_type, n) in [("journalArticle", 4), ("book", 2), ("attachment", 1)] {
+        Mock::given(method("GET"))
+            .and(path("/users/test/items/top"))
+            .and(query_param("itemType", item_type))
+            .and(query_param("limit", "1"))
+            .respond_with(count_response(n))
+            .mount(&mock)
+            .await;
+    }
+    Mock::given(method("GET"))
+        .and(path("/users/test/items/top"))
+        .respond_with(count_response(0))
+        .with_priority(10)
+        .mount(&mock)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/users/test/collections"))
+        .and(query_param("limit", "1"))
+        .respond_with(count_response(3))
+        .mount(&mock)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/users/test/tags"))
+        .and(query_param("limit", "1"))
+        .respond_with(count_response(12))
+        .mount(&mock)
+        .await;
+    let attachment = |key: &str, parent: Option<&str>, content_type: &str| {
+        let parent = parent.map(|p| format!(r#""parentItem": "{p}", "#)).unwrap_or_default();
+        collection_item_json(key, &format!(r#"{parent}"itemType": "attachment", "contentType": "{content_type}""#))
+    };
+    let attachments = [
+        attachment("PDF00001", Some("ART00001"), "application/pdf"),
+        attachment("PDF00002", Some("ART00001"), "application/pdf"),
+        attachment("PDF00003", Some("ART00002"), "application/pdf"),
+        attachment("EPUB0001", Some("BOOK0001"), "application/epub+zip"),
+        attachment("STAND001", None, "application/pdf"),
+    ];
+    Mock::given(method("GET"))
+        .and(path("/users/test/items"))
+        .and(query_param("itemType", "attachment"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("Total-Results", "5")
+                .set_body_string(format!("[{}]", attachments.join(","))),
+        )
+        .expect(1)
+        .mount(&mock)
+        .await;
+
+    let stats = library_stats(&make_client(&mock)).await.unwrap();
+    assert_eq!(stats.items, 7);
+    let by_type: Vec<(&str, u64)> = stats.by_type.iter().map(|(t, n)| (t.as_str(), *n)).collect();
+    assert_eq!(by_type, [("attachment", 1), ("book", 2), ("journalArticle", 4)]);
+    assert_eq!(stats.collections, 3);
+    assert_eq!(stats.tags, 12);
+    assert_eq!(stats.items_with_pdfs, 3);
+}
+
+// ── fulltext_status ───────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_fulltext_status_splits_indexed_and_missing() {
+    let mock = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/users/test/fulltext"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("Last-Modified-Version", "40")
+                .set_body_string(r#"{"PDF00001": 12, "EPUB0001": 3, "TRASHED1": 7}"#),
+        )
+        .expect(1)
+        .mount(&mock)
+        .await;
+    let attachments = [
+        attachment_json("PDF00001", "application/pdf", "cloth.pdf"),
+        attachment_json("PDF00002", "application/pdf", "fluids.pdf"),
+        collection_item_json(
+            "LINK0001",
+            r#""itemType": "attachment", "parentItem": "PARENT01", "linkMode": "linked_url", "url": "https://example.org""#,
+        ),
+        attachment_json("EPUB0001", "application/epub+zip", "book.epub"),
+    ];
+    Mock::given(method("GET"))
+        .and(path("/users/test/items"))
+        .and(query_param("itemType", "attachment"))
+        .respond_with(array_response(&format!("[{}]", attachments.join(","))))
+        .mount(&mock)
+        .await;
+
+    let status = fulltext_status(&make_client(&mock)).await.unwrap();
+    let keys = |entries: &[FulltextEntry]| -> Vec<(String, Option<u64>)> {
+        entries.iter().map(|e| (e.key.clone(), e.version)).collect()
+    };
+    assert_eq!(keys(&status.indexed), [("PDF00001".to_string(), Some(12)), ("EPUB0001".to_string(), Some(3))]);
+    assert_eq!(keys(&status.missing), [("PDF00002".to_string(), None)]);
+    assert_eq!(status.missing[0].parent_item.as_deref(), Some("PARENT01"));
+}
@@ -1,10 +1,146 @@
+use std::ops::RangeInclusive;
 use std::time::Duration;
 
+pub use papers_http::DEFAULT_USER_AGENT;
+use papers_http::{body_snippet, HttpClient};
+use tokio_util::sync::CancellationToken;
+
 use crate::error::{DatalabError, Result};
-use crate::types::{MarkerPollResponse, MarkerRequest, MarkerStatus, MarkerSubmitResponse, StepTypesResponse};
+use crate::types::{
+    MarkerPollResponse, MarkerRequest, MarkerStatus, MarkerSubmitResponse, ProcessingMode,
+    StepTypesResponse,
+};
 
 const DEFAULT_BASE_URL: &str = "https://www.datalab.to";
 
+/// Environment variable overriding how long
+/// [`DatalabClient::convert_document`] waits before its first poll, in
+/// milliseconds (100–60000; default 2000). Later waits back off from there.
+pub const POLL_INTERVAL_ENV: &str = "DATALAB_POLL_INTERVAL_MS";
+
+/// Environment variable giving up on a [`DatalabClient::convert_document`]
+/// job still processing after this many seconds (1–86400; default no limit).
+pub const POLL_TIMEOUT_ENV: &str = "DATALAB_POLL_TIMEOUT_S";
+
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+const DEFAULT_MAX_POLL_INTERVAL: Duration = Duration::from_secs(30);
+const DEFAULT_POLL_MULTIPLIER: f64 = 2.0;
+const POLL_INTERVAL_MS_RANGE: RangeInclusive<u64> = 100..=60_000;
+const POLL_TIMEOUT_S_RANGE: RangeInclusive<u64> = 1..=86_400;
+
+/// How [`DatalabClient::convert_document_with`] waits for a job.
+///
+/// The first poll comes `interval` after submitting; each poll that finds the
+/// job still processing multiplies the wait by `multiplier`, up to
+/// `max_interval`. The default goes 2s → 4s → 8s → 16s → 30s → 30s ...
+#[derive(Debug, Clone, PartialEq)]
+pub struct PollConfig {
+    /// Wait before the first poll.
+    pub interval: Duration,
+    /// Longest wait between polls. An `interval` above this is kept as is.
+    pub max_interval: Duration,
+    /// Growth of the wait after each poll; `1.0` (or less) polls at a fixed
+    /// `interval`.
+    pub multiplier: f64,
+    /// Give up with [`DatalabError::Timeout`] on a job still processing this
+    /// long after it was submitted. `None` waits indefinitely.
+    pub timeout: Option<Duration>,
+}
+
+impl Default for PollConfig {
+    fn default() -> Self {
+        Self {
+            interval: DEFAULT_POLL_INTERVAL,
+            max_interval: DEFAULT_MAX_POLL_INTERVAL,
+            multiplier: DEFAULT_POLL_MULTIPLIER,
+            timeout: None,
+        }
+    }
+}
+
+impl PollConfig {
+    /// The default, with `interval` from [`POLL_INTERVAL_ENV`] and `timeout`
+    /// from [`POLL_TIMEOUT_ENV`] when they hold in-range integers.
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+        if let Some(ms) = env_in_range(POLL_INTERVAL_ENV, POLL_INTERVAL_MS_RANGE) {
+            config.interval = Duration::from_millis(ms);
+        }
+        config.timeout = env_in_range(POLL_TIMEOUT_ENV, POLL_TIMEOUT_S_RANGE).map(Duration::from_secs);
+        config
+    }
+
+    /// The wait after one of `current`.
+    fn next_interval(&self, current: Duration) -> Duration {
+        let cap = self.max_interval.max(self.interval);
+        Duration::try_from_secs_f64(current.as_secs_f64() * self.multiplier.max(1.0)).map_or(cap, |next| next.min(cap))
+    }
+}
+
+/// Callback given each poll response by
+/// [`DatalabClient::convert_document_with`].
+pub type ProgressFn<'a> = Box<dyn FnMut(&MarkerPollResponse) + Send + 'a>;
+
+/// How [`DatalabClient::convert_document_with`] runs a conversion: its
+/// polling settings, plus an optional cancellation token and progress
+/// callback.
+///
+/// ```
+/// use papers_datalab::{CancellationToken, ConvertOptions, PollConfig};
+///
+/// let token = CancellationToken::new();
+/// let options = ConvertOptions { poll: PollConfig::from_env(), ..Default::default() }
+///     .with_cancel(&token)
+///     .with_progress(|poll| eprintln!("{:?}", poll.status));
+/// # drop(options);
+/// ```
+#[derive(Default)]
+pub struct ConvertOptions<'a> {
+    /// When to poll and when to give up.
+    pub poll: PollConfig,
+    /// When this fires, the upload or poll in flight is dropped and the
+    /// conversion returns [`DatalabError::Cancelled`] at once. DataLab has no
+    /// way to stop a submitted job, so it still runs (and is billed)
+    /// server-side.
+    pub cancel: Option<&'a CancellationToken>,
+    /// Sees every poll response, including the final one, as soon as it
+    /// arrives. It runs between requests, never during one, and must be
+    /// `Send` so the conversion future can still move across threads.
+    pub progress: Option<ProgressFn<'a>>,
+}
+
+impl<'a> ConvertOptions<'a> {
+    /// Cancel the conversion when `token` fires.
+    pub fn with_cancel(mut self, token: &'a CancellationToken) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    /// Call `progress` with every poll response.
+    pub fn with_progress(mut self, progress: impl FnMut(&MarkerPollResponse) + Send + 'a) -> Self {
+        self.progress = Some(Box::new(progress));
+        self
+    }
+}
+
+/// Credits charged per converted page, by processing mode.
+///
+/// | Mode       | Credits/page |
+/// |------------|--------------|
+/// | `fast`     | 1            |
+/// | `balanced` | 2            |
+/// | `accurate` | 4            |
+///
+/// These are planning figures for [`DatalabClient::estimate_cost`], not a
+/// billing quote; DataLab's account dashboard is authoritative.
+pub fn credits_per_page(mode: &ProcessingMode) -> u64 {
+    match mode {
+        ProcessingMode::Fast => 1,
+        ProcessingMode::Balanced => 2,
+        ProcessingMode::Accurate => 4,
+    }
+}
+
 /// Async client for the DataLab Marker REST API.
 ///
 /// # Authentication
@@ -36,7 +172,7 @@ const DEFAULT_BASE_URL: &str = "https://www.datalab.to";
 /// ```
 #[derive(Clone)]
 pub struct DatalabClient {
-    http: reqwest::Client,
+    http: HttpClient,
     api_key: String,
     base_url: String,
 }
@@ -45,7 +181,7 @@ impl DatalabClient {
     /// Create a new client with an explicit API key.
     pub fn new(api_key: impl Into<String>) -> Self {
         Self {
-            http: reqwest::Client::new(),
+            http: HttpClient::default(),
             api_key: api_key.into(),
             base_url: DEFAULT_BASE_URL.to_string(),
         }
@@ -57,6 +193,22 @@ impl DatalabClient {
         self
     }
 
+    /// Use a caller-supplied HTTP client instead of the process-wide
+    /// [`shared_client`](papers_http::shared_client), e.g. one with a proxy or
+    /// custom timeouts. That client's own default headers decide the
+    /// `User-Agent` unless [`with_user_agent`](Self::with_user_agent) is also
+    /// set, in either order.
+    pub fn with_http_client(mut self, http: reqwest::Client) -> Self {
+        self.http.set_client(http);
+        self
+    }
+
+    /// Override the `User-Agent` header (default [`DEFAULT_USER_AGENT`]).
+    pub fn with_user_agent(mut self, user_agent: &str) -> Self {
+        self.http.set_user_agent(user_agent);
+        self
+    }
+
     /// Create a client from the `DATALAB_API_KEY` environment variable.
     ///
     /// Returns [`DatalabError::MissingApiKey`] if the variable is not set.
@@ -67,28 +219,85 @@ impl DatalabClient {
 
     /// High-level: submit a document and poll until conversion is complete.
     ///
-    /// Uses a 2-second poll interval. Returns the completed [`MarkerPollResponse`]
-    /// or an error if the job fails. No timeout is applied — the caller is
-    /// responsible for cancellation if needed.
+    /// Polls with [`PollConfig::from_env`]: first after 2 seconds (or
+    /// [`POLL_INTERVAL_ENV`] milliseconds), then backing off to every 30
+    /// seconds. Returns the completed [`MarkerPollResponse`] or an error if the
+    /// job fails. No timeout is applied unless [`POLL_TIMEOUT_ENV`] is set, in
+    /// which case a job still processing after that long ends in
+    /// [`DatalabError::Timeout`]. Unparseable or out-of-range values for
+    /// either variable are ignored.
     pub async fn convert_document(&self, req: MarkerRequest) -> Result<MarkerPollResponse> {
+        self.convert_document_with(req, ConvertOptions { poll: PollConfig::from_env(), ..Default::default() })
+            .await
+    }
+
+    /// [`convert_document`](Self::convert_document) with explicit
+    /// [`ConvertOptions`]; the environment is not consulted.
+    pub async fn convert_document_with(
+        &self,
+        req: MarkerRequest,
+        options: ConvertOptions<'_>,
+    ) -> Result<MarkerPollResponse> {
+        let ConvertOptions { poll: config, cancel, progress } = options;
+        let job = self.submit_and_poll(req, &config, progress);
+        match cancel {
+            Some(token) => token.run_until_cancelled(job).await.unwrap_or(Err(DatalabError::Cancelled)),
+            None => job.await,
+        }
+    }
+
+    async fn submit_and_poll(
+        &self,
+        req: MarkerRequest,
+        config: &PollConfig,
+        mut progress: Option<ProgressFn<'_>>,
+    ) -> Result<MarkerPollResponse> {
         let submit = self.submit_marker(req).await?;
         let request_id = submit.request_id;
+        let submitted = tokio::time::Instant::now();
+        let mut interval = config.interval;
 
         loop {
-            tokio::time::sleep(Duration::from_secs(2)).await;
+            // Never sleep past the deadline, so a timeout is reported on time.
+            let wait = match config.timeout {
+                Some(timeout) => interval.min(timeout.saturating_sub(submitted.elapsed())),
+                None => interval,
+            };
+            tokio::time::sleep(wait).await;
+            interval = config.next_interval(interval);
             let poll = self.get_marker_result(&request_id).await?;
+            if let Some(progress) = progress.as_mut() {
+                progress(&poll);
+            }
             match poll.status {
                 MarkerStatus::Complete => return Ok(poll),
-                MarkerStatus::Failed => {
-                    return Err(DatalabError::Processing(
-                        poll.error.unwrap_or_else(|| "unknown processing error".to_string()),
-                    ));
+                MarkerStatus::Failed => return Err(DatalabError::Processing(poll.failure_message())),
+                MarkerStatus::Processing | MarkerStatus::Unknown => {
+                    if let Some(timeout) = config.timeout
+                        && submitted.elapsed() >= timeout
+                    {
+                        return Err(DatalabError::Timeout { request_id, timeout });
+                    }
                 }
-                MarkerStatus::Processing => continue,
             }
         }
     }
 
+    /// Estimate the credits `req` would spend on a document of `page_count` pages.
+    ///
+    /// Only pages selected by `page_range` and capped by `max_pages` are
+    /// counted, priced with [`credits_per_page`]. Nothing is sent to the API.
+    pub fn estimate_cost(req: &MarkerRequest, page_count: u32) -> u64 {
+        let mut pages = match req.page_range.as_deref() {
+            Some(range) => pages_in_range(range, page_count),
+            None => page_count,
+        };
+        if let Some(max) = req.max_pages {
+            pages = pages.min(max);
+        }
+        u64::from(pages) * credits_per_page(&req.mode)
+    }
+
     /// POST /api/v1/marker — submit a conversion job.
     ///
     /// Returns immediately with a `request_id`. Use [`get_marker_result`](Self::get_marker_result)
@@ -104,9 +313,10 @@ impl DatalabClient {
         // File source
         if let Some(bytes) = req.file {
             let filename = req.filename.unwrap_or_else(|| "document.pdf".to_string());
+            let mime = req.content_type.unwrap_or_else(|| mime_for_filename(&filename).to_string());
             let part = reqwest::multipart::Part::bytes(bytes)
                 .file_name(filename)
-                .mime_str("application/pdf")
+                .mime_str(&mime)
                 .map_err(|e| DatalabError::Http(e))?;
             form = form.part("file", part);
         } else if let Some(url) = req.file_url {
@@ -199,8 +409,7 @@ impl DatalabClient {
         }
 
         let body = resp.text().await?;
-        let submit = serde_json::from_str::<MarkerSubmitResponse>(&body)
-            .map_err(|e| DatalabError::Api { status: 0, message: format!("JSON parse error: {e}") })?;
+        let submit: MarkerSubmitResponse = self.parse_json("/api/v1/marker", &body)?;
         if submit.success == Some(false) {
             return Err(DatalabError::Api {
                 status: 0,
@@ -213,7 +422,8 @@ impl DatalabClient {
     /// GET /api/v1/marker/{request_id} — poll for a single conversion result.
     ///
     /// Returns the current state of the job. `status` will be `processing`,
-    /// `complete`, or `failed`. Poll every 2 seconds until `complete` or `failed`.
+    /// `complete`, or `failed`. Poll until `complete` or `failed`, backing off
+    /// as [`PollConfig`] describes.
     pub async fn get_marker_result(&self, request_id: &str) -> Result<MarkerPollResponse> {
         let url = format!("{}/api/v1/marker/{}", self.base_url, request_id);
         let resp = self
@@ -233,12 +443,11 @@ impl DatalabClient {
         }
 
         let body = resp.text().await?;
-        serde_json::from_str::<MarkerPollResponse>(&body)
-            .map_err(|e| DatalabError::Api { status: 0, message: format!("JSON parse error: {e}") })
+        self.parse_json(&format!("/api/v1/marker/{request_id}"), &body)
     }
 
     /// GET /api/v1/workflows/step-types — list available workflow step types.
-    pub async fn list_step_types(&self) -> Result<StepTypesResponse> {
+    pub async fn get_step_types(&self) -> Result<StepTypesResponse> {
         let url = format!("{}/api/v1/workflows/step-types", self.base_url);
         let resp = self
             .http
@@ -256,6 +465,364 @@ impl DatalabClient {
             });
         }
 
-        Ok(resp.json::<StepTypesResponse>().await?)
+        let body = resp.text().await?;
+        self.parse_json("/api/v1/workflows/step-types", &body)
+    }
+
+    /// GET /api/v1/workflows/step-types — renamed to
+    /// [`get_step_types`](Self::get_step_types).
+    #[deprecated(note = "renamed to `get_step_types`")]
+    pub async fn list_step_types(&self) -> Result<StepTypesResponse> {
+        self.get_step_types().await
+    }
+
+    /// Parse a response body, naming the endpoint and quoting the start of the
+    /// body (key masked) if it doesn't match `T`.
+    fn parse_json<T: serde::de::DeserializeOwned>(&self, path: &str, body: &str) -> Result<T> {
+        serde_json::from_str(body).map_err(|source| DatalabError::Json {
+            path: path.to_string(),
+            snippet: body_snippet(body, &self.api_key),
+            source,
+        })
+    }
+}
+
+/// Environment variable `name` as an integer within `range`, or `None` if it
+/// is unset, malformed, or out of range.
+fn env_in_range(name: &str, range: RangeInclusive<u64>) -> Option<u64> {
+    std::env::var(name).ok()?.trim().parse().ok().filter(|v| range.contains(v))
+}
+
+/// MIME type for an uploaded document, from its file extension (PDF by default).
+fn mime_for_filename(filename: &str) -> &'static str {
+    let ext = filename.rsplit_once('.').map(|(_, ext)| ext.to_ascii_lowercase());
+    match ext.as_deref() {
+        Some("epub") => "application/epub+zip",
+        Some("html" | "htm") => "text/html",
+        _ => "application/pdf",
+    }
+}
+
+/// Number of distinct pages below `page_count` selected by a 0-indexed
+/// `page_range` such as `"0-5,7"`. Unparseable parts are ignored.
+fn pages_in_range(range: &str, page_count: u32) -> u32 {
+    let mut selected = std::collections::BTreeSet::new();
+    for part in range.split(',').map(str::trim) {
+        let bounds = match part.split_once('-') {
+            Some((a, b)) => a.trim().parse::<u32>().ok().zip(b.trim().parse::<u32>().ok()),
+            None => part.parse::<u32>().ok().map(|p| (p, p)),
+        };
+        if let Some((start, end)) = bounds {
+            selected.extend((start..=end.min(page_count.saturating_sub(1))).filter(|&p| p < page_count));
+        }
+    }
+    selected.len() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+    fn request(mode: ProcessingMode) -> MarkerRequest {
+        MarkerRequest { mode, ..Default::default() }
+    }
+
+    #[test]
+    fn test_unknown_marker_status_parses() {
+        let poll: MarkerPollResponse =
+            serde_json::from_str(r#"{"status": "queued", "success": null}"#).unwrap();
+        assert_eq!(poll.status, MarkerStatus::Unknown);
+    }
+
+    #[test]
+    fn test_processing_poll_with_progress_parses() {
+        let poll: MarkerPollResponse = serde_json::from_str(
+            r#"{"status": "processing", "success": null, "page_count": 12, "progress": 0.25}"#,
+        )
+        .unwrap();
+        assert_eq!(poll.status, MarkerStatus::Processing);
+        assert_eq!(poll.page_count, Some(12));
+        assert_eq!(poll.progress, Some(0.25));
+
+        let odd: MarkerPollResponse = serde_json::from_str(r#"{"status": "processing", "progress": "0.5"}"#).unwrap();
+        assert_eq!(odd.progress, Some(0.5));
+        let odd: MarkerPollResponse = serde_json::from_str(r#"{"status": "processing", "progress": {"step": 2}}"#).unwrap();
+        assert_eq!(odd.progress, None);
+
+        let failed: MarkerPollResponse =
+            serde_json::from_str(r#"{"status": "failed", "error": "Could not open PDF", "error_in": "ocr"}"#).unwrap();
+        assert_eq!(failed.failure_message(), "Could not open PDF (in ocr)");
+    }
+
+    #[test]
+    fn test_estimate_cost_fast_vs_accurate() {
+        assert_eq!(DatalabClient::estimate_cost(&request(ProcessingMode::Fast), 10), 10);
+        assert_eq!(DatalabClient::estimate_cost(&request(ProcessingMode::Balanced), 10), 20);
+        assert_eq!(DatalabClient::estimate_cost(&request(ProcessingMode::Accurate), 10), 40);
+        assert_eq!(DatalabClient::estimate_cost(&request(ProcessingMode::Accurate), 0), 0);
+    }
+
+    #[test]
+    fn test_estimate_cost_respects_max_pages_and_range() {
+        let req = MarkerRequest { max_pages: Some(3), ..request(ProcessingMode::Fast) };
+        assert_eq!(DatalabClient::estimate_cost(&req, 10), 3);
+        let req = MarkerRequest { page_range: Some("0-4, 2, 8-20".into()), ..request(ProcessingMode::Accurate) };
+        // pages 0..=4 and 8..=9 of a 10-page document
+        assert_eq!(DatalabClient::estimate_cost(&req, 10), 7 * 4);
+    }
+
+    // ── Polling ───────────────────────────────────────────────────────
+
+    /// Point the poll environment variables at `interval_ms` and `timeout_s`
+    /// (unset when `None`). Callers are `#[serial]`.
+    fn set_poll_env(interval_ms: Option<&str>, timeout_s: Option<&str>) {
+        for (name, value) in [(POLL_INTERVAL_ENV, interval_ms), (POLL_TIMEOUT_ENV, timeout_s)] {
+            // Safety: every test touching these variables is #[serial]
+            match value {
+                Some(value) => unsafe { std::env::set_var(name, value) },
+                None => unsafe { std::env::remove_var(name) },
+            }
+        }
+    }
+
+    async fn mock_submit(server: &MockServer) {
+        Mock::given(method("POST"))
+            .and(path("/api/v1/marker"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{"success": true, "request_id": "r1", "request_check_url": "/api/v1/marker/r1"}"#,
+            ))
+            .mount(server)
+            .await;
+    }
+
+    fn url_request() -> MarkerRequest {
+        MarkerRequest { file_url: Some("https://example.org/paper.pdf".into()), ..Default::default() }
+    }
+
+    #[test]
+    #[serial]
+    fn test_env_in_range_rejects_bad_values() {
+        for (value, expected) in [("250", Some(250)), (" 100 ", Some(100)), ("5", None), ("60001", None), ("fast", None)] {
+            set_poll_env(Some(value), None);
+            assert_eq!(env_in_range(POLL_INTERVAL_ENV, POLL_INTERVAL_MS_RANGE), expected, "{value:?}");
+        }
+        set_poll_env(None, None);
+        assert_eq!(env_in_range(POLL_INTERVAL_ENV, POLL_INTERVAL_MS_RANGE), None);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_convert_document_uses_poll_interval_env() {
+        let server = MockServer::start().await;
+        mock_submit(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/marker/r1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"status": "processing"}"#))
+            .up_to_n_times(2)
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/marker/r1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(r##"{"status": "complete", "markdown": "# Done"}"##))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        set_poll_env(Some("100"), None);
+        let client = DatalabClient::new("key").with_base_url(server.uri());
+        let started = std::time::Instant::now();
+        let result = client.convert_document(url_request()).await;
+        set_poll_env(None, None);
+
+        assert_eq!(result.unwrap().markdown.as_deref(), Some("# Done"));
+        // Three polls at the 2s default would take at least 6s
+        assert!(started.elapsed() < Duration::from_secs(2), "took {:?}", started.elapsed());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_convert_document_times_out_from_env() {
+        let server = MockServer::start().await;
+        mock_submit(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/marker/r1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"status": "processing"}"#))
+            .mount(&server)
+            .await;
+
+        set_poll_env(Some("100"), Some("1"));
+        let client = DatalabClient::new("key").with_base_url(server.uri());
+        let started = std::time::Instant::now();
+        let result = client.convert_document(url_request()).await;
+        set_poll_env(None, None);
+
+        match result {
+            Err(DatalabError::Timeout { request_id, timeout }) => {
+                assert_eq!(request_id, "r1");
+                assert_eq!(timeout, Duration::from_secs(1));
+            }
+            Err(e) => panic!("expected Timeout, got {e}"),
+            Ok(_) => panic!("expected Timeout, got a result"),
+        }
+        let elapsed = started.elapsed();
+        assert!(elapsed >= Duration::from_secs(1) && elapsed < Duration::from_secs(3), "took {elapsed:?}");
+    }
+
+    #[test]
+    fn test_poll_interval_backs_off_to_cap() {
+        let config = PollConfig::default();
+        let waits: Vec<u64> = std::iter::successors(Some(config.interval), |&d| Some(config.next_interval(d)))
+            .take(7)
+            .map(|d| d.as_secs())
+            .collect();
+        assert_eq!(waits, [2, 4, 8, 16, 30, 30, 30]);
+
+        let fixed = PollConfig { multiplier: 1.0, ..PollConfig::default() };
+        assert_eq!(fixed.next_interval(fixed.interval), fixed.interval);
+        // An interval above the cap is kept rather than shortened
+        let slow = PollConfig { interval: Duration::from_secs(45), ..PollConfig::default() };
+        assert_eq!(slow.next_interval(slow.interval), Duration::from_secs(45));
+    }
+
+    /// Reports the job processing for the first `processing` polls, then complete.
+    struct CompleteAfter {
+        processing: usize,
+        polls: std::sync::atomic::AtomicUsize,
+    }
+
+    impl Respond for CompleteAfter {
+        fn respond(&self, _: &Request) -> ResponseTemplate {
+            if self.polls.fetch_add(1, std::sync::atomic::Ordering::SeqCst) < self.processing {
+                ResponseTemplate::new(200).set_body_string(r#"{"status": "processing"}"#)
+            } else {
+                ResponseTemplate::new(200).set_body_string(r##"{"status": "complete", "markdown": "# Done"}"##)
+            }
+        }
+    }
+
+    #[tokio::test]
+    #[allow(deprecated)]
+    async fn test_list_step_types_forwards_to_get_step_types() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/workflows/step-types"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"step_types": []}"#))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let client = DatalabClient::new("key").with_base_url(server.uri());
+        assert!(client.list_step_types().await.unwrap().step_types.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_malformed_json_error_quotes_truncated_snippet() {
+        let server = MockServer::start().await;
+        let body = format!(r#"{{"note": "test-key-123", "pad": "{}"}}"#, "x".repeat(500));
+        Mock::given(method("GET"))
+            .and(path("/api/v1/workflows/step-types"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .mount(&server)
+            .await;
+        let client = DatalabClient::new("test-key-123").with_base_url(server.uri());
+        match client.get_step_types().await.unwrap_err() {
+            DatalabError::Json { path, snippet, .. } => {
+                assert_eq!(path, "/api/v1/workflows/step-types");
+                assert!(snippet.ends_with('…'), "snippet not marked as truncated: {snippet}");
+                assert!(!snippet.contains("test-key-123"), "key leaked into: {snippet}");
+            }
+            e => panic!("Expected Json error, got {e}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_convert_document_with_backoff_waits_longer_each_poll() {
+        let server = MockServer::start().await;
+        mock_submit(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/marker/r1"))
+            .respond_with(CompleteAfter { processing: 4, polls: Default::default() })
+            .expect(5)
+            .mount(&server)
+            .await;
+
+        let config = PollConfig {
+            interval: Duration::from_millis(20),
+            max_interval: Duration::from_millis(80),
+            ..PollConfig::default()
+        };
+        let client = DatalabClient::new("key").with_base_url(server.uri());
+        let started = std::time::Instant::now();
+        let options = ConvertOptions { poll: config, ..Default::default() };
+        let result = client.convert_document_with(url_request(), options).await.unwrap();
+        assert_eq!(result.markdown.as_deref(), Some("# Done"));
+
+        // Five polls after waits of 20, 40, 80, 80, 80ms; a fixed interval
+        // would be done after 100ms.
+        let elapsed = started.elapsed();
+        assert!(elapsed >= Duration::from_millis(300), "finished after {elapsed:?}");
+    }
+
+    #[tokio::test]
+    async fn test_convert_document_cancelled_mid_poll() {
+        let server = MockServer::start().await;
+        mock_submit(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/marker/r1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"status": "processing"}"#))
+            .mount(&server)
+            .await;
+
+        let token = CancellationToken::new();
+        let canceller = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            canceller.cancel();
+        });
+        // The first poll comes at 100ms; cancelling at 300ms lands in the 200ms wait after it
+        let config = PollConfig { interval: Duration::from_millis(100), ..PollConfig::default() };
+        let client = DatalabClient::new("key").with_base_url(server.uri());
+        let started = std::time::Instant::now();
+        let options = ConvertOptions { poll: config, ..Default::default() }.with_cancel(&token);
+        let result = client.convert_document_with(url_request(), options).await;
+
+        assert!(matches!(result, Err(DatalabError::Cancelled)), "expected Cancelled");
+        let elapsed = started.elapsed();
+        assert!(elapsed < Duration::from_secs(2), "took {elapsed:?}");
+        let polls = server.received_requests().await.unwrap().iter().filter(|r| r.method == wiremock::http::Method::GET).count();
+        assert!(polls >= 1, "cancelled before polling");
+    }
+
+    #[tokio::test]
+    async fn test_convert_document_reports_each_poll() {
+        let server = MockServer::start().await;
+        mock_submit(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/marker/r1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"status": "processing"}"#))
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/marker/r1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(r##"{"status": "complete", "markdown": "# Done"}"##))
+            .mount(&server)
+            .await;
+
+        let mut seen = Vec::new();
+        let config = PollConfig { interval: Duration::from_millis(20), multiplier: 1.0, ..PollConfig::default() };
+        let client = DatalabClient::new("key").with_base_url(server.uri());
+        let options = ConvertOptions { poll: config, ..Default::default() }
+            .with_progress(|poll: &MarkerPollResponse| seen.push(poll.status.clone()));
+        let job = client.convert_document_with(url_request(), options);
+        fn assert_send<T: Send>(t: T) -> T {
+            t
+        }
+        assert_send(job).await.unwrap();
+
+        assert_eq!(seen, [MarkerStatus::Processing, MarkerStatus::Processing, MarkerStatus::Complete]);
     }
 }
@@ -7,9 +7,32 @@ pub enum DatalabError {
     #[error("API error ({status}): {message}")]
     Api { status: u16, message: String },
 
+    /// A response body could not be deserialized. `snippet` holds its first
+    /// 200 characters with the API key masked. `path` and `snippet` are empty
+    /// when converted from a bare [`serde_json::Error`] with `?`.
+    #[error("JSON error from {path}: {source} (body: {snippet:?})")]
+    Json {
+        path: String,
+        snippet: String,
+        #[source]
+        source: serde_json::Error,
+    },
+
     #[error("Processing failed: {0}")]
     Processing(String),
 
+    /// [`convert_document`](crate::DatalabClient::convert_document) gave up
+    /// on a job still processing after [`PollConfig::timeout`](crate::PollConfig::timeout)
+    /// (from [`POLL_TIMEOUT_ENV`](crate::POLL_TIMEOUT_ENV) by default).
+    #[error("Conversion {request_id} still processing after {}s", timeout.as_secs())]
+    Timeout { request_id: String, timeout: std::time::Duration },
+
+    /// The [`CancellationToken`](tokio_util::sync::CancellationToken) in
+    /// [`ConvertOptions::cancel`](crate::ConvertOptions::cancel) fired before
+    /// the conversion finished.
+    #[error("Conversion cancelled")]
+    Cancelled,
+
     #[error("DATALAB_API_KEY environment variable not set")]
     MissingApiKey,
 
@@ -17,5 +40,11 @@ pub enum DatalabError {
     InvalidRequest,
 }
 
+impl From<serde_json::Error> for DatalabError {
+    fn from(source: serde_json::Error) -> Self {
+        Self::Json { path: String::new(), snippet: String::new(), source }
+    }
+}
+
 /// A [`Result`](std::result::Result) alias with [`DatalabError`] as the error type.
 pub type Result<T> = std::result::Result<T, DatalabError>;
@@ -37,7 +37,8 @@ pub mod client;
 pub mod error;
 pub mod types;
 
-pub use client::DatalabClient;
+pub use client::{credits_per_page, ConvertOptions, DatalabClient, PollConfig, ProgressFn, DEFAULT_USER_AGENT, POLL_INTERVAL_ENV, POLL_TIMEOUT_ENV};
+pub use tokio_util::sync::CancellationToken;
 pub use error::{DatalabError, Result};
 pub use types::{
     MarkerPollResponse, MarkerRequest, MarkerStatus, MarkerSubmitResponse, OutputFormat,
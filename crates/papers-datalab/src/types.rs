@@ -21,12 +21,16 @@ pub enum ProcessingMode {
     Accurate,
 }
 
-#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum MarkerStatus {
     Processing,
     Complete,
     Failed,
+    /// A status this client doesn't know yet (e.g. a new queueing state).
+    /// Treated as still in progress while polling.
+    #[serde(other)]
+    Unknown,
 }
 
 // -- Request --
@@ -39,6 +43,9 @@ pub struct MarkerRequest {
     pub file: Option<Vec<u8>>,
     /// Filename for the uploaded file (e.g. `"paper.pdf"`). Used when `file` is set.
     pub filename: Option<String>,
+    /// MIME type sent with `file` (e.g. `"application/pdf"`). Overrides the
+    /// type guessed from `filename`, for misnamed or extensionless files.
+    pub content_type: Option<String>,
     /// Public URL to the file. Alternative to `file`.
     pub file_url: Option<String>,
     /// Output format(s). Defaults to `[Markdown]`.
@@ -84,6 +91,7 @@ impl Default for MarkerRequest {
         Self {
             file: None,
             filename: None,
+            content_type: None,
             file_url: None,
             output_format: vec![OutputFormat::Markdown],
             mode: ProcessingMode::default(),
@@ -110,7 +118,7 @@ impl Default for MarkerRequest {
 // -- Submit response --
 
 /// Response from POST /api/v1/marker (submit).
-#[derive(serde::Deserialize)]
+#[derive(serde::Serialize, serde::Deserialize)]
 pub struct MarkerSubmitResponse {
     #[serde(default)]
     pub success: Option<bool>,
@@ -121,7 +129,7 @@ pub struct MarkerSubmitResponse {
 // -- Poll response --
 
 /// Response from GET /api/v1/marker/{request_id} (poll).
-#[derive(serde::Deserialize)]
+#[derive(serde::Serialize, serde::Deserialize)]
 pub struct MarkerPollResponse {
     #[serde(default)]
     pub success: Option<bool>,
@@ -135,9 +143,17 @@ pub struct MarkerPollResponse {
     pub segmentation_results: Option<serde_json::Value>,
     pub images: Option<HashMap<String, String>>,
     pub metadata: Option<serde_json::Value>,
+    /// Why a `failed` job failed.
     pub error: Option<String>,
+    /// The pipeline stage that failed, when DataLab reports it.
     pub error_in: Option<String>,
+    /// Pages in the document. Reported once known, often while still
+    /// `processing`.
     pub page_count: Option<u32>,
+    /// Fraction of the job done (0.0–1.0) while `processing`, when reported.
+    /// Numeric strings are accepted; anything unparseable is `None`.
+    #[serde(default, deserialize_with = "lenient_f64")]
+    pub progress: Option<f64>,
     pub checkpoint_id: Option<String>,
     pub versions: Option<serde_json::Value>,
     pub parse_quality_score: Option<f64>,
@@ -145,10 +161,31 @@ pub struct MarkerPollResponse {
     pub cost_breakdown: Option<serde_json::Value>,
 }
 
+impl MarkerPollResponse {
+    /// `error`, qualified by `error_in` when present, for reporting a failed job.
+    pub fn failure_message(&self) -> String {
+        let error = self.error.as_deref().unwrap_or("unknown processing error");
+        match self.error_in.as_deref() {
+            Some(stage) if !stage.is_empty() => format!("{error} (in {stage})"),
+            _ => error.to_string(),
+        }
+    }
+}
+
+/// A number, a numeric string, or anything else as `None`, so an unexpected
+/// progress value never fails the whole poll.
+fn lenient_f64<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Option<f64>, D::Error> {
+    Ok(match <serde_json::Value as serde::Deserialize>::deserialize(deserializer)? {
+        serde_json::Value::Number(n) => n.as_f64(),
+        serde_json::Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    })
+}
+
 // -- Step types response --
 
 /// A single workflow step type.
-#[derive(serde::Deserialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct StepType {
     pub id: u32,
     #[serde(rename = "type")]
@@ -162,7 +199,7 @@ pub struct StepType {
 }
 
 /// Response from GET /api/v1/workflows/step-types.
-#[derive(serde::Deserialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct StepTypesResponse {
     pub step_types: Vec<StepType>,
 }
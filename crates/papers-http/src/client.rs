@@ -0,0 +1,124 @@
+//! The HTTP client shared by the API clients.
+
+use std::sync::OnceLock;
+
+use reqwest::header::USER_AGENT;
+use reqwest::{IntoUrl, Method, RequestBuilder};
+
+/// `User-Agent` sent unless overridden with [`HttpClient::set_user_agent`].
+pub const DEFAULT_USER_AGENT: &str =
+    concat!("papers-mcp/", env!("CARGO_PKG_VERSION"), " (+https://github.com/mmgeorge/papers)");
+
+/// The process-wide [`reqwest::Client`], identifying as [`DEFAULT_USER_AGENT`].
+///
+/// Built on first use; later calls return a clone sharing its connection pool
+/// and TLS setup. Every API client starts from this one, so a CLI command or
+/// MCP server talking to several services opens one pool, not one per client.
+pub fn shared_client() -> reqwest::Client {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    CLIENT
+        .get_or_init(|| {
+            reqwest::Client::builder()
+                .user_agent(DEFAULT_USER_AGENT)
+                .build()
+                .expect("failed to build HTTP client")
+        })
+        .clone()
+}
+
+/// A [`reqwest::Client`] plus an optional `User-Agent` override.
+///
+/// The override is set on each request rather than baked into the client, so
+/// swapping the client ([`set_client`](Self::set_client)) and overriding the
+/// agent ([`set_user_agent`](Self::set_user_agent)) compose in either order.
+///
+/// ```
+/// let mut http = papers_http::HttpClient::default();
+/// http.set_user_agent("my-tool/1.0");
+/// http.set_client(reqwest::Client::new());
+/// let req = http.get("https://example.com").build().unwrap();
+/// assert_eq!(req.headers()["user-agent"], "my-tool/1.0");
+/// ```
+#[derive(Clone, Debug)]
+pub struct HttpClient {
+    client: reqwest::Client,
+    user_agent: Option<String>,
+}
+
+impl Default for HttpClient {
+    /// The [`shared_client`] with no override.
+    fn default() -> Self {
+        Self { client: shared_client(), user_agent: None }
+    }
+}
+
+impl HttpClient {
+    /// Send requests through `client`. Without a
+    /// [`set_user_agent`](Self::set_user_agent) override, `client`'s own
+    /// default headers decide the `User-Agent`.
+    pub fn set_client(&mut self, client: reqwest::Client) {
+        self.client = client;
+    }
+
+    /// Send `user_agent` as the `User-Agent` of every request.
+    pub fn set_user_agent(&mut self, user_agent: impl Into<String>) {
+        self.user_agent = Some(user_agent.into());
+    }
+
+    /// Start a `method` request to `url`.
+    pub fn request(&self, method: Method, url: impl IntoUrl) -> RequestBuilder {
+        let request = self.client.request(method, url);
+        match &self.user_agent {
+            Some(user_agent) => request.header(USER_AGENT, user_agent),
+            None => request,
+        }
+    }
+
+    /// Start a `GET` request to `url`.
+    pub fn get(&self, url: impl IntoUrl) -> RequestBuilder {
+        self.request(Method::GET, url)
+    }
+
+    /// Start a `POST` request to `url`.
+    pub fn post(&self, url: impl IntoUrl) -> RequestBuilder {
+        self.request(Method::POST, url)
+    }
+
+    /// Start a `PUT` request to `url`.
+    pub fn put(&self, url: impl IntoUrl) -> RequestBuilder {
+        self.request(Method::PUT, url)
+    }
+
+    /// Start a `PATCH` request to `url`.
+    pub fn patch(&self, url: impl IntoUrl) -> RequestBuilder {
+        self.request(Method::PATCH, url)
+    }
+
+    /// Start a `DELETE` request to `url`.
+    pub fn delete(&self, url: impl IntoUrl) -> RequestBuilder {
+        self.request(Method::DELETE, url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user_agent(http: &HttpClient) -> String {
+        let req = http.get("http://localhost/").build().unwrap();
+        req.headers()[USER_AGENT].to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_override_survives_client_swap() {
+        let mut http = HttpClient::default();
+        http.set_user_agent("custom/1.0");
+        http.set_client(reqwest::Client::new());
+        assert_eq!(user_agent(&http), "custom/1.0");
+
+        let mut http = HttpClient::default();
+        http.set_client(reqwest::Client::new());
+        http.set_user_agent("custom/1.0");
+        assert_eq!(user_agent(&http), "custom/1.0");
+    }
+}
@@ -0,0 +1,81 @@
+//! HTTP helpers shared by the papers API clients (`papers-openalex`,
+//! `papers-zotero` and `papers-datalab`).
+//!
+//! Everything here is small so each client behaves the same way: requests go
+//! through an [`HttpClient`] (by default the process-wide [`shared_client`],
+//! identifying as [`DEFAULT_USER_AGENT`]), API keys are masked with [`redact`]
+//! before they reach an error or a log line, and unparseable bodies are quoted
+//! with [`body_snippet`].
+//!
+//! Enable the `testing` feature (as a dev-dependency) for
+//! [`testing::CapturedLogs`], which records `tracing` output so tests can
+//! assert on it.
+
+mod client;
+#[cfg(feature = "testing")]
+pub mod testing;
+
+pub use client::{shared_client, HttpClient, DEFAULT_USER_AGENT};
+
+/// Replace every occurrence of `secret` in `text` with `[REDACTED]`.
+///
+/// An empty `secret` leaves `text` unchanged.
+///
+/// ```
+/// assert_eq!(papers_http::redact("key=abc", "abc"), "key=[REDACTED]");
+/// assert_eq!(papers_http::redact("key=abc", ""), "key=abc");
+/// ```
+pub fn redact(text: &str, secret: &str) -> String {
+    if secret.is_empty() {
+        text.to_string()
+    } else {
+        text.replace(secret, "[REDACTED]")
+    }
+}
+
+/// Longest body prefix quoted by [`body_snippet`].
+pub const BODY_SNIPPET_CHARS: usize = 200;
+
+/// The first [`BODY_SNIPPET_CHARS`] characters of `body` with `secret`
+/// [redacted](redact), and `…` appended when truncated.
+///
+/// The secret is masked before truncating, so a key straddling the cut is
+/// never partially quoted.
+///
+/// ```
+/// assert_eq!(papers_http::body_snippet("{\"key\":\"abc\"}", "abc"), "{\"key\":\"[REDACTED]\"}");
+/// assert!(papers_http::body_snippet(&"x".repeat(300), "").ends_with('…'));
+/// ```
+pub fn body_snippet(body: &str, secret: &str) -> String {
+    let body = redact(body, secret);
+    match body.char_indices().nth(BODY_SNIPPET_CHARS) {
+        Some((i, _)) => format!("{}…", &body[..i]),
+        None => body,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact() {
+        assert_eq!(redact("a?api_key=abc&b=abc", "abc"), "a?api_key=[REDACTED]&b=[REDACTED]");
+        assert_eq!(redact("unchanged", ""), "unchanged");
+    }
+
+    #[test]
+    fn test_body_snippet_truncates_on_char_boundary() {
+        let body = "é".repeat(BODY_SNIPPET_CHARS + 1);
+        let snippet = body_snippet(&body, "");
+        assert_eq!(snippet.chars().count(), BODY_SNIPPET_CHARS + 1);
+        assert!(snippet.ends_with('…'));
+        assert_eq!(body_snippet("short", ""), "short");
+    }
+
+    #[test]
+    fn test_body_snippet_redacts_before_truncating() {
+        let body = format!("{}secretkey", "x".repeat(BODY_SNIPPET_CHARS - 4));
+        assert!(!body_snippet(&body, "secretkey").contains("secr"));
+    }
+}
@@ -0,0 +1,44 @@
+//! Test helpers for asserting on `tracing` output.
+
+use std::sync::{Arc, Mutex};
+
+/// Collects formatted `tracing` output so tests can inspect it.
+///
+/// ```
+/// use papers_http::testing::CapturedLogs;
+///
+/// let logs = CapturedLogs::default();
+/// let _guard = logs.install();
+/// tracing::debug!(status = 200, "done");
+/// assert!(logs.contents().contains("status=200"));
+/// ```
+#[derive(Clone, Default)]
+pub struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+impl CapturedLogs {
+    /// Route `DEBUG` and above on the current thread into this buffer until
+    /// the returned guard is dropped.
+    pub fn install(&self) -> tracing::subscriber::DefaultGuard {
+        let writer = self.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::DEBUG)
+            .with_writer(move || writer.clone())
+            .finish();
+        tracing::subscriber::set_default(subscriber)
+    }
+
+    /// Everything logged so far.
+    pub fn contents(&self) -> String {
+        String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+    }
+}
+
+impl std::io::Write for CapturedLogs {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
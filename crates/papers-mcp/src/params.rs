@@ -11,7 +11,8 @@ pub struct ListToolParams {
     pub search: Option<String>,
     /// Sort field with optional `:desc` suffix.
     pub sort: Option<String>,
-    /// Results per page (1-200, default 25).
+    /// Results per page (1-200, default 25). Also accepted as `limit`.
+    #[serde(alias = "limit")]
     pub per_page: Option<u32>,
     /// Page number for offset pagination (max page * per_page <= 10,000).
     pub page: Option<u32>,
@@ -34,8 +35,8 @@ impl ListToolParams {
             filter: self.filter,
             search: self.search,
             sort: self.sort,
-            per_page: self.per_page,
-            page: self.page,
+            per_page: clamp_per_page(self.per_page),
+            page: clamp_page(self.page, self.per_page),
             cursor: self.cursor,
             sample: self.sample,
             seed: self.seed,
@@ -54,7 +55,8 @@ pub struct WorkListToolParams {
     pub search: Option<String>,
     /// Sort field with optional `:desc` suffix. Example: `"cited_by_count:desc"`
     pub sort: Option<String>,
-    /// Results per page (1-200, default 25).
+    /// Results per page (1-200, default 25). Also accepted as `limit`.
+    #[serde(alias = "limit")]
     pub per_page: Option<u32>,
     /// Page number for offset pagination (max page * per_page <= 10,000).
     pub page: Option<u32>,
@@ -87,6 +89,10 @@ pub struct WorkListToolParams {
     pub institution: Option<String>,
     /// Filter by publication year (e.g. "2024", ">2008", "2008-2024")
     pub year: Option<String>,
+    /// Only works published on or after this date (YYYY-MM-DD). Cannot be combined with `year`.
+    pub from_publication_date: Option<String>,
+    /// Only works published on or before this date (YYYY-MM-DD). Cannot be combined with `year`.
+    pub to_publication_date: Option<String>,
     /// Filter by citation count (e.g. ">100", "10-50")
     pub citations: Option<String>,
     /// Filter by country code of author institutions (e.g. "US", "GB")
@@ -97,6 +103,8 @@ pub struct WorkListToolParams {
     pub r#type: Option<String>,
     /// Filter for open access works only. Set to true to include only OA works.
     pub open: Option<bool>,
+    /// Set to true to leave out retracted works.
+    pub exclude_retracted: Option<bool>,
 }
 
 impl WorkListToolParams {
@@ -105,8 +113,8 @@ impl WorkListToolParams {
             filter: self.filter.clone(),
             search: self.search.clone(),
             sort: self.sort.clone(),
-            per_page: self.per_page,
-            page: self.page,
+            per_page: clamp_per_page(self.per_page),
+            page: clamp_page(self.page, self.per_page),
             cursor: self.cursor.clone(),
             sample: self.sample,
             seed: self.seed,
@@ -121,11 +129,14 @@ impl WorkListToolParams {
             source: self.source.clone(),
             institution: self.institution.clone(),
             year: self.year.clone(),
+            from_publication_date: self.from_publication_date.clone(),
+            to_publication_date: self.to_publication_date.clone(),
             citations: self.citations.clone(),
             country: self.country.clone(),
             continent: self.continent.clone(),
             r#type: self.r#type.clone(),
             open: self.open,
+            exclude_retracted: self.exclude_retracted,
         }
     }
 }
@@ -139,7 +150,8 @@ pub struct AuthorListToolParams {
     pub search: Option<String>,
     /// Sort field with optional `:desc` suffix.
     pub sort: Option<String>,
-    /// Results per page (1-200, default 25).
+    /// Results per page (1-200, default 25). Also accepted as `limit`.
+    #[serde(alias = "limit")]
     pub per_page: Option<u32>,
     /// Page number for offset pagination.
     pub page: Option<u32>,
@@ -176,8 +188,8 @@ impl AuthorListToolParams {
             filter: self.filter.clone(),
             search: self.search.clone(),
             sort: self.sort.clone(),
-            per_page: self.per_page,
-            page: self.page,
+            per_page: clamp_per_page(self.per_page),
+            page: clamp_page(self.page, self.per_page),
             cursor: self.cursor.clone(),
             sample: self.sample,
             seed: self.seed,
@@ -202,7 +214,8 @@ pub struct SourceListToolParams {
     pub search: Option<String>,
     /// Sort field with optional `:desc` suffix.
     pub sort: Option<String>,
-    /// Results per page (1-200, default 25).
+    /// Results per page (1-200, default 25). Also accepted as `limit`.
+    #[serde(alias = "limit")]
     pub per_page: Option<u32>,
     /// Page number for offset pagination.
     pub page: Option<u32>,
@@ -230,6 +243,10 @@ pub struct SourceListToolParams {
     pub citations: Option<String>,
     /// Filter by works count (e.g. ">100000")
     pub works: Option<String>,
+    /// true: only sources indexed in DOAJ; false: only sources not in DOAJ.
+    pub is_in_doaj: Option<bool>,
+    /// true: only sources charging an APC; false: only sources listing a zero APC.
+    pub has_apc: Option<bool>,
 }
 
 impl SourceListToolParams {
@@ -238,8 +255,8 @@ impl SourceListToolParams {
             filter: self.filter.clone(),
             search: self.search.clone(),
             sort: self.sort.clone(),
-            per_page: self.per_page,
-            page: self.page,
+            per_page: clamp_per_page(self.per_page),
+            page: clamp_page(self.page, self.per_page),
             cursor: self.cursor.clone(),
             sample: self.sample,
             seed: self.seed,
@@ -252,6 +269,8 @@ impl SourceListToolParams {
             open: self.open,
             citations: self.citations.clone(),
             works: self.works.clone(),
+            is_in_doaj: self.is_in_doaj,
+            has_apc: self.has_apc,
         }
     }
 }
@@ -265,7 +284,8 @@ pub struct InstitutionListToolParams {
     pub search: Option<String>,
     /// Sort field with optional `:desc` suffix.
     pub sort: Option<String>,
-    /// Results per page (1-200, default 25).
+    /// Results per page (1-200, default 25). Also accepted as `limit`.
+    #[serde(alias = "limit")]
     pub per_page: Option<u32>,
     /// Page number for offset pagination.
     pub page: Option<u32>,
@@ -297,8 +317,8 @@ impl InstitutionListToolParams {
             filter: self.filter.clone(),
             search: self.search.clone(),
             sort: self.sort.clone(),
-            per_page: self.per_page,
-            page: self.page,
+            per_page: clamp_per_page(self.per_page),
+            page: clamp_page(self.page, self.per_page),
             cursor: self.cursor.clone(),
             sample: self.sample,
             seed: self.seed,
@@ -322,7 +342,8 @@ pub struct TopicListToolParams {
     pub search: Option<String>,
     /// Sort field with optional `:desc` suffix.
     pub sort: Option<String>,
-    /// Results per page (1-200, default 25).
+    /// Results per page (1-200, default 25). Also accepted as `limit`.
+    #[serde(alias = "limit")]
     pub per_page: Option<u32>,
     /// Page number for offset pagination.
     pub page: Option<u32>,
@@ -354,8 +375,8 @@ impl TopicListToolParams {
             filter: self.filter.clone(),
             search: self.search.clone(),
             sort: self.sort.clone(),
-            per_page: self.per_page,
-            page: self.page,
+            per_page: clamp_per_page(self.per_page),
+            page: clamp_page(self.page, self.per_page),
             cursor: self.cursor.clone(),
             sample: self.sample,
             seed: self.seed,
@@ -379,7 +400,8 @@ pub struct PublisherListToolParams {
     pub search: Option<String>,
     /// Sort field with optional `:desc` suffix.
     pub sort: Option<String>,
-    /// Results per page (1-200, default 25).
+    /// Results per page (1-200, default 25). Also accepted as `limit`.
+    #[serde(alias = "limit")]
     pub per_page: Option<u32>,
     /// Page number for offset pagination.
     pub page: Option<u32>,
@@ -409,8 +431,8 @@ impl PublisherListToolParams {
             filter: self.filter.clone(),
             search: self.search.clone(),
             sort: self.sort.clone(),
-            per_page: self.per_page,
-            page: self.page,
+            per_page: clamp_per_page(self.per_page),
+            page: clamp_page(self.page, self.per_page),
             cursor: self.cursor.clone(),
             sample: self.sample,
             seed: self.seed,
@@ -433,7 +455,8 @@ pub struct FunderListToolParams {
     pub search: Option<String>,
     /// Sort field with optional `:desc` suffix.
     pub sort: Option<String>,
-    /// Results per page (1-200, default 25).
+    /// Results per page (1-200, default 25). Also accepted as `limit`.
+    #[serde(alias = "limit")]
     pub per_page: Option<u32>,
     /// Page number for offset pagination.
     pub page: Option<u32>,
@@ -463,8 +486,8 @@ impl FunderListToolParams {
             filter: self.filter.clone(),
             search: self.search.clone(),
             sort: self.sort.clone(),
-            per_page: self.per_page,
-            page: self.page,
+            per_page: clamp_per_page(self.per_page),
+            page: clamp_page(self.page, self.per_page),
             cursor: self.cursor.clone(),
             sample: self.sample,
             seed: self.seed,
@@ -487,7 +510,8 @@ pub struct DomainListToolParams {
     pub search: Option<String>,
     /// Sort field with optional `:desc` suffix.
     pub sort: Option<String>,
-    /// Results per page (1-200, default 25).
+    /// Results per page (1-200, default 25). Also accepted as `limit`.
+    #[serde(alias = "limit")]
     pub per_page: Option<u32>,
     /// Page number for offset pagination.
     pub page: Option<u32>,
@@ -511,8 +535,8 @@ impl DomainListToolParams {
             filter: self.filter.clone(),
             search: self.search.clone(),
             sort: self.sort.clone(),
-            per_page: self.per_page,
-            page: self.page,
+            per_page: clamp_per_page(self.per_page),
+            page: clamp_page(self.page, self.per_page),
             cursor: self.cursor.clone(),
             sample: self.sample,
             seed: self.seed,
@@ -532,7 +556,8 @@ pub struct FieldListToolParams {
     pub search: Option<String>,
     /// Sort field with optional `:desc` suffix.
     pub sort: Option<String>,
-    /// Results per page (1-200, default 25).
+    /// Results per page (1-200, default 25). Also accepted as `limit`.
+    #[serde(alias = "limit")]
     pub per_page: Option<u32>,
     /// Page number for offset pagination.
     pub page: Option<u32>,
@@ -558,8 +583,8 @@ impl FieldListToolParams {
             filter: self.filter.clone(),
             search: self.search.clone(),
             sort: self.sort.clone(),
-            per_page: self.per_page,
-            page: self.page,
+            per_page: clamp_per_page(self.per_page),
+            page: clamp_page(self.page, self.per_page),
             cursor: self.cursor.clone(),
             sample: self.sample,
             seed: self.seed,
@@ -580,7 +605,8 @@ pub struct SubfieldListToolParams {
     pub search: Option<String>,
     /// Sort field with optional `:desc` suffix.
     pub sort: Option<String>,
-    /// Results per page (1-200, default 25).
+    /// Results per page (1-200, default 25). Also accepted as `limit`.
+    #[serde(alias = "limit")]
     pub per_page: Option<u32>,
     /// Page number for offset pagination.
     pub page: Option<u32>,
@@ -608,8 +634,8 @@ impl SubfieldListToolParams {
             filter: self.filter.clone(),
             search: self.search.clone(),
             sort: self.sort.clone(),
-            per_page: self.per_page,
-            page: self.page,
+            per_page: clamp_per_page(self.per_page),
+            page: clamp_page(self.page, self.per_page),
             cursor: self.cursor.clone(),
             sample: self.sample,
             seed: self.seed,
@@ -685,6 +711,32 @@ impl FindWorksToolParams {
 
 // ── Zotero tool params ────────────────────────────────────────────────────
 
+/// Largest `per_page` OpenAlex accepts.
+const OPENALEX_MAX_PER_PAGE: u32 = 200;
+/// OpenAlex rejects offset pagination past this many results (`page * per_page`).
+const OPENALEX_MAX_OFFSET_RESULTS: u32 = 10_000;
+/// OpenAlex's page size when `per_page` is omitted.
+const OPENALEX_DEFAULT_PER_PAGE: u32 = 25;
+/// Largest `limit` the Zotero API accepts.
+const ZOTERO_MAX_LIMIT: u32 = 100;
+
+/// Clamp an OpenAlex page size into `1..=200`.
+fn clamp_per_page(per_page: Option<u32>) -> Option<u32> {
+    per_page.map(|n| n.clamp(1, OPENALEX_MAX_PER_PAGE))
+}
+
+/// Clamp an OpenAlex page number so `page * per_page` stays within the
+/// 10,000-result offset pagination window.
+fn clamp_page(page: Option<u32>, per_page: Option<u32>) -> Option<u32> {
+    let per_page = clamp_per_page(per_page).unwrap_or(OPENALEX_DEFAULT_PER_PAGE);
+    page.map(|n| n.clamp(1, OPENALEX_MAX_OFFSET_RESULTS / per_page))
+}
+
+/// Clamp a Zotero `limit` into `1..=100`.
+pub fn clamp_zotero_limit(limit: Option<u32>) -> Option<u32> {
+    limit.map(|n| n.clamp(1, ZOTERO_MAX_LIMIT))
+}
+
 /// Deserialize `Option<u32>` accepting both JSON integers and quoted strings.
 /// Some MCP clients serialize numeric parameters as strings ("10" vs 10).
 fn lax_optional_u32<'de, D>(d: D) -> Result<Option<u32>, D::Error>
@@ -748,8 +800,8 @@ pub struct ZoteroWorkListToolParams {
     /// Results per page (1–100, default 25).
     #[serde(default, deserialize_with = "lax_optional_u32")]
     pub limit: Option<u32>,
-    /// Pagination offset (0-based).
-    #[serde(default, deserialize_with = "lax_optional_u32")]
+    /// Pagination offset (0-based). Also accepted as `offset`.
+    #[serde(default, alias = "offset", deserialize_with = "lax_optional_u32")]
     pub start: Option<u32>,
 }
 
@@ -761,8 +813,8 @@ pub struct ZoteroWorkChildrenToolParams {
     /// Results per page (1–100).
     #[serde(default, deserialize_with = "lax_optional_u32")]
     pub limit: Option<u32>,
-    /// Pagination offset (0-based).
-    #[serde(default, deserialize_with = "lax_optional_u32")]
+    /// Pagination offset (0-based). Also accepted as `offset`.
+    #[serde(default, alias = "offset", deserialize_with = "lax_optional_u32")]
     pub start: Option<u32>,
 }
 
@@ -776,8 +828,8 @@ pub struct ZoteroWorkTagsToolParams {
     /// Results per page (1–100).
     #[serde(default, deserialize_with = "lax_optional_u32")]
     pub limit: Option<u32>,
-    /// Pagination offset (0-based).
-    #[serde(default, deserialize_with = "lax_optional_u32")]
+    /// Pagination offset (0-based). Also accepted as `offset`.
+    #[serde(default, alias = "offset", deserialize_with = "lax_optional_u32")]
     pub start: Option<u32>,
 }
 
@@ -793,8 +845,8 @@ pub struct ZoteroAttachmentListToolParams {
     /// Results per page (1–100, default 25).
     #[serde(default, deserialize_with = "lax_optional_u32")]
     pub limit: Option<u32>,
-    /// Pagination offset (0-based).
-    #[serde(default, deserialize_with = "lax_optional_u32")]
+    /// Pagination offset (0-based). Also accepted as `offset`.
+    #[serde(default, alias = "offset", deserialize_with = "lax_optional_u32")]
     pub start: Option<u32>,
 }
 
@@ -804,8 +856,8 @@ pub struct ZoteroAnnotationListToolParams {
     /// Results per page (1–100, default 25).
     #[serde(default, deserialize_with = "lax_optional_u32")]
     pub limit: Option<u32>,
-    /// Pagination offset (0-based).
-    #[serde(default, deserialize_with = "lax_optional_u32")]
+    /// Pagination offset (0-based). Also accepted as `offset`.
+    #[serde(default, alias = "offset", deserialize_with = "lax_optional_u32")]
     pub start: Option<u32>,
 }
 
@@ -817,8 +869,8 @@ pub struct ZoteroNoteListToolParams {
     /// Results per page (1–100, default 25).
     #[serde(default, deserialize_with = "lax_optional_u32")]
     pub limit: Option<u32>,
-    /// Pagination offset (0-based).
-    #[serde(default, deserialize_with = "lax_optional_u32")]
+    /// Pagination offset (0-based). Also accepted as `offset`.
+    #[serde(default, alias = "offset", deserialize_with = "lax_optional_u32")]
     pub start: Option<u32>,
 }
 
@@ -832,8 +884,8 @@ pub struct ZoteroCollectionListToolParams {
     /// Results per page (1–100, default 25).
     #[serde(default, deserialize_with = "lax_optional_u32")]
     pub limit: Option<u32>,
-    /// Pagination offset (0-based).
-    #[serde(default, deserialize_with = "lax_optional_u32")]
+    /// Pagination offset (0-based). Also accepted as `offset`.
+    #[serde(default, alias = "offset", deserialize_with = "lax_optional_u32")]
     pub start: Option<u32>,
     /// Scope: `"all"` (default) lists all collections; `"top"` lists only root-level.
     pub scope: Option<String>,
@@ -860,8 +912,8 @@ pub struct ZoteroCollectionWorksToolParams {
     /// Results per page (1–100, default 25).
     #[serde(default, deserialize_with = "lax_optional_u32")]
     pub limit: Option<u32>,
-    /// Pagination offset (0-based).
-    #[serde(default, deserialize_with = "lax_optional_u32")]
+    /// Pagination offset (0-based). Also accepted as `offset`.
+    #[serde(default, alias = "offset", deserialize_with = "lax_optional_u32")]
     pub start: Option<u32>,
 }
 
@@ -875,8 +927,8 @@ pub struct ZoteroCollectionNotesToolParams {
     /// Results per page (1–100, default 25).
     #[serde(default, deserialize_with = "lax_optional_u32")]
     pub limit: Option<u32>,
-    /// Pagination offset (0-based).
-    #[serde(default, deserialize_with = "lax_optional_u32")]
+    /// Pagination offset (0-based). Also accepted as `offset`.
+    #[serde(default, alias = "offset", deserialize_with = "lax_optional_u32")]
     pub start: Option<u32>,
 }
 
@@ -892,8 +944,8 @@ pub struct ZoteroCollectionSubcollectionsToolParams {
     /// Results per page (1–100, default 25).
     #[serde(default, deserialize_with = "lax_optional_u32")]
     pub limit: Option<u32>,
-    /// Pagination offset (0-based).
-    #[serde(default, deserialize_with = "lax_optional_u32")]
+    /// Pagination offset (0-based). Also accepted as `offset`.
+    #[serde(default, alias = "offset", deserialize_with = "lax_optional_u32")]
     pub start: Option<u32>,
 }
 
@@ -907,8 +959,8 @@ pub struct ZoteroCollectionTagsToolParams {
     /// Results per page (1–100, default 25).
     #[serde(default, deserialize_with = "lax_optional_u32")]
     pub limit: Option<u32>,
-    /// Pagination offset (0-based).
-    #[serde(default, deserialize_with = "lax_optional_u32")]
+    /// Pagination offset (0-based). Also accepted as `offset`.
+    #[serde(default, alias = "offset", deserialize_with = "lax_optional_u32")]
     pub start: Option<u32>,
     /// When true, return only tags on top-level items in the collection.
     pub top: Option<bool>,
@@ -926,8 +978,8 @@ pub struct ZoteroTagListToolParams {
     /// Results per page (1–100, default 25).
     #[serde(default, deserialize_with = "lax_optional_u32")]
     pub limit: Option<u32>,
-    /// Pagination offset (0-based).
-    #[serde(default, deserialize_with = "lax_optional_u32")]
+    /// Pagination offset (0-based). Also accepted as `offset`.
+    #[serde(default, alias = "offset", deserialize_with = "lax_optional_u32")]
     pub start: Option<u32>,
     /// Scope: `"all"` (default) = global index, `"top"` = top-level items only, `"trash"` = trashed items.
     pub scope: Option<String>,
@@ -962,6 +1014,13 @@ pub struct ZoteroDeletedListToolParams {
     pub since: Option<u64>,
 }
 
+/// Parameters for the `zotero_import_work` tool.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct ZoteroImportWorkToolParams {
+    /// OpenAlex work ID (e.g. `W2741809807`) or DOI.
+    pub id: String,
+}
+
 /// Parameters for `zotero_setting_get`.
 #[derive(Debug, Clone, Deserialize, JsonSchema)]
 pub struct ZoteroSettingGetToolParams {
@@ -998,6 +1057,18 @@ mod tests {
         assert_eq!(params.group_by.as_deref(), Some("type"));
     }
 
+    #[test]
+    fn test_list_params_clamp_pagination() {
+        let tool_params: ListToolParams =
+            serde_json::from_value(serde_json::json!({"limit": 1000, "page": 500})).unwrap();
+        let params = tool_params.into_list_params();
+        assert_eq!(params.per_page, Some(200));
+        assert_eq!(params.page, Some(50));
+        assert_eq!(clamp_per_page(Some(0)), Some(1));
+        assert_eq!(clamp_zotero_limit(Some(500)), Some(100));
+        assert_eq!(clamp_zotero_limit(None), None);
+    }
+
     #[test]
     fn test_get_params_conversion() {
         let tool_params = GetToolParams {
@@ -11,14 +11,14 @@ use rmcp::{Peer, ServerHandler, tool, tool_handler, tool_router};
 use serde::Serialize;
 
 use crate::params::{
-    AutocompleteToolParams, AuthorListToolParams, DomainListToolParams, FieldListToolParams,
+    clamp_zotero_limit, AutocompleteToolParams, AuthorListToolParams, DomainListToolParams, FieldListToolParams,
     FindWorksToolParams, FunderListToolParams, GetToolParams, InstitutionListToolParams,
     PublisherListToolParams, SourceListToolParams, SubfieldListToolParams, TopicListToolParams,
     WorkListToolParams, WorkTextToolParams,
     ZoteroAnnotationListToolParams, ZoteroAttachmentListToolParams, ZoteroCollectionListToolParams,
     ZoteroCollectionNotesToolParams, ZoteroCollectionSubcollectionsToolParams,
     ZoteroCollectionTagsToolParams, ZoteroCollectionWorksToolParams, ZoteroDeletedListToolParams,
-    ZoteroKeyToolParams, ZoteroNoParamsToolParams, ZoteroNoteListToolParams,
+    ZoteroImportWorkToolParams, ZoteroKeyToolParams, ZoteroNoParamsToolParams, ZoteroNoteListToolParams,
     ZoteroSettingGetToolParams, ZoteroTagGetToolParams, ZoteroTagListToolParams,
     ZoteroWorkChildrenToolParams, ZoteroWorkListToolParams, ZoteroWorkTagsToolParams,
 };
@@ -55,6 +55,16 @@ impl PapersMcp {
         }
     }
 
+    /// Create a server with explicit OpenAlex and Zotero clients (for testing).
+    pub fn with_clients(client: OpenAlexClient, zotero: ZoteroClient) -> Self {
+        Self {
+            client,
+            zotero: Arc::new(tokio::sync::Mutex::new(Some(zotero))),
+            datalab: None,
+            tool_router: Self::tool_router(),
+        }
+    }
+
     /// Create a server with an explicit Zotero client (for testing).
     pub fn with_zotero(zotero: ZoteroClient) -> Self {
         Self {
@@ -109,14 +119,6 @@ impl PapersMcp {
     }
 }
 
-/// Returns true if this attachment supports annotation children (PDF, EPUB, or HTML snapshot).
-fn is_annotatable_attachment(att: &papers_zotero::Item) -> bool {
-    matches!(
-        att.data.content_type.as_deref(),
-        Some("application/pdf") | Some("application/epub+zip") | Some("text/html")
-    )
-}
-
 fn json_result<T: Serialize, E: std::fmt::Display>(result: Result<T, E>) -> Result<String, String> {
     match result {
         Ok(response) => {
@@ -214,6 +216,7 @@ impl PapersMcp {
 
     /// Get a single work by ID (OpenAlex ID, DOI, PMID, or PMCID).
     /// Response includes `in_zotero` (bool) and `zotero` (object or null) with brief Zotero library info.
+    /// If nothing matches, returns `no_exact_match` with up to 5 candidate titles and citation counts.
     #[tool]
     pub async fn work_get(&self, Parameters(params): Parameters<GetToolParams>) -> Result<String, String> {
         let zotero = self.get_optional_zotero().await?;
@@ -311,7 +314,7 @@ impl PapersMcp {
     /// Type-ahead search for institutions. Returns up to 10 results.
     #[tool]
     pub async fn institution_autocomplete(&self, Parameters(params): Parameters<AutocompleteToolParams>) -> Result<String, String> {
-        json_result(papers_core::api::institution_autocomplete(&self.client, &params.q).await)
+        json_result(papers_core::api::institution_autocomplete(&self.client, &params.q, None).await)
     }
 
     /// Type-ahead search for publishers. Returns up to 10 results.
@@ -340,6 +343,18 @@ impl PapersMcp {
         json_result(papers_core::api::work_find(&self.client, &params.into_find_params()).await)
     }
 
+    /// Semantic search for works by conceptual similarity, returning slim work summaries
+    /// each with a similarity `score` (0-1, best first). Spends OpenAlex API credits and
+    /// requires OPENALEX_KEY. Params: query (max 10,000 chars), count (1-100), filter.
+    #[tool]
+    pub async fn openalex_find_works(&self, Parameters(params): Parameters<FindWorksToolParams>) -> Result<String, String> {
+        if !self.client.has_api_key() {
+            return Err("openalex_find_works requires an OpenAlex API key. Set OPENALEX_KEY=<your-key>.".to_string());
+        }
+        let resp = papers_core::api::work_find(&self.client, &params.into_find_params()).await;
+        json_result(resp.map(papers_core::summary::scored_work_summaries))
+    }
+
     // ── Zotero tools ─────────────────────────────────────────────────────
 
     /// List bibliographic items in your Zotero library (journalArticle, book, conferencePaper, etc.).
@@ -356,7 +371,7 @@ impl PapersMcp {
             since: p.since,
             sort: p.sort,
             direction: p.direction,
-            limit: p.limit,
+            limit: clamp_zotero_limit(p.limit),
             start: p.start,
             ..Default::default()
         };
@@ -397,7 +412,7 @@ impl PapersMcp {
     pub async fn zotero_work_notes(&self, Parameters(p): Parameters<ZoteroWorkChildrenToolParams>) -> Result<String, String> {
         let z = self.require_zotero().await?;
         let key = zotero_resolve::resolve_item_key(&z, &p.key).await.map_err(|e| e.to_string())?;
-        let params = papers_zotero::ItemListParams { item_type: Some("note".into()), limit: p.limit, start: p.start, ..Default::default() };
+        let params = papers_zotero::ItemListParams { item_type: Some("note".into()), limit: clamp_zotero_limit(p.limit), start: p.start, ..Default::default() };
         json_result(z.list_item_children(&key, &params).await)
     }
 
@@ -406,7 +421,7 @@ impl PapersMcp {
     pub async fn zotero_work_attachments(&self, Parameters(p): Parameters<ZoteroWorkChildrenToolParams>) -> Result<String, String> {
         let z = self.require_zotero().await?;
         let key = zotero_resolve::resolve_item_key(&z, &p.key).await.map_err(|e| e.to_string())?;
-        let params = papers_zotero::ItemListParams { item_type: Some("attachment".into()), limit: p.limit, start: p.start, ..Default::default() };
+        let params = papers_zotero::ItemListParams { item_type: Some("attachment".into()), limit: clamp_zotero_limit(p.limit), start: p.start, ..Default::default() };
         json_result(z.list_item_children(&key, &params).await)
     }
 
@@ -416,17 +431,7 @@ impl PapersMcp {
     pub async fn zotero_work_annotations(&self, Parameters(p): Parameters<ZoteroKeyToolParams>) -> Result<String, String> {
         let z = self.require_zotero().await?;
         let key = zotero_resolve::resolve_item_key(&z, &p.key).await.map_err(|e| e.to_string())?;
-        let att_params = papers_zotero::ItemListParams { item_type: Some("attachment".into()), ..Default::default() };
-        let attachments = z.list_item_children(&key, &att_params).await.map_err(|e| e.to_string())?;
-        let ann_params = papers_zotero::ItemListParams { item_type: Some("annotation".into()), ..Default::default() };
-        let mut all_annotations = Vec::new();
-        for att in &attachments.items {
-            if !is_annotatable_attachment(att) { continue; }
-            if let Ok(r) = z.list_item_children(&att.key, &ann_params).await {
-                all_annotations.extend(r.items);
-            }
-        }
-        json_result::<Vec<_>, String>(Ok(all_annotations))
+        json_result(zotero_resolve::work_annotations(&z, &key).await)
     }
 
     /// List tags attached to a specific work. Requires ZOTERO_USER_ID and ZOTERO_API_KEY.
@@ -434,7 +439,7 @@ impl PapersMcp {
     pub async fn zotero_work_tags(&self, Parameters(p): Parameters<ZoteroWorkTagsToolParams>) -> Result<String, String> {
         let z = self.require_zotero().await?;
         let key = zotero_resolve::resolve_item_key(&z, &p.key).await.map_err(|e| e.to_string())?;
-        let params = papers_zotero::TagListParams { q: p.search, qmode: Some("contains".to_string()), limit: p.limit, start: p.start, ..Default::default() };
+        let params = papers_zotero::TagListParams { q: p.search, qmode: Some("contains".to_string()), limit: clamp_zotero_limit(p.limit), start: p.start, ..Default::default() };
         json_result(z.list_item_tags(&key, &params).await)
     }
 
@@ -443,7 +448,7 @@ impl PapersMcp {
     #[tool]
     pub async fn zotero_attachment_list(&self, Parameters(p): Parameters<ZoteroAttachmentListToolParams>) -> Result<String, String> {
         let z = self.require_zotero().await?;
-        let params = papers_zotero::ItemListParams { item_type: Some("attachment".into()), q: p.search, sort: p.sort, direction: p.direction, limit: p.limit, start: p.start, ..Default::default() };
+        let params = papers_zotero::ItemListParams { item_type: Some("attachment".into()), q: p.search, sort: p.sort, direction: p.direction, limit: clamp_zotero_limit(p.limit), start: p.start, ..Default::default() };
         json_result(z.list_items(&params).await)
     }
 
@@ -460,7 +465,7 @@ impl PapersMcp {
     #[tool]
     pub async fn zotero_annotation_list(&self, Parameters(p): Parameters<ZoteroAnnotationListToolParams>) -> Result<String, String> {
         let z = self.require_zotero().await?;
-        let params = papers_zotero::ItemListParams { item_type: Some("annotation".into()), limit: p.limit, start: p.start, ..Default::default() };
+        let params = papers_zotero::ItemListParams { item_type: Some("annotation".into()), limit: clamp_zotero_limit(p.limit), start: p.start, ..Default::default() };
         json_result(z.list_items(&params).await)
     }
 
@@ -477,7 +482,7 @@ impl PapersMcp {
     #[tool]
     pub async fn zotero_note_list(&self, Parameters(p): Parameters<ZoteroNoteListToolParams>) -> Result<String, String> {
         let z = self.require_zotero().await?;
-        let params = papers_zotero::ItemListParams { item_type: Some("note".into()), q: p.search, limit: p.limit, start: p.start, ..Default::default() };
+        let params = papers_zotero::ItemListParams { item_type: Some("note".into()), q: p.search, limit: clamp_zotero_limit(p.limit), start: p.start, ..Default::default() };
         json_result(z.list_items(&params).await)
     }
 
@@ -494,7 +499,7 @@ impl PapersMcp {
     #[tool]
     pub async fn zotero_collection_list(&self, Parameters(p): Parameters<ZoteroCollectionListToolParams>) -> Result<String, String> {
         let z = self.require_zotero().await?;
-        let params = papers_zotero::CollectionListParams { sort: p.sort, direction: p.direction, limit: p.limit, start: p.start };
+        let params = papers_zotero::CollectionListParams { sort: p.sort, direction: p.direction, limit: clamp_zotero_limit(p.limit), start: p.start };
         let result = if p.scope.as_deref() == Some("top") {
             z.list_top_collections(&params).await
         } else {
@@ -524,7 +529,7 @@ impl PapersMcp {
             tag: p.tag,
             sort: p.sort,
             direction: p.direction,
-            limit: p.limit,
+            limit: clamp_zotero_limit(p.limit),
             start: p.start,
             ..Default::default()
         };
@@ -536,7 +541,7 @@ impl PapersMcp {
     pub async fn zotero_collection_attachments(&self, Parameters(p): Parameters<ZoteroWorkChildrenToolParams>) -> Result<String, String> {
         let z = self.require_zotero().await?;
         let key = zotero_resolve::resolve_collection_key(&z, &p.key).await.map_err(|e| e.to_string())?;
-        let params = papers_zotero::ItemListParams { item_type: Some("attachment".into()), limit: p.limit, start: p.start, ..Default::default() };
+        let params = papers_zotero::ItemListParams { item_type: Some("attachment".into()), limit: clamp_zotero_limit(p.limit), start: p.start, ..Default::default() };
         json_result(z.list_collection_items(&key, &params).await)
     }
 
@@ -545,27 +550,19 @@ impl PapersMcp {
     pub async fn zotero_collection_notes(&self, Parameters(p): Parameters<ZoteroCollectionNotesToolParams>) -> Result<String, String> {
         let z = self.require_zotero().await?;
         let key = zotero_resolve::resolve_collection_key(&z, &p.key).await.map_err(|e| e.to_string())?;
-        let params = papers_zotero::ItemListParams { item_type: Some("note".into()), q: p.search, limit: p.limit, start: p.start, ..Default::default() };
+        let params = papers_zotero::ItemListParams { item_type: Some("note".into()), q: p.search, limit: clamp_zotero_limit(p.limit), start: p.start, ..Default::default() };
         json_result(z.list_collection_items(&key, &params).await)
     }
 
-    /// List annotations on PDFs within a collection. Multi-step: fetches attachments then
-    /// annotations per attachment. Requires ZOTERO_USER_ID and ZOTERO_API_KEY.
+    /// List annotations on the PDFs, EPUBs, and snapshots of every item in a
+    /// collection, each item's in reading order. Multi-step: lists the items,
+    /// then their attachments and annotations. Requires ZOTERO_USER_ID and
+    /// ZOTERO_API_KEY.
     #[tool]
     pub async fn zotero_collection_annotations(&self, Parameters(p): Parameters<ZoteroKeyToolParams>) -> Result<String, String> {
         let z = self.require_zotero().await?;
         let key = zotero_resolve::resolve_collection_key(&z, &p.key).await.map_err(|e| e.to_string())?;
-        let att_params = papers_zotero::ItemListParams { item_type: Some("attachment".into()), ..Default::default() };
-        let attachments = z.list_collection_items(&key, &att_params).await.map_err(|e| e.to_string())?;
-        let ann_params = papers_zotero::ItemListParams { item_type: Some("annotation".into()), ..Default::default() };
-        let mut all_annotations = Vec::new();
-        for att in &attachments.items {
-            if !is_annotatable_attachment(att) { continue; }
-            if let Ok(r) = z.list_item_children(&att.key, &ann_params).await {
-                all_annotations.extend(r.items);
-            }
-        }
-        json_result::<Vec<_>, String>(Ok(all_annotations))
+        json_result(zotero_resolve::collection_annotations(&z, &key).await)
     }
 
     /// List sub-collections of a collection. Requires ZOTERO_USER_ID and ZOTERO_API_KEY.
@@ -573,7 +570,7 @@ impl PapersMcp {
     pub async fn zotero_collection_subcollections(&self, Parameters(p): Parameters<ZoteroCollectionSubcollectionsToolParams>) -> Result<String, String> {
         let z = self.require_zotero().await?;
         let key = zotero_resolve::resolve_collection_key(&z, &p.key).await.map_err(|e| e.to_string())?;
-        let params = papers_zotero::CollectionListParams { sort: p.sort, direction: p.direction, limit: p.limit, start: p.start };
+        let params = papers_zotero::CollectionListParams { sort: p.sort, direction: p.direction, limit: clamp_zotero_limit(p.limit), start: p.start };
         json_result(z.list_subcollections(&key, &params).await)
     }
 
@@ -582,7 +579,7 @@ impl PapersMcp {
     pub async fn zotero_collection_tags(&self, Parameters(p): Parameters<ZoteroCollectionTagsToolParams>) -> Result<String, String> {
         let z = self.require_zotero().await?;
         let key = zotero_resolve::resolve_collection_key(&z, &p.key).await.map_err(|e| e.to_string())?;
-        let params = papers_zotero::TagListParams { q: p.search, qmode: Some("contains".to_string()), limit: p.limit, start: p.start, ..Default::default() };
+        let params = papers_zotero::TagListParams { q: p.search, qmode: Some("contains".to_string()), limit: clamp_zotero_limit(p.limit), start: p.start, ..Default::default() };
         let result = if p.top == Some(true) {
             z.list_collection_top_items_tags(&key, &params).await
         } else {
@@ -597,7 +594,7 @@ impl PapersMcp {
     #[tool]
     pub async fn zotero_tag_list(&self, Parameters(p): Parameters<ZoteroTagListToolParams>) -> Result<String, String> {
         let z = self.require_zotero().await?;
-        let params = papers_zotero::TagListParams { q: p.search, qmode: Some("contains".to_string()), sort: p.sort, direction: p.direction, limit: p.limit, start: p.start };
+        let params = papers_zotero::TagListParams { q: p.search, qmode: Some("contains".to_string()), sort: p.sort, direction: p.direction, limit: clamp_zotero_limit(p.limit), start: p.start };
         let result = match p.scope.as_deref() {
             Some("trash") => z.list_trash_tags(&params).await,
             Some("top") => z.list_top_items_tags(&params).await,
@@ -716,6 +713,21 @@ impl PapersMcp {
         json_result(z.get_deleted(&params).await)
     }
 
+    /// Save an OpenAlex work to the Zotero library as a new item of the
+    /// matching Zotero type (journal article, conference paper, thesis,
+    /// preprint, ...).
+    /// Skips works whose DOI is already in the library. Returns
+    /// `{"status": "created" | "already_exists", "key": "<zotero key>"}`.
+    /// Requires ZOTERO_USER_ID and ZOTERO_API_KEY.
+    #[tool]
+    pub async fn zotero_import_work(&self, Parameters(p): Parameters<ZoteroImportWorkToolParams>) -> Result<String, String> {
+        let z = self.require_zotero().await?;
+        let work = papers_core::api::work_get(&self.client, &p.id, &papers_core::GetParams::default())
+            .await
+            .map_err(|e| e.to_string())?;
+        json_result(papers_core::zotero::import_work(&z, &work).await)
+    }
+
     /// Get the full text content of a scholarly work by downloading and extracting its PDF.
     /// Tries multiple sources: local Zotero library, remote Zotero API,
     /// direct open-access URLs, and the OpenAlex content API.
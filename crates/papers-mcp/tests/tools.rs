@@ -2,7 +2,7 @@ use papers_core::OpenAlexClient;
 use papers_mcp::server::PapersMcp;
 use papers_zotero::ZoteroClient;
 use rmcp::handler::server::wrapper::Parameters;
-use wiremock::matchers::{method, path, query_param};
+use wiremock::matchers::{header, method, path, query_param};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 fn minimal_list_json() -> String {
@@ -360,6 +360,53 @@ async fn test_find_works_post_for_long_query() {
     assert!(result.is_ok());
 }
 
+#[tokio::test]
+async fn test_openalex_find_works_requires_key() {
+    let mock = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/find/works"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(minimal_find_json()))
+        .expect(0)
+        .mount(&mock)
+        .await;
+
+    let client = OpenAlexClient::with_api_key("").with_base_url(mock.uri());
+    let server = PapersMcp::with_client(client).await;
+    let params = serde_json::from_value(serde_json::json!({"query": "drug discovery"})).unwrap();
+    let err = server.openalex_find_works(Parameters(params)).await.unwrap_err();
+    assert!(err.contains("OPENALEX_KEY"), "unexpected error: {err}");
+}
+
+#[tokio::test]
+async fn test_openalex_find_works_returns_scored_summaries() {
+    let mock = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/find/works"))
+        .and(query_param("api_key", "test-key"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "meta": null,
+            "results": [{
+                "score": 0.91,
+                "id": "https://openalex.org/W1",
+                "display_name": "Drug Discovery with GNNs",
+                "publication_year": 2021,
+                "authorships": [{"author": {"id": "https://openalex.org/A1", "display_name": "Alice"}}]
+            }]
+        })))
+        .mount(&mock)
+        .await;
+
+    let client = OpenAlexClient::with_api_key("test-key").with_base_url(mock.uri());
+    let server = PapersMcp::with_client(client).await;
+    let params = serde_json::from_value(serde_json::json!({"query": "drug discovery", "count": 5})).unwrap();
+    let text = server.openalex_find_works(Parameters(params)).await.unwrap();
+    let json: serde_json::Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(json[0]["score"], 0.91);
+    assert_eq!(json[0]["id"], "https://openalex.org/W1");
+    assert_eq!(json[0]["authors"][0], "Alice");
+    assert!(json[0].get("authorships").is_none(), "results should be slimmed");
+}
+
 // ── Error handling tests ─────────────────────────────────────────────
 
 #[tokio::test]
@@ -618,10 +665,10 @@ async fn test_zotero_deleted_list_no_params() {
 // ── Tool listing tests ───────────────────────────────────────────────
 
 #[test]
-fn test_tool_router_has_62_tools() {
+fn test_tool_router_has_64_tools() {
     let router = PapersMcp::tool_router();
     let tools = router.list_all();
-    assert_eq!(tools.len(), 62);
+    assert_eq!(tools.len(), 64);
 }
 
 #[test]
@@ -659,6 +706,7 @@ fn test_all_tool_names_present() {
         "funder_autocomplete",
         "subfield_autocomplete",
         "work_find",
+        "openalex_find_works",
         "work_text",
         // Zotero tools
         "zotero_work_list",
@@ -695,6 +743,7 @@ fn test_all_tool_names_present() {
         "zotero_setting_list",
         "zotero_setting_get",
         "zotero_deleted_list",
+        "zotero_import_work",
     ];
 
     for name in &expected {
@@ -721,7 +770,7 @@ fn test_all_tools_have_descriptions() {
 mod summary_unit {
     use papers_core::summary::{
         AuthorSummary, DomainSummary, FieldSummary, FunderSummary, InstitutionSummary,
-        PublisherSummary, SourceSummary, SubfieldSummary, TopicSummary, WorkSummary,
+        PublisherSummary, SlimProfile, SourceSummary, SubfieldSummary, TopicSummary, WorkSummary,
     };
     use papers_core::{Author, Domain, Field, Funder, Institution, Publisher, Source, Subfield, Topic, Work};
 
@@ -846,6 +895,59 @@ mod summary_unit {
         assert_eq!(s.authors, vec!["Alice", "Bob"]);
     }
 
+    #[test]
+    fn work_summary_profiles_control_fields() {
+        let fixture = || -> Work {
+            serde_json::from_str(include_str!("../../papers-openalex/tests/fixtures/work.json")).unwrap()
+        };
+
+        let minimal = WorkSummary::from_work(fixture(), SlimProfile::Minimal);
+        assert!(minimal.authors.is_empty());
+        let json: serde_json::Value = serde_json::to_value(&minimal).unwrap();
+        for omitted in ["authors", "journal", "abstract_text", "topics"] {
+            assert!(json.get(omitted).is_none(), "minimal should omit {omitted}");
+        }
+        assert_eq!(json["id"], "https://openalex.org/W2741809807");
+        assert_eq!(json["has_abstract"], true);
+
+        let standard = serde_json::to_value(WorkSummary::from(fixture())).unwrap();
+        assert!(standard["authors"].as_array().is_some_and(|a| !a.is_empty()));
+        assert!(standard.get("topics").is_none());
+
+        let detailed = WorkSummary::from_work(fixture(), SlimProfile::Detailed);
+        assert_eq!(detailed.journal.as_deref(), Some("PeerJ"));
+        assert!(!detailed.topics.is_empty());
+        let json = serde_json::to_value(&detailed).unwrap();
+        assert_eq!(json["journal"], "PeerJ");
+        assert!(json["topics"].as_array().is_some_and(|t| !t.is_empty()));
+        assert!(json["keywords"].is_array());
+    }
+
+    #[test]
+    fn work_summary_topic_and_top_keywords() {
+        let fixture: Work =
+            serde_json::from_str(include_str!("../../papers-openalex/tests/fixtures/work.json")).unwrap();
+        let s = WorkSummary::from_work(fixture, SlimProfile::Detailed);
+        assert_eq!(s.primary_topic.as_deref(), Some("scientometrics and bibliometrics research"));
+        assert_eq!(
+            s.keywords,
+            ["Citation", "License", "Scholarly communication", "Web of science", "Bibliometrics"]
+        );
+    }
+
+    #[test]
+    fn work_summary_has_abstract_flag() {
+        let s = WorkSummary::from(minimal_work());
+        assert!(!s.has_abstract);
+        assert!(serde_json::to_string(&s).unwrap().contains(r#""has_abstract":false"#));
+
+        let fixture: Work =
+            serde_json::from_str(include_str!("../../papers-openalex/tests/fixtures/work.json")).unwrap();
+        let s = WorkSummary::from(fixture);
+        assert!(s.has_abstract);
+        assert!(serde_json::to_string(&s).unwrap().contains(r#""has_abstract":true"#));
+    }
+
     #[test]
     fn work_summary_serializes_without_verbose_fields() {
         let json = serde_json::to_string(&WorkSummary::from(minimal_work())).unwrap();
@@ -1538,6 +1640,24 @@ fn test_tool_params_schema() {
 
 // ── Work list filter alias tests ─────────────────────────────────────
 
+/// Author autocomplete response with the given `(id, display_name)` candidates.
+fn author_autocomplete_json(candidates: &[(&str, &str)]) -> String {
+    let results: Vec<_> = candidates
+        .iter()
+        .map(|(id, name)| {
+            serde_json::json!({
+                "id": id, "display_name": name, "hint": null, "cited_by_count": 100,
+                "works_count": 10, "entity_type": "author", "external_id": null,
+            })
+        })
+        .collect();
+    serde_json::json!({
+        "meta": {"count": results.len(), "db_response_time_ms": 5, "page": 1, "per_page": 10},
+        "results": results,
+    })
+    .to_string()
+}
+
 fn search_result_json(id: &str) -> String {
     format!(
         r#"{{
@@ -1548,6 +1668,49 @@ fn search_result_json(id: &str) -> String {
     )
 }
 
+#[tokio::test]
+async fn test_work_list_custom_limit_honored_and_clamped() {
+    let mock = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/works"))
+        .and(query_param("per-page", "50"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(minimal_list_json()))
+        .expect(1)
+        .mount(&mock)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/works"))
+        .and(query_param("per-page", "200"))
+        .and(query_param("page", "50"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(minimal_list_json()))
+        .expect(1)
+        .mount(&mock)
+        .await;
+
+    let server = make_server(&mock).await;
+    let params = serde_json::from_value(serde_json::json!({"limit": 50})).unwrap();
+    assert!(server.work_list(Parameters(params)).await.is_ok());
+    let params = serde_json::from_value(serde_json::json!({"per_page": 5000, "page": 900})).unwrap();
+    assert!(server.work_list(Parameters(params)).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_zotero_work_list_limit_clamped_and_offset_alias() {
+    let mock = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/users/test/items/top"))
+        .and(query_param("limit", "100"))
+        .and(query_param("start", "40"))
+        .respond_with(zotero_array_response(&zotero_items_body()))
+        .expect(1)
+        .mount(&mock)
+        .await;
+
+    let server = make_zotero_server(&mock);
+    let params = serde_json::from_value(serde_json::json!({"limit": 1000, "offset": 40})).unwrap();
+    assert!(server.zotero_work_list(Parameters(params)).await.is_ok());
+}
+
 #[tokio::test]
 async fn test_work_list_with_year_alias() {
     let mock = MockServer::start().await;
@@ -1602,11 +1765,11 @@ async fn test_work_list_with_author_search() {
     let mock = MockServer::start().await;
     // Mock the author search resolution
     Mock::given(method("GET"))
-        .and(path("/authors"))
-        .and(query_param("filter", "display_name.search:einstein"))
+        .and(path("/autocomplete/authors"))
+        .and(query_param("q", "einstein"))
         .respond_with(
             ResponseTemplate::new(200)
-                .set_body_string(search_result_json("https://openalex.org/A5083138872")),
+                .set_body_string(author_autocomplete_json(&[("https://openalex.org/A5083138872", "Albert Einstein")])),
         )
         .mount(&mock)
         .await;
@@ -2047,6 +2210,118 @@ async fn test_zotero_work_list_no_qmode_by_default() {
     assert!(!requests[0].url.query().unwrap_or("").contains("qmode"));
 }
 
+fn mount_openalex_work(doi: &str) -> Mock {
+    Mock::given(method("GET"))
+        .and(path("/works/W2741809807"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "https://openalex.org/W2741809807",
+            "display_name": "The state of OA",
+            "doi": format!("https://doi.org/{doi}"),
+            "publication_year": 2018
+        })))
+}
+
+#[tokio::test]
+async fn test_zotero_import_work_creates_item() {
+    let mock = MockServer::start().await;
+    mount_openalex_work("10.7717/peerj.4375").mount(&mock).await;
+    Mock::given(method("GET"))
+        .and(path("/users/test/items/top"))
+        .respond_with(zotero_array_response("[]"))
+        .mount(&mock)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/users/test/items"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            r#"{"successful":{"0":{"key":"NEW12345","version":2}},"unchanged":{},"failed":{}}"#,
+        ))
+        .expect(1)
+        .mount(&mock)
+        .await;
+
+    let server = PapersMcp::with_clients(
+        OpenAlexClient::new().with_base_url(mock.uri()),
+        ZoteroClient::new("test", "test-key").with_base_url(mock.uri()),
+    );
+    let params = serde_json::from_value(serde_json::json!({"id": "W2741809807"})).unwrap();
+    let text = server.zotero_import_work(Parameters(params)).await.unwrap();
+    let json: serde_json::Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(json["status"], "created");
+    assert_eq!(json["key"], "NEW12345");
+}
+
+#[tokio::test]
+async fn test_clients_share_http_client() {
+    let mock = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/works/W2741809807"))
+        .and(header("user-agent", "shared-agent/1.0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "https://openalex.org/W2741809807",
+            "display_name": "The state of OA",
+            "doi": "https://doi.org/10.7717/peerj.4375"
+        })))
+        .expect(1)
+        .mount(&mock)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/users/test/items/top"))
+        .and(header("user-agent", "shared-agent/1.0"))
+        .respond_with(zotero_array_response("[]"))
+        .expect(1)
+        .mount(&mock)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/users/test/items"))
+        .and(header("user-agent", "shared-agent/1.0"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            r#"{"successful":{"0":{"key":"NEW12345","version":2}},"unchanged":{},"failed":{}}"#,
+        ))
+        .expect(1)
+        .mount(&mock)
+        .await;
+
+    let http = reqwest::Client::builder().user_agent("shared-agent/1.0").build().unwrap();
+    let server = PapersMcp::with_clients(
+        OpenAlexClient::new().with_base_url(mock.uri()).with_http_client(http.clone()),
+        ZoteroClient::new("test", "test-key").with_base_url(mock.uri()).with_http_client(http),
+    );
+    let params = serde_json::from_value(serde_json::json!({"id": "W2741809807"})).unwrap();
+    server.zotero_import_work(Parameters(params)).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_zotero_import_work_skips_existing_doi() {
+    let mock = MockServer::start().await;
+    mount_openalex_work("10.7717/peerj.4375").mount(&mock).await;
+    let existing = zotero_items_body().replace(
+        r#""title": "Test Paper""#,
+        r#""title": "Test Paper", "DOI": "10.7717/PEERJ.4375""#,
+    );
+    Mock::given(method("GET"))
+        .and(path("/users/test/items/top"))
+        .and(query_param("qmode", "everything"))
+        .respond_with(zotero_array_response(&existing))
+        .mount(&mock)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/users/test/items"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&mock)
+        .await;
+
+    let server = PapersMcp::with_clients(
+        OpenAlexClient::new().with_base_url(mock.uri()),
+        ZoteroClient::new("test", "test-key").with_base_url(mock.uri()),
+    );
+    let params = serde_json::from_value(serde_json::json!({"id": "W2741809807"})).unwrap();
+    let text = server.zotero_import_work(Parameters(params)).await.unwrap();
+    let json: serde_json::Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(json["status"], "already_exists");
+    assert_eq!(json["key"], "ABC12345");
+}
+
 #[tokio::test]
 async fn test_zotero_tag_list_always_uses_contains() {
     // qmode=contains is always sent, even without a search string
@@ -2334,9 +2609,9 @@ async fn test_zotero_collection_notes() {
 #[tokio::test]
 async fn test_zotero_collection_annotations() {
     let mock = MockServer::start().await;
-    // Return empty attachment list so the second step is skipped
+    // Return an empty item list so the later steps are skipped
     Mock::given(method("GET"))
-        .and(path("/users/test/collections/COL12345/items"))
+        .and(path("/users/test/collections/COL12345/items/top"))
         .respond_with(zotero_array_response("[]"))
         .mount(&mock)
         .await;
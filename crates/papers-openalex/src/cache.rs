@@ -1,8 +1,9 @@
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::DefaultHasher;
+use std::ffi::OsString;
 use std::hash::{Hash, Hasher};
 use std::io;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// Disk-based response cache with configurable TTL.
@@ -38,22 +39,52 @@ impl DiskCache {
         Ok(cache)
     }
 
-    /// Create a cache in the platform-standard cache directory.
+    /// Create a cache in the default location: `$PAPERS_CACHE_DIR` if set,
+    /// otherwise the platform-standard cache directory.
     ///
     /// - Linux: `~/.cache/papers/requests`
     /// - macOS: `~/Library/Caches/papers/requests`
     /// - Windows: `{FOLDERID_LocalAppData}/papers/requests`
     ///
-    /// Returns `Err` if no cache directory can be determined or created.
+    /// Returns `Err` if no cache directory can be determined, created, or
+    /// written to.
     pub fn default_location(ttl: Duration) -> io::Result<Self> {
-        let base = dirs::cache_dir().ok_or_else(|| {
+        Self::locate(None, ttl)
+    }
+
+    /// Create a cache in `dir` if given, else as [`default_location`](Self::default_location).
+    ///
+    /// Entries are written directly into the chosen directory, which is created
+    /// if missing. The directory is probed with a test write so a read-only
+    /// location fails here, with the path in the error, rather than on every
+    /// silently dropped [`set`](Self::set).
+    pub fn locate(dir: Option<PathBuf>, ttl: Duration) -> io::Result<Self> {
+        let cache_dir = resolve_cache_dir(dir, std::env::var_os(CACHE_DIR_ENV)).ok_or_else(|| {
             io::Error::new(io::ErrorKind::NotFound, "no platform cache directory")
         })?;
-        Self::new(base.join("papers").join("requests"), ttl)
+        let with_path = |e: io::Error| {
+            io::Error::new(
+                e.kind(),
+                format!("cache directory {} is not writable: {e}", cache_dir.display()),
+            )
+        };
+        std::fs::create_dir_all(&cache_dir).map_err(with_path)?;
+        let probe = cache_dir.join(".write-test.tmp");
+        std::fs::write(&probe, b"").map_err(with_path)?;
+        let _ = std::fs::remove_file(&probe);
+        Self::new(cache_dir, ttl)
+    }
+
+    /// The directory entries are stored in.
+    pub fn dir(&self) -> &Path {
+        &self.cache_dir
     }
 
     /// Look up a cached response.
     ///
+    /// Query pairs are sorted before hashing, so the same parameters given in
+    /// a different order hit the same entry.
+    ///
     /// Returns `None` on cache miss, expired entry, or any I/O / parse error.
     pub fn get(&self, url: &str, query: &[(&str, String)], body: Option<&str>) -> Option<String> {
         let key = cache_key(url, query, body);
@@ -149,6 +180,17 @@ impl DiskCache {
     }
 }
 
+/// Environment variable that overrides the default cache directory.
+pub const CACHE_DIR_ENV: &str = "PAPERS_CACHE_DIR";
+
+/// Pick the cache directory: explicit path, then a non-empty env value, then
+/// the platform default.
+fn resolve_cache_dir(explicit: Option<PathBuf>, env: Option<OsString>) -> Option<PathBuf> {
+    explicit
+        .or_else(|| env.filter(|v| !v.is_empty()).map(PathBuf::from))
+        .or_else(|| dirs::cache_dir().map(|d| d.join("papers").join("requests")))
+}
+
 /// Compute a deterministic cache key from (url, sorted query pairs, optional body).
 fn cache_key(url: &str, query: &[(&str, String)], body: Option<&str>) -> u64 {
     let mut sorted: Vec<(&str, &str)> = query.iter().map(|(k, v)| (*k, v.as_str())).collect();
@@ -188,6 +230,51 @@ mod tests {
         hasher.finish()
     }
 
+    #[test]
+    fn resolve_dir_prefers_explicit_then_env() {
+        let explicit = PathBuf::from("/flag/dir");
+        let env = OsString::from("/env/dir");
+        assert_eq!(
+            resolve_cache_dir(Some(explicit.clone()), Some(env.clone())),
+            Some(explicit)
+        );
+        assert_eq!(resolve_cache_dir(None, Some(env)), Some(PathBuf::from("/env/dir")));
+        // Empty env value is ignored
+        assert_eq!(
+            resolve_cache_dir(None, Some(OsString::new())),
+            dirs::cache_dir().map(|d| d.join("papers").join("requests"))
+        );
+    }
+
+    #[test]
+    fn locate_creates_dir_and_stores_entries_there() {
+        let dir = std::env::temp_dir()
+            .join("papers-test-cache")
+            .join(format!("{:x}", rand_u64()))
+            .join("nested");
+        let cache = DiskCache::locate(Some(dir.clone()), Duration::from_secs(60)).unwrap();
+        assert_eq!(cache.dir(), dir);
+        cache.set("http://x/works", &[], None, "body");
+        let files: Vec<_> = std::fs::read_dir(&dir)
+            .unwrap()
+            .flatten()
+            .map(|e| e.file_name().into_string().unwrap())
+            .collect();
+        assert_eq!(files.len(), 1);
+        assert!(files[0].ends_with(".json"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn locate_reports_unusable_dir() {
+        // A regular file where the directory should be
+        let file = std::env::temp_dir().join(format!("papers-test-cache-file-{:x}", rand_u64()));
+        std::fs::write(&file, b"").unwrap();
+        let err = DiskCache::locate(Some(file.clone()), Duration::from_secs(60)).unwrap_err();
+        assert!(err.to_string().contains(&file.display().to_string()));
+        std::fs::remove_file(&file).unwrap();
+    }
+
     #[test]
     fn key_is_deterministic() {
         let q = vec![("a", "1".into()), ("b", "2".into())];
@@ -225,6 +312,36 @@ mod tests {
         assert_eq!(cache_key("http://x", &q1, None), cache_key("http://x", &q2, None));
     }
 
+    #[test]
+    fn key_no_collision_across_pair_boundaries() {
+        // Concatenating key and value must not make distinct queries hash alike.
+        let q1 = vec![("ab", "c".into())];
+        let q2 = vec![("a", "bc".into())];
+        assert_ne!(cache_key("http://x", &q1, None), cache_key("http://x", &q2, None));
+        let q3 = vec![("a", "1".into()), ("b", "2".into())];
+        let q4 = vec![("a", "2".into()), ("b", "1".into())];
+        assert_ne!(cache_key("http://x", &q3, None), cache_key("http://x", &q4, None));
+    }
+
+    #[test]
+    fn reordered_query_hits_same_entry() {
+        let cache = temp_cache(60);
+        let set_q = vec![
+            ("filter", "publication_year:2020".into()),
+            ("per_page", "10".into()),
+            ("sort", "cited_by_count:desc".into()),
+        ];
+        let get_q = vec![
+            ("sort", "cited_by_count:desc".into()),
+            ("filter", "publication_year:2020".into()),
+            ("per_page", "10".into()),
+        ];
+        cache.set("http://x/works", &set_q, None, "cached works");
+        assert_eq!(cache.get("http://x/works", &get_q, None).as_deref(), Some("cached works"));
+        let entries = std::fs::read_dir(&cache.cache_dir).unwrap().count();
+        assert_eq!(entries, 1);
+    }
+
     #[test]
     fn set_get_roundtrip() {
         let cache = temp_cache(60);
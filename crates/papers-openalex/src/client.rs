@@ -1,9 +1,15 @@
 use crate::cache::DiskCache;
 use crate::error::{OpenAlexError, Result};
 use crate::params::{FindWorksParams, GetParams, ListParams};
+use crate::rate_limit::RateLimiter;
+use crate::single_flight::SingleFlight;
 use crate::response::{AutocompleteResponse, FindWorksResponse, ListResponse};
 use crate::types::*;
+pub use papers_http::DEFAULT_USER_AGENT;
+use papers_http::{body_snippet, redact, HttpClient};
 use serde::de::DeserializeOwned;
+use std::time::Instant;
+use tracing::Instrument;
 
 const DEFAULT_BASE_URL: &str = "https://api.openalex.org";
 
@@ -75,10 +81,12 @@ const DEFAULT_BASE_URL: &str = "https://api.openalex.org";
 /// ```
 #[derive(Clone)]
 pub struct OpenAlexClient {
-    http: reqwest::Client,
+    http: HttpClient,
     base_url: String,
     api_key: Option<String>,
     cache: Option<DiskCache>,
+    rate_limiter: Option<RateLimiter>,
+    in_flight: SingleFlight,
 }
 
 impl Default for OpenAlexClient {
@@ -98,10 +106,12 @@ impl OpenAlexClient {
     /// ```
     pub fn new() -> Self {
         Self {
-            http: reqwest::Client::new(),
+            http: HttpClient::default(),
             base_url: DEFAULT_BASE_URL.to_string(),
             api_key: std::env::var("OPENALEX_KEY").ok(),
             cache: None,
+            rate_limiter: None,
+            in_flight: SingleFlight::default(),
         }
     }
 
@@ -113,10 +123,12 @@ impl OpenAlexClient {
     /// ```
     pub fn with_api_key(api_key: impl Into<String>) -> Self {
         Self {
-            http: reqwest::Client::new(),
+            http: HttpClient::default(),
             base_url: DEFAULT_BASE_URL.to_string(),
             api_key: Some(api_key.into()),
             cache: None,
+            rate_limiter: None,
+            in_flight: SingleFlight::default(),
         }
     }
 
@@ -132,6 +144,35 @@ impl OpenAlexClient {
         self
     }
 
+    /// Use a caller-supplied HTTP client instead of the process-wide
+    /// [`shared_client`](papers_http::shared_client), e.g. one with a proxy or
+    /// custom timeouts. That client's own default headers decide the
+    /// `User-Agent` unless [`with_user_agent`](Self::with_user_agent) is also
+    /// set, in either order.
+    pub fn with_http_client(mut self, http: reqwest::Client) -> Self {
+        self.http.set_client(http);
+        self
+    }
+
+    /// Override the `User-Agent` header (default [`DEFAULT_USER_AGENT`]).
+    /// OpenAlex asks heavy users to identify themselves with a contact address.
+    ///
+    /// ```no_run
+    /// use papers_openalex::OpenAlexClient;
+    /// let client = OpenAlexClient::new()
+    ///     .with_user_agent("my-tool/1.0 (mailto:me@example.com)");
+    /// ```
+    pub fn with_user_agent(mut self, user_agent: &str) -> Self {
+        self.http.set_user_agent(user_agent);
+        self
+    }
+
+    /// Whether a non-empty API key is configured (required by
+    /// [`find_works`](Self::find_works)).
+    pub fn has_api_key(&self) -> bool {
+        self.api_key.as_deref().is_some_and(|k| !k.is_empty())
+    }
+
     /// Enable disk caching of successful responses.
     ///
     /// ```no_run
@@ -146,40 +187,149 @@ impl OpenAlexClient {
         self
     }
 
+    /// Throttle requests to `per_second` on average, allowing bursts of up
+    /// to `burst` back-to-back requests. OpenAlex allows 10 requests per
+    /// second (and a daily budget), so bulk crawls should stay below that.
+    ///
+    /// The limit is shared by all clones of this client, so concurrent
+    /// tasks and successive pages of a paginated crawl draw from one budget.
+    /// Responses served from the [disk cache](Self::with_cache) don't count.
+    ///
+    /// Panics if `per_second` is not positive or `burst` is zero.
+    ///
+    /// ```no_run
+    /// use papers_openalex::OpenAlexClient;
+    /// let client = OpenAlexClient::new().with_rate_limit(8.0, 4);
+    /// ```
+    pub fn with_rate_limit(mut self, per_second: f64, burst: u32) -> Self {
+        self.rate_limiter = Some(RateLimiter::new(per_second, burst));
+        self
+    }
+
     // ── Private helpers ────────────────────────────────────────────────
 
+    async fn throttle(&self) {
+        if let Some(limiter) = &self.rate_limiter {
+            limiter.acquire().await;
+        }
+    }
+
     fn append_api_key(&self, pairs: &mut Vec<(&str, String)>) {
         if let Some(key) = &self.api_key {
             pairs.push(("api_key", key.clone()));
         }
     }
 
+    /// Build an [`OpenAlexError::Api`] from a failed response body, masking
+    /// the API key in case the server echoed the request query back.
+    fn api_error(&self, status: u16, body: String) -> OpenAlexError {
+        OpenAlexError::Api {
+            status,
+            message: redact(&body, self.api_key.as_deref().unwrap_or_default()),
+        }
+    }
+
+    /// Parse a response body, naming the endpoint and quoting the start of the
+    /// body (key masked) if it doesn't match `T`.
+    fn parse_json<T: DeserializeOwned>(&self, path: &str, body: &str) -> Result<T> {
+        serde_json::from_str(body).map_err(|source| OpenAlexError::Json {
+            path: path.to_string(),
+            snippet: body_snippet(body, self.api_key.as_deref().unwrap_or_default()),
+            source,
+        })
+    }
+
+    /// Wrap a transport error, masking the API key in the request URL
+    /// reqwest attaches to it (the key is sent as a query parameter).
+    fn http_error(&self, e: reqwest::Error) -> OpenAlexError {
+        let key = self.api_key.as_deref().unwrap_or_default();
+        let redacted = e
+            .url()
+            .map(|u| u.as_str())
+            .filter(|u| !key.is_empty() && u.contains(key))
+            .map(|u| redact(u, key));
+        match redacted.map(|u| reqwest::Url::parse(&u)) {
+            Some(Ok(url)) => OpenAlexError::Http(e.with_url(url)),
+            Some(Err(_)) => OpenAlexError::Http(e.without_url()),
+            None => OpenAlexError::Http(e),
+        }
+    }
+
+    /// Render `url?query` for logging with the API key masked.
+    fn loggable_url(&self, url: &str, query: &[(&str, String)]) -> String {
+        let mut out = url.to_string();
+        for (i, (k, v)) in query.iter().enumerate() {
+            out.push(if i == 0 { '?' } else { '&' });
+            out.push_str(k);
+            out.push('=');
+            out.push_str(v);
+        }
+        redact(&out, self.api_key.as_deref().unwrap_or_default())
+    }
+
     async fn get_json<T: DeserializeOwned>(
         &self,
         path: &str,
-        mut query: Vec<(&str, String)>,
+        query: Vec<(&str, String)>,
     ) -> Result<T> {
+        let text = self.get_text(path, query).await?;
+        self.parse_json(path, &text)
+    }
+
+    /// GET `path` and return the response body, from the disk cache if it
+    /// holds a fresh copy.
+    async fn get_text(&self, path: &str, mut query: Vec<(&str, String)>) -> Result<String> {
         self.append_api_key(&mut query);
         let url = format!("{}{}", self.base_url, path);
         if let Some(cache) = &self.cache {
             if let Some(text) = cache.get(&url, &query, None) {
-                return serde_json::from_str(&text).map_err(OpenAlexError::Json);
+                return Ok(text);
             }
         }
-        let resp = self.http.get(&url).query(&query).send().await?;
+        // Concurrent identical GETs (same URL and query) share one request
+        let flight_key = self.loggable_url(&url, &query);
+        self.in_flight
+            .run(flight_key, || self.send_get(&url, &query))
+            .await
+    }
+
+    /// Send a GET and return the body of a successful response, storing it
+    /// in the disk cache if one is configured. Logs the status, body size, and
+    /// time to the end of the body under an `openalex_request` span.
+    async fn send_get(&self, url: &str, query: &[(&str, String)]) -> Result<String> {
+        self.throttle().await;
+        let span = tracing::debug_span!(
+            "openalex_request",
+            method = "GET",
+            url = %self.loggable_url(url, query),
+        );
+        let start = Instant::now();
+        let resp = self
+            .http
+            .get(url)
+            .query(query)
+            .send()
+            .instrument(span.clone())
+            .await
+            .map_err(|e| self.http_error(e))?;
         let status = resp.status();
+        let body = resp.text().instrument(span.clone()).await;
+        span.in_scope(|| {
+            tracing::debug!(
+                status = status.as_u16(),
+                bytes = body.as_ref().map_or(0, String::len),
+                elapsed_ms = start.elapsed().as_millis() as u64,
+                "response"
+            )
+        });
         if !status.is_success() {
-            let message = resp.text().await.unwrap_or_default();
-            return Err(OpenAlexError::Api {
-                status: status.as_u16(),
-                message,
-            });
+            return Err(self.api_error(status.as_u16(), body.unwrap_or_default()));
         }
-        let text = resp.text().await?;
+        let text = body.map_err(|e| self.http_error(e))?;
         if let Some(cache) = &self.cache {
-            cache.set(&url, &query, None, &text);
+            cache.set(url, query, None, &text);
         }
-        serde_json::from_str(&text).map_err(OpenAlexError::Json)
+        Ok(text)
     }
 
     async fn post_json<T: DeserializeOwned>(
@@ -193,23 +343,28 @@ impl OpenAlexClient {
         let body_str = body.to_string();
         if let Some(cache) = &self.cache {
             if let Some(text) = cache.get(&url, &query, Some(&body_str)) {
-                return serde_json::from_str(&text).map_err(OpenAlexError::Json);
+                return self.parse_json(path, &text);
             }
         }
-        let resp = self.http.post(&url).query(&query).json(&body).send().await?;
+        self.throttle().await;
+        let resp = self
+            .http
+            .post(&url)
+            .query(&query)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| self.http_error(e))?;
         let status = resp.status();
         if !status.is_success() {
             let message = resp.text().await.unwrap_or_default();
-            return Err(OpenAlexError::Api {
-                status: status.as_u16(),
-                message,
-            });
+            return Err(self.api_error(status.as_u16(), message));
         }
-        let text = resp.text().await?;
+        let text = resp.text().await.map_err(|e| self.http_error(e))?;
         if let Some(cache) = &self.cache {
             cache.set(&url, &query, Some(&body_str), &text);
         }
-        serde_json::from_str(&text).map_err(OpenAlexError::Json)
+        self.parse_json(path, &text)
     }
 
     async fn list_entities<T: DeserializeOwned>(
@@ -234,9 +389,22 @@ impl OpenAlexClient {
         &self,
         entity: &str,
         q: &str,
+    ) -> Result<AutocompleteResponse> {
+        self.autocomplete_entity_filtered(entity, q, None).await
+    }
+
+    async fn autocomplete_entity_filtered(
+        &self,
+        entity: &str,
+        q: &str,
+        filter: Option<String>,
     ) -> Result<AutocompleteResponse> {
         let path = format!("/autocomplete/{}", entity);
-        self.get_json(&path, vec![("q", q.to_string())]).await
+        let mut query = vec![("q", q.to_string())];
+        if let Some(filter) = filter {
+            query.push(("filter", filter));
+        }
+        self.get_json(&path, query).await
     }
 
     // ── List endpoints ─────────────────────────────────────────────────
@@ -873,6 +1041,20 @@ impl OpenAlexClient {
         self.autocomplete_entity("institutions", q).await
     }
 
+    /// Autocomplete for institutions in one country, for names shared across
+    /// countries ("Cambridge" matches institutions in both GB and US).
+    /// `country_code` is an ISO 3166-1 alpha-2 code such as `"GB"`.
+    ///
+    /// `GET /autocomplete/institutions?q=...&filter=country_code:...`
+    pub async fn autocomplete_institutions_in_country(
+        &self,
+        q: &str,
+        country_code: &str,
+    ) -> Result<AutocompleteResponse> {
+        let filter = format!("country_code:{}", country_code.trim().to_uppercase());
+        self.autocomplete_entity_filtered("institutions", q, Some(filter)).await
+    }
+
     /// Autocomplete for publishers. Searches display names. Returns up to 10
     /// results sorted by citation count. Hint shows country.
     ///
@@ -1020,14 +1202,34 @@ impl OpenAlexClient {
         self.post_json("/find/works", params.to_post_query_pairs(), body)
             .await
     }
+
+    // ── Raw responses ──────────────────────────────────────────────────
+
+    /// List any entity type (`"works"`, `"authors"`, ...) and return the
+    /// response body exactly as OpenAlex sent it, including fields the typed
+    /// entity structs don't model.
+    ///
+    /// `GET /{entity}`
+    pub async fn list_raw(&self, entity: &str, params: &ListParams) -> Result<String> {
+        self.get_text(&format!("/{entity}"), params.to_query_pairs()).await
+    }
+
+    /// Get a single entity and return the response body exactly as OpenAlex
+    /// sent it. See [`list_raw`](Self::list_raw).
+    ///
+    /// `GET /{entity}/{id}`
+    pub async fn get_raw(&self, entity: &str, id: &str, params: &GetParams) -> Result<String> {
+        self.get_text(&format!("/{entity}/{id}"), params.to_query_pairs()).await
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::cache::DiskCache;
+    use papers_http::testing::CapturedLogs;
     use std::time::Duration;
-    use wiremock::matchers::{method, path, query_param};
+    use wiremock::matchers::{header, method, path, query_param};
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
     fn minimal_list_json() -> String {
@@ -1324,6 +1526,27 @@ mod tests {
         assert_eq!(work.id, "https://openalex.org/W123");
     }
 
+    #[tokio::test]
+    async fn test_get_raw_returns_body_verbatim() {
+        let server = MockServer::start().await;
+        let body = r#"{"id": "https://openalex.org/W123", "referenced_works": ["https://openalex.org/W1"]}"#;
+        Mock::given(method("GET"))
+            .and(path("/works/W123"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/works"))
+            .and(query_param("search", "x"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(minimal_list_json()))
+            .mount(&server)
+            .await;
+        let client = setup_client(&server).await;
+        assert_eq!(client.get_raw("works", "W123", &GetParams::default()).await.unwrap(), body);
+        let params = ListParams { search: Some("x".into()), ..Default::default() };
+        assert_eq!(client.list_raw("works", &params).await.unwrap(), minimal_list_json());
+    }
+
     #[tokio::test]
     async fn test_get_author() {
         let server = MockServer::start().await;
@@ -1579,6 +1802,24 @@ mod tests {
         assert_eq!(resp.results.len(), 1);
     }
 
+    #[tokio::test]
+    async fn test_autocomplete_institutions_in_country() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/autocomplete/institutions"))
+            .and(query_param("q", "cambridge"))
+            .and(query_param("filter", "country_code:GB"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string(minimal_autocomplete_json()),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        let client = setup_client(&server).await;
+        let resp = client.autocomplete_institutions_in_country("cambridge", "gb").await.unwrap();
+        assert_eq!(resp.results.len(), 1);
+    }
+
     #[tokio::test]
     async fn test_autocomplete_publishers() {
         let server = MockServer::start().await;
@@ -1680,6 +1921,174 @@ mod tests {
         assert_eq!(resp.meta.count, 1);
     }
 
+    #[tokio::test]
+    async fn test_user_agent_header() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/works"))
+            .and(header("user-agent", DEFAULT_USER_AGENT))
+            .respond_with(ResponseTemplate::new(200).set_body_string(minimal_list_json()))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/works"))
+            .and(header("user-agent", "custom-agent/1.0"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(minimal_list_json()))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let client = OpenAlexClient::with_api_key("").with_base_url(server.uri());
+        client.list_works(&ListParams::default()).await.unwrap();
+        let client = client.with_user_agent("custom-agent/1.0");
+        client.list_works(&ListParams::default()).await.unwrap();
+        assert!(DEFAULT_USER_AGENT.starts_with("papers-mcp/"));
+    }
+
+    #[tokio::test]
+    async fn test_user_agent_survives_with_http_client() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/works"))
+            .and(header("user-agent", "custom-agent/1.0"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(minimal_list_json()))
+            .expect(2)
+            .mount(&server)
+            .await;
+        let client = OpenAlexClient::with_api_key("")
+            .with_base_url(server.uri())
+            .with_user_agent("custom-agent/1.0")
+            .with_http_client(reqwest::Client::new());
+        client.list_works(&ListParams::default()).await.unwrap();
+        let client = OpenAlexClient::with_api_key("")
+            .with_base_url(server.uri())
+            .with_http_client(reqwest::Client::new())
+            .with_user_agent("custom-agent/1.0");
+        client.list_works(&ListParams::default()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_spaces_requests_across_clones() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/works"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(minimal_list_json()))
+            .expect(6)
+            .mount(&server)
+            .await;
+        // Burst of 2, then one request every 50ms: the last of 6 starts at 200ms
+        let client = setup_client(&server).await.with_rate_limit(20.0, 2);
+        let start = std::time::Instant::now();
+        let handles: Vec<_> = (1..=6)
+            .map(|page| {
+                let client = client.clone();
+                let params = ListParams { page: Some(page), ..Default::default() };
+                tokio::spawn(async move { client.list_works(&params).await.unwrap() })
+            })
+            .collect();
+        for handle in handles {
+            handle.await.unwrap();
+        }
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(190), "finished too early: {elapsed:?}");
+        assert!(elapsed < Duration::from_secs(2), "finished too late: {elapsed:?}");
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_identical_gets_share_one_request() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/works/W1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(r#"{"id": "https://openalex.org/W1"}"#)
+                    .set_delay(Duration::from_millis(200)),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        let client = setup_client(&server).await;
+        let handles: Vec<_> = (0..10)
+            .map(|_| {
+                let client = client.clone();
+                tokio::spawn(async move { client.get_work("W1", &GetParams::default()).await })
+            })
+            .collect();
+        for handle in handles {
+            assert_eq!(handle.await.unwrap().unwrap().id, "https://openalex.org/W1");
+        }
+        server.verify().await;
+
+        // Once the shared call completes, the next one goes upstream again
+        server.reset().await;
+        Mock::given(method("GET"))
+            .and(path("/works/W1"))
+            .respond_with(ResponseTemplate::new(404).set_body_string("Not found"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let err = client.get_work("W1", &GetParams::default()).await.unwrap_err();
+        assert!(matches!(err, OpenAlexError::Api { status: 404, .. }));
+    }
+
+    #[tokio::test]
+    async fn test_api_error_redacts_key() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/works"))
+            .respond_with(
+                ResponseTemplate::new(400)
+                    .set_body_string("Invalid query parameters: api_key=test-key-123&foo=bar"),
+            )
+            .mount(&server)
+            .await;
+        let client = OpenAlexClient::with_api_key("test-key-123").with_base_url(server.uri());
+        let err = client.list_works(&ListParams::default()).await.unwrap_err();
+        match &err {
+            OpenAlexError::Api { status, message } => {
+                assert_eq!(*status, 400);
+                assert_eq!(message, "Invalid query parameters: api_key=[REDACTED]&foo=bar");
+            }
+            e => panic!("Expected Api error, got {e}"),
+        }
+        assert!(!err.to_string().contains("test-key-123"));
+    }
+
+    #[tokio::test]
+    async fn test_malformed_json_error_names_path() {
+        let server = MockServer::start().await;
+        let body = format!(r#"{{"id": 42, "note": "test-key-123", "pad": "{}"}}"#, "x".repeat(500));
+        Mock::given(method("GET"))
+            .and(path("/works/W1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .mount(&server)
+            .await;
+        let client = OpenAlexClient::with_api_key("test-key-123").with_base_url(server.uri());
+        let err = client.get_work("W1", &GetParams::default()).await.unwrap_err();
+        match &err {
+            OpenAlexError::Json { path, snippet, .. } => {
+                assert_eq!(path, "/works/W1");
+                assert!(snippet.starts_with(r#"{"id": 42"#), "snippet: {snippet}");
+                assert!(snippet.chars().count() <= 201, "snippet not bounded: {snippet}");
+            }
+            e => panic!("Expected Json error, got {e}"),
+        }
+        let msg = err.to_string();
+        assert!(msg.contains("/works/W1"), "no path in: {msg}");
+        assert!(!msg.contains("test-key-123"), "key leaked into: {msg}");
+    }
+
+    #[tokio::test]
+    async fn test_http_error_redacts_key_in_url() {
+        // Nothing listens on this port, so the request fails at the transport layer.
+        let client = OpenAlexClient::with_api_key("test-key-123").with_base_url("http://127.0.0.1:1");
+        let err = client.list_works(&ListParams::default()).await.unwrap_err();
+        assert!(matches!(err, OpenAlexError::Http(_)));
+        let msg = err.to_string();
+        assert!(msg.contains("127.0.0.1"), "url should be kept: {msg}");
+        assert!(!msg.contains("test-key-123"), "key leaked: {msg}");
+    }
+
     #[tokio::test]
     async fn test_error_404() {
         let server = MockServer::start().await;
@@ -1823,4 +2232,32 @@ mod tests {
         // Second call served from cache
         client.find_works_post(&params).await.unwrap();
     }
+
+    // ── Tracing tests ──────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_request_span_emitted_with_key_redacted() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/works"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(minimal_list_json()))
+            .mount(&server)
+            .await;
+        let logs = CapturedLogs::default();
+        let _guard = logs.install();
+
+        let client = OpenAlexClient::with_api_key("secret-key-123").with_base_url(server.uri());
+        client.list_works(&ListParams::default()).await.unwrap();
+
+        let out = logs.contents();
+        assert!(out.contains("openalex_request"), "no request span in: {out}");
+        assert!(out.contains("method=\"GET\""), "no method in: {out}");
+        assert!(out.contains("/works"), "no url in: {out}");
+        assert!(out.contains("status=200"), "no status in: {out}");
+        let bytes = format!("bytes={}", minimal_list_json().len());
+        assert!(out.contains(&bytes), "no body size in: {out}");
+        assert!(out.contains("elapsed_ms="), "no timing in: {out}");
+        assert!(out.contains("api_key=[REDACTED]"), "key not masked in: {out}");
+        assert!(!out.contains("secret-key-123"), "key leaked into logs: {out}");
+    }
 }
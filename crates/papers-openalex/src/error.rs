@@ -5,7 +5,7 @@
 /// - [`Http`](OpenAlexError::Http) — network or connection failure (wraps
 ///   [`reqwest::Error`])
 /// - [`Json`](OpenAlexError::Json) — response body could not be deserialized
-///   (wraps [`serde_json::Error`])
+///   (wraps [`serde_json::Error`] with the endpoint path and a body snippet)
 /// - [`Api`](OpenAlexError::Api) — the OpenAlex API returned a non-success HTTP
 ///   status code (e.g. 404 for unknown entity, 403 for forbidden, 500 for
 ///   server error)
@@ -34,17 +34,32 @@ pub enum OpenAlexError {
     Http(#[from] reqwest::Error),
 
     /// Failed to deserialize the JSON response body.
-    #[error("JSON error: {0}")]
-    Json(#[from] serde_json::Error),
+    ///
+    /// `path` is the endpoint that returned the body and `snippet` its first
+    /// 200 characters, with the API key masked as `[REDACTED]`. Both are
+    /// empty when converted from a bare [`serde_json::Error`] with `?`.
+    #[error("JSON error from {path}: {source} (body: {snippet:?})")]
+    Json {
+        path: String,
+        snippet: String,
+        #[source]
+        source: serde_json::Error,
+    },
 
     /// The API returned a non-success HTTP status code.
     ///
     /// `status` is the HTTP status code (e.g. 404, 403, 500) and `message`
-    /// contains the response body text.
+    /// contains the response body text, with the API key masked as `[REDACTED]`.
     #[error("API error (status {status}): {message}")]
     Api { status: u16, message: String },
 }
 
+impl From<serde_json::Error> for OpenAlexError {
+    fn from(source: serde_json::Error) -> Self {
+        Self::Json { path: String::new(), snippet: String::new(), source }
+    }
+}
+
 /// A [`Result`](std::result::Result) alias with [`OpenAlexError`] as the error
 /// type.
 pub type Result<T> = std::result::Result<T, OpenAlexError>;
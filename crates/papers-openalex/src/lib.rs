@@ -61,11 +61,13 @@ pub mod cache;
 pub mod client;
 pub mod error;
 pub mod params;
+mod rate_limit;
+mod single_flight;
 pub mod response;
 pub mod types;
 
-pub use cache::DiskCache;
-pub use client::OpenAlexClient;
+pub use cache::{DiskCache, CACHE_DIR_ENV};
+pub use client::{OpenAlexClient, DEFAULT_USER_AGENT};
 pub use error::{OpenAlexError, Result};
 pub use params::{FindWorksParams, GetParams, ListParams};
 pub use response::{
@@ -0,0 +1,55 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Token-bucket limiter shared by every clone of an
+/// [`OpenAlexClient`](crate::OpenAlexClient).
+///
+/// The bucket holds up to `burst` tokens and refills at `per_second`. Each
+/// request takes one token; when none is left the caller reserves the next
+/// one (the balance goes negative) and sleeps until it is due, so concurrent
+/// callers queue up in order rather than all waking at once.
+#[derive(Debug, Clone)]
+pub(crate) struct RateLimiter {
+    per_second: f64,
+    burst: f64,
+    bucket: Arc<Mutex<Bucket>>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl RateLimiter {
+    /// Panics unless `per_second` is positive and finite and `burst` is at
+    /// least 1.
+    pub(crate) fn new(per_second: f64, burst: u32) -> Self {
+        assert!(per_second.is_finite() && per_second > 0.0, "rate limit must be positive");
+        assert!(burst >= 1, "rate limit burst must be at least 1");
+        let burst = f64::from(burst);
+        Self {
+            per_second,
+            burst,
+            bucket: Arc::new(Mutex::new(Bucket { tokens: burst, refilled_at: Instant::now() })),
+        }
+    }
+
+    /// Wait until a request may be sent.
+    pub(crate) async fn acquire(&self) {
+        let wait = {
+            let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+            let now = Instant::now();
+            let refill = now.duration_since(bucket.refilled_at).as_secs_f64() * self.per_second;
+            bucket.tokens = (bucket.tokens + refill).min(self.burst);
+            bucket.refilled_at = now;
+            bucket.tokens -= 1.0;
+            (bucket.tokens < 0.0).then(|| Duration::from_secs_f64(-bucket.tokens / self.per_second))
+        };
+        if let Some(wait) = wait {
+            tracing::trace!(wait_ms = wait.as_millis() as u64, "rate limited");
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
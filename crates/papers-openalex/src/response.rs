@@ -36,6 +36,13 @@ pub struct ListResponse<T> {
     pub group_by: Vec<GroupByResult>,
 }
 
+impl<T> ListResponse<T> {
+    /// Whether this is the final page of results. See [`ListMeta::is_last_page`].
+    pub fn is_last_page(&self) -> bool {
+        self.meta.is_last_page()
+    }
+}
+
 /// Metadata returned with every list response.
 ///
 /// ```json
@@ -65,6 +72,28 @@ pub struct ListMeta {
     pub groups_count: Option<i64>,
 }
 
+impl ListMeta {
+    /// Whether no further page follows this one.
+    ///
+    /// With offset pagination (`page` set) this is true once
+    /// `page * per_page >= count`. With cursor pagination (`page` is `None`)
+    /// it is true when `next_cursor` is `None`.
+    pub fn is_last_page(&self) -> bool {
+        match (self.page, self.per_page) {
+            (Some(page), Some(per_page)) => i64::from(page) * i64::from(per_page) >= self.count,
+            _ => self.next_cursor.is_none(),
+        }
+    }
+
+    /// Number of pages needed to cover `count` at `per_page` results each, or
+    /// `None` when `per_page` is missing or not positive. Zero when nothing
+    /// matched.
+    pub fn total_pages(&self) -> Option<i64> {
+        let per_page = i64::from(self.per_page.filter(|&n| n > 0)?);
+        Some((self.count.max(0) + per_page - 1) / per_page)
+    }
+}
+
 /// A single group in a `group_by` aggregation result.
 ///
 /// ```json
@@ -252,6 +281,44 @@ mod tests {
         );
     }
 
+    fn meta(count: i64, page: Option<i32>, per_page: Option<i32>, next_cursor: Option<&str>) -> ListMeta {
+        ListMeta {
+            count,
+            db_response_time_ms: 0,
+            page,
+            per_page,
+            next_cursor: next_cursor.map(str::to_string),
+            groups_count: None,
+        }
+    }
+
+    #[test]
+    fn test_is_last_page_offset() {
+        assert!(meta(0, Some(1), Some(25), None).is_last_page());
+        assert!(meta(25, Some(1), Some(25), None).is_last_page());
+        assert!(!meta(26, Some(1), Some(25), None).is_last_page());
+        assert!(meta(26, Some(2), Some(25), None).is_last_page());
+        assert!(!meta(51, Some(2), Some(25), None).is_last_page());
+        assert!(meta(10, Some(5), Some(25), None).is_last_page());
+    }
+
+    #[test]
+    fn test_is_last_page_cursor() {
+        assert!(!meta(100, None, Some(25), Some("abc")).is_last_page());
+        assert!(meta(100, None, Some(25), None).is_last_page());
+    }
+
+    #[test]
+    fn test_total_pages() {
+        assert_eq!(meta(0, Some(1), Some(25), None).total_pages(), Some(0));
+        assert_eq!(meta(1, Some(1), Some(25), None).total_pages(), Some(1));
+        assert_eq!(meta(25, Some(1), Some(25), None).total_pages(), Some(1));
+        assert_eq!(meta(26, Some(1), Some(25), None).total_pages(), Some(2));
+        assert_eq!(meta(288286684, Some(1), Some(1), None).total_pages(), Some(288286684));
+        assert_eq!(meta(10, Some(1), None, None).total_pages(), None);
+        assert_eq!(meta(10, Some(1), Some(0), None).total_pages(), None);
+    }
+
     #[test]
     fn test_deserialize_list_with_group_by() {
         let json = r#"{
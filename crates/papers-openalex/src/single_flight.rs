@@ -0,0 +1,70 @@
+use crate::error::{OpenAlexError, Result};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use tokio::sync::OnceCell;
+
+/// Coalesces concurrent identical GETs into one upstream request.
+///
+/// The first caller for a key (the leader) sends the request; callers that
+/// arrive while it is in flight wait for and share its response body. The
+/// entry is dropped once the leader finishes, so later calls fetch afresh.
+/// Shared by every clone of an [`OpenAlexClient`](crate::OpenAlexClient).
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SingleFlight {
+    calls: Arc<Mutex<HashMap<String, Arc<OnceCell<Shared>>>>>,
+}
+
+/// What waiters receive from the leader's request.
+#[derive(Debug, Clone)]
+enum Shared {
+    Body(String),
+    Api { status: u16, message: String },
+    /// A transport or decode error, which can't be cloned; waiters retry on
+    /// their own.
+    Failed,
+}
+
+impl SingleFlight {
+    /// Run `fetch` for `key` unless an identical call is already in flight,
+    /// in which case wait for that call's result instead.
+    pub(crate) async fn run<F, Fut>(&self, key: String, fetch: F) -> Result<String>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<String>>,
+    {
+        let cell = {
+            let mut calls = self.calls.lock().unwrap_or_else(|e| e.into_inner());
+            calls.entry(key.clone()).or_default().clone()
+        };
+        let mut own = None;
+        let shared = cell
+            .get_or_init(|| async {
+                let result = fetch().await;
+                let shared = match &result {
+                    Ok(body) => Shared::Body(body.clone()),
+                    Err(OpenAlexError::Api { status, message }) => {
+                        Shared::Api { status: *status, message: message.clone() }
+                    }
+                    Err(_) => Shared::Failed,
+                };
+                own = Some(result);
+                shared
+            })
+            .await
+            .clone();
+
+        if let Some(result) = own {
+            let mut calls = self.calls.lock().unwrap_or_else(|e| e.into_inner());
+            if calls.get(&key).is_some_and(|c| Arc::ptr_eq(c, &cell)) {
+                calls.remove(&key);
+            }
+            return result;
+        }
+        match shared {
+            Shared::Body(body) => Ok(body),
+            Shared::Api { status, message } => Err(OpenAlexError::Api { status, message }),
+            Shared::Failed => fetch().await,
+        }
+    }
+}
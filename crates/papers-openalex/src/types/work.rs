@@ -265,6 +265,19 @@ pub struct Work {
     pub created_date: Option<String>,
 }
 
+impl Work {
+    /// URL of the best open-access full text: the PDF link of
+    /// [`best_oa_location`](Self::best_oa_location), else
+    /// [`open_access.oa_url`](OpenAccess::oa_url). The fallback may point to
+    /// a landing page rather than a PDF, so check the response content type.
+    pub fn best_oa_pdf_url(&self) -> Option<&str> {
+        self.best_oa_location
+            .as_ref()
+            .and_then(|l| l.pdf_url.as_deref())
+            .or_else(|| self.open_access.as_ref()?.oa_url.as_deref())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -292,6 +305,25 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_best_oa_pdf_url() {
+        let json = include_str!("../../tests/fixtures/work.json");
+        let work: Work = serde_json::from_str(json).unwrap();
+        // The fixture's best OA location has no pdf_url
+        assert_eq!(work.best_oa_pdf_url(), Some("https://doi.org/10.7717/peerj.4375"));
+
+        let work: Work = serde_json::from_value(serde_json::json!({
+            "id": "https://openalex.org/W1",
+            "best_oa_location": {"pdf_url": "https://arxiv.org/pdf/1234.pdf"},
+            "open_access": {"is_oa": true, "oa_url": "https://arxiv.org/abs/1234"}
+        }))
+        .unwrap();
+        assert_eq!(work.best_oa_pdf_url(), Some("https://arxiv.org/pdf/1234.pdf"));
+
+        let closed: Work = serde_json::from_value(serde_json::json!({"id": "https://openalex.org/W2"})).unwrap();
+        assert_eq!(closed.best_oa_pdf_url(), None);
+    }
+
     #[test]
     fn test_abstract_text_reconstructed() {
         let json = include_str!("../../tests/fixtures/work.json");
@@ -307,6 +339,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_unfamiliar_type_value_parses() {
+        let json = r#"{"id":"https://openalex.org/W1","type":"hologram","type_crossref":"not-a-crossref-type"}"#;
+        let work: Work = serde_json::from_str(json).expect("unknown type should not fail parsing");
+        assert_eq!(work.r#type.as_deref(), Some("hologram"));
+        assert_eq!(work.type_crossref.as_deref(), Some("not-a-crossref-type"));
+    }
+
     #[test]
     fn test_abstract_text_none_when_absent() {
         let json = r#"{"id":"https://openalex.org/W1","display_name":"Test"}"#;
@@ -11,6 +11,14 @@ use std::time::{Duration, SystemTime, UNIX_EPOCH};
 /// query parameters, and optional POST body. Expired entries are treated as
 /// cache misses and silently ignored.
 ///
+/// # Revalidation
+///
+/// Entries stored with a library version ([`DiskCache::set_with_version`])
+/// outlive their TTL for [`REVALIDATE_RETENTION`]: once expired they are no
+/// longer served by [`DiskCache::get`], but [`DiskCache::get_with_version`]
+/// still returns them so the client can ask Zotero whether anything changed
+/// (`If-Modified-Since-Version`) instead of downloading the body again.
+///
 /// # Atomic writes
 ///
 /// Writes use a temporary file + rename pattern to prevent partial reads from
@@ -21,10 +29,17 @@ pub struct DiskCache {
     ttl: Duration,
 }
 
+/// How long entries stored with a version are kept for revalidation, when
+/// that is longer than the TTL.
+pub const REVALIDATE_RETENTION: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
 #[derive(Serialize, Deserialize)]
 struct CacheEntry {
     ts: u64,
     body: String,
+    /// `Last-Modified-Version` of the cached response, if it had one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    version: Option<u64>,
 }
 
 impl DiskCache {
@@ -56,10 +71,7 @@ impl DiskCache {
     ///
     /// Returns `None` on cache miss, expired entry, or any I/O / parse error.
     pub fn get(&self, url: &str, query: &[(&str, String)], body: Option<&str>) -> Option<String> {
-        let key = cache_key(url, query, body);
-        let path = self.cache_dir.join(format!("{key:016x}.json"));
-        let data = std::fs::read_to_string(&path).ok()?;
-        let entry: CacheEntry = serde_json::from_str(&data).ok()?;
+        let entry = self.read_entry(url, query, body)?;
         let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
         if now.saturating_sub(entry.ts) > self.ttl.as_secs() {
             return None;
@@ -67,12 +79,47 @@ impl DiskCache {
         Some(entry.body)
     }
 
+    /// Look up a response stored with a version, expired or not.
+    ///
+    /// Returns the cached body and its version, for a conditional request
+    /// that decides whether the body is still current.
+    pub fn get_with_version(
+        &self,
+        url: &str,
+        query: &[(&str, String)],
+        body: Option<&str>,
+    ) -> Option<(String, u64)> {
+        let entry = self.read_entry(url, query, body)?;
+        Some((entry.body, entry.version?))
+    }
+
+    fn read_entry(&self, url: &str, query: &[(&str, String)], body: Option<&str>) -> Option<CacheEntry> {
+        let key = cache_key(url, query, body);
+        let path = self.cache_dir.join(format!("{key:016x}.json"));
+        let data = std::fs::read_to_string(&path).ok()?;
+        serde_json::from_str(&data).ok()
+    }
+
     /// Store a response in the cache.
     ///
     /// Writes atomically via a `.tmp` file + rename. Errors are silently
     /// ignored — a failed cache write should never break a request.
     pub fn set(&self, url: &str, query: &[(&str, String)], body: Option<&str>, response: &str) {
-        let _ = self.set_inner(url, query, body, response);
+        self.set_with_version(url, query, body, response, None);
+    }
+
+    /// Store a response along with its `Last-Modified-Version`, making it
+    /// available to [`DiskCache::get_with_version`] after it expires.
+    /// Storing an entry again restarts its TTL.
+    pub fn set_with_version(
+        &self,
+        url: &str,
+        query: &[(&str, String)],
+        body: Option<&str>,
+        response: &str,
+        version: Option<u64>,
+    ) {
+        let _ = self.set_inner(url, query, body, response, version);
     }
 
     fn set_inner(
@@ -81,6 +128,7 @@ impl DiskCache {
         query: &[(&str, String)],
         body: Option<&str>,
         response: &str,
+        version: Option<u64>,
     ) -> io::Result<()> {
         let key = cache_key(url, query, body);
         let ts = SystemTime::now()
@@ -90,6 +138,7 @@ impl DiskCache {
         let entry = CacheEntry {
             ts,
             body: response.to_string(),
+            version,
         };
         let json = serde_json::to_string(&entry).map_err(io::Error::other)?;
         let tmp_path = self.cache_dir.join(format!("{key:016x}.tmp"));
@@ -100,6 +149,8 @@ impl DiskCache {
     }
 
     /// Remove expired entries and leftover `.tmp` files from the cache directory.
+    /// Entries with a version are kept until [`REVALIDATE_RETENTION`] if that
+    /// is longer than the TTL.
     ///
     /// Called automatically on construction. Errors on individual files are
     /// silently ignored.
@@ -141,7 +192,11 @@ impl DiskCache {
                     continue;
                 }
             };
-            if now.saturating_sub(entry.ts) > self.ttl.as_secs() {
+            let keep = match entry.version {
+                Some(_) => self.ttl.max(REVALIDATE_RETENTION),
+                None => self.ttl,
+            };
+            if now.saturating_sub(entry.ts) > keep.as_secs() {
                 let _ = std::fs::remove_file(&path);
             }
         }
@@ -229,4 +284,17 @@ mod tests {
         sleep(Duration::from_secs(2));
         assert!(cache.get("http://x", &q, None).is_none());
     }
+
+    #[test]
+    fn versioned_entry_survives_expiry_for_revalidation() {
+        let cache = temp_cache(0);
+        let q: Vec<(&str, String)> = vec![];
+        cache.set_with_version("http://x", &q, None, "versioned", Some(100));
+        cache.set("http://y", &q, None, "unversioned");
+        sleep(Duration::from_millis(1100));
+        cache.prune();
+        assert!(cache.get("http://x", &q, None).is_none());
+        assert_eq!(cache.get_with_version("http://x", &q, None), Some(("versioned".into(), 100)));
+        assert!(cache.get_with_version("http://y", &q, None).is_none());
+    }
 }
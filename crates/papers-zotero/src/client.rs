@@ -1,12 +1,157 @@
 use crate::cache::DiskCache;
 use crate::error::{Result, ZoteroError};
 use crate::params::{CollectionListParams, DeletedParams, FulltextParams, ItemListParams, TagListParams};
-use crate::response::{PagedResponse, VersionedResponse};
+use crate::response::{BatchResponse, ChunkFailure, PagedResponse, VersionedResponse};
 use crate::types::*;
+pub use papers_http::DEFAULT_USER_AGENT;
+use papers_http::{body_snippet, redact, HttpClient};
 use serde::de::DeserializeOwned;
 use std::collections::HashMap;
+use std::time::Instant;
+use tokio_util::sync::CancellationToken;
+use tracing::Instrument;
 
 const DEFAULT_BASE_URL: &str = "https://api.zotero.org";
+/// Zotero's local API, served by the desktop app when "Enable Local API" is on.
+const LOCAL_BASE_URL: &str = "http://127.0.0.1:23119/api";
+/// Environment variable forcing a [`Transport`].
+pub const TRANSPORT_ENV: &str = "ZOTERO_TRANSPORT";
+/// Environment variable replacing the local API base URL, for a non-default
+/// port or a forwarded host (e.g. `http://192.168.1.5:23119/api`).
+pub const LOCAL_URL_ENV: &str = "ZOTERO_LOCAL_URL";
+const DEFAULT_API_VERSION: u8 = 3;
+/// Maximum number of keys the API accepts in a single `itemKey` filter.
+const ITEM_KEY_CHUNK: usize = 50;
+/// Read-modify-write attempts [`ZoteroClient::update_item_tags`] makes when
+/// the item keeps changing underneath it.
+const TAG_UPDATE_ATTEMPTS: usize = 3;
+
+/// How [`ZoteroClient::from_env_with_transport`] reaches Zotero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Transport {
+    /// The local API if it answers, otherwise the web API.
+    #[default]
+    Auto,
+    /// Always the local API; an error if it isn't reachable.
+    Local,
+    /// Always the web API, without probing for a running Zotero.
+    Remote,
+}
+
+impl Transport {
+    /// Read [`TRANSPORT_ENV`] (`auto`, `local`, or `remote`, any case). Unset
+    /// or empty means [`Transport::Auto`].
+    pub fn from_env() -> Result<Self> {
+        match std::env::var(TRANSPORT_ENV) {
+            Ok(v) if !v.trim().is_empty() => v.parse().map_err(|_| ZoteroError::InvalidEnv {
+                var: TRANSPORT_ENV,
+                value: v.trim().to_string(),
+                expected: "auto, local, or remote".into(),
+            }),
+            _ => Ok(Self::Auto),
+        }
+    }
+}
+
+impl std::str::FromStr for Transport {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "local" => Ok(Self::Local),
+            "remote" => Ok(Self::Remote),
+            other => Err(format!("{TRANSPORT_ENV} must be auto, local, or remote (got {other:?})")),
+        }
+    }
+}
+
+/// Where [`ZoteroClient::from_env_with_transport`] ends up for a given probe
+/// result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Resolution {
+    /// The local API.
+    Local,
+    /// The web API.
+    Remote,
+    /// Fails with [`ZoteroError::NotRunning`].
+    NotRunning,
+    /// Fails with [`ZoteroError::LocalUnavailable`].
+    LocalUnavailable,
+}
+
+/// What [`ZoteroClient::diagnose`] found.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct Diagnosis {
+    /// `ZOTERO_USER_ID` is set and non-empty.
+    pub user_id_set: bool,
+    /// `ZOTERO_API_KEY` is set and non-empty.
+    pub api_key_set: bool,
+    /// The transport that was asked for.
+    pub transport: Transport,
+    /// Base URL of the local API that was probed.
+    pub local_url: String,
+    /// The local API answered the probe with a success status.
+    pub local_reachable: bool,
+    /// Path of the Zotero executable, if one was found on disk.
+    pub installed_path: Option<String>,
+    /// `false` when `ZOTERO_CHECK_LAUNCHED=0` turns off the not-running check.
+    pub check_launched: bool,
+    /// Where connecting would end up (once credentials are set).
+    pub resolution: Resolution,
+}
+
+/// The detection rules behind [`ZoteroClient::from_env_with_transport`]: a
+/// reachable local API wins unless the web API is forced; otherwise an
+/// installed-but-unreachable Zotero is an error in `Auto` mode (unless
+/// `check_launched` is off), and forced-local always is.
+fn resolve_transport(transport: Transport, local_ok: bool, installed: bool, check_launched: bool) -> Resolution {
+    match transport {
+        Transport::Remote => Resolution::Remote,
+        _ if local_ok => Resolution::Local,
+        Transport::Local => Resolution::LocalUnavailable,
+        Transport::Auto if installed && check_launched => Resolution::NotRunning,
+        Transport::Auto => Resolution::Remote,
+    }
+}
+
+/// Whether the local API at `local_base` answers a cheap request for `user_id`.
+async fn probe_local(local_base: &str, user_id: &str) -> bool {
+    reqwest::Client::new()
+        .get(format!("{local_base}/users/{user_id}/items?limit=0"))
+        .timeout(std::time::Duration::from_millis(500))
+        .send()
+        .await
+        .is_ok_and(|r| r.status().is_success())
+}
+
+/// The local API base URL: [`LOCAL_URL_ENV`] if set, else Zotero's default.
+fn local_base_url() -> Result<String> {
+    match std::env::var(LOCAL_URL_ENV) {
+        Ok(v) if !v.trim().is_empty() => parse_local_url(v.trim()),
+        _ => Ok(LOCAL_BASE_URL.to_string()),
+    }
+}
+
+/// Accept an absolute `http`/`https` URL with a host, without its trailing `/`.
+fn parse_local_url(s: &str) -> Result<String> {
+    let valid = reqwest::Url::parse(s).is_ok_and(|u| matches!(u.scheme(), "http" | "https") && u.host_str().is_some());
+    if !valid {
+        return Err(ZoteroError::InvalidEnv {
+            var: LOCAL_URL_ENV,
+            value: s.to_string(),
+            expected: format!("an http(s) URL such as {LOCAL_BASE_URL}"),
+        });
+    }
+    Ok(s.trim_end_matches('/').to_string())
+}
+
+/// `false` when `ZOTERO_CHECK_LAUNCHED=0` opts out of the not-running check.
+fn check_launched() -> bool {
+    std::env::var("ZOTERO_CHECK_LAUNCHED").as_deref() != Ok("0")
+}
 
 /// Returns the path to the Zotero executable if it is found on disk, or
 /// `None` if Zotero does not appear to be installed.
@@ -80,10 +225,11 @@ fn find_zotero_exe() -> Option<String> {
 /// ```
 #[derive(Clone)]
 pub struct ZoteroClient {
-    http: reqwest::Client,
+    http: HttpClient,
     base_url: String,
     user_id: String,
     api_key: String,
+    api_version: u8,
     cache: Option<DiskCache>,
 }
 
@@ -91,10 +237,11 @@ impl ZoteroClient {
     /// Create a new client with explicit user ID and API key.
     pub fn new(user_id: impl Into<String>, api_key: impl Into<String>) -> Self {
         Self {
-            http: reqwest::Client::new(),
+            http: HttpClient::default(),
             base_url: DEFAULT_BASE_URL.to_string(),
             user_id: user_id.into(),
             api_key: api_key.into(),
+            api_version: DEFAULT_API_VERSION,
             cache: None,
         }
     }
@@ -117,12 +264,25 @@ impl ZoteroClient {
 
     /// Create a client from environment variables, preferring the local Zotero
     /// API (`http://localhost:23119`) if it is running and has the local API
-    /// enabled. Falls back to the web API with disk cache when unavailable.
+    /// enabled. Falls back to the web API when unavailable. Setting
+    /// [`TRANSPORT_ENV`] to `local` or `remote` forces one instead (see
+    /// [`from_env_with_transport`](Self::from_env_with_transport)).
     ///
     /// Use this instead of `from_env()` for interactive tools where low latency
     /// matters. The local API requires "Enable Local API" to be turned on in
     /// Zotero → Settings → Advanced.
     pub async fn from_env_prefer_local() -> Result<Self> {
+        Self::from_env_with_transport(Transport::from_env()?).await
+    }
+
+    /// Create a client from `ZOTERO_USER_ID` and `ZOTERO_API_KEY`, reaching
+    /// Zotero as `transport` says. The local API is probed at
+    /// [`LOCAL_URL_ENV`] when set, else `http://127.0.0.1:23119/api`.
+    ///
+    /// [`Transport::Local`] fails with [`ZoteroError::LocalUnavailable`] when
+    /// the local API doesn't answer; [`Transport::Remote`] skips the probe and
+    /// the installed-but-not-running check.
+    pub async fn from_env_with_transport(transport: Transport) -> Result<Self> {
         let user_id = std::env::var("ZOTERO_USER_ID").map_err(|_| ZoteroError::Api {
             status: 0,
             message: "ZOTERO_USER_ID environment variable not set".into(),
@@ -131,33 +291,18 @@ impl ZoteroClient {
             status: 0,
             message: "ZOTERO_API_KEY environment variable not set".into(),
         })?;
+        Self::connect(user_id, api_key, transport, &local_base_url()?).await
+    }
 
-        const LOCAL_BASE: &str = "http://127.0.0.1:23119/api";
-        let probe_url = format!("{LOCAL_BASE}/users/{user_id}/items?limit=0");
-        let local_ok = reqwest::Client::new()
-            .get(&probe_url)
-            .timeout(std::time::Duration::from_millis(500))
-            .send()
-            .await
-            .map(|r| r.status().is_success())
-            .unwrap_or(false);
-
-        if local_ok {
+    /// Pick the base URL for `transport`, probing the local API at `local_base`.
+    async fn connect(user_id: String, api_key: String, transport: Transport, local_base: &str) -> Result<Self> {
+        let local_ok = transport != Transport::Remote && probe_local(local_base, &user_id).await;
+        let installed = if transport == Transport::Auto && !local_ok { find_zotero_exe() } else { None };
+        match resolve_transport(transport, local_ok, installed.is_some(), check_launched()) {
             // Local API is up — no cache needed, it's all in-process on this machine.
-            Ok(Self::new(user_id, api_key).with_base_url(LOCAL_BASE))
-        } else {
-            // If Zotero is installed but not running, surface an actionable error
-            // rather than silently falling back to the slower remote API.
-            // Set ZOTERO_CHECK_LAUNCHED=0 to opt out of this check.
-            let skip_check = std::env::var("ZOTERO_CHECK_LAUNCHED")
-                .map(|v| v == "0")
-                .unwrap_or(false);
-            if !skip_check {
-                if let Some(path) = find_zotero_exe() {
-                    return Err(ZoteroError::NotRunning { path });
-                }
-            }
-            // Zotero not found on disk — fall back to the web API.
+            Resolution::Local => Ok(Self::new(user_id, api_key).with_base_url(local_base)),
+            Resolution::LocalUnavailable => Err(ZoteroError::LocalUnavailable { url: local_base.to_string() }),
+            Resolution::NotRunning => Err(ZoteroError::NotRunning { path: installed.unwrap_or_default() }),
             // Cache disabled: write operations (upload/download) would make
             // subsequent list calls return stale results within the TTL window.
             // Re-enable once cache invalidation on write is implemented.
@@ -165,7 +310,36 @@ impl ZoteroClient {
             // if let Ok(c) = DiskCache::default_location(std::time::Duration::from_secs(60)) {
             //     client = client.with_cache(c);
             // }
-            Ok(Self::new(user_id, api_key))
+            Resolution::Remote => Ok(Self::new(user_id, api_key)),
+        }
+    }
+
+    /// Report how [`from_env_with_transport`](Self::from_env_with_transport)
+    /// would connect with `transport`, without failing on missing credentials.
+    ///
+    /// Always probes the local API and looks for an installed Zotero, even
+    /// when `transport` wouldn't, so the report is complete. Fails only when
+    /// [`LOCAL_URL_ENV`] isn't a valid URL.
+    pub async fn diagnose(transport: Transport) -> Result<Diagnosis> {
+        Ok(Self::diagnose_at(transport, &local_base_url()?).await)
+    }
+
+    async fn diagnose_at(transport: Transport, local_base: &str) -> Diagnosis {
+        let user_id = std::env::var("ZOTERO_USER_ID").ok().filter(|v| !v.is_empty());
+        let api_key_set = std::env::var("ZOTERO_API_KEY").is_ok_and(|v| !v.is_empty());
+        // The local API ignores the user ID, so probe with Zotero's "current user" 0 when unset.
+        let local_reachable = probe_local(local_base, user_id.as_deref().unwrap_or("0")).await;
+        let installed_path = find_zotero_exe();
+        let check_launched = check_launched();
+        Diagnosis {
+            user_id_set: user_id.is_some(),
+            api_key_set,
+            transport,
+            local_url: local_base.to_string(),
+            local_reachable,
+            resolution: resolve_transport(transport, local_reachable, installed_path.is_some(), check_launched),
+            installed_path,
+            check_launched,
         }
     }
 
@@ -175,6 +349,29 @@ impl ZoteroClient {
         self
     }
 
+    /// Use a caller-supplied HTTP client instead of the process-wide
+    /// [`shared_client`](papers_http::shared_client), e.g. one with a proxy or
+    /// custom timeouts. That client's own default headers decide the
+    /// `User-Agent` unless [`with_user_agent`](Self::with_user_agent) is also
+    /// set, in either order.
+    pub fn with_http_client(mut self, http: reqwest::Client) -> Self {
+        self.http.set_client(http);
+        self
+    }
+
+    /// Override the `User-Agent` header (default [`DEFAULT_USER_AGENT`]).
+    pub fn with_user_agent(mut self, user_agent: &str) -> Self {
+        self.http.set_user_agent(user_agent);
+        self
+    }
+
+    /// Override the `Zotero-API-Version` header sent with every request.
+    /// Defaults to 3.
+    pub fn with_api_version(mut self, version: u8) -> Self {
+        self.api_version = version;
+        self
+    }
+
     /// Enable disk caching of successful responses.
     pub fn with_cache(mut self, cache: DiskCache) -> Self {
         self.cache = Some(cache);
@@ -187,6 +384,117 @@ impl ZoteroClient {
         format!("/users/{}", self.user_id)
     }
 
+    /// Build an [`ZoteroError::Api`] from a failed response body, masking the
+    /// API key in case the server echoed it back.
+    fn api_error(&self, status: u16, body: String) -> ZoteroError {
+        ZoteroError::Api {
+            status,
+            message: redact(&body, &self.api_key),
+        }
+    }
+
+    /// Parse a response body, naming the endpoint and quoting the start of the
+    /// body (key masked) if it doesn't match `T`.
+    fn parse_json<T: DeserializeOwned>(&self, path: &str, body: &str) -> Result<T> {
+        serde_json::from_str(body).map_err(|source| ZoteroError::Json {
+            path: path.to_string(),
+            snippet: body_snippet(body, &self.api_key),
+            source,
+        })
+    }
+
+    /// Wrap a transport error, masking the API key if it appears in the
+    /// request URL (as it does for `/keys/<key>`).
+    fn http_error(&self, e: reqwest::Error) -> ZoteroError {
+        let redacted = e
+            .url()
+            .map(|u| u.as_str())
+            .filter(|u| !self.api_key.is_empty() && u.contains(self.api_key.as_str()))
+            .map(|u| redact(u, &self.api_key));
+        match redacted.map(|u| reqwest::Url::parse(&u)) {
+            Some(Ok(url)) => ZoteroError::Http(e.with_url(url)),
+            Some(Err(_)) => ZoteroError::Http(e.without_url()),
+            None => ZoteroError::Http(e),
+        }
+    }
+
+    /// Send an authenticated GET and read the whole body, logging method, URL,
+    /// status, body size, and time to the end of the body under a
+    /// `zotero_request` span. The API key is masked in logged URLs (it appears
+    /// in the path of `/keys/<key>`).
+    async fn send_get(&self, url: &str, query: &[(&str, String)]) -> Result<Received> {
+        self.send_get_since(url, query, None).await
+    }
+
+    /// [`send_get`](Self::send_get), adding `If-Modified-Since-Version` when
+    /// `since` is set so Zotero can answer `304 Not Modified`.
+    async fn send_get_since(
+        &self,
+        url: &str,
+        query: &[(&str, String)],
+        since: Option<u64>,
+    ) -> Result<Received> {
+        let mut logged_url = url.to_string();
+        for (i, (k, v)) in query.iter().enumerate() {
+            logged_url.push(if i == 0 { '?' } else { '&' });
+            logged_url.push_str(&format!("{k}={v}"));
+        }
+        let span = tracing::debug_span!(
+            "zotero_request",
+            method = "GET",
+            url = %redact(&logged_url, &self.api_key),
+        );
+        let start = Instant::now();
+        let mut request = self
+            .http
+            .get(url)
+            .query(query)
+            .header("Zotero-API-Version", self.api_version.to_string())
+            .header("Zotero-API-Key", &self.api_key);
+        if let Some(version) = since {
+            request = request.header("If-Modified-Since-Version", version.to_string());
+        }
+        let resp = request
+            .send()
+            .instrument(span.clone())
+            .await
+            .map_err(|e| self.http_error(e))?;
+        let status = resp.status();
+        let headers = resp.headers().clone();
+        let body = resp.bytes().instrument(span.clone()).await;
+        span.in_scope(|| {
+            tracing::debug!(
+                status = status.as_u16(),
+                bytes = body.as_ref().map_or(0, |b| b.len()),
+                elapsed_ms = start.elapsed().as_millis() as u64,
+                "response"
+            )
+        });
+        let body = match body {
+            Ok(body) => body.to_vec(),
+            // An error status is reported as such even if its body was lost
+            Err(_) if !status.is_success() => Vec::new(),
+            Err(e) => return Err(self.http_error(e)),
+        };
+        Ok(Received { status, headers, body })
+    }
+
+    /// Send a GET for a response that missed the fresh cache. If an expired
+    /// entry with a version is still cached, the request is made conditional
+    /// on it; a `304 Not Modified` restarts that entry's TTL and returns its
+    /// cached text as [`Fetched::Cached`].
+    async fn send_get_revalidating(&self, url: &str, query: &[(&str, String)]) -> Result<Fetched> {
+        let stale = self.cache.as_ref().and_then(|c| c.get_with_version(url, query, None));
+        let resp = self.send_get_since(url, query, stale.as_ref().map(|(_, v)| *v)).await?;
+        if resp.status == reqwest::StatusCode::NOT_MODIFIED
+            && let (Some(cache), Some((text, version))) = (&self.cache, stale)
+        {
+            cache.set_with_version(url, query, None, &text, Some(version));
+            return Ok(Fetched::Cached(text));
+        }
+        Ok(Fetched::Response(resp))
+    }
+
     /// GET request returning a JSON array with header-based pagination.
     async fn get_json_array<T: DeserializeOwned>(
         &self,
@@ -194,47 +502,31 @@ impl ZoteroClient {
         query: Vec<(&str, String)>,
     ) -> Result<PagedResponse<T>> {
         let url = format!("{}{}", self.base_url, path);
-        if let Some(cache) = &self.cache
-            && let Some(text) = cache.get(&url, &query, None)
-        {
-            // Cached responses store body + header metadata as JSON
-            let cached: CachedArrayResponse =
-                serde_json::from_str(&text).map_err(ZoteroError::Json)?;
-            let items: Vec<T> =
-                serde_json::from_str(&cached.body).map_err(ZoteroError::Json)?;
-            return Ok(PagedResponse {
+        // Cached responses store body + header metadata as JSON
+        let from_cache = |text: &str| -> Result<PagedResponse<T>> {
+            let cached: CachedArrayResponse = self.parse_json(path, text)?;
+            let items: Vec<T> = self.parse_json(path, &cached.body)?;
+            Ok(PagedResponse {
                 items,
                 total_results: cached.total_results,
                 last_modified_version: cached.last_modified_version,
-            });
+            })
+        };
+        if let Some(cache) = &self.cache
+            && let Some(text) = cache.get(&url, &query, None)
+        {
+            return from_cache(&text);
         }
-        let resp = self
-            .http
-            .get(&url)
-            .query(&query)
-            .header("Zotero-API-Version", "3")
-            .header("Zotero-API-Key", &self.api_key)
-            .send()
-            .await?;
-        let status = resp.status();
-        if !status.is_success() {
-            let message = resp.text().await.unwrap_or_default();
-            return Err(ZoteroError::Api {
-                status: status.as_u16(),
-                message,
-            });
-        }
-        let total_results = resp
-            .headers()
-            .get("Total-Results")
-            .and_then(|v| v.to_str().ok())
-            .and_then(|v| v.parse().ok());
-        let last_modified_version = resp
-            .headers()
-            .get("Last-Modified-Version")
-            .and_then(|v| v.to_str().ok())
-            .and_then(|v| v.parse().ok());
-        let text = resp.text().await?;
+        let resp = match self.send_get_revalidating(&url, &query).await? {
+            Fetched::Cached(text) => return from_cache(&text),
+            Fetched::Response(resp) => resp,
+        };
+        if !resp.status.is_success() {
+            return Err(self.api_error(resp.status.as_u16(), resp.text()));
+        }
+        let total_results = resp.header_u64("Total-Results");
+        let last_modified_version = resp.header_u64("Last-Modified-Version");
+        let text = resp.text();
         if let Some(cache) = &self.cache {
             let cached = CachedArrayResponse {
                 body: text.clone(),
@@ -242,10 +534,10 @@ impl ZoteroClient {
                 last_modified_version,
             };
             if let Ok(cache_text) = serde_json::to_string(&cached) {
-                cache.set(&url, &query, None, &cache_text);
+                cache.set_with_version(&url, &query, None, &cache_text, last_modified_version);
             }
         }
-        let items: Vec<T> = serde_json::from_str(&text).map_err(ZoteroError::Json)?;
+        let items: Vec<T> = self.parse_json(path, &text)?;
         Ok(PagedResponse {
             items,
             total_results,
@@ -263,29 +555,21 @@ impl ZoteroClient {
         if let Some(cache) = &self.cache
             && let Some(text) = cache.get(&url, &query, None)
         {
-            return serde_json::from_str(&text).map_err(ZoteroError::Json);
+            return self.parse_json(path, &text);
         }
-        let resp = self
-            .http
-            .get(&url)
-            .query(&query)
-            .header("Zotero-API-Version", "3")
-            .header("Zotero-API-Key", &self.api_key)
-            .send()
-            .await?;
-        let status = resp.status();
-        if !status.is_success() {
-            let message = resp.text().await.unwrap_or_default();
-            return Err(ZoteroError::Api {
-                status: status.as_u16(),
-                message,
-            });
+        let resp = match self.send_get_revalidating(&url, &query).await? {
+            Fetched::Cached(text) => return self.parse_json(path, &text),
+            Fetched::Response(resp) => resp,
+        };
+        if !resp.status.is_success() {
+            return Err(self.api_error(resp.status.as_u16(), resp.text()));
         }
-        let text = resp.text().await?;
+        let last_modified_version = resp.header_u64("Last-Modified-Version");
+        let text = resp.text();
         if let Some(cache) = &self.cache {
-            cache.set(&url, &query, None, &text);
+            cache.set_with_version(&url, &query, None, &text, last_modified_version);
         }
-        serde_json::from_str(&text).map_err(ZoteroError::Json)
+        self.parse_json(path, &text)
     }
 
     /// GET request returning a single JSON object plus `Last-Modified-Version`.
@@ -298,75 +582,77 @@ impl ZoteroClient {
         query: Vec<(&str, String)>,
     ) -> Result<VersionedResponse<T>> {
         let url = format!("{}{}", self.base_url, path);
+        let from_cache = |text: &str| -> Result<VersionedResponse<T>> {
+            let cached: CachedVersionedResponse = self.parse_json(path, text)?;
+            let data: T = self.parse_json(path, &cached.body)?;
+            Ok(VersionedResponse {
+                data,
+                last_modified_version: cached.last_modified_version,
+            })
+        };
         if let Some(cache) = &self.cache
             && let Some(text) = cache.get(&url, &query, None)
         {
-            let cached: CachedVersionedResponse =
-                serde_json::from_str(&text).map_err(ZoteroError::Json)?;
-            let data: T = serde_json::from_str(&cached.body).map_err(ZoteroError::Json)?;
-            return Ok(VersionedResponse {
-                data,
-                last_modified_version: cached.last_modified_version,
-            });
+            return from_cache(&text);
         }
-        let resp = self
-            .http
-            .get(&url)
-            .query(&query)
-            .header("Zotero-API-Version", "3")
-            .header("Zotero-API-Key", &self.api_key)
-            .send()
-            .await?;
-        let status = resp.status();
-        if !status.is_success() {
-            let message = resp.text().await.unwrap_or_default();
-            return Err(ZoteroError::Api {
-                status: status.as_u16(),
-                message,
-            });
-        }
-        let last_modified_version = resp
-            .headers()
-            .get("Last-Modified-Version")
-            .and_then(|v| v.to_str().ok())
-            .and_then(|v| v.parse().ok());
-        let text = resp.text().await?;
+        let resp = match self.send_get_revalidating(&url, &query).await? {
+            Fetched::Cached(text) => return from_cache(&text),
+            Fetched::Response(resp) => resp,
+        };
+        if !resp.status.is_success() {
+            return Err(self.api_error(resp.status.as_u16(), resp.text()));
+        }
+        let last_modified_version = resp.header_u64("Last-Modified-Version");
+        let text = resp.text();
         if let Some(cache) = &self.cache {
             let cached = CachedVersionedResponse {
                 body: text.clone(),
                 last_modified_version,
             };
             if let Ok(cache_text) = serde_json::to_string(&cached) {
-                cache.set(&url, &query, None, &cache_text);
+                cache.set_with_version(&url, &query, None, &cache_text, last_modified_version);
             }
         }
-        let data: T = serde_json::from_str(&text).map_err(ZoteroError::Json)?;
+        let data: T = self.parse_json(path, &text)?;
         Ok(VersionedResponse {
             data,
             last_modified_version,
         })
     }
 
+    /// GET request returning the body as text (export formats such as
+    /// `format=bibtex`), cached like the JSON helpers.
+    async fn get_text(&self, path: &str, query: Vec<(&str, String)>) -> Result<String> {
+        let url = format!("{}{}", self.base_url, path);
+        if let Some(cache) = &self.cache
+            && let Some(text) = cache.get(&url, &query, None)
+        {
+            return Ok(text);
+        }
+        let resp = match self.send_get_revalidating(&url, &query).await? {
+            Fetched::Cached(text) => return Ok(text),
+            Fetched::Response(resp) => resp,
+        };
+        if !resp.status.is_success() {
+            return Err(self.api_error(resp.status.as_u16(), resp.text()));
+        }
+        let last_modified_version = resp.header_u64("Last-Modified-Version");
+        let text = resp.text();
+        if let Some(cache) = &self.cache {
+            cache.set_with_version(&url, &query, None, &text, last_modified_version);
+        }
+        Ok(text)
+    }
+
     /// GET request returning raw bytes (for file downloads).
     /// Does not use caching (files are too large).
     async fn get_binary(&self, path: &str) -> Result<Vec<u8>> {
         let url = format!("{}{}", self.base_url, path);
-        let resp = self
-            .http
-            .get(&url)
-            .header("Zotero-API-Version", "3")
-            .header("Zotero-API-Key", &self.api_key)
-            .send()
-            .await?;
-        let status = resp.status();
-        if !status.is_success() {
-            let message = resp.text().await.unwrap_or_default();
-            return Err(ZoteroError::Api {
-                status: status.as_u16(),
-                message,
-            });
+        let resp = self.send_get(&url, &[]).await?;
+        if !resp.status.is_success() {
+            return Err(self.api_error(resp.status.as_u16(), resp.text()));
         }
-        Ok(resp.bytes().await?.to_vec())
+        Ok(resp.body)
     }
 
     // ── Item endpoints ─────────────────────────────────────────────────
@@ -403,6 +689,86 @@ impl ZoteroClient {
         self.get_json_single(&path, vec![]).await
     }
 
+    /// Find a top-level item by DOI.
+    ///
+    /// Runs an `everything` quick search for the DOI, then keeps only items
+    /// whose DOI matches exactly (case-insensitive), so DOIs that merely
+    /// appear in another item's notes or full text are not reported. The DOI
+    /// is read from the `DOI` field or, for item types without one (theses,
+    /// books, reports), a `DOI: ...` line in `extra`. The input may be a bare
+    /// DOI, a `doi:` prefix, or a `https://doi.org/` URL.
+    ///
+    /// `GET /users/<id>/items/top?q=<doi>&qmode=everything`
+    pub async fn find_by_doi(&self, doi: &str) -> Result<Option<Item>> {
+        let doi = normalize_doi(doi);
+        if doi.is_empty() {
+            return Ok(None);
+        }
+        let params = ItemListParams::builder().q(doi.as_str()).qmode("everything").build();
+        let resp = self.list_top_items(&params).await?;
+        Ok(resp
+            .items
+            .into_iter()
+            .find(|item| item_dois(&item.data).any(|d| normalize_doi(d) == doi)))
+    }
+
+    /// Get multiple items by key in as few requests as possible.
+    ///
+    /// Keys are sent through the `itemKey` list filter in chunks of 50 (the
+    /// API maximum), with the chunks fetched concurrently. Keys that do not
+    /// exist in the library are silently absent from the result.
+    ///
+    /// A chunk that fails is reported in [`BatchResponse::failed`] with its
+    /// keys; items from the chunks that succeeded are still returned.
+    ///
+    /// `GET /users/<id>/items?itemKey=<k1,k2,...>`
+    pub async fn get_items(&self, keys: &[&str]) -> BatchResponse<Item> {
+        let requests = keys.chunks(ITEM_KEY_CHUNK).map(|chunk| {
+            // Set limit == chunk size so we never silently truncate to the default 25.
+            let params = ItemListParams {
+                item_key: Some(chunk.join(",")),
+                limit: Some(chunk.len() as u32),
+                ..Default::default()
+            };
+            async move { (chunk, self.list_items(&params).await) }
+        });
+        let mut batch = BatchResponse { items: Vec::new(), failed: Vec::new() };
+        for (chunk, result) in futures::future::join_all(requests).await {
+            match result {
+                Ok(page) => batch.items.extend(page.items),
+                Err(error) => batch.failed.push(ChunkFailure {
+                    keys: chunk.iter().map(|k| k.to_string()).collect(),
+                    error,
+                }),
+            }
+        }
+        batch
+    }
+
+    /// Export items by key in one of Zotero's export formats (`bibtex`,
+    /// `biblatex`, `ris`, `csljson`, ...), using Zotero's own translators.
+    ///
+    /// Keys are sent through the `itemKey` list filter in chunks of 50, and
+    /// the chunk outputs are concatenated in order. Attachments and notes
+    /// produce no output in bibliographic formats.
+    ///
+    /// `GET /users/<id>/items?itemKey=<k1,k2,...>&format=<format>`
+    pub async fn export_items(&self, keys: &[&str], format: &str) -> Result<String> {
+        let path = format!("{}/items", self.user_prefix());
+        let requests = keys.chunks(ITEM_KEY_CHUNK).map(|chunk| {
+            let params = ItemListParams {
+                item_key: Some(chunk.join(",")),
+                limit: Some(chunk.len() as u32),
+                format: Some(format.to_string()),
+                ..Default::default()
+            };
+            let path = &path;
+            async move { self.get_text(path, params.to_query_pairs()).await }
+        });
+        let chunks = futures::future::try_join_all(requests).await?;
+        Ok(chunks.concat())
+    }
+
     /// List child items (attachments and notes) of a parent item.
     ///
     /// `GET /users/<id>/items/<key>/children`
@@ -470,6 +836,12 @@ impl ZoteroClient {
         self.get_binary(&path).await
     }
 
+    /// [`download_item_file`](Self::download_item_file), abandoned with
+    /// [`ZoteroError::Cancelled`] as soon as `cancel` fires.
+    pub async fn download_item_file_cancellable(&self, key: &str, cancel: &CancellationToken) -> Result<Vec<u8>> {
+        cancel.run_until_cancelled(self.download_item_file(key)).await.unwrap_or(Err(ZoteroError::Cancelled))
+    }
+
     /// Create a child attachment item under a parent.
     ///
     /// `POST /users/<id>/items`
@@ -492,16 +864,11 @@ impl ZoteroClient {
             "collections": []
         }]);
         let path = format!("{}/items", self.user_prefix());
-        let resp = self.post_json_write(&path, &item).await?;
-        resp.successful
-            .get("0")
-            .and_then(|v| v.get("key"))
-            .and_then(|k| k.as_str())
-            .map(|s| s.to_string())
-            .ok_or_else(|| ZoteroError::Api {
-                status: 0,
-                message: "create_imported_attachment: no key in successful[\"0\"]".into(),
-            })
+        let resp = self.write_result(self.post_json_write(&path, &item).await?)?;
+        resp.first_key().map(str::to_string).ok_or_else(|| ZoteroError::Api {
+            status: 0,
+            message: "create_imported_attachment: no key in successful[\"0\"]".into(),
+        })
     }
 
     /// Upload file bytes to an attachment item using Zotero's 3-step S3 protocol.
@@ -534,7 +901,7 @@ impl ZoteroClient {
         let resp = self
             .http
             .post(&url)
-            .header("Zotero-API-Version", "3")
+            .header("Zotero-API-Version", self.api_version.to_string())
             .header("Zotero-API-Key", &self.api_key)
             .header("Content-Type", "application/x-www-form-urlencoded")
             .header("If-None-Match", "*")
@@ -544,11 +911,11 @@ impl ZoteroClient {
         let status = resp.status();
         if !status.is_success() {
             let message = resp.text().await.unwrap_or_default();
-            return Err(ZoteroError::Api { status: status.as_u16(), message });
+            return Err(self.api_error(status.as_u16(), message));
         }
         let register_text = resp.text().await?;
         let register_json: serde_json::Value =
-            serde_json::from_str(&register_text).map_err(ZoteroError::Json)?;
+            self.parse_json(&path, &register_text)?;
 
         // If file already exists on S3, we're done
         if register_json.get("exists").and_then(|v| v.as_u64()) == Some(1) {
@@ -585,7 +952,7 @@ impl ZoteroClient {
         let s3_status = s3_resp.status();
         if !s3_status.is_success() {
             let message = s3_resp.text().await.unwrap_or_default();
-            return Err(ZoteroError::Api { status: s3_status.as_u16(), message });
+            return Err(self.api_error(s3_status.as_u16(), message));
         }
 
         // Step 4: register completion
@@ -593,7 +960,7 @@ impl ZoteroClient {
         let complete_resp = self
             .http
             .post(&url)
-            .header("Zotero-API-Version", "3")
+            .header("Zotero-API-Version", self.api_version.to_string())
             .header("Zotero-API-Key", &self.api_key)
             .header("Content-Type", "application/x-www-form-urlencoded")
             .header("If-None-Match", "*")
@@ -603,7 +970,7 @@ impl ZoteroClient {
         let complete_status = complete_resp.status();
         if !complete_status.is_success() {
             let message = complete_resp.text().await.unwrap_or_default();
-            return Err(ZoteroError::Api { status: complete_status.as_u16(), message });
+            return Err(self.api_error(complete_status.as_u16(), message));
         }
 
         Ok(())
@@ -930,6 +1297,22 @@ impl ZoteroClient {
         self.get_json_versioned(&path, vec![]).await
     }
 
+    /// The library's colored tags, in display order, from the `tagColors`
+    /// setting. A library without any has no such setting; that is an empty
+    /// list rather than a 404.
+    pub async fn get_tag_colors(&self) -> Result<Vec<TagColor>> {
+        let entry = match self.get_setting("tagColors").await {
+            Ok(resp) => resp.data,
+            Err(ZoteroError::Api { status: 404, .. }) => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        entry.tag_colors().map_err(|source| ZoteroError::Json {
+            path: format!("{}/settings/tagColors", self.user_prefix()),
+            snippet: body_snippet(&entry.value.to_string(), &self.api_key),
+            source,
+        })
+    }
+
     // ── File view endpoints ────────────────────────────────────────────
 
     /// Download the file content of an attachment via the browser-view URL.
@@ -941,14 +1324,20 @@ impl ZoteroClient {
     /// returns a `302 Location: file:///…` pointing at the local Zotero
     /// storage directory; in that case the file is read directly from disk.
     ///
+    /// A redirect the HTTP client did not follow is followed here, without
+    /// the API key. If the CDN rejects the pre-signed URL as expired
+    /// (`403`/`410`), a fresh one is requested with
+    /// [`get_item_file_view_url_fresh`] and the download retried once.
+    ///
     /// [`download_item_file`]: ZoteroClient::download_item_file
+    /// [`get_item_file_view_url_fresh`]: ZoteroClient::get_item_file_view_url_fresh
     pub async fn get_item_file_view(&self, key: &str) -> Result<Vec<u8>> {
         let path = format!("{}/items/{}/file/view", self.user_prefix(), key);
         let url = format!("{}{}", self.base_url, path);
         let resp = self
             .http
             .get(&url)
-            .header("Zotero-API-Version", "3")
+            .header("Zotero-API-Version", self.api_version.to_string())
             .header("Zotero-API-Key", &self.api_key)
             .send()
             .await?;
@@ -956,12 +1345,28 @@ impl ZoteroClient {
         if status.is_success() {
             return Ok(resp.bytes().await?.to_vec());
         }
+        // Redirected to the CDN, which refused the signed URL
+        if resp.url().as_str() != url && is_expired_link(status) {
+            return self.get_item_file_view_refreshed(key).await;
+        }
         // The local Zotero connector redirects to file:// which reqwest cannot
         // follow.  Detect that case and read the file from disk instead.
-        if status.as_u16() == 302 {
+        if status.is_redirection() {
             if let Some(location) = resp.headers().get("Location") {
                 if let Ok(loc_str) = location.to_str() {
                     if let Ok(file_url) = reqwest::Url::parse(loc_str) {
+                        if matches!(file_url.scheme(), "http" | "https") {
+                            let cdn = self.http.get(file_url).send().await?;
+                            if cdn.status().is_success() {
+                                return Ok(cdn.bytes().await?.to_vec());
+                            }
+                            if is_expired_link(cdn.status()) {
+                                return self.get_item_file_view_refreshed(key).await;
+                            }
+                            let status = cdn.status().as_u16();
+                            let message = cdn.text().await.unwrap_or_default();
+                            return Err(self.api_error(status, message));
+                        }
                         if file_url.scheme() == "file" {
                             if let Ok(file_path) = file_url.to_file_path() {
                                 return std::fs::read(&file_path).map_err(|e| {
@@ -980,7 +1385,19 @@ impl ZoteroClient {
             }
         }
         let message = resp.text().await.unwrap_or_default();
-        Err(ZoteroError::Api { status: status.as_u16(), message })
+        Err(self.api_error(status.as_u16(), message))
+    }
+
+    /// Download from a newly issued view URL after the previous one expired.
+    async fn get_item_file_view_refreshed(&self, key: &str) -> Result<Vec<u8>> {
+        let fresh = self.get_item_file_view_url_fresh(key).await?;
+        let resp = self.http.get(&fresh).send().await?;
+        let status = resp.status();
+        if !status.is_success() {
+            let message = resp.text().await.unwrap_or_default();
+            return Err(self.api_error(status.as_u16(), message));
+        }
+        Ok(resp.bytes().await?.to_vec())
     }
 
     /// Get a pre-signed CDN URL for viewing an attachment file.
@@ -990,12 +1407,45 @@ impl ZoteroClient {
     /// Returns the redirect target URL as a plain string without following the
     /// redirect. Useful when you need the URL itself rather than the content —
     /// e.g. to pass to a browser or PDF viewer.
+    ///
+    /// The web API's URL is pre-signed and expires a short while after it is
+    /// issued, so it should be used right away rather than stored; fetch
+    /// another with [`get_item_file_view_url_fresh`] when handing one out
+    /// later. The local API returns a non-expiring `file://` URL.
+    ///
+    /// [`get_item_file_view_url_fresh`]: ZoteroClient::get_item_file_view_url_fresh
     pub async fn get_item_file_view_url(&self, key: &str) -> Result<String> {
         let path = format!("{}/items/{}/file/view/url", self.user_prefix(), key);
         let bytes = self.get_binary(&path).await?;
         Ok(String::from_utf8_lossy(&bytes).into_owned())
     }
 
+    /// Like [`get_item_file_view_url`], but always asks Zotero for a newly
+    /// signed URL: the request carries `Cache-Control: no-cache` so no
+    /// intermediate cache can answer with an expired one, and surrounding
+    /// whitespace is trimmed from the result.
+    ///
+    /// [`get_item_file_view_url`]: ZoteroClient::get_item_file_view_url
+    pub async fn get_item_file_view_url_fresh(&self, key: &str) -> Result<String> {
+        let path = format!("{}/items/{}/file/view/url", self.user_prefix(), key);
+        let url = format!("{}{}", self.base_url, path);
+        let resp = self
+            .http
+            .get(&url)
+            .header("Zotero-API-Version", self.api_version.to_string())
+            .header("Zotero-API-Key", &self.api_key)
+            .header("Cache-Control", "no-cache")
+            .send()
+            .await
+            .map_err(|e| self.http_error(e))?;
+        let status = resp.status();
+        if !status.is_success() {
+            let message = resp.text().await.unwrap_or_default();
+            return Err(self.api_error(status.as_u16(), message));
+        }
+        Ok(resp.text().await?.trim().to_string())
+    }
+
     // ── Write helpers ──────────────────────────────────────────────────
 
     /// POST a JSON body, expecting a `200 OK` with a [`WriteResponse`] body.
@@ -1008,7 +1458,7 @@ impl ZoteroClient {
         let resp = self
             .http
             .post(&url)
-            .header("Zotero-API-Version", "3")
+            .header("Zotero-API-Version", self.api_version.to_string())
             .header("Zotero-API-Key", &self.api_key)
             .header("Content-Type", "application/json")
             .json(body)
@@ -1017,10 +1467,10 @@ impl ZoteroClient {
         let status = resp.status();
         if !status.is_success() {
             let message = resp.text().await.unwrap_or_default();
-            return Err(ZoteroError::Api { status: status.as_u16(), message });
+            return Err(self.api_error(status.as_u16(), message));
         }
         let text = resp.text().await?;
-        serde_json::from_str(&text).map_err(ZoteroError::Json)
+        self.parse_json(path, &text)
     }
 
     /// PUT a JSON body to a single-object path, expecting `204 No Content`.
@@ -1035,7 +1485,7 @@ impl ZoteroClient {
         let resp = self
             .http
             .put(&url)
-            .header("Zotero-API-Version", "3")
+            .header("Zotero-API-Version", self.api_version.to_string())
             .header("Zotero-API-Key", &self.api_key)
             .header("Content-Type", "application/json")
             .header("If-Unmodified-Since-Version", version.to_string())
@@ -1045,7 +1495,7 @@ impl ZoteroClient {
         let status = resp.status();
         if !status.is_success() {
             let message = resp.text().await.unwrap_or_default();
-            return Err(ZoteroError::Api { status: status.as_u16(), message });
+            return Err(self.api_error(status.as_u16(), message));
         }
         Ok(())
     }
@@ -1062,7 +1512,7 @@ impl ZoteroClient {
         let resp = self
             .http
             .patch(&url)
-            .header("Zotero-API-Version", "3")
+            .header("Zotero-API-Version", self.api_version.to_string())
             .header("Zotero-API-Key", &self.api_key)
             .header("Content-Type", "application/json")
             .header("If-Unmodified-Since-Version", version.to_string())
@@ -1072,7 +1522,7 @@ impl ZoteroClient {
         let status = resp.status();
         if !status.is_success() {
             let message = resp.text().await.unwrap_or_default();
-            return Err(ZoteroError::Api { status: status.as_u16(), message });
+            return Err(self.api_error(status.as_u16(), message));
         }
         Ok(())
     }
@@ -1084,7 +1534,7 @@ impl ZoteroClient {
         let resp = self
             .http
             .delete(&url)
-            .header("Zotero-API-Version", "3")
+            .header("Zotero-API-Version", self.api_version.to_string())
             .header("Zotero-API-Key", &self.api_key)
             .header("If-Unmodified-Since-Version", version.to_string())
             .send()
@@ -1092,7 +1542,7 @@ impl ZoteroClient {
         let status = resp.status();
         if !status.is_success() {
             let message = resp.text().await.unwrap_or_default();
-            return Err(ZoteroError::Api { status: status.as_u16(), message });
+            return Err(self.api_error(status.as_u16(), message));
         }
         Ok(())
     }
@@ -1112,7 +1562,7 @@ impl ZoteroClient {
         let resp = self
             .http
             .delete(&url)
-            .header("Zotero-API-Version", "3")
+            .header("Zotero-API-Version", self.api_version.to_string())
             .header("Zotero-API-Key", &self.api_key)
             .header("If-Unmodified-Since-Version", library_version.to_string())
             .query(&[(query_key, &joined)])
@@ -1121,7 +1571,7 @@ impl ZoteroClient {
         let status = resp.status();
         if !status.is_success() {
             let message = resp.text().await.unwrap_or_default();
-            return Err(ZoteroError::Api { status: status.as_u16(), message });
+            return Err(self.api_error(status.as_u16(), message));
         }
         Ok(())
     }
@@ -1134,7 +1584,9 @@ impl ZoteroClient {
     ///
     /// Each element of `items` must be a JSON object containing at least
     /// `itemType`. Returns a [`WriteResponse`] with `successful`, `unchanged`,
-    /// and `failed` maps keyed by the input array index.
+    /// and `failed` maps keyed by the input array index. Per-object failures
+    /// still return `Ok`; use [`write_result`](Self::write_result) to treat
+    /// any failure as an error.
     ///
     /// # Example
     ///
@@ -1156,6 +1608,53 @@ impl ZoteroClient {
         self.post_json_write(&path, &serde_json::Value::Array(items)).await
     }
 
+    /// `Ok(resp)` if no object in `resp` failed, otherwise a
+    /// [`ZoteroError::Api`] with the status of the first (lowest-index)
+    /// failure and the API key masked in its message.
+    ///
+    /// A single failure keeps the server's message as is; several are joined
+    /// as `#<index> [(<key>)]: <code> <message>` entries in input order.
+    pub fn write_result(&self, resp: WriteResponse) -> Result<WriteResponse> {
+        match resp.failure() {
+            Some((status, message)) => Err(self.api_error(status, message)),
+            None => Ok(resp),
+        }
+    }
+
+    /// Create a single item and return its new key.
+    ///
+    /// `POST /users/<id>/items`
+    ///
+    /// A typed convenience over [`create_items`](Self::create_items) for the
+    /// common one-item case. Returns `Err` if the server rejects the item.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example() -> papers_zotero::Result<()> {
+    /// use papers_zotero::{NewItem, ZoteroClient};
+    ///
+    /// let client = ZoteroClient::from_env()?;
+    /// let key = client.create_item(&NewItem {
+    ///     item_type: "journalArticle".into(),
+    ///     title: Some("Attention Is All You Need".into()),
+    ///     ..Default::default()
+    /// }).await?;
+    /// println!("created {key}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create_item(&self, item: &NewItem) -> Result<String> {
+        let path = format!("{}/items", self.user_prefix());
+        let body = serde_json::to_value([item])
+            .map_err(|source| ZoteroError::Serialize { path: path.clone(), source })?;
+        let resp = self.write_result(self.post_json_write(&path, &body).await?)?;
+        resp.first_key().map(str::to_string).ok_or_else(|| ZoteroError::Api {
+            status: 0,
+            message: "create_item: no key in successful[\"0\"]".into(),
+        })
+    }
+
     /// Fully replace a single item.
     ///
     /// `PUT /users/<id>/items/<key>`
@@ -1188,6 +1687,35 @@ impl ZoteroClient {
         self.patch_no_content(&path, version, &data).await
     }
 
+    /// Add and remove tags on an item, returning its tags afterwards.
+    ///
+    /// Reads the item, then `PATCH`es its `tags` array (see
+    /// [`ItemData::tags_with`]) at the version read. If the item is modified in
+    /// between (`412 Precondition Failed`), the read and write are retried.
+    /// No request is written when nothing would change. The read bypasses
+    /// the response cache, whose version could be stale.
+    pub async fn update_item_tags(&self, key: &str, add: &[&str], remove: &[&str]) -> Result<Vec<ItemTag>> {
+        let path = format!("{}/items/{}", self.user_prefix(), key);
+        let url = format!("{}{}", self.base_url, path);
+        let mut attempt = 1;
+        loop {
+            let resp = self.send_get(&url, &[]).await?;
+            if !resp.status.is_success() {
+                return Err(self.api_error(resp.status.as_u16(), resp.text()));
+            }
+            let item: Item = self.parse_json(&path, &resp.text())?;
+            let tags = item.data.tags_with(add, remove);
+            if tags == item.data.tags {
+                return Ok(tags);
+            }
+            match self.patch_item(key, item.version, serde_json::json!({ "tags": tags })).await {
+                Err(ZoteroError::Api { status: 412, .. }) if attempt < TAG_UPDATE_ATTEMPTS => attempt += 1,
+                Err(e) => return Err(e),
+                Ok(()) => return Ok(tags),
+            }
+        }
+    }
+
     /// Delete a single item.
     ///
     /// `DELETE /users/<id>/items/<key>`
@@ -1304,7 +1832,7 @@ impl ZoteroClient {
         let resp = self
             .http
             .delete(&url)
-            .header("Zotero-API-Version", "3")
+            .header("Zotero-API-Version", self.api_version.to_string())
             .header("Zotero-API-Key", &self.api_key)
             .header("If-Unmodified-Since-Version", library_version.to_string())
             .query(&[("tag", &tag_param)])
@@ -1313,7 +1841,7 @@ impl ZoteroClient {
         let status = resp.status();
         if !status.is_success() {
             let message = resp.text().await.unwrap_or_default();
-            return Err(ZoteroError::Api { status: status.as_u16(), message });
+            return Err(self.api_error(status.as_u16(), message));
         }
         Ok(())
     }
@@ -1341,6 +1869,40 @@ impl ZoteroClient {
     }
 }
 
+/// Whether a file CDN `status` means the pre-signed URL is no longer valid.
+fn is_expired_link(status: reqwest::StatusCode) -> bool {
+    matches!(status.as_u16(), 403 | 410)
+}
+
+/// DOIs recorded on an item: its `DOI` field and any `DOI: ...` lines in
+/// `extra`, where Zotero keeps them for types without a `DOI` field.
+fn item_dois(data: &ItemData) -> impl Iterator<Item = &str> {
+    let from_extra = data.extra.as_deref().unwrap_or_default().lines().filter_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        name.trim().eq_ignore_ascii_case("doi").then_some(value)
+    });
+    data.doi.as_deref().into_iter().chain(from_extra)
+}
+
+/// Reduce a DOI to its bare, lowercase form (`10.xxxx/yyyy`), stripping any
+/// `doi:` or `https://doi.org/` prefix. DOIs are case-insensitive.
+fn normalize_doi(doi: &str) -> String {
+    let doi = doi.trim();
+    let lower = doi.to_ascii_lowercase();
+    let prefixes = [
+        "https://doi.org/",
+        "http://doi.org/",
+        "https://dx.doi.org/",
+        "http://dx.doi.org/",
+        "doi:",
+    ];
+    let bare = prefixes
+        .iter()
+        .find_map(|p| lower.strip_prefix(p))
+        .unwrap_or(&lower);
+    bare.trim().to_string()
+}
+
 /// Minimal percent-encoding for tag names in URL paths.
 fn urlencoded(s: &str) -> String {
     let mut out = String::with_capacity(s.len());
@@ -1364,6 +1926,32 @@ fn urlencoded(s: &str) -> String {
     out
 }
 
+/// What [`ZoteroClient::send_get_revalidating`] got back.
+enum Fetched {
+    /// Zotero answered `304 Not Modified`; this is the cached text.
+    Cached(String),
+    Response(Received),
+}
+
+/// A GET response read to the end by [`ZoteroClient::send_get`].
+struct Received {
+    status: reqwest::StatusCode,
+    headers: reqwest::header::HeaderMap,
+    body: Vec<u8>,
+}
+
+impl Received {
+    fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+
+    /// Header `name` parsed as an integer (`Total-Results`,
+    /// `Last-Modified-Version`).
+    fn header_u64(&self, name: &str) -> Option<u64> {
+        self.headers.get(name)?.to_str().ok()?.parse().ok()
+    }
+}
+
 /// Internal type for caching array responses with header metadata.
 #[derive(serde::Serialize, serde::Deserialize)]
 struct CachedArrayResponse {
@@ -1384,8 +1972,9 @@ mod tests {
     use super::*;
     use crate::cache::DiskCache;
     use crate::params::{DeletedParams, FulltextParams};
+    use papers_http::testing::CapturedLogs;
     use std::time::Duration;
-    use wiremock::matchers::{header, method, path, query_param};
+    use wiremock::matchers::{body_json, header, method, path, query_param};
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
     fn item_list_json() -> String {
@@ -1493,27 +2082,169 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_list_top_items() {
+    async fn test_custom_api_version_header() {
         let server = MockServer::start().await;
         Mock::given(method("GET"))
-            .and(path("/users/12345/items/top"))
+            .and(path("/users/12345/items"))
+            .and(header("Zotero-API-Version", "4"))
             .respond_with(array_response(&item_list_json()))
+            .expect(1)
             .mount(&server)
             .await;
-        let client = setup_client(&server).await;
-        let resp = client
-            .list_top_items(&ItemListParams::default())
-            .await
-            .unwrap();
+        let client = setup_client(&server).await.with_api_version(4);
+        let resp = client.list_items(&ItemListParams::default()).await.unwrap();
         assert_eq!(resp.items.len(), 1);
     }
 
+    #[test]
+    fn test_transport_parse() {
+        assert_eq!("auto".parse::<Transport>(), Ok(Transport::Auto));
+        assert_eq!(" Local ".parse::<Transport>(), Ok(Transport::Local));
+        assert_eq!("REMOTE".parse::<Transport>(), Ok(Transport::Remote));
+        assert!("web".parse::<Transport>().is_err());
+    }
+
+    #[test]
+    fn test_resolve_transport_branches() {
+        use Resolution::*;
+        // (transport, local reachable, installed, check launched) -> resolution
+        let cases = [
+            (Transport::Auto, true, true, true, Local),
+            (Transport::Auto, false, true, true, NotRunning),
+            (Transport::Auto, false, true, false, Remote),
+            (Transport::Auto, false, false, true, Remote),
+            (Transport::Local, true, false, true, Local),
+            (Transport::Local, false, true, false, LocalUnavailable),
+            (Transport::Remote, true, true, true, Remote),
+        ];
+        for (transport, local_ok, installed, check, expected) in cases {
+            assert_eq!(
+                resolve_transport(transport, local_ok, installed, check),
+                expected,
+                "{transport:?} local_ok={local_ok} installed={installed} check={check}"
+            );
+        }
+    }
+
     #[tokio::test]
-    async fn test_list_trash_items() {
+    async fn test_diagnose_reports_reachable_local_api() {
         let server = MockServer::start().await;
         Mock::given(method("GET"))
-            .and(path("/users/12345/items/trash"))
-            .respond_with(array_response(&item_list_json()))
+            .and(query_param("limit", "0"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let d = ZoteroClient::diagnose_at(Transport::Local, &server.uri()).await;
+        assert!(d.local_reachable);
+        assert_eq!(d.local_url, server.uri());
+        assert_eq!(d.transport, Transport::Local);
+        assert_eq!(d.resolution, Resolution::Local);
+    }
+
+    #[tokio::test]
+    async fn test_forced_local_transport() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/users/12345/items"))
+            .and(query_param("limit", "0"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let client = ZoteroClient::connect("12345".into(), "key".into(), Transport::Local, &server.uri())
+            .await
+            .unwrap();
+        assert_eq!(client.base_url, server.uri());
+    }
+
+    #[tokio::test]
+    async fn test_local_url_override_is_probed() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/users/12345/items"))
+            .and(query_param("limit", "0"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let base = format!("{}/api", server.uri());
+        let local_base = parse_local_url(&format!("{base}/")).unwrap();
+        assert_eq!(local_base, base);
+
+        let client = ZoteroClient::connect("12345".into(), "key".into(), Transport::Local, &local_base)
+            .await
+            .unwrap();
+        assert_eq!(client.base_url, base);
+    }
+
+    #[test]
+    fn test_parse_local_url_rejects_non_http() {
+        assert_eq!(parse_local_url("http://10.0.0.2:23119/api").unwrap(), "http://10.0.0.2:23119/api");
+        for bad in ["127.0.0.1:23119", "ftp://host/api", "not a url", "file:///tmp"] {
+            assert!(
+                matches!(parse_local_url(bad), Err(ZoteroError::InvalidEnv { var: LOCAL_URL_ENV, .. })),
+                "{bad:?}"
+            );
+        }
+        assert_eq!(
+            parse_local_url("ftp://host/api").unwrap_err().to_string(),
+            "ZOTERO_LOCAL_URL must be an http(s) URL such as http://127.0.0.1:23119/api (got \"ftp://host/api\")"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_forced_local_transport_unreachable() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/users/12345/items"))
+            .respond_with(ResponseTemplate::new(403).set_body_string("Local API is not enabled"))
+            .mount(&server)
+            .await;
+        let result = ZoteroClient::connect("12345".into(), "key".into(), Transport::Local, &server.uri()).await;
+        match result {
+            Err(ZoteroError::LocalUnavailable { url }) => assert_eq!(url, server.uri()),
+            Err(other) => panic!("expected LocalUnavailable, got {other:?}"),
+            Ok(_) => panic!("expected LocalUnavailable, got a client"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_forced_remote_transport_skips_probe() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+            .expect(0)
+            .mount(&server)
+            .await;
+        let client = ZoteroClient::connect("12345".into(), "key".into(), Transport::Remote, &server.uri())
+            .await
+            .unwrap();
+        assert_eq!(client.base_url, DEFAULT_BASE_URL);
+    }
+
+    #[tokio::test]
+    async fn test_list_top_items() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/users/12345/items/top"))
+            .respond_with(array_response(&item_list_json()))
+            .mount(&server)
+            .await;
+        let client = setup_client(&server).await;
+        let resp = client
+            .list_top_items(&ItemListParams::default())
+            .await
+            .unwrap();
+        assert_eq!(resp.items.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_list_trash_items() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/users/12345/items/trash"))
+            .respond_with(array_response(&item_list_json()))
             .mount(&server)
             .await;
         let client = setup_client(&server).await;
@@ -1537,6 +2268,166 @@ mod tests {
         assert_eq!(item.key, "ABC12345");
     }
 
+    fn item_with_doi_json(key: &str, doi: &str) -> String {
+        item_list_json()
+            .replace("ABC12345", key)
+            .replace(r#""title": "Test","#, &format!(r#""title": "Test", "DOI": "{doi}","#))
+    }
+
+    #[tokio::test]
+    async fn test_find_by_doi_hit() {
+        let server = MockServer::start().await;
+        // The first result only mentions the DOI (e.g. in a note); the second has it in its DOI field.
+        let body = format!(
+            "[{},{}]",
+            item_with_doi_json("OTHER123", "10.9999/other").trim_matches(['[', ']']),
+            item_with_doi_json("MATCH123", "10.1234/ABC").trim_matches(['[', ']']),
+        );
+        Mock::given(method("GET"))
+            .and(path("/users/12345/items/top"))
+            .and(query_param("q", "10.1234/abc"))
+            .and(query_param("qmode", "everything"))
+            .respond_with(array_response(&body))
+            .mount(&server)
+            .await;
+        let client = setup_client(&server).await;
+        let item = client.find_by_doi("https://doi.org/10.1234/abc").await.unwrap();
+        assert_eq!(item.map(|i| i.key).as_deref(), Some("MATCH123"));
+    }
+
+    #[tokio::test]
+    async fn test_find_by_doi_miss() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/users/12345/items/top"))
+            .and(query_param("qmode", "everything"))
+            .respond_with(array_response(&item_with_doi_json("OTHER123", "10.1234/abcd")))
+            .mount(&server)
+            .await;
+        let client = setup_client(&server).await;
+        let item = client.find_by_doi("doi:10.1234/abc").await.unwrap();
+        assert!(item.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_find_by_doi_reads_extra() {
+        let server = MockServer::start().await;
+        let thesis = item_list_json().replace(
+            r#""title": "Test","#,
+            r#""title": "Test", "extra": "Type: dissertation\nDOI: 10.5555/T","#,
+        );
+        Mock::given(method("GET"))
+            .and(path("/users/12345/items/top"))
+            .respond_with(array_response(&thesis))
+            .mount(&server)
+            .await;
+        let client = setup_client(&server).await;
+        let item = client.find_by_doi("10.5555/t").await.unwrap();
+        assert_eq!(item.map(|i| i.key).as_deref(), Some("ABC12345"));
+    }
+
+    #[test]
+    fn test_normalize_doi() {
+        assert_eq!(normalize_doi("10.1234/ABC"), "10.1234/abc");
+        assert_eq!(normalize_doi(" https://doi.org/10.1234/abc "), "10.1234/abc");
+        assert_eq!(normalize_doi("DOI:10.1234/abc"), "10.1234/abc");
+        assert_eq!(normalize_doi("http://dx.doi.org/10.1234/abc"), "10.1234/abc");
+    }
+
+    #[tokio::test]
+    async fn test_get_items_chunks_keys() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/users/12345/items"))
+            .and(query_param("limit", "50"))
+            .respond_with(array_response(&item_list_json()))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/users/12345/items"))
+            .and(query_param("limit", "10"))
+            .respond_with(array_response(&item_list_json()))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let client = setup_client(&server).await;
+        let keys: Vec<String> = (0..60).map(|i| format!("KEY{i:05}")).collect();
+        let key_refs: Vec<&str> = keys.iter().map(String::as_str).collect();
+        let batch = client.get_items(&key_refs).await;
+        assert!(batch.failed.is_empty());
+        assert_eq!(batch.items.len(), 2);
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 2);
+        let sent: Vec<String> = requests
+            .iter()
+            .flat_map(|r| {
+                r.url
+                    .query_pairs()
+                    .filter(|(k, _)| k == "itemKey")
+                    .flat_map(|(_, v)| v.split(',').map(str::to_string).collect::<Vec<_>>())
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        assert_eq!(sent.len(), 60);
+    }
+
+    #[tokio::test]
+    async fn test_export_items_requests_format() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/users/12345/items"))
+            .and(query_param("itemKey", "AAAA1111,BBBB2222"))
+            .and(query_param("format", "bibtex"))
+            .and(query_param("limit", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("\n@article{a_2020,\n}\n"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let client = setup_client(&server).await;
+        let bib = client.export_items(&["AAAA1111", "BBBB2222"], "bibtex").await.unwrap();
+        assert_eq!(bib, "\n@article{a_2020,\n}\n");
+    }
+
+    #[tokio::test]
+    async fn test_get_items_empty() {
+        let server = MockServer::start().await;
+        let client = setup_client(&server).await;
+        let batch = client.get_items(&[]).await;
+        assert!(batch.items.is_empty());
+        assert!(batch.failed.is_empty());
+        assert!(server.received_requests().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_items_keeps_successful_chunks() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/users/12345/items"))
+            .and(query_param("limit", "50"))
+            .respond_with(array_response(&item_list_json()))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/users/12345/items"))
+            .and(query_param("limit", "10"))
+            .respond_with(ResponseTemplate::new(500).set_body_string("boom"))
+            .mount(&server)
+            .await;
+        let client = setup_client(&server).await;
+        let keys: Vec<String> = (0..60).map(|i| format!("KEY{i:05}")).collect();
+        let key_refs: Vec<&str> = keys.iter().map(String::as_str).collect();
+        let batch = client.get_items(&key_refs).await;
+        assert_eq!(batch.items.len(), 1);
+        assert_eq!(batch.failed.len(), 1);
+        assert_eq!(batch.failed[0].keys, keys[50..]);
+        match &batch.failed[0].error {
+            ZoteroError::Api { status, .. } => assert_eq!(*status, 500),
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
     #[tokio::test]
     async fn test_list_item_children() {
         let server = MockServer::start().await;
@@ -1840,6 +2731,10 @@ mod tests {
     // ── Cache tests ───────────────────────────────────────────────────
 
     fn temp_cache() -> DiskCache {
+        temp_cache_with_ttl(Duration::from_secs(600))
+    }
+
+    fn temp_cache_with_ttl(ttl: Duration) -> DiskCache {
         use std::collections::hash_map::DefaultHasher;
         use std::hash::{Hash, Hasher};
         use std::time::{SystemTime, UNIX_EPOCH};
@@ -1853,7 +2748,7 @@ mod tests {
         let dir = std::env::temp_dir()
             .join("papers-zotero-test-cache")
             .join(format!("{:x}", h.finish()));
-        DiskCache::new(dir, Duration::from_secs(600)).unwrap()
+        DiskCache::new(dir, ttl).unwrap()
     }
 
     #[tokio::test]
@@ -1878,6 +2773,35 @@ mod tests {
         drop(mock);
     }
 
+    #[tokio::test]
+    async fn test_expired_cache_revalidated_with_304() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/users/12345/items"))
+            .and(header("If-Modified-Since-Version", "100"))
+            .respond_with(ResponseTemplate::new(304).set_body_string("not json"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/users/12345/items"))
+            .respond_with(array_response(&item_list_json()))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let client = ZoteroClient::new("12345", "test-key")
+            .with_base_url(server.uri())
+            .with_cache(temp_cache_with_ttl(Duration::ZERO));
+        let fresh = client.list_items(&ItemListParams::default()).await.unwrap();
+        // Let the entry expire so the next call has to ask the server
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+        let revalidated = client.list_items(&ItemListParams::default()).await.unwrap();
+        assert_eq!(revalidated.items.len(), 1);
+        assert_eq!(revalidated.items[0].key, fresh.items[0].key);
+        assert_eq!(revalidated.total_results, Some(42));
+        assert_eq!(revalidated.last_modified_version, fresh.last_modified_version);
+    }
+
     #[tokio::test]
     async fn test_cache_error_not_cached() {
         let server = MockServer::start().await;
@@ -1914,6 +2838,27 @@ mod tests {
         assert_eq!(bytes, b"fake-pdf-bytes");
     }
 
+    #[tokio::test]
+    async fn test_download_item_file_cancelled() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/users/12345/items/ATTACH1/file"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"slow".to_vec()).set_delay(Duration::from_secs(10)))
+            .mount(&server)
+            .await;
+        let client = setup_client(&server).await;
+        let token = CancellationToken::new();
+        let canceller = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            canceller.cancel();
+        });
+        let started = std::time::Instant::now();
+        let err = client.download_item_file_cancellable("ATTACH1", &token).await.unwrap_err();
+        assert!(matches!(err, ZoteroError::Cancelled), "got {err:?}");
+        assert!(started.elapsed() < Duration::from_secs(2), "took {:?}", started.elapsed());
+    }
+
     #[tokio::test]
     async fn test_download_item_file_404() {
         let server = MockServer::start().await;
@@ -2215,6 +3160,34 @@ mod tests {
         assert_eq!(arr[0]["name"], "Starred");
     }
 
+    #[tokio::test]
+    async fn test_get_tag_colors() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/users/12345/settings/tagColors"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r##"{"value":[{"name":"Starred","color":"#FF8C19"},{"name":"Survey","color":"#FF6666"}],"version":3826}"##,
+            ))
+            .mount(&server)
+            .await;
+        let client = setup_client(&server).await;
+        let colors = client.get_tag_colors().await.unwrap();
+        let names: Vec<(&str, &str)> = colors.iter().map(|c| (c.name.as_str(), c.color.as_str())).collect();
+        assert_eq!(names, [("Starred", "#FF8C19"), ("Survey", "#FF6666")]);
+    }
+
+    #[tokio::test]
+    async fn test_get_tag_colors_unset() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/users/12345/settings/tagColors"))
+            .respond_with(ResponseTemplate::new(404).set_body_string("Not found"))
+            .mount(&server)
+            .await;
+        let client = setup_client(&server).await;
+        assert!(client.get_tag_colors().await.unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn test_get_setting_not_found() {
         let server = MockServer::start().await;
@@ -2302,6 +3275,86 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_get_item_file_view_url_fresh() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/users/12345/items/ATTACH1/file/view/url"))
+            .and(header("Cache-Control", "no-cache"))
+            .and(header("Zotero-API-Key", "test-key"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("https://files.zotero.net/new/paper.pdf\n"),
+            )
+            .expect(2)
+            .mount(&server)
+            .await;
+        let client = setup_client(&server).await;
+        for _ in 0..2 {
+            let url = client.get_item_file_view_url_fresh("ATTACH1").await.unwrap();
+            assert_eq!(url, "https://files.zotero.net/new/paper.pdf");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_item_file_view_refreshes_expired_url() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/users/12345/items/ATTACH1/file/view"))
+            .respond_with(
+                ResponseTemplate::new(302)
+                    .insert_header("Location", format!("{}/cdn/stale.pdf", server.uri())),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/cdn/stale.pdf"))
+            .respond_with(ResponseTemplate::new(403).set_body_string("Request has expired"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/users/12345/items/ATTACH1/file/view/url"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string(format!("{}/cdn/fresh.pdf", server.uri())),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/cdn/fresh.pdf"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"fresh-pdf-bytes".to_vec()))
+            .mount(&server)
+            .await;
+
+        let client = setup_client(&server).await;
+        let bytes = client.get_item_file_view("ATTACH1").await.unwrap();
+        assert_eq!(bytes, b"fresh-pdf-bytes");
+
+        // A client that doesn't follow redirects gets the same result
+        let no_redirect = reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .unwrap();
+        server.reset().await;
+        Mock::given(method("GET"))
+            .and(path("/users/12345/items/ATTACH1/file/view"))
+            .respond_with(
+                ResponseTemplate::new(302)
+                    .insert_header("Location", format!("{}/cdn/fresh.pdf", server.uri())),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/cdn/fresh.pdf"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"fresh-pdf-bytes".to_vec()))
+            .mount(&server)
+            .await;
+        let client = setup_client(&server).await.with_http_client(no_redirect);
+        let bytes = client.get_item_file_view("ATTACH1").await.unwrap();
+        assert_eq!(bytes, b"fresh-pdf-bytes");
+    }
+
     // ── Key info tests ────────────────────────────────────────────────
 
     /// Real response shape (captured from live API, /keys/current):
@@ -2343,6 +3396,77 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_user_agent_header() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/users/12345/items"))
+            .and(header("user-agent", DEFAULT_USER_AGENT))
+            .respond_with(array_response("[]"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let client = setup_client(&server).await;
+        client.list_items(&ItemListParams::default()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_api_error_redacts_key() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/users/12345/items"))
+            .respond_with(
+                ResponseTemplate::new(400)
+                    .set_body_string("Invalid request: key=test-key&q=foo (key test-key)"),
+            )
+            .mount(&server)
+            .await;
+        let client = setup_client(&server).await;
+        let err = client.list_items(&ItemListParams::default()).await.unwrap_err();
+        match &err {
+            ZoteroError::Api { status, message } => {
+                assert_eq!(*status, 400);
+                assert_eq!(message, "Invalid request: key=[REDACTED]&q=foo (key [REDACTED])");
+            }
+            e => panic!("Expected Api error, got {e}"),
+        }
+        assert!(!err.to_string().contains("test-key"));
+    }
+
+    #[tokio::test]
+    async fn test_malformed_json_error_names_path() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/users/12345/items/ABC12345"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("Last-Modified-Version", "1")
+                    .set_body_string(r#"{"key": 7, "data": "test-key"}"#),
+            )
+            .mount(&server)
+            .await;
+        let client = setup_client(&server).await;
+        let err = client.get_item("ABC12345").await.unwrap_err();
+        match &err {
+            ZoteroError::Json { path, snippet, .. } => {
+                assert_eq!(path, "/users/12345/items/ABC12345");
+                assert_eq!(snippet, r#"{"key": 7, "data": "[REDACTED]"}"#);
+            }
+            e => panic!("Expected Json error, got {e}"),
+        }
+        assert!(err.to_string().contains("/users/12345/items/ABC12345"));
+    }
+
+    #[tokio::test]
+    async fn test_http_error_redacts_key_in_url() {
+        // Nothing listens on this port, so the request fails at the transport layer.
+        let client = ZoteroClient::new("12345", "secret-key-123").with_base_url("http://127.0.0.1:1");
+        let err = client.get_key_info().await.unwrap_err();
+        assert!(matches!(err, ZoteroError::Http(_)));
+        let msg = err.to_string();
+        assert!(!msg.contains("secret-key-123"), "key leaked: {msg}");
+    }
+
     // ── Write fixtures ────────────────────────────────────────────────
 
     fn item_write_response_json(key: &str) -> String {
@@ -2413,6 +3537,103 @@ mod tests {
         assert_eq!(resp.failed["1"].code, 400);
     }
 
+    #[tokio::test]
+    async fn test_create_item_returns_key() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/users/12345/items"))
+            .and(wiremock::matchers::body_json(serde_json::json!([{
+                "itemType": "journalArticle",
+                "title": "Test",
+                "creators": [{"creatorType": "author", "firstName": "Jane", "lastName": "Doe"}],
+                "DOI": "10.1234/test",
+                "tags": [],
+                "collections": []
+            }])))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string(item_write_response_json("NEW12345")),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        let client = setup_client(&server).await;
+        let item = NewItem {
+            item_type: "journalArticle".into(),
+            title: Some("Test".into()),
+            creators: vec![Creator {
+                creator_type: "author".into(),
+                first_name: Some("Jane".into()),
+                last_name: Some("Doe".into()),
+                name: None,
+            }],
+            doi: Some("10.1234/test".into()),
+            ..Default::default()
+        };
+        let key = client.create_item(&item).await.unwrap();
+        assert_eq!(key, "NEW12345");
+    }
+
+    #[tokio::test]
+    async fn test_create_items_partial_failure_write_result() {
+        let server = MockServer::start().await;
+        let body = r#"{"successful":{"0":{"key":"OK123456"}},"unchanged":{"2":"SAME0000"},"failed":{"1":{"key":null,"code":400,"message":"Invalid item type"}}}"#;
+        Mock::given(method("POST"))
+            .and(path("/users/12345/items"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .mount(&server)
+            .await;
+        let client = setup_client(&server).await;
+        let resp = client.create_items(vec![serde_json::json!({"itemType": "note"})]).await.unwrap();
+        assert_eq!(resp.first_key(), Some("OK123456"));
+        match client.write_result(resp).unwrap_err() {
+            ZoteroError::Api { status, message } => {
+                assert_eq!(status, 400);
+                assert_eq!(message, "Invalid item type");
+            }
+            e => panic!("unexpected error: {e}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_write_result_redacts_key() {
+        let server = MockServer::start().await;
+        let body = r#"{"successful":{},"unchanged":{},"failed":{"0":{"key":null,"code":403,"message":"Key test-key cannot write"}}}"#;
+        Mock::given(method("POST"))
+            .and(path("/users/12345/items"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .mount(&server)
+            .await;
+        let client = setup_client(&server).await;
+        let resp = client.create_items(vec![serde_json::json!({"itemType": "note"})]).await.unwrap();
+        match client.write_result(resp).unwrap_err() {
+            ZoteroError::Api { status, message } => {
+                assert_eq!(status, 403);
+                assert_eq!(message, "Key [REDACTED] cannot write");
+            }
+            e => panic!("unexpected error: {e}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_item_failed() {
+        let server = MockServer::start().await;
+        let body = r#"{"successful":{},"unchanged":{},"failed":{"0":{"key":null,"code":400,"message":"Invalid item type"}}}"#;
+        Mock::given(method("POST"))
+            .and(path("/users/12345/items"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .mount(&server)
+            .await;
+        let client = setup_client(&server).await;
+        let item = NewItem { item_type: "badType".into(), ..Default::default() };
+        match client.create_item(&item).await.unwrap_err() {
+            ZoteroError::Api { status, message } => {
+                assert_eq!(status, 400);
+                assert_eq!(message, "Invalid item type");
+            }
+            e => panic!("unexpected error: {e}"),
+        }
+    }
+
     #[tokio::test]
     async fn test_update_item() {
         let server = MockServer::start().await;
@@ -2445,6 +3666,85 @@ mod tests {
             .unwrap();
     }
 
+    /// Mount `GET /items/ABC12345` returning the item at `version` with `tags`.
+    async fn mount_tagged_item(server: &MockServer, version: u64, tags: serde_json::Value) {
+        let mut item: serde_json::Value = serde_json::from_str(&single_item_json()).unwrap();
+        item["version"] = version.into();
+        item["data"]["version"] = version.into();
+        item["data"]["tags"] = tags;
+        Mock::given(method("GET"))
+            .and(path("/users/12345/items/ABC12345"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(item))
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_update_item_tags_adds_new_tag() {
+        let server = MockServer::start().await;
+        mount_tagged_item(&server, 100, serde_json::json!([{"tag": "Survey", "type": 1}])).await;
+        Mock::given(method("PATCH"))
+            .and(path("/users/12345/items/ABC12345"))
+            .and(header("If-Unmodified-Since-Version", "100"))
+            .and(body_json(serde_json::json!({"tags": [{"tag": "Survey", "type": 1}, {"tag": "GPU"}]})))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let client = setup_client(&server).await;
+        let tags = client.update_item_tags("ABC12345", &["GPU", "Survey"], &[]).await.unwrap();
+        let names: Vec<&str> = tags.iter().map(|t| t.tag.as_str()).collect();
+        assert_eq!(names, ["Survey", "GPU"]);
+    }
+
+    #[tokio::test]
+    async fn test_update_item_tags_removes_existing_tag() {
+        let server = MockServer::start().await;
+        mount_tagged_item(&server, 100, serde_json::json!([{"tag": "Survey"}, {"tag": "GPU"}])).await;
+        Mock::given(method("PATCH"))
+            .and(path("/users/12345/items/ABC12345"))
+            .and(body_json(serde_json::json!({"tags": [{"tag": "GPU"}]})))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let client = setup_client(&server).await;
+        let tags = client.update_item_tags("ABC12345", &[], &["Survey"]).await.unwrap();
+        assert_eq!(tags, [ItemTag { tag: "GPU".into(), tag_type: None }]);
+    }
+
+    #[tokio::test]
+    async fn test_update_item_tags_no_change_skips_write() {
+        let server = MockServer::start().await;
+        mount_tagged_item(&server, 100, serde_json::json!([{"tag": "GPU"}])).await;
+        Mock::given(method("PATCH")).respond_with(ResponseTemplate::new(204)).expect(0).mount(&server).await;
+        let client = setup_client(&server).await;
+        let tags = client.update_item_tags("ABC12345", &["GPU"], &["Absent"]).await.unwrap();
+        assert_eq!(tags.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_update_item_tags_retries_on_version_conflict() {
+        let server = MockServer::start().await;
+        mount_tagged_item(&server, 100, serde_json::json!([])).await;
+        Mock::given(method("PATCH"))
+            .and(path("/users/12345/items/ABC12345"))
+            .respond_with(ResponseTemplate::new(412).set_body_string("Precondition Failed"))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PATCH"))
+            .and(path("/users/12345/items/ABC12345"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let client = setup_client(&server).await;
+        let tags = client.update_item_tags("ABC12345", &["GPU"], &[]).await.unwrap();
+        assert_eq!(tags.len(), 1);
+    }
+
     #[tokio::test]
     async fn test_delete_item() {
         let server = MockServer::start().await;
@@ -2643,4 +3943,30 @@ mod tests {
             .await
             .unwrap();
     }
+
+    // ── Tracing tests ─────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_request_span_emitted_with_key_redacted() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/keys/secret-key-123"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"userID":12345}"#))
+            .mount(&server)
+            .await;
+        let logs = CapturedLogs::default();
+        let _guard = logs.install();
+
+        let client = ZoteroClient::new("12345", "secret-key-123").with_base_url(server.uri());
+        client.get_key_info().await.unwrap();
+
+        let out = logs.contents();
+        assert!(out.contains("zotero_request"), "no request span in: {out}");
+        assert!(out.contains("method=\"GET\""), "no method in: {out}");
+        assert!(out.contains("/keys/[REDACTED]"), "key not masked in: {out}");
+        assert!(out.contains("status=200"), "no status in: {out}");
+        let bytes = format!("bytes={}", r#"{"userID":12345}"#.len());
+        assert!(out.contains(&bytes), "no body size in: {out}");
+        assert!(!out.contains("secret-key-123"), "key leaked into logs: {out}");
+    }
 }
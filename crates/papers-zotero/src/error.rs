@@ -5,10 +5,17 @@
 /// - [`Http`](ZoteroError::Http) — network or connection failure (wraps
 ///   [`reqwest::Error`])
 /// - [`Json`](ZoteroError::Json) — response body could not be deserialized
-///   (wraps [`serde_json::Error`])
+///   (wraps [`serde_json::Error`] with the endpoint path and a body snippet)
+/// - [`Serialize`](ZoteroError::Serialize) — a request body could not be
+///   serialized
 /// - [`Api`](ZoteroError::Api) — the Zotero API returned a non-success HTTP
 ///   status code (e.g. 404 for unknown item, 403 for forbidden, 500 for server
 ///   error)
+/// - [`NotRunning`](ZoteroError::NotRunning) /
+///   [`LocalUnavailable`](ZoteroError::LocalUnavailable) — the local API
+///   couldn't be used
+/// - [`Cancelled`](ZoteroError::Cancelled) — a cancellable request was
+///   cancelled
 #[derive(Debug, thiserror::Error)]
 pub enum ZoteroError {
     /// Network or connection error from reqwest.
@@ -16,13 +23,30 @@ pub enum ZoteroError {
     Http(#[from] reqwest::Error),
 
     /// Failed to deserialize the JSON response body.
-    #[error("JSON error: {0}")]
-    Json(#[from] serde_json::Error),
+    ///
+    /// `path` is the endpoint that returned the body and `snippet` its first
+    /// 200 characters, with the API key masked as `[REDACTED]`. Both are
+    /// empty when converted from a bare [`serde_json::Error`] with `?`.
+    #[error("JSON error from {path}: {source} (body: {snippet:?})")]
+    Json {
+        path: String,
+        snippet: String,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    /// A request body could not be serialized before sending it to `path`.
+    #[error("Failed to serialize request to {path}: {source}")]
+    Serialize {
+        path: String,
+        #[source]
+        source: serde_json::Error,
+    },
 
     /// The API returned a non-success HTTP status code.
     ///
     /// `status` is the HTTP status code (e.g. 404, 403, 500) and `message`
-    /// contains the response body text.
+    /// contains the response body text, with the API key masked as `[REDACTED]`.
     #[error("API error (status {status}): {message}")]
     Api { status: u16, message: String },
 
@@ -33,6 +57,35 @@ pub enum ZoteroError {
          Start Zotero and try again, or set ZOTERO_CHECK_LAUNCHED=0 to use the remote API instead."
     )]
     NotRunning { path: String },
+
+    /// The local API was required (`ZOTERO_TRANSPORT=local` or
+    /// [`Transport::Local`](crate::Transport::Local)) but `url` didn't answer.
+    #[error(
+        "Zotero's local API is not reachable at {url}.\n\
+         Start Zotero and check Settings → Advanced → \"Enable Local API\", or use the remote web API instead."
+    )]
+    LocalUnavailable { url: String },
+
+    /// An environment variable is set to a value that can't be used, such as
+    /// an unknown [`TRANSPORT_ENV`](crate::TRANSPORT_ENV) or a malformed
+    /// [`LOCAL_URL_ENV`](crate::LOCAL_URL_ENV).
+    #[error("{var} must be {expected} (got {value:?})")]
+    InvalidEnv {
+        var: &'static str,
+        value: String,
+        expected: String,
+    },
+
+    /// The [`CancellationToken`](tokio_util::sync::CancellationToken) passed
+    /// to a cancellable request fired before it finished.
+    #[error("Request cancelled")]
+    Cancelled,
+}
+
+impl From<serde_json::Error> for ZoteroError {
+    fn from(source: serde_json::Error) -> Self {
+        Self::Json { path: String::new(), snippet: String::new(), source }
+    }
 }
 
 /// A [`Result`](std::result::Result) alias with [`ZoteroError`] as the error
@@ -75,8 +75,9 @@ pub mod response;
 pub mod types;
 
 pub use cache::DiskCache;
-pub use client::ZoteroClient;
+pub use client::{Diagnosis, Resolution, Transport, ZoteroClient, DEFAULT_USER_AGENT, LOCAL_URL_ENV, TRANSPORT_ENV};
 pub use error::{Result, ZoteroError};
 pub use params::{CollectionListParams, DeletedParams, FulltextParams, ItemListParams, TagListParams};
-pub use response::{PagedResponse, VersionedResponse};
+pub use response::{BatchResponse, ChunkFailure, PagedResponse, VersionedResponse};
 pub use types::*;
+pub use tokio_util::sync::CancellationToken;
@@ -59,3 +59,27 @@ pub struct PagedResponse<T> {
     /// incremental sync via the `since` parameter.
     pub last_modified_version: Option<u64>,
 }
+
+/// Result of a batched lookup that is split into several requests.
+///
+/// A failed chunk doesn't discard the chunks that succeeded: their items are
+/// kept in `items`, and each failure is recorded in `failed` together with
+/// the keys it covered.
+#[derive(Debug)]
+pub struct BatchResponse<T> {
+    /// Items returned by every chunk that succeeded.
+    pub items: Vec<T>,
+
+    /// One entry per chunk that failed.
+    pub failed: Vec<ChunkFailure>,
+}
+
+/// A chunk of a [`BatchResponse`] whose request failed.
+#[derive(Debug)]
+pub struct ChunkFailure {
+    /// The keys requested by the failed chunk.
+    pub keys: Vec<String>,
+
+    /// Why the chunk failed.
+    pub error: crate::ZoteroError,
+}
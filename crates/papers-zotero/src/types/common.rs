@@ -56,24 +56,66 @@ pub struct LinkEntry {
 pub struct Creator {
     #[serde(rename = "creatorType")]
     pub creator_type: String,
-    #[serde(rename = "firstName")]
+    #[serde(rename = "firstName", skip_serializing_if = "Option::is_none")]
     pub first_name: Option<String>,
-    #[serde(rename = "lastName")]
+    #[serde(rename = "lastName", skip_serializing_if = "Option::is_none")]
     pub last_name: Option<String>,
     /// Single-field name (used for institutional authors).
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
 }
 
+/// `creatorType` values credited as authors of the work itself (as opposed
+/// to editors, translators, reviewers, ...).
+pub const AUTHOR_ROLES: &[&str] = &["author", "contributor"];
+
+impl Creator {
+    /// Whether this creator has one of the [`AUTHOR_ROLES`].
+    pub fn is_author(&self) -> bool {
+        AUTHOR_ROLES.contains(&self.creator_type.as_str())
+    }
+
+    /// Name in reading order: `"First Last"`, or whichever part is present.
+    /// `None` when the creator has no non-empty name at all.
+    pub fn display_name(&self) -> Option<String> {
+        let first = non_empty(&self.first_name);
+        match (first, non_empty(&self.last_name), non_empty(&self.name)) {
+            (Some(f), Some(l), _) => Some(format!("{f} {l}")),
+            (_, Some(l), _) => Some(l.to_string()),
+            (_, _, Some(n)) => Some(n.to_string()),
+            (Some(f), None, None) => Some(f.to_string()),
+            (None, None, None) => None,
+        }
+    }
+
+    /// Name in sorting order: `"Last, First"`, or whichever part is present.
+    pub fn sort_name(&self) -> Option<String> {
+        if let Some(n) = non_empty(&self.name) {
+            return Some(n.to_string());
+        }
+        match (non_empty(&self.first_name), non_empty(&self.last_name)) {
+            (Some(f), Some(l)) => Some(format!("{l}, {f}")),
+            (None, Some(l)) => Some(l.to_string()),
+            (Some(f), None) => Some(f.to_string()),
+            (None, None) => None,
+        }
+    }
+}
+
+fn non_empty(s: &Option<String>) -> Option<&str> {
+    s.as_deref().filter(|s| !s.trim().is_empty())
+}
+
 /// A tag attached to an item.
 ///
 /// ```json
 /// { "tag": "machine learning", "type": 0 }
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ItemTag {
     pub tag: String,
     /// Tag type: 0 = user-created, 1 = automatic/imported. May be absent.
-    #[serde(rename = "type", default)]
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
     pub tag_type: Option<i32>,
 }
 
@@ -113,6 +155,27 @@ mod tests {
         assert!(c.first_name.is_none());
     }
 
+    #[test]
+    fn test_creator_names() {
+        let c: Creator = serde_json::from_str(
+            r#"{"creatorType": "author", "firstName": "John", "lastName": "Doe"}"#,
+        )
+        .unwrap();
+        assert_eq!(c.display_name().as_deref(), Some("John Doe"));
+        assert_eq!(c.sort_name().as_deref(), Some("Doe, John"));
+
+        let c: Creator =
+            serde_json::from_str(r#"{"creatorType": "editor", "name": "NVIDIA Corporation"}"#).unwrap();
+        assert_eq!(c.display_name().as_deref(), Some("NVIDIA Corporation"));
+        assert_eq!(c.sort_name().as_deref(), Some("NVIDIA Corporation"));
+        assert!(!c.is_author());
+
+        let c: Creator =
+            serde_json::from_str(r#"{"creatorType": "author", "firstName": "", "lastName": ""}"#).unwrap();
+        assert!(c.display_name().is_none());
+        assert!(c.sort_name().is_none());
+    }
+
     #[test]
     fn test_deserialize_item_tag() {
         let json = r#"{"tag": "ML", "type": 0}"#;
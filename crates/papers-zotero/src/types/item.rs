@@ -71,7 +71,7 @@ pub struct ItemMeta {
 /// Since different item types (journalArticle, book, attachment, note, etc.)
 /// have different fields, only the common fields are typed explicitly. All
 /// remaining type-specific fields are captured in `extra_fields`.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ItemData {
     pub key: String,
     pub version: u64,
@@ -167,6 +167,95 @@ pub struct ItemData {
     pub extra_fields: HashMap<String, serde_json::Value>,
 }
 
+/// Attachment content types Zotero's reader can annotate: PDFs, EPUBs, and
+/// HTML snapshots.
+pub const ANNOTATABLE_CONTENT_TYPES: &[&str] = &["application/pdf", "application/epub+zip", "text/html"];
+
+/// Every item type in Zotero's schema. `annotation` items are always
+/// children of an attachment; the rest can be top-level.
+pub const ITEM_TYPES: &[&str] = &[
+    "annotation", "artwork", "attachment", "audioRecording", "bill", "blogPost", "book", "bookSection",
+    "case", "computerProgram", "conferencePaper", "dataset", "dictionaryEntry", "document", "email",
+    "encyclopediaArticle", "film", "forumPost", "hearing", "instantMessage", "interview", "journalArticle",
+    "letter", "magazineArticle", "manuscript", "map", "newspaperArticle", "note", "patent", "podcast",
+    "preprint", "presentation", "radioBroadcast", "report", "standard", "statute", "thesis", "tvBroadcast",
+    "videoRecording", "webpage",
+];
+
+impl Item {
+    /// Whether this is an attachment that can have annotation children (see
+    /// [`ANNOTATABLE_CONTENT_TYPES`]).
+    pub fn is_annotatable_attachment(&self) -> bool {
+        self.data.item_type == "attachment"
+            && self
+                .data
+                .content_type
+                .as_deref()
+                .is_some_and(|ct| ANNOTATABLE_CONTENT_TYPES.contains(&ct))
+    }
+
+    /// The annotation fields of an `annotation` item, or `None` for any
+    /// other item type.
+    pub fn as_annotation(&self) -> Option<Annotation<'_>> {
+        if self.data.item_type != "annotation" {
+            return None;
+        }
+        let field = |name: &str| {
+            self.data
+                .extra_fields
+                .get(name)
+                .and_then(|v| v.as_str())
+                .filter(|s| !s.is_empty())
+        };
+        Some(Annotation {
+            annotation_type: field("annotationType"),
+            text: field("annotationText"),
+            comment: field("annotationComment"),
+            color: field("annotationColor"),
+            page_label: field("annotationPageLabel"),
+            sort_index: field("annotationSortIndex"),
+        })
+    }
+}
+
+/// Typed view of an annotation item's `annotation*` fields, which Zotero
+/// returns alongside the common item data (so they live in
+/// [`ItemData::extra_fields`]). Fields Zotero sends as empty strings are `None`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Annotation<'a> {
+    /// `highlight`, `underline`, `note`, `text`, `image`, or `ink`.
+    pub annotation_type: Option<&'a str>,
+    /// The highlighted or underlined text.
+    pub text: Option<&'a str>,
+    pub comment: Option<&'a str>,
+    /// Hex color, e.g. `#ffd400`.
+    pub color: Option<&'a str>,
+    /// The page label shown in the reader (not necessarily the page index).
+    pub page_label: Option<&'a str>,
+    /// Reading-order key (`page|offset|top`, zero-padded so it sorts as a string).
+    pub sort_index: Option<&'a str>,
+}
+
+impl ItemData {
+    /// Creators credited as authors (see [`AUTHOR_ROLES`]), in library order.
+    pub fn authors(&self) -> Vec<&Creator> {
+        self.creators.iter().filter(|c| c.is_author()).collect()
+    }
+
+    /// This item's tags with `remove` dropped (exact name match) and `add`
+    /// appended unless already present. Existing tags keep their order and type.
+    pub fn tags_with(&self, add: &[&str], remove: &[&str]) -> Vec<ItemTag> {
+        let mut tags: Vec<ItemTag> =
+            self.tags.iter().filter(|t| !remove.contains(&t.tag.as_str())).cloned().collect();
+        for &name in add {
+            if !tags.iter().any(|t| t.tag == name) {
+                tags.push(ItemTag { tag: name.to_string(), tag_type: None });
+            }
+        }
+        tags
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -183,6 +272,28 @@ mod tests {
         assert_eq!(item.data.creators[0].last_name.as_deref(), Some("Akenine-Moller"));
     }
 
+    #[test]
+    fn test_unfamiliar_item_type_parses() {
+        let json = include_str!("../../tests/fixtures/item.json")
+            .replace(r#""itemType": "journalArticle""#, r#""itemType": "futureThing""#);
+        let item: Item = serde_json::from_str(&json).unwrap();
+        assert_eq!(item.data.item_type, "futureThing");
+    }
+
+    #[test]
+    fn test_authors_filters_roles() {
+        let mut item: Item = serde_json::from_str(include_str!("../../tests/fixtures/item.json")).unwrap();
+        item.data.creators = serde_json::from_value(serde_json::json!([
+            {"creatorType": "editor", "firstName": "Ed", "lastName": "Itor"},
+            {"creatorType": "author", "firstName": "Ada", "lastName": "Lovelace"},
+            {"creatorType": "translator", "name": "Babel Inc."},
+            {"creatorType": "contributor", "name": "CERN"},
+        ]))
+        .unwrap();
+        let names: Vec<String> = item.data.authors().iter().filter_map(|c| c.display_name()).collect();
+        assert_eq!(names, ["Ada Lovelace", "CERN"]);
+    }
+
     #[test]
     fn test_deserialize_attachment_fixture() {
         let json = include_str!("../../tests/fixtures/attachment.json");
@@ -191,4 +302,67 @@ mod tests {
         assert_eq!(item.data.link_mode.as_deref(), Some("imported_file"));
         assert!(item.data.filename.is_some());
     }
+
+    #[test]
+    fn test_as_annotation_fixture() {
+        let item: Item = serde_json::from_str(include_str!("../../tests/fixtures/annotation.json")).unwrap();
+        let ann = item.as_annotation().unwrap();
+        assert_eq!(
+            ann,
+            Annotation {
+                annotation_type: Some("highlight"),
+                text: Some("Our solver converges in a handful of iterations even for stiff constraints."),
+                comment: Some("Compare against XPBD"),
+                color: Some("#ffd400"),
+                page_label: Some("4"),
+                sort_index: Some("00003|001204|00312"),
+            }
+        );
+    }
+
+    #[test]
+    fn test_as_annotation_empty_and_missing_fields() {
+        let mut item: Item = serde_json::from_str(include_str!("../../tests/fixtures/annotation.json")).unwrap();
+        item.data.extra_fields.insert("annotationComment".into(), "".into());
+        item.data.extra_fields.remove("annotationColor");
+        let ann = item.as_annotation().unwrap();
+        assert_eq!(ann.comment, None);
+        assert_eq!(ann.color, None);
+        assert_eq!(ann.annotation_type, Some("highlight"));
+
+        let attachment: Item = serde_json::from_str(include_str!("../../tests/fixtures/attachment.json")).unwrap();
+        assert!(attachment.as_annotation().is_none());
+    }
+
+    fn attachment(content_type: Option<&str>) -> Item {
+        let mut item: Item = serde_json::from_str(include_str!("../../tests/fixtures/attachment.json")).unwrap();
+        item.data.content_type = content_type.map(String::from);
+        item
+    }
+
+    #[test]
+    fn test_is_annotatable_attachment_pdf() {
+        assert!(attachment(Some("application/pdf")).is_annotatable_attachment());
+    }
+
+    #[test]
+    fn test_is_annotatable_attachment_epub() {
+        assert!(attachment(Some("application/epub+zip")).is_annotatable_attachment());
+    }
+
+    #[test]
+    fn test_is_annotatable_attachment_html_snapshot() {
+        assert!(attachment(Some("text/html")).is_annotatable_attachment());
+    }
+
+    #[test]
+    fn test_is_annotatable_attachment_negatives() {
+        assert!(!attachment(Some("application/zip")).is_annotatable_attachment());
+        assert!(!attachment(Some("image/png")).is_annotatable_attachment());
+        assert!(!attachment(None).is_annotatable_attachment());
+
+        let mut note = attachment(Some("application/pdf"));
+        note.data.item_type = "note".into();
+        assert!(!note.is_annotatable_attachment());
+    }
 }
@@ -14,8 +14,8 @@ pub use common::{Creator, ItemTag, Library, LinkEntry};
 pub use deleted::DeletedObjects;
 pub use fulltext::ItemFulltext;
 pub use group::{Group, GroupData, GroupMeta};
-pub use item::{Item, ItemData, ItemMeta};
+pub use item::{ANNOTATABLE_CONTENT_TYPES, Annotation, ITEM_TYPES, Item, ItemData, ItemMeta};
 pub use search::{SavedSearch, SearchCondition, SearchData};
-pub use settings::SettingEntry;
+pub use settings::{SettingEntry, TagColor};
 pub use tag::{Tag, TagMeta};
-pub use write::{WriteFailed, WriteResponse};
+pub use write::{NewItem, WriteFailed, WriteResponse};
@@ -16,3 +16,41 @@ pub struct SettingEntry {
     /// Library version when this setting was last modified.
     pub version: u64,
 }
+
+/// One entry of the `tagColors` setting. Zotero shows these tags as colored
+/// swatches, in this order; the first nine are bound to keys `1`–`9`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TagColor {
+    /// Tag name.
+    pub name: String,
+    /// Hex color, e.g. `#FF8C19`.
+    pub color: String,
+}
+
+impl SettingEntry {
+    /// Parse `value` as the `tagColors` list.
+    pub fn tag_colors(&self) -> serde_json::Result<Vec<TagColor>> {
+        Vec::<TagColor>::deserialize(&self.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tag_colors_from_setting_body() {
+        let body = r##"{"value":[{"name":"Starred","color":"#FF8C19"},{"name":"Survey","color":"#FF6666"}],"version":3826}"##;
+        let entry: SettingEntry = serde_json::from_str(body).unwrap();
+        assert_eq!(
+            entry.tag_colors().unwrap(),
+            [
+                TagColor { name: "Starred".into(), color: "#FF8C19".into() },
+                TagColor { name: "Survey".into(), color: "#FF6666".into() },
+            ]
+        );
+
+        let page_index: SettingEntry = serde_json::from_str(r#"{"value":12,"version":1}"#).unwrap();
+        assert!(page_index.tag_colors().is_err());
+    }
+}
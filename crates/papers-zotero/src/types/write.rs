@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use super::common::{Creator, ItemTag};
+
 /// Response from a Zotero write request (POST /items, POST /collections, POST /searches).
 ///
 /// The API processes each object in the request array independently. Successful
@@ -50,6 +52,44 @@ impl WriteResponse {
             .filter_map(|v| v.get("key").and_then(|k| k.as_str()).map(|s| s.to_string()))
             .collect()
     }
+
+    /// Key of the successful object with the lowest input index — for a
+    /// one-object write, the key of the object just saved.
+    pub fn first_key(&self) -> Option<&str> {
+        self.successful
+            .iter()
+            .filter_map(|(index, v)| Some((index.parse::<usize>().ok()?, v.get("key")?.as_str()?)))
+            .min_by_key(|&(index, _)| index)
+            .map(|(_, key)| key)
+    }
+
+    /// Status and message of the failures, if any: the status of the first
+    /// (lowest-index) failure, for [`ZoteroClient::write_result`](crate::ZoteroClient::write_result).
+    ///
+    /// A single failure keeps the server's message as is; several are joined
+    /// as `#<index> [(<key>)]: <code> <message>` entries in input order.
+    pub(crate) fn failure(&self) -> Option<(u16, String)> {
+        let mut failed: Vec<_> = self
+            .failed
+            .iter()
+            .map(|(index, f)| (index.parse::<usize>().unwrap_or(usize::MAX), index, f))
+            .collect();
+        failed.sort_by_key(|&(n, index, _)| (n, index.clone()));
+        let &(_, _, first) = failed.first()?;
+        let message = if failed.len() == 1 {
+            first.message.clone()
+        } else {
+            let entries: Vec<String> = failed
+                .iter()
+                .map(|(_, index, f)| match &f.key {
+                    Some(key) => format!("#{index} ({key}): {} {}", f.code, f.message),
+                    None => format!("#{index}: {} {}", f.code, f.message),
+                })
+                .collect();
+            format!("{} objects failed to save: {}", failed.len(), entries.join("; "))
+        };
+        Some((first.code, message))
+    }
 }
 
 /// A single failed write operation within a [`WriteResponse`].
@@ -59,3 +99,98 @@ pub struct WriteFailed {
     pub code: u16,
     pub message: String,
 }
+
+/// A new bibliographic item to create via [`ZoteroClient::create_item`](crate::ZoteroClient::create_item).
+///
+/// The fields common to most publications are typed; type-specific ones
+/// (`proceedingsTitle`, `bookTitle`, `university`, ...) go in `extra_fields`
+/// and are sent alongside them. Unset fields are omitted from the request body
+/// so Zotero applies its own defaults.
+///
+/// ```json
+/// {
+///   "itemType": "journalArticle",
+///   "title": "Attention Is All You Need",
+///   "creators": [{ "creatorType": "author", "firstName": "Ashish", "lastName": "Vaswani" }],
+///   "DOI": "10.48550/arXiv.1706.03762",
+///   "date": "2017",
+///   "tags": [],
+///   "collections": []
+/// }
+/// ```
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct NewItem {
+    #[serde(rename = "itemType")]
+    pub item_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    pub creators: Vec<Creator>,
+    #[serde(rename = "abstractNote", skip_serializing_if = "Option::is_none")]
+    pub abstract_note: Option<String>,
+    #[serde(rename = "publicationTitle", skip_serializing_if = "Option::is_none")]
+    pub publication_title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volume: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issue: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pages: Option<String>,
+    #[serde(rename = "DOI", skip_serializing_if = "Option::is_none")]
+    pub doi: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Free-form `Key: value` lines, e.g. `DOI: 10.1234/x` on item types
+    /// without a `DOI` field.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extra: Option<String>,
+    pub tags: Vec<ItemTag>,
+    pub collections: Vec<String>,
+    /// Type-specific fields not covered above, keyed by their Zotero name.
+    #[serde(flatten)]
+    pub extra_fields: HashMap<String, serde_json::Value>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(json: serde_json::Value) -> WriteResponse {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_first_key_uses_lowest_index() {
+        let resp = response(serde_json::json!({
+            "successful": {"10": {"key": "LATER000"}, "2": {"key": "EARLIER0"}},
+            "failed": {"0": {"key": null, "code": 400, "message": "Invalid item type"}}
+        }));
+        assert_eq!(resp.first_key(), Some("EARLIER0"));
+        assert_eq!(response(serde_json::json!({})).first_key(), None);
+    }
+
+    #[test]
+    fn test_failure_mixed_success_and_failure() {
+        let ok = response(serde_json::json!({
+            "successful": {"0": {"key": "OK123456"}},
+            "unchanged": {"1": "SAME0000"}
+        }));
+        assert_eq!(ok.failure(), None);
+
+        let mixed = response(serde_json::json!({
+            "successful": {"0": {"key": "OK123456"}},
+            "failed": {
+                "3": {"key": "ABCD1234", "code": 412, "message": "Item has been modified"},
+                "1": {"key": null, "code": 400, "message": "Invalid item type"}
+            }
+        }));
+        assert_eq!(
+            mixed.failure(),
+            Some((
+                400,
+                "2 objects failed to save: #1: 400 Invalid item type; #3 (ABCD1234): 412 Item has been modified".into()
+            ))
+        );
+    }
+}